@@ -8,14 +8,789 @@
 #[derive(Clone, Debug, PartialEq, PartialOrd)]
 pub enum LiteralExpression {
     NumericLiteral(NumericLiteral),
-    // String(StringLiteral),
-    // Char(CharacterLiteral),
-    // Bool(BooleanLiteral),
+    String(StringLiteral),
+    Char {
+        value: char,
+    },
+    Byte {
+        value: u8,
+    },
+    /// A numeric literal with a unit of measure (e.g. `9.81 m/s²`, `3 kg·m`).
+    Quantity {
+        value: NumericLiteral,
+        unit: crate::units::Unit,
+    },
+    Logic(LogicLiteral),
+}
+
+/// The byte range of a node within the source text.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Hash)]
+pub struct Span {
+    pub start: usize,
+    pub end: usize,
+}
+
+/// An expression, whose operators bind as given by the precedence table of the
+/// grammar's `Expression` rule (e.g. `1 + 2 * 3` is `1 + (2 * 3)`).
+#[derive(Clone, Debug, PartialEq, PartialOrd)]
+pub enum Expression {
+    Literal {
+        literal: LiteralExpression,
+        span: Span,
+    },
+    Identifier {
+        identifier: Identifier,
+        span: Span,
+    },
+    /// The receiver of a method (i.e. `self`)
+    SelfValue {
+        span: Span,
+    },
+    /// An item named by its enclosing item (e.g. the variant `Shape::Circle`)
+    Path {
+        segments: Vec<Identifier>,
+        span: Span,
+    },
+    /// A parenthesized expression (e.g. `(a + b)`)
+    Grouped {
+        expression: Box<Expression>,
+        span: Span,
+    },
+    Unary {
+        operator: UnaryOperator,
+        operand: Box<Expression>,
+        span: Span,
+    },
+    Binary {
+        left: Box<Expression>,
+        operator: BinaryOperator,
+        right: Box<Expression>,
+        span: Span,
+    },
+    /// The `index`th root of the `radicand` (e.g. `√x`, `∛(y + 1)`, `ⁿ√x`)
+    Root {
+        index: Box<Expression>,
+        radicand: Box<Expression>,
+        span: Span,
+    },
+    /// e.g. `{1, 2, 3}`, `{}`
+    Set {
+        elements: Vec<Expression>,
+        span: Span,
+    },
+    /// e.g. `[1, 2, 3]`, `[]`
+    List {
+        elements: Vec<Expression>,
+        span: Span,
+    },
+    /// The set or list of the values of the element for each combination of
+    /// the generators' values which satisfies the filters (e.g.
+    /// `{x² | x ∈ 1..10, x % 2 == 0}`, `[(x, y) | x ∈ A, y ∈ B]`)
+    Comprehension {
+        kind: ComprehensionKind,
+        element: Box<Expression>,
+        clauses: Vec<Clause>,
+        span: Span,
+    },
+    /// A call of a function, or the construction of a tuple struct or variant,
+    /// whose function is an identifier or path (e.g. `sin(x)`, `f(a, b)`,
+    /// `Point(1, 2)`, `Shape::Circle(r)`)
+    Call {
+        function: Box<Expression>,
+        arguments: Vec<Expression>,
+        span: Span,
+    },
+    /// The construction of a struct or struct variant with named fields (e.g.
+    /// `Point { x: 1, y: 2 }`, `Shape::Rectangle { width, height }`)
+    Struct {
+        path: Vec<Identifier>,
+        fields: Vec<FieldInit>,
+        span: Span,
+    },
+    /// e.g. `point.x`, `pair.0`
+    Field {
+        base: Box<Expression>,
+        field: FieldName,
+        span: Span,
+    },
+    /// A call of a method of the receiver's type, as defined by an impl of
+    /// the type or of one of its traits (e.g. `p.norm()`, `c.scale(2)`)
+    MethodCall {
+        receiver: Box<Expression>,
+        method: Identifier,
+        arguments: Vec<Expression>,
+        span: Span,
+    },
+    /// A sum or product over the values of a binding (e.g.
+    /// `∑(i in 1..=n) i²`, `∏(k in 1..=n) k`)
+    BigOperator {
+        operator: BigOperator,
+        binding: Binding,
+        body: Box<Expression>,
+        span: Span,
+    },
+    /// The definite integral of the integrand over the range of a binding,
+    /// whose variable is named by the differential (e.g.
+    /// `∫(x in 0..π) sin(x) dx`)
+    Integral {
+        binding: Binding,
+        integrand: Box<Expression>,
+        span: Span,
+    },
+    /// Whether the predicate holds for all (or some) of the values of a
+    /// binding (e.g. `∀ x ∈ xs: p(x)`, `∃ x ∈ 1..n: f(x) == 0`)
+    Quantifier {
+        quantifier: Quantifier,
+        binding: Binding,
+        predicate: Box<Expression>,
+        span: Span,
+    },
+    /// e.g. `x = 1`, `x += 1`
+    Assignment {
+        target: Identifier,
+        operator: AssignmentOperator,
+        value: Box<Expression>,
+        span: Span,
+    },
+}
+
+impl Expression {
+    pub fn binary(
+        left: Expression,
+        operator: BinaryOperator,
+        right: Expression,
+        span: Span,
+    ) -> Expression {
+        Expression::Binary {
+            left: Box::new(left),
+            operator,
+            right: Box::new(right),
+            span,
+        }
+    }
+
+    /// An identifier, or the path of an item within another (e.g. `E::A`).
+    pub fn path(mut segments: Vec<Identifier>, span: Span) -> Expression {
+        if segments.len() == 1 {
+            Expression::Identifier {
+                identifier: segments.remove(0),
+                span,
+            }
+        } else {
+            Expression::Path { segments, span }
+        }
+    }
+
+    pub fn span(&self) -> Span {
+        match self {
+            Expression::Literal { span, .. }
+            | Expression::Identifier { span, .. }
+            | Expression::SelfValue { span }
+            | Expression::Path { span, .. }
+            | Expression::Grouped { span, .. }
+            | Expression::Unary { span, .. }
+            | Expression::Binary { span, .. }
+            | Expression::Root { span, .. }
+            | Expression::Set { span, .. }
+            | Expression::List { span, .. }
+            | Expression::Comprehension { span, .. }
+            | Expression::Call { span, .. }
+            | Expression::Struct { span, .. }
+            | Expression::Field { span, .. }
+            | Expression::MethodCall { span, .. }
+            | Expression::BigOperator { span, .. }
+            | Expression::Integral { span, .. }
+            | Expression::Quantifier { span, .. }
+            | Expression::Assignment { span, .. } => *span,
+        }
+    }
+
+    /// The expressions of which an expression is immediately composed.
+    pub fn operands(&self) -> Vec<&Expression> {
+        match self {
+            Expression::Literal { .. }
+            | Expression::Identifier { .. }
+            | Expression::SelfValue { .. }
+            | Expression::Path { .. } => vec![],
+            Expression::Grouped { expression, .. } => vec![&**expression],
+            Expression::Unary { operand, .. } => vec![&**operand],
+            Expression::Binary { left, right, .. } => vec![&**left, &**right],
+            Expression::Root {
+                index, radicand, ..
+            } => vec![&**index, &**radicand],
+            Expression::Set { elements, .. } | Expression::List { elements, .. } => {
+                elements.iter().collect()
+            }
+            Expression::Comprehension {
+                element, clauses, ..
+            } => std::iter::once(&**element)
+                .chain(clauses.iter().map(|clause| match clause {
+                    Clause::Generator(binding) => &*binding.domain,
+                    Clause::Filter(condition) => condition,
+                }))
+                .collect(),
+            Expression::Call {
+                function,
+                arguments,
+                ..
+            } => std::iter::once(&**function).chain(arguments).collect(),
+            Expression::Struct { fields, .. } => {
+                fields.iter().map(|field| &field.value).collect()
+            }
+            Expression::Field { base, .. } => vec![&**base],
+            Expression::MethodCall {
+                receiver,
+                arguments,
+                ..
+            } => std::iter::once(&**receiver).chain(arguments).collect(),
+            Expression::BigOperator { binding, body, .. } => {
+                vec![&*binding.domain, &**body]
+            }
+            Expression::Integral {
+                binding, integrand, ..
+            } => vec![&*binding.domain, &**integrand],
+            Expression::Quantifier {
+                binding, predicate, ..
+            } => vec![&*binding.domain, &**predicate],
+            Expression::Assignment { value, .. } => vec![&**value],
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd)]
+pub enum UnaryOperator {
+    /// `-`
+    Negate,
+    /// `!`
+    Not,
+}
+
+impl UnaryOperator {
+    /// The operator's symbol.
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            UnaryOperator::Negate => "-",
+            UnaryOperator::Not => "!",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd)]
+pub enum BinaryOperator {
+    /// `+`
+    Add,
+    /// `-`
+    Subtract,
+    /// `*` or `·`
+    Multiply,
+    /// `/` or `÷`
+    Divide,
+    /// `%`
+    Remainder,
+    /// `^`
+    Power,
+    /// `&`
+    BitAnd,
+    /// `|`
+    BitOr,
+    /// `==`
+    Equal,
+    /// `!=`
+    NotEqual,
+    /// `<`
+    LessThan,
+    /// `<=`
+    LessThanEqual,
+    /// `>`
+    GreaterThan,
+    /// `>=`
+    GreaterThanEqual,
+    /// `&&`
+    And,
+    /// `||`
+    Or,
+    /// `..`
+    Range,
+    /// `..=`
+    RangeInclusive,
+    /// `∩`
+    Intersection,
+    /// `∪`
+    Union,
+    /// `∈`
+    ElementOf,
+    /// `∉`
+    NotElementOf,
+    /// `⊂` (i.e. a proper subset)
+    SubsetOf,
+    /// `⊆`
+    SubsetOfOrEqualTo,
+    /// `⊄`
+    NotSubsetOf,
+    /// `⊃` (i.e. a proper superset)
+    SupersetOf,
+    /// `⊇`
+    SupersetOfOrEqualTo,
+    /// `⊅`
+    NotSupersetOf,
+}
+
+impl BinaryOperator {
+    /// The operator's symbol, which is ASCII unless it's a set operator.
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            BinaryOperator::Add => "+",
+            BinaryOperator::Subtract => "-",
+            BinaryOperator::Multiply => "*",
+            BinaryOperator::Divide => "/",
+            BinaryOperator::Remainder => "%",
+            BinaryOperator::Power => "^",
+            BinaryOperator::BitAnd => "&",
+            BinaryOperator::BitOr => "|",
+            BinaryOperator::Equal => "==",
+            BinaryOperator::NotEqual => "!=",
+            BinaryOperator::LessThan => "<",
+            BinaryOperator::LessThanEqual => "<=",
+            BinaryOperator::GreaterThan => ">",
+            BinaryOperator::GreaterThanEqual => ">=",
+            BinaryOperator::And => "&&",
+            BinaryOperator::Or => "||",
+            BinaryOperator::Range => "..",
+            BinaryOperator::RangeInclusive => "..=",
+            BinaryOperator::Intersection => "∩",
+            BinaryOperator::Union => "∪",
+            BinaryOperator::ElementOf => "∈",
+            BinaryOperator::NotElementOf => "∉",
+            BinaryOperator::SubsetOf => "⊂",
+            BinaryOperator::SubsetOfOrEqualTo => "⊆",
+            BinaryOperator::NotSubsetOf => "⊄",
+            BinaryOperator::SupersetOf => "⊃",
+            BinaryOperator::SupersetOfOrEqualTo => "⊇",
+            BinaryOperator::NotSupersetOf => "⊅",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd)]
+pub enum BigOperator {
+    /// `∑`
+    Sum,
+    /// `∏`
+    Product,
+}
+
+impl BigOperator {
+    /// The operator's symbol.
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            BigOperator::Sum => "∑",
+            BigOperator::Product => "∏",
+        }
+    }
+}
+
+/// A declaration within a source file or block.
+#[derive(Clone, Debug, PartialEq, PartialOrd)]
+pub enum Item {
+    /// e.g. `fn add(a: Int, b: Int) -> Int { a + b }`
+    Function(Function),
+    /// e.g. `struct Point { x: Int, y: Int }`, `struct Meters(Float);`
+    Struct(Struct),
+    /// e.g. `enum Shape { Empty, Circle(Float), Rectangle { w: Float, h: Float } }`
+    Enum(Enum),
+    /// e.g. `trait Area { fn area(self) -> Float; }`
+    Trait(Trait),
+    /// e.g. `impl Circle { ... }`, `impl Area for Circle { ... }`
+    Impl(Impl),
+}
+
+impl Item {
+    pub fn span(&self) -> Span {
+        match self {
+            Item::Function(function) => function.span,
+            Item::Struct(structure) => structure.span,
+            Item::Enum(enumeration) => enumeration.span,
+            Item::Trait(declaration) => declaration.span,
+            Item::Impl(implementation) => implementation.span,
+        }
+    }
+
+    /// The functions which the item declares: itself if it's a function, or
+    /// its methods if it's a trait or an impl.
+    pub fn functions(&self) -> &[Function] {
+        match self {
+            Item::Function(function) => std::slice::from_ref(function),
+            Item::Trait(declaration) => &declaration.methods,
+            Item::Impl(implementation) => &implementation.methods,
+            Item::Struct(_) | Item::Enum(_) => &[],
+        }
+    }
+}
+
+/// Whether an item may be used outside of its module (i.e. `pub`).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd)]
+pub enum Visibility {
+    Private,
+    Public,
+}
+
+/// A function, which returns the value of its body (e.g. the `a + b` of
+/// `fn add(a: Int, b: Int) -> Int { a + b }`) unless a `return` statement
+/// returns earlier. A function without a return type returns nothing.
+///
+/// A method (i.e. a function of a trait or impl) may take a `self` receiver
+/// (e.g. `fn area(self) -> Float`), and a required method of a trait has no
+/// body (e.g. `fn area(self) -> Float;`).
+#[derive(Clone, Debug, PartialEq, PartialOrd)]
+pub struct Function {
+    pub visibility: Visibility,
+    pub name: Identifier,
+    /// The span of the `self` receiver, if any
+    pub receiver: Option<Span>,
+    pub parameters: Vec<Parameter>,
+    pub return_type: Option<Type>,
+    pub where_clause: Vec<WherePredicate>,
+    pub body: Option<Block>,
+    /// Its doc comments, including the inner doc comments of its body (see
+    /// [`crate::docs`])
+    pub docs: Vec<DocComment>,
+    pub span: Span,
+}
+
+/// A trait, whose methods are either required of its impls (e.g.
+/// `fn area(self) -> Float;`) or have a default body, which impls inherit
+/// unless they define the method themselves.
+#[derive(Clone, Debug, PartialEq, PartialOrd)]
+pub struct Trait {
+    pub visibility: Visibility,
+    pub name: Identifier,
+    pub where_clause: Vec<WherePredicate>,
+    pub methods: Vec<Function>,
+    pub docs: Vec<DocComment>,
+    pub span: Span,
+}
+
+/// The methods of a type, either inherent to it (e.g. `impl Circle { ... }`)
+/// or those of a trait (e.g. `impl Area for Circle { ... }`).
+#[derive(Clone, Debug, PartialEq, PartialOrd)]
+pub struct Impl {
+    pub trait_type: Option<Type>,
+    pub self_type: Type,
+    pub where_clause: Vec<WherePredicate>,
+    pub methods: Vec<Function>,
+    pub docs: Vec<DocComment>,
+    pub span: Span,
+}
+
+/// A bound of a type by traits which it must implement (e.g. the
+/// `Self: Area + Display` of `where Self: Area + Display`).
+#[derive(Clone, Debug, PartialEq, PartialOrd)]
+pub struct WherePredicate {
+    pub ty: Type,
+    pub bounds: Vec<Type>,
+    pub span: Span,
+}
+
+/// e.g. the `a: Int` of `fn f(a: Int)`
+#[derive(Clone, Debug, PartialEq, PartialOrd)]
+pub struct Parameter {
+    pub name: Identifier,
+    pub ty: Type,
+    pub span: Span,
+}
+
+/// A struct, whose fields are named (e.g. `struct Point { x: Int, y: Int }`),
+/// positional (e.g. `struct Meters(Float);`), or absent (e.g. `struct Origin;`).
+#[derive(Clone, Debug, PartialEq, PartialOrd)]
+pub struct Struct {
+    pub visibility: Visibility,
+    pub name: Identifier,
+    pub fields: Fields,
+    pub docs: Vec<DocComment>,
+    pub span: Span,
+}
+
+/// An enum, whose value is one of its variants (e.g.
+/// `enum Shape { Empty, Circle(Float), Rectangle { w: Float, h: Float } }`).
+#[derive(Clone, Debug, PartialEq, PartialOrd)]
+pub struct Enum {
+    pub visibility: Visibility,
+    pub name: Identifier,
+    pub variants: Vec<Variant>,
+    pub docs: Vec<DocComment>,
+    pub span: Span,
+}
+
+/// A variant of an enum, whose fields are as those of a struct (e.g. `Empty`,
+/// `Circle(Float)`, `Rectangle { w: Float, h: Float }`).
+#[derive(Clone, Debug, PartialEq, PartialOrd)]
+pub struct Variant {
+    pub name: Identifier,
+    pub fields: Fields,
+    pub docs: Vec<DocComment>,
+    pub span: Span,
+}
+
+/// The fields of a struct or variant.
+#[derive(Clone, Debug, PartialEq, PartialOrd)]
+pub enum Fields {
+    /// e.g. `{ x: Int, y: Int }`
+    Named(Vec<Field>),
+    /// e.g. `(Int, Int)`
+    Tuple(Vec<Type>),
+    Unit,
+}
+
+/// e.g. the `x: Int` of `struct Point { x: Int, y: Int }`
+#[derive(Clone, Debug, PartialEq, PartialOrd)]
+pub struct Field {
+    pub visibility: Visibility,
+    pub name: Identifier,
+    pub ty: Type,
+    pub docs: Vec<DocComment>,
+    pub span: Span,
+}
+
+/// The value of a named field in the construction of a struct, where the
+/// shorthand `x` is `x: x` (e.g. the `x: 1` of `Point { x: 1, y }`).
+#[derive(Clone, Debug, PartialEq, PartialOrd)]
+pub struct FieldInit {
+    pub name: Identifier,
+    pub value: Expression,
+    pub span: Span,
+}
+
+/// The field of a field access, which is named for a struct with named fields
+/// (e.g. `point.x`) and its index otherwise (e.g. `pair.0`).
+#[derive(Clone, Debug, PartialEq, PartialOrd)]
+pub enum FieldName {
+    Named(Identifier),
+    Index(usize),
+}
+
+/// A sequence of statements, optionally followed by an expression which is
+/// the value of the block (e.g. the `a` of `a := 3; a + 1`). A source file is a
+/// block without braces.
+#[derive(Clone, Debug, PartialEq, PartialOrd)]
+pub struct Block {
+    pub statements: Vec<Statement>,
+    pub value: Option<Box<Expression>>,
+    /// The inner doc comments of a source file (e.g. `//! ...`), documenting
+    /// its module, while those of a function's body document the function
+    pub docs: Vec<DocComment>,
+    pub span: Span,
+}
+
+#[derive(Clone, Debug, PartialEq, PartialOrd)]
+pub enum Statement {
+    /// Declares a variable, whose type is inferred from its value unless it's
+    /// given (e.g. `a := 3;`, `a: Int := 3;`)
+    Declaration {
+        name: Identifier,
+        ty: Option<Type>,
+        value: Expression,
+        span: Span,
+    },
+    /// An expression evaluated for its effect (e.g. `x += 1;`)
+    Expression { expression: Expression, span: Span },
+    /// Returns from the enclosing function, with the value if any (e.g.
+    /// `return x;`, `return;`)
+    Return {
+        value: Option<Expression>,
+        span: Span,
+    },
+    /// Asserts that a condition holds, which is checked while compiling when
+    /// it's constant (e.g. `assert ∀ x ∈ 1..=n: x > 0;`)
+    Assertion { condition: Expression, span: Span },
+    /// An item declared within a block (e.g. a nested function)
+    Item(Item),
+}
+
+impl Statement {
+    pub fn span(&self) -> Span {
+        match self {
+            Statement::Declaration { span, .. }
+            | Statement::Expression { span, .. }
+            | Statement::Return { span, .. }
+            | Statement::Assertion { span, .. } => *span,
+            Statement::Item(item) => item.span(),
+        }
+    }
+}
+
+/// A type, as written in a declaration.
+#[derive(Clone, Debug, PartialEq, PartialOrd)]
+pub enum Type {
+    /// A type and its type arguments, if any (e.g. `Int`, `Set<Int>`)
+    Named {
+        name: Identifier,
+        arguments: Vec<Type>,
+        span: Span,
+    },
+    /// The type of an impl, or the implementor of a trait (i.e. `Self`)
+    SelfType { span: Span },
+}
+
+impl Type {
+    pub fn span(&self) -> Span {
+        match self {
+            Type::Named { span, .. } | Type::SelfType { span } => *span,
+        }
+    }
+}
+
+/// A variable bound to each of the values of its domain in turn (e.g. the
+/// `i in 1..=n` of `∑(i in 1..=n) i²`).
+#[derive(Clone, Debug, PartialEq, PartialOrd)]
+pub struct Binding {
+    pub variable: Identifier,
+    pub domain: Box<Expression>,
+    pub span: Span,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd)]
+pub enum Quantifier {
+    ForAll,
+    Exists,
+}
+
+impl Quantifier {
+    /// The quantifier's symbol.
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            Quantifier::ForAll => "∀",
+            Quantifier::Exists => "∃",
+        }
+    }
+}
+
+/// Whether a comprehension builds a set (e.g. `{x | x ∈ S}`) or a list (e.g.
+/// `[x | x ∈ S]`), the latter keeping the order and duplicates of its values.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd)]
+pub enum ComprehensionKind {
+    Set,
+    List,
+}
+
+/// A clause of a comprehension, i.e. either a generator binding a variable to
+/// each of the values of its domain in turn (e.g. `x ∈ 1..10`), or a filter
+/// which must hold for a value to be included (e.g. `x % 2 == 0`).
+#[derive(Clone, Debug, PartialEq, PartialOrd)]
+pub enum Clause {
+    Generator(Binding),
+    Filter(Expression),
+}
+
+impl Clause {
+    /// The clauses of a comprehension, parsed as expressions. A membership
+    /// test of an identifier which isn't bound by an earlier generator is a
+    /// generator, and any other expression is a filter (e.g. in
+    /// `x ∈ S, y ∈ S, x ∈ T` the last clause is a filter).
+    ///
+    /// NOTE: Since `..` binds looser than `∈`, a generator over a range (e.g.
+    /// `x ∈ 1..10`) is parsed as `(x ∈ 1)..10`, and so is regrouped here as
+    /// `x ∈ (1..10)`.
+    pub fn from_expressions(expressions: Vec<Expression>) -> Vec<Clause> {
+        let mut bound: Vec<String> = Vec::new();
+        let mut clauses = Vec::with_capacity(expressions.len());
+        for expression in expressions {
+            let clause = Clause::from_expression(expression, &bound);
+            if let Clause::Generator(binding) = &clause {
+                bound.push(binding.variable.name.clone());
+            }
+            clauses.push(clause);
+        }
+        clauses
+    }
+
+    fn from_expression(expression: Expression, bound: &[String]) -> Clause {
+        let is_unbound = |expression: &Expression| {
+            matches!(expression, Expression::Identifier { identifier, .. }
+                if !bound.contains(&identifier.name))
+        };
+        let is_membership = |expression: &Expression| {
+            matches!(expression, Expression::Binary {
+                left,
+                operator: BinaryOperator::ElementOf,
+                ..
+            } if is_unbound(left))
+        };
+        let is_generator = match &expression {
+            Expression::Binary {
+                left,
+                operator: BinaryOperator::Range | BinaryOperator::RangeInclusive,
+                ..
+            } => is_membership(left),
+            expression => is_membership(expression),
+        };
+        if !is_generator {
+            return Clause::Filter(expression);
+        }
+
+        // The shapes of the expression were checked above
+        let (variable, domain, span) = match expression {
+            Expression::Binary {
+                left,
+                operator: BinaryOperator::ElementOf,
+                right,
+                span,
+            } => (*left, *right, span),
+            Expression::Binary {
+                left,
+                operator,
+                right: end,
+                span,
+            } => match *left {
+                Expression::Binary {
+                    left: variable,
+                    right: start,
+                    ..
+                } => {
+                    let range = Span {
+                        start: start.span().start,
+                        end: span.end,
+                    };
+                    let domain = Expression::binary(*start, operator, *end, range);
+                    (*variable, domain, span)
+                }
+                _ => unreachable!(),
+            },
+            _ => unreachable!(),
+        };
+        match variable {
+            Expression::Identifier { identifier, .. } => {
+                Clause::Generator(Binding {
+                    variable: identifier,
+                    domain: Box::new(domain),
+                    span,
+                })
+            }
+            _ => unreachable!(),
+        }
+    }
+}
+
+/// The operator of an assignment, where a compound assignment (e.g. `x += 1`)
+/// applies its binary operator to the target and the value.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd)]
+pub enum AssignmentOperator {
+    /// `=`
+    Assign,
+    /// `+=`, `-=`, `*=`, `/=`, `%=`, `^=`, `&=` or `|=`
+    Compound(BinaryOperator),
+}
+
+/// The decoded value of a (raw) string or (raw) byte string literal.
+#[derive(Clone, Debug, PartialEq, PartialOrd)]
+pub enum StringLiteral {
+    String { value: String },
+    ByteString { value: Vec<u8> },
 }
 
 #[derive(Clone, Debug, PartialEq, PartialOrd)]
 pub enum Keyword {
     As,
+    Assert,
     Break,
     Const,
     Continue,
@@ -93,43 +868,165 @@ pub enum DocComment {
 pub enum NumericLiteral {
     Integer(Integer),
     Float(Float),
+    /// An exact quotient of integers (e.g. `3/4r`, `¾`, `1½`).
+    Rational(rug::Rational),
+    /// An imaginary number, as the exact value of its imaginary part (e.g.
+    /// `2i`, `1.5𝑖`, `𝑖`).
+    Imaginary(rug::Rational),
     MathematicalConstant(MathematicalConstant),
 }
 
 #[derive(Clone, Debug, PartialEq, PartialOrd)]
 pub enum Integer {
-    U8 { n: u8 },
-    U16 { n: u16 },
-    U32 { n: u32 },
-    U64 { n: u64 },
-    U128 { n: u128 },
-    USize { n: usize },
-    I8 { n: i8 },
-    I16 { n: i16 },
-    I32 { n: i32 },
-    I64 { n: i64 },
-    I128 { n: i128 },
-    ISize { n: isize },
+    U8 {
+        n: u8,
+    },
+    U16 {
+        n: u16,
+    },
+    U32 {
+        n: u32,
+    },
+    U64 {
+        n: u64,
+    },
+    U128 {
+        n: u128,
+    },
+    USize {
+        n: usize,
+    },
+    I8 {
+        n: i8,
+    },
+    I16 {
+        n: i16,
+    },
+    I32 {
+        n: i32,
+    },
+    I64 {
+        n: i64,
+    },
+    I128 {
+        n: i128,
+    },
+    ISize {
+        n: isize,
+    },
+    /// Unsuffixed integer literals too wide for any of the fixed-width
+    /// integer types
+    Big {
+        n: rug::Integer,
+    },
 }
 
 #[derive(Clone, Debug, PartialEq, PartialOrd)]
 pub enum Float {
-    F32 { f: f32 },
-    F64 { f: f64 },
+    F32 {
+        f: f32,
+    },
+    F64 {
+        f: f64,
+    },
+    /// The exact value of a float literal, prior to its rounding to an f32 or
+    /// f64
+    Exact {
+        value: rug::Rational,
+    },
 }
 
 #[derive(Clone, Debug, PartialEq, PartialOrd)]
+/// A mathematical constant, whose `value` is its nearest f64.
+///
+/// Constants may carry a precision suffix (e.g. `pi_40`, `π_100`), in which
+/// case `digits` is the number of significant decimal digits requested, to
+/// which the constant can be computed with [`MathematicalConstant::to_digits`].
 pub enum MathematicalConstant {
-    Pi { value: f64 },
-    Euler { value: f64 },
-    EulerMascheroni { value: f64 },
-    Tau { value: f64 },
-    Catalan { value: f64 },
-    GoldenRatio { value: f64 },
+    Pi { value: f64, digits: Option<u32> },
+    Euler { value: f64, digits: Option<u32> },
+    EulerMascheroni { value: f64, digits: Option<u32> },
+    Tau { value: f64, digits: Option<u32> },
+    Catalan { value: f64, digits: Option<u32> },
+    GoldenRatio { value: f64, digits: Option<u32> },
     Infinity { value: f64 },
     NotANumber,
 }
 
+impl MathematicalConstant {
+    /// The greatest number of significant decimal digits which a precision
+    /// suffix may request, few enough that a precise constant remains cheap to
+    /// evaluate within a loop (e.g. a big operator).
+    pub const MAX_DIGITS: u32 = 10_000;
+
+    /// The number of significant decimal digits requested by the constant's
+    /// precision suffix, if any.
+    pub fn digits(&self) -> Option<u32> {
+        match self {
+            MathematicalConstant::Pi { digits, .. }
+            | MathematicalConstant::Euler { digits, .. }
+            | MathematicalConstant::EulerMascheroni { digits, .. }
+            | MathematicalConstant::Tau { digits, .. }
+            | MathematicalConstant::Catalan { digits, .. }
+            | MathematicalConstant::GoldenRatio { digits, .. } => *digits,
+            MathematicalConstant::Infinity { .. } | MathematicalConstant::NotANumber => {
+                None
+            }
+        }
+    }
+
+    /// The constant's nearest f64.
+    pub fn to_f64(&self) -> f64 {
+        match self {
+            MathematicalConstant::Pi { value, .. }
+            | MathematicalConstant::Euler { value, .. }
+            | MathematicalConstant::EulerMascheroni { value, .. }
+            | MathematicalConstant::Tau { value, .. }
+            | MathematicalConstant::Catalan { value, .. }
+            | MathematicalConstant::GoldenRatio { value, .. }
+            | MathematicalConstant::Infinity { value } => *value,
+            MathematicalConstant::NotANumber => f64::NAN,
+        }
+    }
+
+    /// Computes the constant to (at least) the given number of significant
+    /// decimal digits. A few guard bits are kept beyond the requested
+    /// precision so that the value rounds correctly when printed.
+    pub fn to_digits(&self, digits: u32) -> rug::Float {
+        use rug::float::{Constant, Special};
+        use rug::Float;
+
+        let precision =
+            (f64::from(digits) * std::f64::consts::LOG2_10).ceil() as u32 + 16;
+        match self {
+            MathematicalConstant::Pi { .. } => {
+                Float::with_val(precision, Constant::Pi)
+            }
+            MathematicalConstant::Tau { .. } => {
+                Float::with_val(precision, Constant::Pi) * 2
+            }
+            MathematicalConstant::Euler { .. } => {
+                Float::with_val(precision, 1).exp()
+            }
+            MathematicalConstant::EulerMascheroni { .. } => {
+                Float::with_val(precision, Constant::Euler)
+            }
+            MathematicalConstant::Catalan { .. } => {
+                Float::with_val(precision, Constant::Catalan)
+            }
+            MathematicalConstant::GoldenRatio { .. } => {
+                (Float::with_val(precision, 5).sqrt() + 1) / 2
+            }
+            MathematicalConstant::Infinity { .. } => {
+                Float::with_val(precision, Special::Infinity)
+            }
+            MathematicalConstant::NotANumber => {
+                Float::with_val(precision, Special::Nan)
+            }
+        }
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, PartialOrd)]
 pub enum Punctuation {
     Plus,
@@ -166,6 +1063,7 @@ pub enum Punctuation {
     DotDotEquals,
     Comma,
     Colon,
+    ColonEquals,
     Semicolon,
     PathSeparator,
     RightArrow,
@@ -194,11 +1092,13 @@ pub enum MathematicalSymbol {
     Root { exponent: i8 },
     Power { exponent: i8 },
     Division,
+    DotOperator,
     ProportionalTo,
     Intersection,
     Union,
     Integral,
     Sum,
+    Product,
     Therefore,
     Because,
     ApproximatelyEqual,
@@ -211,6 +1111,10 @@ pub enum MathematicalSymbol {
     SupersetOf,
     NotSupersetOf,
     SupersetOfOrEqualTo,
+    ElementOf,
+    NotElementOf,
+    ForAll,
+    Exists,
     Logarithm,
     NaturalLogarithm,
 }
@@ -238,6 +1142,34 @@ pub enum SuperscriptPunctuation {
     RightParen,
 }
 
+/// The operators combining the factors of a unit of measure (e.g. the `·` and
+/// `/` of `kg·m/s²`).
+#[derive(Clone, Debug, PartialEq, PartialOrd)]
+pub enum UnitOperator {
+    Multiplication,
+    Division,
+}
+
+/// Subscript digits and signs (e.g. `₀`, `₋`, `₍`), as used for indices in
+/// mathematical notation.
+#[derive(Clone, Debug, PartialEq, PartialOrd)]
+pub enum SubscriptSymbol {
+    Zero,
+    One,
+    Two,
+    Three,
+    Four,
+    Five,
+    Six,
+    Seven,
+    Eight,
+    Nine,
+    Plus,
+    Minus,
+    LeftParen,
+    RightParen,
+}
+
 #[derive(Clone, Debug, PartialEq, PartialOrd)]
 pub struct Identifier {
     pub name: String,
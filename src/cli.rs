@@ -3,6 +3,8 @@ use miette::Diagnostic;
 use std::path::PathBuf;
 use thiserror::Error;
 
+use crate::quadrature::DEFAULT_TOLERANCE;
+
 #[derive(Parser, Default, Debug)]
 #[clap(name = "balsapopc")]
 #[clap(
@@ -43,6 +45,37 @@ pub(crate) struct BalsapopCli {
         default_value_t = false
     )]
     pub(crate) interactive: bool,
+
+    /// The error tolerance of the integrals which are evaluated while
+    /// compiling (e.g. `∫(x in 0..1) sqrt(x) dx`). [default: 1e-10]
+    ///
+    /// An integral whose estimated error isn't within the tolerance, either
+    /// absolutely or relative to the integral, is refined until it is, or
+    /// until it's deemed not to converge.
+    #[clap(
+        long,
+        value_parser = parse_tolerance,
+        required = false,
+        default_value_t = DEFAULT_TOLERANCE
+    )]
+    pub(crate) tolerance: f64,
+
+    /// Keep the exact value of float literals (e.g. `0.1` is exactly `1/10`),
+    /// rather than rounding them to an f32 or f64. [default: false]
+    ///
+    /// Constant expressions of exact floats are then evaluated exactly (e.g.
+    /// `0.1 + 0.2` is `3/10`), like those of integers and rationals.
+    #[clap(long, value_parser, required = false, default_value_t = false)]
+    pub(crate) exact_floats: bool,
+}
+
+/// Parses a tolerance, which must be a positive number.
+fn parse_tolerance(s: &str) -> Result<f64, String> {
+    match s.parse::<f64>() {
+        Ok(tolerance) if tolerance > 0.0 && tolerance.is_finite() => Ok(tolerance),
+        Ok(_) => Err(String::from("the tolerance must be a positive number")),
+        Err(error) => Err(error.to_string()),
+    }
 }
 
 #[derive(Error, Debug, Diagnostic)]
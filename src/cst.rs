@@ -0,0 +1,675 @@
+//! The lossless concrete syntax tree (CST) of Balsa source text.
+//!
+//! Unlike the AST, which is built by the LALRPOP generated parser from the
+//! significant tokens alone, the CST retains every byte of its source,
+//! including whitespace and comments (collectively, trivia). Its text is
+//! therefore always identical to the text it was built from, allowing tools
+//! such as a formatter, refactorings or an IDE to round-trip source exactly.
+//!
+//! The tree is built on [`rowan`], and so is split into an immutable, position
+//! independent green tree and a red tree of cursors over it (i.e.
+//! [`SyntaxNode`] and [`SyntaxToken`]), which know their parent and offset.
+//! Its nodes are those of the grammar's productions (e.g. items, statements,
+//! types and expressions), as given by the spans of the AST which the parser
+//! produces (see [`build`]). Trivia belong to the innermost node enclosing
+//! them, except that the comments (and doc comments) preceding an item,
+//! variant or field belong to it.
+
+use rowan::GreenNodeBuilder;
+
+use crate::{
+    ast::*,
+    lexer::{Lexer, Token},
+    parser,
+    parser_errors::InternalParserError,
+};
+
+/// The kinds of the nodes and tokens of the concrete syntax tree.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(u16)]
+pub enum SyntaxKind {
+    // Trivia
+    Space,
+    Tab,
+    Newline,
+    CarriageReturn,
+    VerticalTab,
+    FormFeed,
+    LineComment,
+    BlockComment,
+    InnerLineDocComment,
+    InnerBlockDocComment,
+    OuterLineDocComment,
+    OuterBlockDocComment,
+
+    // Tokens
+    Keyword,
+    Punctuation,
+    Delimiter,
+    MathematicalSymbol,
+    MathematicalConstant,
+    SuperscriptIntegerLiteral,
+    SuperscriptPunctuation,
+    SuperscriptIdentifier,
+    SubscriptSymbol,
+    Identifier,
+    HexadecimalLiteral,
+    DecimalLiteral,
+    OctalLiteral,
+    BinaryLiteral,
+    FloatLiteral,
+    HexadecimalFloatLiteral,
+    RationalLiteral,
+    ImaginaryLiteral,
+    StringLiteral,
+    RawStringLiteral,
+    ByteStringLiteral,
+    RawByteStringLiteral,
+    CharLiteral,
+    ByteLiteral,
+    UnitSymbol,
+    UnitOperator,
+    /// Input which isn't a valid token (e.g. `$`, `1.f`).
+    Error,
+
+    // Nodes
+    SourceFile,
+    /// A source file which doesn't parse, as its tokens.
+    ErrorNode,
+    // Items
+    Function,
+    Parameter,
+    WherePredicate,
+    Struct,
+    Field,
+    Enum,
+    Variant,
+    Trait,
+    Impl,
+    // Statements
+    Block,
+    Declaration,
+    ExpressionStatement,
+    ReturnStatement,
+    Assertion,
+    // Types
+    NamedType,
+    SelfType,
+    // Expressions
+    LiteralExpression,
+    IdentifierExpression,
+    SelfExpression,
+    PathExpression,
+    GroupedExpression,
+    UnaryExpression,
+    BinaryExpression,
+    RootExpression,
+    SetExpression,
+    ListExpression,
+    ComprehensionExpression,
+    CallExpression,
+    StructExpression,
+    FieldExpression,
+    MethodCallExpression,
+    BigOperatorExpression,
+    IntegralExpression,
+    QuantifierExpression,
+    AssignmentExpression,
+    /// A variable and its domain (e.g. the `i in 1..=n` of `∑(i in 1..=n) i`).
+    Binding,
+    /// e.g. the `x: 1` of `Point { x: 1, y }`
+    FieldInit,
+}
+
+impl SyntaxKind {
+    /// Whether this kind is whitespace or a comment, and so carries no meaning
+    /// for the parser.
+    pub fn is_trivia(self) -> bool {
+        self <= SyntaxKind::OuterBlockDocComment
+    }
+
+    /// Whether this kind is an item (e.g. a function or a struct).
+    pub fn is_item(self) -> bool {
+        matches!(
+            self,
+            SyntaxKind::Function
+                | SyntaxKind::Struct
+                | SyntaxKind::Enum
+                | SyntaxKind::Trait
+                | SyntaxKind::Impl
+        )
+    }
+
+    /// Whether this kind is documented by the outer doc comments preceding
+    /// it, which belong to it along with any other comments preceding it
+    /// (i.e. an item, variant or field).
+    pub fn is_documented(self) -> bool {
+        self.is_item() || matches!(self, SyntaxKind::Variant | SyntaxKind::Field)
+    }
+
+    /// The whitespace character a whitespace token is made up of.
+    pub fn whitespace(self) -> Option<Whitespace> {
+        match self {
+            SyntaxKind::Space => Some(Whitespace::Space),
+            SyntaxKind::Tab => Some(Whitespace::Tab),
+            SyntaxKind::Newline => Some(Whitespace::Newline),
+            SyntaxKind::CarriageReturn => Some(Whitespace::CarriageReturn),
+            SyntaxKind::VerticalTab => Some(Whitespace::VerticalTab),
+            SyntaxKind::FormFeed => Some(Whitespace::FormFeed),
+            _ => None,
+        }
+    }
+}
+
+impl From<SyntaxKind> for rowan::SyntaxKind {
+    fn from(kind: SyntaxKind) -> Self {
+        Self(kind as u16)
+    }
+}
+
+impl From<&Token<'_>> for SyntaxKind {
+    fn from(token: &Token<'_>) -> Self {
+        match token {
+            Token::Keyword(_) => SyntaxKind::Keyword,
+            Token::Punctuation(_) => SyntaxKind::Punctuation,
+            Token::Delimiter(_) => SyntaxKind::Delimiter,
+            Token::MathematicalSymbol(_) => SyntaxKind::MathematicalSymbol,
+            Token::MathematicalConstant(_) => SyntaxKind::MathematicalConstant,
+            Token::SuperscriptIntegerLiteral(_) => {
+                SyntaxKind::SuperscriptIntegerLiteral
+            }
+            Token::SuperscriptPunctuation(_) => SyntaxKind::SuperscriptPunctuation,
+            Token::SuperscriptIdentifier(_) => SyntaxKind::SuperscriptIdentifier,
+            Token::SubscriptSymbol(_) => SyntaxKind::SubscriptSymbol,
+            Token::Identifier(_) => SyntaxKind::Identifier,
+            Token::HexadecimalLiteral(_) => SyntaxKind::HexadecimalLiteral,
+            Token::DecimalLiteral(_) => SyntaxKind::DecimalLiteral,
+            Token::OctalLiteral(_) => SyntaxKind::OctalLiteral,
+            Token::BinaryLiteral(_) => SyntaxKind::BinaryLiteral,
+            Token::FloatLiteral(_) | Token::ExactFloatLiteral(_) => {
+                SyntaxKind::FloatLiteral
+            }
+            Token::HexadecimalFloatLiteral(_) => SyntaxKind::HexadecimalFloatLiteral,
+            Token::RationalLiteral(_) => SyntaxKind::RationalLiteral,
+            Token::ImaginaryLiteral(_) => SyntaxKind::ImaginaryLiteral,
+            Token::StringLiteral(_) => SyntaxKind::StringLiteral,
+            Token::RawStringLiteral(_) => SyntaxKind::RawStringLiteral,
+            Token::ByteStringLiteral(_) => SyntaxKind::ByteStringLiteral,
+            Token::RawByteStringLiteral(_) => SyntaxKind::RawByteStringLiteral,
+            Token::CharLiteral(_) => SyntaxKind::CharLiteral,
+            Token::ByteLiteral(_) => SyntaxKind::ByteLiteral,
+            Token::UnitSymbol(_) => SyntaxKind::UnitSymbol,
+            Token::UnitOperator(_) => SyntaxKind::UnitOperator,
+            Token::Comment(Comment::LineComment { .. }) => SyntaxKind::LineComment,
+            Token::Comment(Comment::BlockComment { .. }) => SyntaxKind::BlockComment,
+            Token::Comment(Comment::DocComment(doc_comment)) => match doc_comment {
+                DocComment::InnerLineDocComment { .. } => {
+                    SyntaxKind::InnerLineDocComment
+                }
+                DocComment::InnerBlockDocComment { .. } => {
+                    SyntaxKind::InnerBlockDocComment
+                }
+                DocComment::OuterLineDocComment { .. } => {
+                    SyntaxKind::OuterLineDocComment
+                }
+                DocComment::OuterBlockDocComment { .. } => {
+                    SyntaxKind::OuterBlockDocComment
+                }
+            },
+            Token::InvalidFloatLiteral(_) | Token::Error => SyntaxKind::Error,
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum BalsaLanguage {}
+
+impl rowan::Language for BalsaLanguage {
+    type Kind = SyntaxKind;
+
+    fn kind_from_raw(raw: rowan::SyntaxKind) -> SyntaxKind {
+        assert!(raw.0 <= SyntaxKind::FieldInit as u16);
+        // SAFETY: `SyntaxKind` is a `repr(u16)` fieldless enum, and `raw` was
+        // checked to be within the range of its discriminants.
+        unsafe { std::mem::transmute::<u16, SyntaxKind>(raw.0) }
+    }
+
+    fn kind_to_raw(kind: SyntaxKind) -> rowan::SyntaxKind {
+        kind.into()
+    }
+}
+
+pub type SyntaxNode = rowan::SyntaxNode<BalsaLanguage>;
+pub type SyntaxToken = rowan::SyntaxToken<BalsaLanguage>;
+pub type SyntaxElement = rowan::SyntaxElement<BalsaLanguage>;
+
+/// Parses the given source text, and builds its concrete syntax tree (see
+/// [`build`]).
+///
+/// Parsing never fails: a source file which doesn't parse is a
+/// [`SyntaxKind::ErrorNode`] of its tokens, where unrecognized input is kept
+/// as [`SyntaxKind::Error`] tokens.
+pub fn parse(source: &str) -> SyntaxNode {
+    match parser::ProgramParser::new().parse(Lexer::new(source)) {
+        Ok(program) => build(source, &program),
+        Err(_) => Builder::new(source).source_file(|builder| {
+            let span = Span {
+                start: 0,
+                end: source.len(),
+            };
+            builder.node(SyntaxKind::ErrorNode, span, |_| {})
+        }),
+    }
+}
+
+/// Builds the concrete syntax tree of the given source text from the program
+/// which the parser produced from it, whose nodes are those of its items,
+/// statements, types and expressions (and of their parts, e.g. parameters),
+/// spanning the same text.
+pub fn build(source: &str, program: &Block) -> SyntaxNode {
+    Builder::new(source).source_file(|builder| builder.statements(program))
+}
+
+/// Builds a concrete syntax tree by adding the nodes of a program in the order
+/// of their source text, each along with the tokens which it spans.
+struct Builder<'a> {
+    builder: GreenNodeBuilder<'static>,
+    /// The kind, text and start of each token of the source, including trivia
+    tokens: Vec<(SyntaxKind, &'a str, usize)>,
+    /// The index of the next token to add
+    next: usize,
+}
+
+impl<'a> Builder<'a> {
+    fn new(source: &'a str) -> Self {
+        let mut tokens = Vec::new();
+        let mut offset = 0;
+        for spanned in Lexer::with_comments(source) {
+            let (start, kind, end) = match spanned {
+                Ok((start, token, end)) => (start, SyntaxKind::from(&token), end),
+                Err(InternalParserError::UnrecognizedToken { start, end })
+                | Err(InternalParserError::InvalidFloatLiteral { start, end }) => {
+                    (start, SyntaxKind::Error, end)
+                }
+                Err(error) => unreachable!("the lexer doesn't produce {:?}", error),
+            };
+            whitespace(&mut tokens, source, offset, start);
+            tokens.push((kind, &source[start..end], start));
+            offset = end;
+        }
+        whitespace(&mut tokens, source, offset, source.len());
+        Self {
+            builder: GreenNodeBuilder::new(),
+            tokens,
+            next: 0,
+        }
+    }
+
+    /// Builds the tree of a source file, whose nodes are added by `children`.
+    fn source_file(mut self, children: impl FnOnce(&mut Self)) -> SyntaxNode {
+        self.builder.start_node(SyntaxKind::SourceFile.into());
+        children(&mut self);
+        self.tokens_until(usize::MAX);
+        self.builder.finish_node();
+        SyntaxNode::new_root(self.builder.finish())
+    }
+
+    /// Adds the next token to the current node.
+    fn token(&mut self) {
+        let (kind, text, _) = self.tokens[self.next];
+        self.builder.token(kind.into(), text);
+        self.next += 1;
+    }
+
+    /// Adds the tokens ending by the given offset to the current node.
+    fn tokens_until(&mut self, offset: usize) {
+        while matches!(
+            self.tokens.get(self.next),
+            Some(&(_, text, start)) if start + text.len() <= offset
+        ) {
+            self.token();
+        }
+    }
+
+    /// Adds a node of the given span, whose children are added by `children`.
+    ///
+    /// The tokens preceding the node are added to the current node, except
+    /// for the leading comments of a documented node (see
+    /// [`Builder::leading_comments`]).
+    fn node(
+        &mut self,
+        kind: SyntaxKind,
+        span: Span,
+        children: impl FnOnce(&mut Self),
+    ) {
+        if kind.is_documented() {
+            let first = self.leading_comments(span.start);
+            while self.next < first {
+                self.token();
+            }
+        } else {
+            self.tokens_until(span.start);
+        }
+        self.builder.start_node(kind.into());
+        children(self);
+        self.tokens_until(span.end);
+        self.builder.finish_node();
+    }
+
+    /// The index of the first of the comments (and the trivia following them)
+    /// which immediately precede the node starting at `start`, other than
+    /// inner doc comments, which document the node enclosing them, and the
+    /// comments on the line of the preceding token (e.g. the `// one` of
+    /// `x := 1; // one`).
+    fn leading_comments(&self, start: usize) -> usize {
+        let mut end = self.next;
+        while matches!(
+            self.tokens.get(end),
+            Some(&(_, text, token_start)) if token_start + text.len() <= start
+        ) {
+            end += 1;
+        }
+
+        let mut first = end;
+        while first > self.next
+            && self.tokens[first - 1].0.is_trivia()
+            && !matches!(
+                self.tokens[first - 1].0,
+                SyntaxKind::InnerLineDocComment | SyntaxKind::InnerBlockDocComment
+            )
+        {
+            first -= 1;
+        }
+        let mut same_line = first > 0;
+        while first < end {
+            match self.tokens[first].0 {
+                SyntaxKind::Newline => same_line = false,
+                SyntaxKind::LineComment | SyntaxKind::BlockComment if same_line => {}
+                kind if kind.whitespace().is_some() => {}
+                _ => break,
+            }
+            first += 1;
+        }
+        first
+    }
+
+    /// Adds the statements of a block and its value, if any.
+    fn statements(&mut self, block: &Block) {
+        for statement in &block.statements {
+            self.statement(statement);
+        }
+        if let Some(value) = &block.value {
+            self.expression(value);
+        }
+    }
+
+    fn block(&mut self, block: &Block) {
+        self.node(SyntaxKind::Block, block.span, |builder| builder.statements(block));
+    }
+
+    fn statement(&mut self, statement: &Statement) {
+        let span = statement.span();
+        match statement {
+            Statement::Declaration { ty, value, .. } => {
+                self.node(SyntaxKind::Declaration, span, |builder| {
+                    if let Some(ty) = ty {
+                        builder.ty(ty);
+                    }
+                    builder.expression(value);
+                })
+            }
+            Statement::Expression { expression, .. } => {
+                self.node(SyntaxKind::ExpressionStatement, span, |builder| {
+                    builder.expression(expression)
+                })
+            }
+            Statement::Return { value, .. } => {
+                self.node(SyntaxKind::ReturnStatement, span, |builder| {
+                    if let Some(value) = value {
+                        builder.expression(value);
+                    }
+                })
+            }
+            Statement::Assertion { condition, .. } => {
+                self.node(SyntaxKind::Assertion, span, |builder| {
+                    builder.expression(condition)
+                })
+            }
+            Statement::Item(item) => self.item(item),
+        }
+    }
+
+    fn item(&mut self, item: &Item) {
+        match item {
+            Item::Function(function) => self.function(function),
+            Item::Struct(structure) => {
+                self.node(SyntaxKind::Struct, structure.span, |builder| {
+                    builder.fields(&structure.fields)
+                })
+            }
+            Item::Enum(enumeration) => {
+                self.node(SyntaxKind::Enum, enumeration.span, |builder| {
+                    for variant in &enumeration.variants {
+                        builder.node(SyntaxKind::Variant, variant.span, |builder| {
+                            builder.fields(&variant.fields)
+                        });
+                    }
+                })
+            }
+            Item::Trait(declaration) => {
+                self.node(SyntaxKind::Trait, declaration.span, |builder| {
+                    builder.where_clause(&declaration.where_clause);
+                    for method in &declaration.methods {
+                        builder.function(method);
+                    }
+                })
+            }
+            Item::Impl(implementation) => {
+                self.node(SyntaxKind::Impl, implementation.span, |builder| {
+                    if let Some(trait_type) = &implementation.trait_type {
+                        builder.ty(trait_type);
+                    }
+                    builder.ty(&implementation.self_type);
+                    builder.where_clause(&implementation.where_clause);
+                    for method in &implementation.methods {
+                        builder.function(method);
+                    }
+                })
+            }
+        }
+    }
+
+    fn function(&mut self, function: &Function) {
+        self.node(SyntaxKind::Function, function.span, |builder| {
+            for parameter in &function.parameters {
+                builder.node(SyntaxKind::Parameter, parameter.span, |builder| {
+                    builder.ty(&parameter.ty)
+                });
+            }
+            if let Some(return_type) = &function.return_type {
+                builder.ty(return_type);
+            }
+            builder.where_clause(&function.where_clause);
+            if let Some(body) = &function.body {
+                builder.block(body);
+            }
+        })
+    }
+
+    fn where_clause(&mut self, where_clause: &[WherePredicate]) {
+        for predicate in where_clause {
+            self.node(SyntaxKind::WherePredicate, predicate.span, |builder| {
+                builder.ty(&predicate.ty);
+                for bound in &predicate.bounds {
+                    builder.ty(bound);
+                }
+            });
+        }
+    }
+
+    fn fields(&mut self, fields: &Fields) {
+        match fields {
+            Fields::Named(fields) => {
+                for field in fields {
+                    self.node(SyntaxKind::Field, field.span, |builder| {
+                        builder.ty(&field.ty)
+                    });
+                }
+            }
+            Fields::Tuple(types) => {
+                for ty in types {
+                    self.ty(ty);
+                }
+            }
+            Fields::Unit => {}
+        }
+    }
+
+    fn ty(&mut self, ty: &Type) {
+        match ty {
+            Type::Named { arguments, span, .. } => {
+                self.node(SyntaxKind::NamedType, *span, |builder| {
+                    for argument in arguments {
+                        builder.ty(argument);
+                    }
+                })
+            }
+            Type::SelfType { span } => self.node(SyntaxKind::SelfType, *span, |_| {}),
+        }
+    }
+
+    fn binding(&mut self, binding: &Binding) {
+        self.node(SyntaxKind::Binding, binding.span, |builder| {
+            builder.expression(&binding.domain)
+        });
+    }
+
+    /// Adds the node of an expression, whose operands are added in the order
+    /// of their source text.
+    fn expression(&mut self, expression: &Expression) {
+        let span = expression.span();
+        let kind = match expression {
+            Expression::Literal { .. } => SyntaxKind::LiteralExpression,
+            Expression::Identifier { .. } => SyntaxKind::IdentifierExpression,
+            Expression::SelfValue { .. } => SyntaxKind::SelfExpression,
+            Expression::Path { .. } => SyntaxKind::PathExpression,
+            Expression::Grouped { .. } => SyntaxKind::GroupedExpression,
+            Expression::Unary { .. } => SyntaxKind::UnaryExpression,
+            Expression::Binary { .. } => SyntaxKind::BinaryExpression,
+            Expression::Root { .. } => SyntaxKind::RootExpression,
+            Expression::Set { .. } => SyntaxKind::SetExpression,
+            Expression::List { .. } => SyntaxKind::ListExpression,
+            Expression::Comprehension { .. } => SyntaxKind::ComprehensionExpression,
+            Expression::Call { .. } => SyntaxKind::CallExpression,
+            Expression::Struct { .. } => SyntaxKind::StructExpression,
+            Expression::Field { .. } => SyntaxKind::FieldExpression,
+            Expression::MethodCall { .. } => SyntaxKind::MethodCallExpression,
+            Expression::BigOperator { .. } => SyntaxKind::BigOperatorExpression,
+            Expression::Integral { .. } => SyntaxKind::IntegralExpression,
+            Expression::Quantifier { .. } => SyntaxKind::QuantifierExpression,
+            Expression::Assignment { .. } => SyntaxKind::AssignmentExpression,
+        };
+        self.node(kind, span, |builder| match expression {
+            // The bindings and field initializers of an expression are nodes
+            // of their own, unlike its other parts
+            Expression::Comprehension {
+                element, clauses, ..
+            } => {
+                builder.expression(element);
+                for clause in clauses {
+                    match clause {
+                        Clause::Generator(binding) => builder.binding(binding),
+                        Clause::Filter(condition) => builder.expression(condition),
+                    }
+                }
+            }
+            Expression::Struct { fields, .. } => {
+                for field in fields {
+                    builder.node(SyntaxKind::FieldInit, field.span, |builder| {
+                        builder.expression(&field.value)
+                    });
+                }
+            }
+            Expression::BigOperator { binding, body, .. } => {
+                builder.binding(binding);
+                builder.expression(body);
+            }
+            Expression::Integral {
+                binding, integrand, ..
+            } => {
+                builder.binding(binding);
+                builder.expression(integrand);
+            }
+            Expression::Quantifier {
+                binding, predicate, ..
+            } => {
+                builder.binding(binding);
+                builder.expression(predicate);
+            }
+            expression => {
+                for operand in expression.operands() {
+                    builder.expression(operand);
+                }
+            }
+        });
+    }
+}
+
+/// Adds the whitespace skipped by the lexer between two tokens (from `start`
+/// to `end`), as one token per run of the same whitespace character (e.g.
+/// `\r\n\t\t` is a carriage return, a newline, and a run of two tabs).
+fn whitespace<'a>(
+    tokens: &mut Vec<(SyntaxKind, &'a str, usize)>,
+    source: &'a str,
+    start: usize,
+    end: usize,
+) {
+    let text = &source[start..end];
+    let mut offset = 0;
+    while let Some(c) = text[offset..].chars().next() {
+        let len = text[offset..]
+            .find(|other| other != c)
+            .unwrap_or(text.len() - offset);
+        let kind = match c {
+            ' ' => SyntaxKind::Space,
+            '\t' => SyntaxKind::Tab,
+            '\n' => SyntaxKind::Newline,
+            '\r' => SyntaxKind::CarriageReturn,
+            '\x0B' => SyntaxKind::VerticalTab,
+            '\x0C' => SyntaxKind::FormFeed,
+            _ => SyntaxKind::Error,
+        };
+        tokens.push((kind, &text[offset..offset + len], start + offset));
+        offset += len;
+    }
+}
+
+/// The comment a comment token stands for.
+pub fn comment(token: &SyntaxToken) -> Option<Comment> {
+    let comment = String::from(token.text());
+    match token.kind() {
+        SyntaxKind::LineComment => Some(Comment::LineComment { comment }),
+        SyntaxKind::BlockComment => Some(Comment::BlockComment { comment }),
+        SyntaxKind::InnerLineDocComment => {
+            Some(Comment::DocComment(DocComment::InnerLineDocComment {
+                comment,
+            }))
+        }
+        SyntaxKind::InnerBlockDocComment => {
+            Some(Comment::DocComment(DocComment::InnerBlockDocComment {
+                comment,
+            }))
+        }
+        SyntaxKind::OuterLineDocComment => {
+            Some(Comment::DocComment(DocComment::OuterLineDocComment {
+                comment,
+            }))
+        }
+        SyntaxKind::OuterBlockDocComment => {
+            Some(Comment::DocComment(DocComment::OuterBlockDocComment {
+                comment,
+            }))
+        }
+        _ => None,
+    }
+}
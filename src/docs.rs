@@ -0,0 +1,173 @@
+//! Attaches doc comments to the items they document.
+//!
+//! Outer doc comments (e.g. `/// ...`, `/** ... */`) document the item,
+//! variant or field which follows them, while inner doc comments (e.g.
+//! `//! ...`, `/*! ... */`) document the item (e.g. the function, trait or
+//! impl) which encloses them, or else the module (i.e. the source file).
+//!
+//! The doc comments are found in the concrete syntax tree of the source file
+//! (see [`crate::cst`]), where the outer doc comments preceding an item,
+//! variant or field belong to its node, and inner doc comments to the node of
+//! the item enclosing them.
+
+use std::collections::HashMap;
+
+use crate::{
+    ast::{Block, Comment, DocComment, Fields, Function, Item, Statement},
+    cst::{self, SyntaxElement, SyntaxNode, SyntaxToken},
+};
+
+/// The doc comments of a source file, by the start of the item, variant or
+/// field which they document, which are claimed by the items of its program
+/// as they're attached.
+struct DocComments {
+    /// The outer doc comments preceding each item, variant or field
+    outer: HashMap<usize, Vec<DocComment>>,
+    /// The inner doc comments within each item, but not within its nested
+    /// items
+    inner: HashMap<usize, Vec<DocComment>>,
+}
+
+/// Attaches the doc comments of a source file, given by its concrete syntax
+/// tree, to the items, variants and fields of its program, and to the program
+/// itself (see [`Block::docs`]).
+///
+/// Outer doc comments which aren't followed by an item, variant or field
+/// (e.g. those preceding a statement) document nothing, and are dropped.
+pub fn attach_doc_comments(tree: &SyntaxNode, program: &mut Block) {
+    let mut comments = DocComments {
+        outer: HashMap::new(),
+        inner: HashMap::new(),
+    };
+    for node in tree.descendants().filter(|node| node.kind().is_documented()) {
+        let mut outer_docs = Vec::new();
+        for element in node.children_with_tokens() {
+            let token = match element.into_token() {
+                Some(token) if token.kind().is_trivia() => token,
+                _ => break,
+            };
+            if let Some(
+                doc_comment @ (DocComment::OuterLineDocComment { .. }
+                | DocComment::OuterBlockDocComment { .. }),
+            ) = doc_comment(&token)
+            {
+                outer_docs.push(doc_comment);
+            }
+        }
+        comments.outer.insert(start(&node), outer_docs);
+    }
+
+    let mut module_docs = Vec::new();
+    for token in tree
+        .descendants_with_tokens()
+        .filter_map(SyntaxElement::into_token)
+    {
+        let docs = match doc_comment(&token) {
+            Some(
+                doc_comment @ (DocComment::InnerLineDocComment { .. }
+                | DocComment::InnerBlockDocComment { .. }),
+            ) => doc_comment,
+            _ => continue,
+        };
+        match token.parent_ancestors().find(|node| node.kind().is_item()) {
+            Some(item) => comments.inner.entry(start(&item)).or_default().push(docs),
+            None => module_docs.push(docs),
+        }
+    }
+
+    block(program, &mut comments);
+    program.docs = module_docs;
+}
+
+/// The doc comment which a token is, if any.
+fn doc_comment(token: &SyntaxToken) -> Option<DocComment> {
+    match cst::comment(token)? {
+        Comment::DocComment(doc_comment) => Some(doc_comment),
+        _ => None,
+    }
+}
+
+/// The start of a node's first token which isn't trivia, i.e. that of the
+/// span of the AST node which it stands for.
+fn start(node: &SyntaxNode) -> usize {
+    node.children_with_tokens()
+        .find(|element| !element.kind().is_trivia())
+        .map_or(node.text_range().end(), |element| element.text_range().start())
+        .into()
+}
+
+fn block(block: &mut Block, comments: &mut DocComments) {
+    for statement in &mut block.statements {
+        if let Statement::Item(item) = statement {
+            self::item(item, comments);
+        }
+    }
+}
+
+fn item(item: &mut Item, comments: &mut DocComments) {
+    let span = item.span();
+    let docs = match item {
+        Item::Function(function) => {
+            self::function(function, comments);
+            return;
+        }
+        Item::Struct(structure) => {
+            fields(&mut structure.fields, comments);
+            &mut structure.docs
+        }
+        Item::Enum(enumeration) => {
+            for variant in &mut enumeration.variants {
+                variant.docs = comments.outer(variant.span.start);
+                fields(&mut variant.fields, comments);
+            }
+            &mut enumeration.docs
+        }
+        Item::Trait(declaration) => {
+            declaration
+                .methods
+                .iter_mut()
+                .for_each(|method| function(method, comments));
+            &mut declaration.docs
+        }
+        Item::Impl(implementation) => {
+            implementation
+                .methods
+                .iter_mut()
+                .for_each(|method| function(method, comments));
+            &mut implementation.docs
+        }
+    };
+    *docs = comments.outer(span.start);
+    docs.extend(comments.inner(span.start));
+}
+
+/// Attaches the doc comments of a function (or method), after those of the
+/// items nested within its body.
+fn function(function: &mut Function, comments: &mut DocComments) {
+    if let Some(body) = &mut function.body {
+        block(body, comments);
+    }
+    function.docs = comments.outer(function.span.start);
+    function.docs.extend(comments.inner(function.span.start));
+}
+
+fn fields(fields: &mut Fields, comments: &mut DocComments) {
+    if let Fields::Named(fields) = fields {
+        for field in fields {
+            field.docs = comments.outer(field.span.start);
+        }
+    }
+}
+
+impl DocComments {
+    /// Claims the outer doc comments preceding the item, variant or field at
+    /// `start`.
+    fn outer(&mut self, start: usize) -> Vec<DocComment> {
+        self.outer.remove(&start).unwrap_or_default()
+    }
+
+    /// Claims the inner doc comments of the item at `start`.
+    fn inner(&mut self, start: usize) -> Vec<DocComment> {
+        self.inner.remove(&start).unwrap_or_default()
+    }
+}
@@ -0,0 +1,1228 @@
+//! Evaluation of constant expressions.
+//!
+//! Numbers are evaluated exactly where possible (see [`crate::number`]), and
+//! quantities are checked for consistent dimensions (see [`crate::units`]),
+//! where a plain number is a dimensionless quantity (e.g. `1 m + 1` is an
+//! error). Logic values follow three-valued (Kleene) logic, in which `missing`
+//! is an unknown truth value (e.g. `missing && false` is `false`, while
+//! `missing && true` is `missing`).
+//!
+//! Expressions are evaluated in an [`Environment`] of variables, which big
+//! operators (e.g. `∑(i in 1..=n) i²`), quantifiers (e.g. `∀ x ∈ S: x > 0`)
+//! and the generators of comprehensions (e.g. `{x² | x ∈ 1..10}`, which are
+//! evaluated as nested loops) bind their variables in. Integrals are computed
+//! numerically (see [`crate::quadrature`]), to within the tolerance of the
+//! environment.
+//!
+//! Compilation evaluates the constant expressions of a program (see [`check`]),
+//! and so reports their errors (e.g. `x := 1 m + 1 s;`). Those which depend on
+//! values that aren't constant (e.g. the parameters of a function, or a
+//! variable which is assigned to), or whose loops iterate more than
+//! [`MAX_ITERATIONS`] times (e.g. `∑(i in 1..=10^12) i`), are left to run time.
+
+use std::{
+    cmp::Ordering,
+    collections::{HashMap, HashSet},
+};
+
+use rug::{Complex, Integer};
+
+use crate::{
+    ast::{
+        BigOperator, BinaryOperator, Binding, Block, Clause, ComprehensionKind,
+        Enum, Expression, Function, Identifier, Impl, Item, LiteralExpression,
+        LogicLiteral, Quantifier, Span, Statement, StringLiteral, Struct, Trait,
+        Type, UnaryOperator,
+    },
+    number::{self, Number},
+    parser_errors::InternalParserError,
+    quadrature::{self, QuadratureError},
+    set::{MismatchedTypes, Set},
+    units::{IncompatibleDimensions, Quantity, Unit},
+};
+
+/// The value of an expression.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Value {
+    Number(Number),
+    Quantity(Quantity),
+    /// A truth value, where `None` is `missing`
+    Logic(Option<bool>),
+    String(StringLiteral),
+    Char(char),
+    Byte(u8),
+    Set(Set),
+    List(Vec<Value>),
+}
+
+impl Value {
+    /// The name of the value's type, as reported by errors.
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            Value::Number(Number::Complex(_)) => "complex",
+            Value::Number(_) => "number",
+            Value::Quantity(_) => "quantity",
+            Value::Logic(_) => "logic",
+            Value::String(StringLiteral::String { .. }) => "string",
+            Value::String(StringLiteral::ByteString { .. }) => "byte string",
+            Value::Char(_) => "char",
+            Value::Byte(_) => "byte",
+            Value::Set(_) => "set",
+            Value::List(_) => "list",
+        }
+    }
+
+    /// The value as a quantity, if it's a quantity or a real number (which is
+    /// dimensionless).
+    fn to_quantity(&self) -> Option<Quantity> {
+        match self {
+            Value::Quantity(quantity) => Some(*quantity),
+            Value::Number(n) if !n.is_complex() => {
+                Some(Quantity::new(n.to_f64(), Unit::DIMENSIONLESS))
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Evaluates a constant expression, reporting errors with the byte range of the
+/// offending (sub)expression.
+///
+/// Unbound identifiers, assignments and ranges aren't constants, and neither
+/// are expressions whose loops iterate more than [`MAX_ITERATIONS`] times, and
+/// are reported as [`InternalParserError::NotConstant`].
+pub fn evaluate(expression: &Expression) -> Result<Value, InternalParserError> {
+    Environment::default().evaluate(expression)
+}
+
+/// Checks a program by evaluating its constant expressions: the values of its
+/// declarations, expression statements and returns, at the top level and in
+/// the body of each function and method, whose integrals are computed to
+/// within the given tolerance (see [`Environment::with_tolerance`]).
+///
+/// A declaration binds its variable to its value when that's constant, unless
+/// the variable is assigned to anywhere in the program. Expressions which
+/// aren't constant aren't errors, and neither are calls of the functions,
+/// tuple structs, variants and associated functions which the program declares.
+pub fn check(program: &Block, tolerance: f64) -> Result<(), InternalParserError> {
+    let mut names = HashSet::new();
+    let mut assigned = HashSet::new();
+    collect_names(program, &mut names, &mut assigned);
+    let environment = Environment {
+        declared: names,
+        ..Environment::default().with_tolerance(tolerance)
+    };
+    check_block(program, &environment, &assigned)
+}
+
+/// Collects the names which a block declares (items, variables and
+/// parameters), and those of the variables which it assigns to.
+fn collect_names<'a>(
+    block: &'a Block,
+    names: &mut HashSet<String>,
+    assigned: &mut HashSet<&'a str>,
+) {
+    let mut expressions = Vec::new();
+    for statement in &block.statements {
+        match statement {
+            Statement::Declaration { name, value, .. } => {
+                names.insert(name.name.clone());
+                expressions.push(value);
+            }
+            Statement::Expression { expression, .. }
+            | Statement::Return {
+                value: Some(expression),
+                ..
+            }
+            | Statement::Assertion {
+                condition: expression,
+                ..
+            } => expressions.push(expression),
+            Statement::Return { value: None, .. } => {}
+            Statement::Item(item) => {
+                match item {
+                    Item::Function(Function { name, .. })
+                    | Item::Struct(Struct { name, .. }) => {
+                        names.insert(name.name.clone());
+                    }
+                    // Variants and associated functions are called through
+                    // their paths (e.g. `Shape::Circle(1.0)`, `Circle::new(1)`)
+                    Item::Enum(Enum { name, variants, .. }) => {
+                        names.extend(variants.iter().map(|variant| {
+                            format!("{}::{}", name.name, variant.name.name)
+                        }));
+                    }
+                    Item::Trait(Trait { name, methods, .. })
+                    | Item::Impl(Impl {
+                        self_type: Type::Named { name, .. },
+                        methods,
+                        ..
+                    }) => {
+                        names.extend(methods.iter().map(|method| {
+                            format!("{}::{}", name.name, method.name.name)
+                        }));
+                    }
+                    Item::Impl(_) => {}
+                }
+                for function in item.functions() {
+                    names.extend(
+                        function
+                            .parameters
+                            .iter()
+                            .map(|parameter| parameter.name.name.clone()),
+                    );
+                    if let Some(body) = &function.body {
+                        collect_names(body, names, assigned);
+                    }
+                }
+            }
+        }
+    }
+    expressions.extend(block.value.as_deref());
+    while let Some(expression) = expressions.pop() {
+        if let Expression::Assignment { target, .. } = expression {
+            assigned.insert(&target.name);
+        }
+        expressions.extend(expression.operands());
+    }
+}
+
+/// Evaluates the constant expressions of a block, in a new scope of the
+/// environment, and checks the bodies of the functions which it declares.
+fn check_block(
+    block: &Block,
+    environment: &Environment,
+    assigned: &HashSet<&str>,
+) -> Result<(), InternalParserError> {
+    let mut scope = environment.clone();
+    for statement in &block.statements {
+        match statement {
+            Statement::Declaration { name, value, .. } => {
+                match constant(scope.evaluate_constant(value))? {
+                    Some(value) if !assigned.contains(name.name.as_str()) => {
+                        scope.variables.insert(name.name.clone(), value);
+                    }
+                    _ => {
+                        scope.variables.remove(&name.name);
+                    }
+                }
+            }
+            Statement::Expression { expression, .. }
+            | Statement::Return {
+                value: Some(expression),
+                ..
+            } => {
+                constant(scope.evaluate_constant(expression))?;
+            }
+            // A constant assertion must hold, where `missing` doesn't (see
+            // `Environment::condition`)
+            Statement::Assertion { condition, .. } => {
+                let span = condition.span();
+                match constant(scope.evaluate_constant(condition))? {
+                    Some(Value::Logic(Some(true))) | None => {}
+                    Some(Value::Logic(_)) => {
+                        return Err(InternalParserError::FailedAssertion {
+                            start: span.start,
+                            end: span.end,
+                        })
+                    }
+                    Some(value) => {
+                        return Err(InternalParserError::InvalidCondition {
+                            found: value.type_name(),
+                            start: span.start,
+                            end: span.end,
+                        })
+                    }
+                }
+            }
+            Statement::Return { value: None, .. } => {}
+            Statement::Item(item) => {
+                // Functions don't capture the variables of their enclosing
+                // block
+                let environment = Environment {
+                    variables: HashMap::new(),
+                    ..environment.clone()
+                };
+                for body in item
+                    .functions()
+                    .iter()
+                    .filter_map(|function| function.body.as_ref())
+                {
+                    check_block(body, &environment, assigned)?;
+                }
+            }
+        }
+    }
+    match &block.value {
+        Some(value) => constant(scope.evaluate_constant(value)).map(|_| ()),
+        None => Ok(()),
+    }
+}
+
+/// The value of an expression if it's constant, or the error of evaluating it.
+fn constant(
+    result: Result<Value, InternalParserError>,
+) -> Result<Option<Value>, InternalParserError> {
+    match result {
+        Ok(value) => Ok(Some(value)),
+        Err(InternalParserError::NotConstant { .. }) => Ok(None),
+        Err(error) => Err(error),
+    }
+}
+
+/// The largest number of iterations of the loops of a constant expression
+/// (e.g. of big operators), beyond which it's left to run time.
+pub const MAX_ITERATIONS: u64 = 1 << 20;
+
+/// The built-in functions of one number, by name, as their real and complex
+/// definitions (see [`Number::apply`]).
+#[allow(clippy::type_complexity)]
+const FUNCTIONS: [(&str, fn(f64) -> f64, fn(Complex) -> Complex); 11] = [
+    ("sin", f64::sin, Complex::sin),
+    ("cos", f64::cos, Complex::cos),
+    ("tan", f64::tan, Complex::tan),
+    ("asin", f64::asin, Complex::asin),
+    ("acos", f64::acos, Complex::acos),
+    ("atan", f64::atan, Complex::atan),
+    ("sinh", f64::sinh, Complex::sinh),
+    ("cosh", f64::cosh, Complex::cosh),
+    ("tanh", f64::tanh, Complex::tanh),
+    ("exp", f64::exp, Complex::exp),
+    ("ln", f64::ln, Complex::ln),
+];
+
+/// The values of the variables in scope.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Environment {
+    variables: HashMap<String, Value>,
+    /// The names (and paths, e.g. `Shape::Circle`) declared by the program
+    /// being checked (see [`check`]), whose calls aren't constant
+    declared: HashSet<String>,
+    /// The error tolerance of integrals (see [`quadrature::integrate`])
+    tolerance: f64,
+    /// The number of loop iterations evaluated so far (see [`MAX_ITERATIONS`])
+    iterations: u64,
+}
+
+impl Default for Environment {
+    fn default() -> Self {
+        Environment {
+            variables: HashMap::new(),
+            declared: HashSet::new(),
+            tolerance: quadrature::DEFAULT_TOLERANCE,
+            iterations: 0,
+        }
+    }
+}
+
+impl Environment {
+    /// The environment, with the given error tolerance of integrals.
+    pub fn with_tolerance(self, tolerance: f64) -> Environment {
+        Environment { tolerance, ..self }
+    }
+
+    /// The value of the variable of the given name, if it's in scope.
+    pub fn get(&self, name: &str) -> Option<&Value> {
+        self.variables.get(name)
+    }
+
+    /// Evaluates a constant expression in this environment, whose loops may
+    /// iterate up to [`MAX_ITERATIONS`] times in all.
+    fn evaluate_constant(
+        &mut self,
+        expression: &Expression,
+    ) -> Result<Value, InternalParserError> {
+        self.iterations = 0;
+        self.evaluate(expression)
+    }
+
+    /// Evaluates an expression in this environment (see [`evaluate`]).
+    pub fn evaluate(
+        &mut self,
+        expression: &Expression,
+    ) -> Result<Value, InternalParserError> {
+        match expression {
+            Expression::Literal { literal, .. } => Ok(literal_value(literal)),
+            Expression::Identifier { identifier, span } => self
+                .get(&identifier.name)
+                .cloned()
+                .ok_or(InternalParserError::NotConstant {
+                    start: span.start,
+                    end: span.end,
+                }),
+            Expression::Grouped { expression, .. } => self.evaluate(expression),
+            Expression::Unary {
+                operator,
+                operand,
+                span,
+            } => unary(*operator, self.evaluate(operand)?, *span),
+            Expression::Binary {
+                left,
+                operator: operator @ (BinaryOperator::And | BinaryOperator::Or),
+                right,
+                span,
+            } => {
+                // `false && x` and `true || x` are decided by their left operand
+                let left = self.evaluate(left)?;
+                if left == Value::Logic(Some(*operator == BinaryOperator::Or)) {
+                    return Ok(left);
+                }
+                binary(left, *operator, self.evaluate(right)?, *span)
+            }
+            Expression::Binary {
+                left,
+                operator,
+                right,
+                span,
+            } => {
+                let left = self.evaluate(left)?;
+                binary(left, *operator, self.evaluate(right)?, *span)
+            }
+            Expression::Root {
+                index,
+                radicand,
+                span,
+            } => {
+                let index = self.evaluate(index)?;
+                root(index, self.evaluate(radicand)?, *span)
+            }
+            Expression::Set { elements, .. } => {
+                let mut set = Set::new();
+                for element in elements {
+                    let value = self.evaluate(element)?;
+                    set.insert(value).map_err(|error| {
+                        mismatched_set_types(error, element.span())
+                    })?;
+                }
+                Ok(Value::Set(set))
+            }
+            Expression::List { elements, .. } => elements
+                .iter()
+                .map(|element| self.evaluate(element))
+                .collect::<Result<_, _>>()
+                .map(Value::List),
+            Expression::Comprehension {
+                kind,
+                element,
+                clauses,
+                ..
+            } => self.comprehension(*kind, element, clauses),
+            Expression::Call {
+                function,
+                arguments,
+                span,
+            } => match &**function {
+                Expression::Identifier { identifier, .. } => {
+                    self.call(identifier, arguments, *span)
+                }
+                Expression::Path { segments, .. } => {
+                    let path = segments
+                        .iter()
+                        .map(|segment| segment.name.as_str())
+                        .collect::<Vec<_>>()
+                        .join("::");
+                    if self.declared.contains(&path) {
+                        Err(InternalParserError::NotConstant {
+                            start: span.start,
+                            end: span.end,
+                        })
+                    } else {
+                        Err(InternalParserError::UnknownFunction {
+                            start: span.start,
+                            end: span.end,
+                        })
+                    }
+                }
+                _ => Err(InternalParserError::UnknownFunction {
+                    start: span.start,
+                    end: span.end,
+                }),
+            },
+            Expression::BigOperator {
+                operator,
+                binding,
+                body,
+                span,
+            } => self.big_operator(*operator, binding, body, *span),
+            Expression::Integral {
+                binding,
+                integrand,
+                span,
+            } => self.integral(binding, integrand, *span),
+            Expression::Quantifier {
+                quantifier,
+                binding,
+                predicate,
+                ..
+            } => self.quantifier(*quantifier, binding, predicate),
+            // Items aren't evaluated, and so neither are the structs and
+            // variants which they declare, nor their methods
+            Expression::SelfValue { span }
+            | Expression::Path { span, .. }
+            | Expression::Struct { span, .. }
+            | Expression::Field { span, .. }
+            | Expression::MethodCall { span, .. }
+            | Expression::Assignment { span, .. } => {
+                Err(InternalParserError::NotConstant {
+                    start: span.start,
+                    end: span.end,
+                })
+            }
+        }
+    }
+
+    /// Calls a built-in function: `sqrt` or `abs`, which are exact for exact
+    /// numbers (e.g. `sqrt(4)` is `2`) and apply to quantities, or one of
+    /// [`FUNCTIONS`].
+    fn call(
+        &mut self,
+        function: &Identifier,
+        arguments: &[Expression],
+        span: Span,
+    ) -> Result<Value, InternalParserError> {
+        if self.declared.contains(&function.name) {
+            return Err(InternalParserError::NotConstant {
+                start: span.start,
+                end: span.end,
+            });
+        }
+        let definition =
+            FUNCTIONS.iter().find(|(name, _, _)| *name == function.name);
+        let name = match definition {
+            Some((name, _, _)) => *name,
+            None => ["sqrt", "abs"]
+                .iter()
+                .find(|name| **name == function.name)
+                .copied()
+                .ok_or(InternalParserError::UnknownFunction {
+                    start: span.start,
+                    end: span.end,
+                })?,
+        };
+        let argument = match arguments {
+            [argument] => self.evaluate(argument)?,
+            _ => {
+                return Err(InternalParserError::WrongArgumentCount {
+                    expected: 1,
+                    found: arguments.len(),
+                    start: span.start,
+                    end: span.end,
+                })
+            }
+        };
+        let invalid = |argument: &Value| InternalParserError::InvalidOperand {
+            operator: name,
+            operand: argument.type_name(),
+            start: span.start,
+            end: span.end,
+        };
+
+        match (name, argument) {
+            ("sqrt", Value::Number(n)) => Ok(Value::Number(
+                n.checked_root(Number::Integer(Integer::from(2)))
+                    .expect("the index of a square root isn't zero"),
+            )),
+            ("sqrt", Value::Quantity(q)) => q
+                .root(2)
+                .map(Value::Quantity)
+                .ok_or_else(|| invalid(&Value::Quantity(q))),
+            ("abs", Value::Number(n)) => Ok(Value::Number(n.abs())),
+            ("abs", Value::Quantity(q)) => {
+                Ok(Value::Quantity(Quantity::new(q.value.abs(), q.unit)))
+            }
+            (_, Value::Number(n)) => match definition {
+                Some((_, real, complex)) => {
+                    Ok(Value::Number(n.apply(*real, *complex)))
+                }
+                None => unreachable!("`{}` is defined", name),
+            },
+            (_, argument) => Err(invalid(&argument)),
+        }
+    }
+
+    /// Counts an iteration of a loop of the expression at `span`, which isn't
+    /// constant once the loops of the expression being evaluated have iterated
+    /// [`MAX_ITERATIONS`] times.
+    fn iterate(&mut self, span: Span) -> Result<(), InternalParserError> {
+        self.iterations += 1;
+        if self.iterations > MAX_ITERATIONS {
+            return Err(InternalParserError::NotConstant {
+                start: span.start,
+                end: span.end,
+            });
+        }
+        Ok(())
+    }
+
+    /// Checks that iterating over the range from `start` (inclusive) to `end`
+    /// (exclusive) wouldn't exceed the iterations left (see [`Self::iterate`]),
+    /// so that a long range isn't iterated only to give up.
+    fn check_range_length(
+        &self,
+        start: &Integer,
+        end: &Integer,
+        span: Span,
+    ) -> Result<(), InternalParserError> {
+        if Integer::from(end - start) > MAX_ITERATIONS - self.iterations {
+            return Err(InternalParserError::NotConstant {
+                start: span.start,
+                end: span.end,
+            });
+        }
+        Ok(())
+    }
+
+    /// Sums (or multiplies) the values of the body for each integer of the
+    /// binding's range, where the empty sum is `0` and the empty product `1`.
+    /// An exact result which grows too large isn't constant.
+    fn big_operator(
+        &mut self,
+        operator: BigOperator,
+        binding: &Binding,
+        body: &Expression,
+        span: Span,
+    ) -> Result<Value, InternalParserError> {
+        let (start, end) = self.integer_range(
+            operator.symbol(),
+            "a range of integers",
+            &binding.domain,
+        )?;
+        self.check_range_length(&start, &end, span)?;
+        let (identity, operator) = match operator {
+            BigOperator::Sum => (0, BinaryOperator::Add),
+            BigOperator::Product => (1, BinaryOperator::Multiply),
+        };
+
+        let mut result = None;
+        let mut i = start;
+        while i < end {
+            self.iterate(span)?;
+            let value = Value::Number(Number::Integer(i.clone()));
+            let term = self.with_variable(&binding.variable.name, value, |env| {
+                env.evaluate(body)
+            })?;
+            // A result too large to compute exactly (see
+            // [`number::MAX_EXACT_BITS`]) is left to run time, rather than
+            // rounded and combined with the rest of the range
+            if let (Some(Value::Number(result)), Value::Number(term)) =
+                (&result, &term)
+            {
+                if number::exceeds_exact(result, term) {
+                    return Err(InternalParserError::NotConstant {
+                        start: span.start,
+                        end: span.end,
+                    });
+                }
+            }
+            result = Some(match result {
+                Some(result) => binary(result, operator, term, span)?,
+                None => term,
+            });
+            i += 1;
+        }
+        Ok(result.unwrap_or_else(|| {
+            Value::Number(Number::Integer(Integer::from(identity)))
+        }))
+    }
+
+    /// The set (or list) of the values of the element for each solution of the
+    /// clauses, in the order in which they're found.
+    fn comprehension(
+        &mut self,
+        kind: ComprehensionKind,
+        element: &Expression,
+        clauses: &[Clause],
+    ) -> Result<Value, InternalParserError> {
+        match kind {
+            ComprehensionKind::Set => {
+                let mut set = Set::new();
+                self.for_each_solution(clauses, &mut |env| {
+                    set.insert(env.evaluate(element)?)
+                        .map(|_| ())
+                        .map_err(|error| {
+                            mismatched_set_types(error, element.span())
+                        })
+                })?;
+                Ok(Value::Set(set))
+            }
+            ComprehensionKind::List => {
+                let mut values = Vec::new();
+                self.for_each_solution(clauses, &mut |env| {
+                    values.push(env.evaluate(element)?);
+                    Ok(())
+                })?;
+                Ok(Value::List(values))
+            }
+        }
+    }
+
+    /// Calls `f` with the variables of the generators bound to each
+    /// combination of their values which satisfies the filters, as nested
+    /// loops over the generators in order, where each filter is tested as soon
+    /// as the variables before it are bound (e.g. `x ∈ S, x > 0, y ∈ T` skips
+    /// `T` for every `x` which isn't positive).
+    fn for_each_solution(
+        &mut self,
+        clauses: &[Clause],
+        f: &mut dyn FnMut(&mut Environment) -> Result<(), InternalParserError>,
+    ) -> Result<(), InternalParserError> {
+        match clauses.split_first() {
+            None => f(self),
+            Some((Clause::Generator(binding), clauses)) => {
+                for value in self.domain_values("∈", &binding.domain, true)? {
+                    self.iterate(binding.domain.span())?;
+                    self.with_variable(&binding.variable.name, value, |env| {
+                        env.for_each_solution(clauses, f)
+                    })?;
+                }
+                Ok(())
+            }
+            Some((Clause::Filter(condition), clauses)) => {
+                if self.condition(condition)? {
+                    self.for_each_solution(clauses, f)
+                } else {
+                    Ok(())
+                }
+            }
+        }
+    }
+
+    /// Whether the predicate holds for all (or some) of the values of the
+    /// binding, which is decided by the first value for which it doesn't (or
+    /// does) hold, without evaluating it for the rest. Otherwise, it's
+    /// `missing` if the predicate is `missing` for any value (e.g.
+    /// `∀ x ∈ {1, 2}: x < 2 || missing` is `missing`). Quantifiers which aren't
+    /// decided within the iterations left aren't constant.
+    fn quantifier(
+        &mut self,
+        quantifier: Quantifier,
+        binding: &Binding,
+        predicate: &Expression,
+    ) -> Result<Value, InternalParserError> {
+        // The value of the predicate which decides the quantifier
+        let decisive = quantifier == Quantifier::Exists;
+        let mut result = Some(!decisive);
+        for value in
+            self.domain_values(quantifier.symbol(), &binding.domain, false)?
+        {
+            self.iterate(binding.domain.span())?;
+            let holds =
+                self.with_variable(&binding.variable.name, value, |env| {
+                    env.logic(predicate)
+                })?;
+            match holds {
+                Some(holds) if holds == decisive => {
+                    return Ok(Value::Logic(Some(decisive)))
+                }
+                Some(_) => {}
+                None => result = None,
+            }
+        }
+        Ok(Value::Logic(result))
+    }
+
+    /// The values of the domain of a binding of the operator, which is a range
+    /// of integers (e.g. `1..10`, whose values are produced as they're needed),
+    /// a set or a list. A range which is iterated exhaustively (e.g. by a
+    /// generator, unlike a quantifier which may be decided by its first value)
+    /// mustn't exceed the iterations left.
+    fn domain_values(
+        &mut self,
+        operator: &'static str,
+        domain: &Expression,
+        exhaustive: bool,
+    ) -> Result<Box<dyn Iterator<Item = Value>>, InternalParserError> {
+        const EXPECTED: &str = "a range of integers, a set or a list";
+        if range_bounds(domain).is_some() {
+            let (start, end) = self.integer_range(operator, EXPECTED, domain)?;
+            if exhaustive {
+                self.check_range_length(&start, &end, domain.span())?;
+            }
+            let integers =
+                std::iter::successors(Some(start), |i| Some(i.clone() + 1))
+                    .take_while(move |i| *i < end);
+            return Ok(Box::new(
+                integers.map(|i| Value::Number(Number::Integer(i))),
+            ));
+        }
+        match self.evaluate(domain)? {
+            Value::Set(set) => {
+                let elements: Vec<_> = set.iter().cloned().collect();
+                Ok(Box::new(elements.into_iter()))
+            }
+            Value::List(list) => Ok(Box::new(list.into_iter())),
+            _ => Err(InternalParserError::InvalidDomain {
+                operator,
+                expected: EXPECTED,
+                start: domain.span().start,
+                end: domain.span().end,
+            }),
+        }
+    }
+
+    /// Whether a condition (e.g. a filter) holds, where `missing` doesn't.
+    fn condition(
+        &mut self,
+        condition: &Expression,
+    ) -> Result<bool, InternalParserError> {
+        Ok(self.logic(condition)? == Some(true))
+    }
+
+    /// The value of a logic expression (e.g. a condition or a predicate).
+    fn logic(
+        &mut self,
+        expression: &Expression,
+    ) -> Result<Option<bool>, InternalParserError> {
+        match self.evaluate(expression)? {
+            Value::Logic(logic) => Ok(logic),
+            value => Err(InternalParserError::InvalidCondition {
+                found: value.type_name(),
+                start: expression.span().start,
+                end: expression.span().end,
+            }),
+        }
+    }
+
+    /// Integrates the integrand over the binding's range, whose bounds may be
+    /// infinite (e.g. `∫(x in 0..∞) ℯ^-x dx`), as a float.
+    fn integral(
+        &mut self,
+        binding: &Binding,
+        integrand: &Expression,
+        span: Span,
+    ) -> Result<Value, InternalParserError> {
+        let (start, end) = self.real_range(&binding.domain)?;
+        let tolerance = self.tolerance;
+        let integral = quadrature::integrate(
+            |x| {
+                let x = Value::Number(Number::Float(x));
+                match self.with_variable(&binding.variable.name, x, |env| {
+                    env.evaluate(integrand)
+                })? {
+                    Value::Number(y) if !y.is_complex() => Ok(y.to_f64()),
+                    y => Err(InternalParserError::InvalidIntegrand {
+                        found: y.type_name(),
+                        start: integrand.span().start,
+                        end: integrand.span().end,
+                    }),
+                }
+            },
+            start,
+            end,
+            tolerance,
+        );
+        match integral {
+            Ok(integral) => Ok(Value::Number(Number::Float(integral))),
+            Err(QuadratureError::Integrand(error)) => Err(error),
+            Err(QuadratureError::NotConverged) => {
+                Err(InternalParserError::NonConvergentIntegral {
+                    start: span.start,
+                    end: span.end,
+                })
+            }
+        }
+    }
+
+    /// The bounds of a range of integers (e.g. `1..=n`), as a half-open range,
+    /// where an invalid domain of the operator is reported as not what was
+    /// expected.
+    fn integer_range(
+        &mut self,
+        operator: &'static str,
+        expected: &'static str,
+        domain: &Expression,
+    ) -> Result<(Integer, Integer), InternalParserError> {
+        let invalid_domain = |span: Span| InternalParserError::InvalidDomain {
+            operator,
+            expected,
+            start: span.start,
+            end: span.end,
+        };
+        let (start, inclusive, end) =
+            range_bounds(domain).ok_or_else(|| invalid_domain(domain.span()))?;
+        let mut bound = |bound: &Expression| match self.evaluate(bound)? {
+            Value::Number(Number::Integer(n)) => Ok(n),
+            _ => Err(invalid_domain(bound.span())),
+        };
+        let start = bound(start)?;
+        let end = bound(end)?;
+        Ok((start, if inclusive { end + 1 } else { end }))
+    }
+
+    /// The bounds of a range of real numbers (e.g. `0..π`), which is the same
+    /// whether or not it's inclusive.
+    fn real_range(
+        &mut self,
+        domain: &Expression,
+    ) -> Result<(f64, f64), InternalParserError> {
+        let invalid_domain = |span: Span| InternalParserError::InvalidDomain {
+            operator: "∫",
+            expected: "a range of real numbers",
+            start: span.start,
+            end: span.end,
+        };
+        let (start, _, end) =
+            range_bounds(domain).ok_or_else(|| invalid_domain(domain.span()))?;
+        let mut bound = |bound: &Expression| match self.evaluate(bound)? {
+            Value::Number(n) if !n.is_complex() && !n.to_f64().is_nan() => {
+                Ok(n.to_f64())
+            }
+            _ => Err(invalid_domain(bound.span())),
+        };
+        Ok((bound(start)?, bound(end)?))
+    }
+
+    /// Evaluates `f` with the variable bound to the value, shadowing any
+    /// variable of the same name until `f` returns.
+    fn with_variable<T>(
+        &mut self,
+        name: &str,
+        value: Value,
+        f: impl FnOnce(&mut Environment) -> T,
+    ) -> T {
+        let shadowed = self.variables.insert(String::from(name), value);
+        let result = f(self);
+        match shadowed {
+            Some(shadowed) => self.variables.insert(String::from(name), shadowed),
+            None => self.variables.remove(name),
+        };
+        result
+    }
+}
+
+/// The bounds of a range (e.g. `1..=n`), and whether it's inclusive, if the
+/// expression is one.
+fn range_bounds(domain: &Expression) -> Option<(&Expression, bool, &Expression)> {
+    match domain {
+        Expression::Grouped { expression, .. } => range_bounds(expression),
+        Expression::Binary {
+            left,
+            operator:
+                operator @ (BinaryOperator::Range | BinaryOperator::RangeInclusive),
+            right,
+            ..
+        } => Some((left, *operator == BinaryOperator::RangeInclusive, right)),
+        _ => None,
+    }
+}
+
+fn literal_value(literal: &LiteralExpression) -> Value {
+    match literal {
+        LiteralExpression::NumericLiteral(n) => Value::Number(Number::from(n)),
+        LiteralExpression::Quantity { value, unit } => {
+            Value::Quantity(Quantity::new(Number::from(value).to_f64(), *unit))
+        }
+        LiteralExpression::Logic(LogicLiteral::True { value })
+        | LiteralExpression::Logic(LogicLiteral::False { value }) => {
+            Value::Logic(Some(*value))
+        }
+        LiteralExpression::Logic(LogicLiteral::Missing) => Value::Logic(None),
+        LiteralExpression::String(s) => Value::String(s.clone()),
+        LiteralExpression::Char { value } => Value::Char(*value),
+        LiteralExpression::Byte { value } => Value::Byte(*value),
+    }
+}
+
+fn unary(
+    operator: UnaryOperator,
+    operand: Value,
+    span: Span,
+) -> Result<Value, InternalParserError> {
+    match (operator, operand) {
+        (UnaryOperator::Negate, Value::Number(n)) => Ok(Value::Number(-n)),
+        (UnaryOperator::Negate, Value::Quantity(q)) => {
+            Ok(Value::Quantity(Quantity::new(-q.value, q.unit)))
+        }
+        (UnaryOperator::Not, Value::Logic(b)) => Ok(Value::Logic(b.map(|b| !b))),
+        (operator, operand) => Err(InternalParserError::InvalidOperand {
+            operator: operator.symbol(),
+            operand: operand.type_name(),
+            start: span.start,
+            end: span.end,
+        }),
+    }
+}
+
+fn binary(
+    left: Value,
+    operator: BinaryOperator,
+    right: Value,
+    span: Span,
+) -> Result<Value, InternalParserError> {
+    use BinaryOperator::*;
+
+    match (operator, left, right) {
+        (Range | RangeInclusive, _, _) => Err(InternalParserError::NotConstant {
+            start: span.start,
+            end: span.end,
+        }),
+        (
+            Equal | NotEqual | LessThan | LessThanEqual | GreaterThan
+            | GreaterThanEqual,
+            left,
+            right,
+        ) => comparison(left, operator, right, span),
+        (ElementOf | NotElementOf, element, Value::Set(set)) => {
+            set.check_element_type(&element)
+                .map_err(|error| mismatched_set_types(error, span))?;
+            Ok(Value::Logic(Some(
+                set.contains(&element) == (operator == ElementOf),
+            )))
+        }
+        (Intersection, Value::Set(left), Value::Set(right)) => left
+            .intersection(&right)
+            .map(Value::Set)
+            .map_err(|error| mismatched_set_types(error, span)),
+        (Union, Value::Set(left), Value::Set(right)) => left
+            .union(&right)
+            .map(Value::Set)
+            .map_err(|error| mismatched_set_types(error, span)),
+        (
+            SubsetOf | SubsetOfOrEqualTo | NotSubsetOf | SupersetOf
+            | SupersetOfOrEqualTo | NotSupersetOf,
+            Value::Set(left),
+            Value::Set(right),
+        ) => {
+            let ordering = left
+                .compare(&right)
+                .map_err(|error| mismatched_set_types(error, span))?;
+            Ok(Value::Logic(Some(match operator {
+                SubsetOf => ordering == Some(Ordering::Less),
+                SubsetOfOrEqualTo => {
+                    matches!(ordering, Some(Ordering::Less | Ordering::Equal))
+                }
+                NotSubsetOf => ordering != Some(Ordering::Less),
+                SupersetOf => ordering == Some(Ordering::Greater),
+                SupersetOfOrEqualTo => {
+                    matches!(ordering, Some(Ordering::Greater | Ordering::Equal))
+                }
+                _ => ordering != Some(Ordering::Greater),
+            })))
+        }
+        (And | BitAnd, Value::Logic(left), Value::Logic(right)) => {
+            Ok(Value::Logic(match (left, right) {
+                (Some(false), _) | (_, Some(false)) => Some(false),
+                (Some(true), Some(true)) => Some(true),
+                _ => None,
+            }))
+        }
+        (Or | BitOr, Value::Logic(left), Value::Logic(right)) => {
+            Ok(Value::Logic(match (left, right) {
+                (Some(true), _) | (_, Some(true)) => Some(true),
+                (Some(false), Some(false)) => Some(false),
+                _ => None,
+            }))
+        }
+        (_, Value::Number(left), Value::Number(right)) => {
+            number_arithmetic(left, operator, right, span).map(Value::Number)
+        }
+        (_, left @ Value::Quantity(_), right)
+        | (_, left, right @ Value::Quantity(_)) => {
+            quantity_arithmetic(left, operator, right, span).map(Value::Quantity)
+        }
+        (_, left, right) => Err(invalid_operands(&left, operator, &right, span)),
+    }
+}
+
+fn number_arithmetic(
+    left: Number,
+    operator: BinaryOperator,
+    right: Number,
+    span: Span,
+) -> Result<Number, InternalParserError> {
+    let division_by_zero = InternalParserError::DivisionByZero {
+        start: span.start,
+        end: span.end,
+    };
+    match (operator, left, right) {
+        (BinaryOperator::Add, left, right) => Ok(left + right),
+        (BinaryOperator::Subtract, left, right) => Ok(left - right),
+        (BinaryOperator::Multiply, left, right) => Ok(left * right),
+        (BinaryOperator::Divide, left, right) => {
+            left.checked_div(right).ok_or(division_by_zero)
+        }
+        (BinaryOperator::Remainder, left, right)
+            if !left.is_complex() && !right.is_complex() =>
+        {
+            left.checked_rem(right).ok_or(division_by_zero)
+        }
+        (BinaryOperator::Power, left, right) => {
+            left.checked_pow(right).ok_or(division_by_zero)
+        }
+        (BinaryOperator::BitAnd, Number::Integer(left), Number::Integer(right)) => {
+            Ok(Number::Integer(left & right))
+        }
+        (BinaryOperator::BitOr, Number::Integer(left), Number::Integer(right)) => {
+            Ok(Number::Integer(left | right))
+        }
+        (operator, left, right) => Err(invalid_operands(
+            &Value::Number(left),
+            operator,
+            &Value::Number(right),
+            span,
+        )),
+    }
+}
+
+fn quantity_arithmetic(
+    left: Value,
+    operator: BinaryOperator,
+    right: Value,
+    span: Span,
+) -> Result<Quantity, InternalParserError> {
+    let invalid = || invalid_operands(&left, operator, &right, span);
+    let incompatible_units = |error| InternalParserError::IncompatibleUnits {
+        error,
+        start: span.start,
+        end: span.end,
+    };
+    let out_of_range = |_| InternalParserError::UnitExponentOutOfRange {
+        start: span.start,
+        end: span.end,
+    };
+    let (lhs, rhs) = match (left.to_quantity(), right.to_quantity()) {
+        (Some(lhs), Some(rhs)) => (lhs, rhs),
+        _ => return Err(invalid()),
+    };
+    match operator {
+        BinaryOperator::Add => lhs.checked_add(rhs).map_err(incompatible_units),
+        BinaryOperator::Subtract => lhs.checked_sub(rhs).map_err(incompatible_units),
+        BinaryOperator::Multiply => lhs.checked_mul(rhs).map_err(out_of_range),
+        BinaryOperator::Divide => lhs.checked_div(rhs).map_err(out_of_range),
+        // Quantities may only be raised to (small) integer powers, so that the
+        // dimension of the result is known (e.g. `(2 m)^2` is `4 m²`)
+        BinaryOperator::Power => match &right {
+            Value::Number(Number::Integer(n)) => match n.to_i8() {
+                Some(n) => lhs.checked_powi(n).map_err(out_of_range),
+                None => Err(invalid()),
+            },
+            _ => Err(invalid()),
+        },
+        _ => Err(invalid()),
+    }
+}
+
+/// Evaluates a root like the corresponding power (e.g. `∛x` like `x ^ (1/3)`),
+/// except that roots of exact perfect powers are exact, odd roots of negative
+/// numbers are real, and a quantity's root must have a dimension.
+fn root(
+    index: Value,
+    radicand: Value,
+    span: Span,
+) -> Result<Value, InternalParserError> {
+    let invalid = || InternalParserError::InvalidOperands {
+        operator: "√",
+        left: index.type_name(),
+        right: radicand.type_name(),
+        start: span.start,
+        end: span.end,
+    };
+    match (&index, &radicand) {
+        (Value::Number(n), Value::Number(radicand)) => radicand
+            .clone()
+            .checked_root(n.clone())
+            .map(Value::Number)
+            .ok_or(InternalParserError::DivisionByZero {
+                start: span.start,
+                end: span.end,
+            }),
+        (Value::Number(Number::Integer(n)), Value::Quantity(radicand)) => n
+            .to_i8()
+            .and_then(|n| radicand.root(n))
+            .map(Value::Quantity)
+            .ok_or_else(invalid),
+        _ => Err(invalid()),
+    }
+}
+
+fn comparison(
+    left: Value,
+    operator: BinaryOperator,
+    right: Value,
+    span: Span,
+) -> Result<Value, InternalParserError> {
+    let is_equality =
+        matches!(operator, BinaryOperator::Equal | BinaryOperator::NotEqual);
+    let ordering = match (&left, &right) {
+        (Value::Number(lhs), Value::Number(rhs))
+            if is_equality || !(lhs.is_complex() || rhs.is_complex()) =>
+        {
+            lhs.compare(rhs)
+        }
+        (Value::Quantity(_), _) | (_, Value::Quantity(_)) => {
+            match (left.to_quantity(), right.to_quantity()) {
+                (Some(lhs), Some(rhs))
+                    if lhs.unit.dimension == rhs.unit.dimension =>
+                {
+                    lhs.si_value().partial_cmp(&rhs.si_value())
+                }
+                (Some(lhs), Some(rhs)) => {
+                    return Err(InternalParserError::IncompatibleUnits {
+                        error: IncompatibleDimensions {
+                            left: lhs.unit.dimension,
+                            right: rhs.unit.dimension,
+                        },
+                        start: span.start,
+                        end: span.end,
+                    })
+                }
+                _ => return Err(invalid_operands(&left, operator, &right, span)),
+            }
+        }
+        // Comparing with `missing` is `missing`
+        (Value::Logic(lhs), Value::Logic(rhs)) if is_equality => match (lhs, rhs) {
+            (Some(lhs), Some(rhs)) => Some(lhs.cmp(rhs)),
+            _ => return Ok(Value::Logic(None)),
+        },
+        (Value::String(lhs), Value::String(rhs))
+            if left.type_name() == right.type_name() =>
+        {
+            lhs.partial_cmp(rhs)
+        }
+        // Sets are only equal or unequal, as `<` etc. would be ambiguous with
+        // `⊂` etc.
+        (Value::Set(lhs), Value::Set(rhs)) if is_equality => lhs
+            .compare(rhs)
+            .map_err(|error| mismatched_set_types(error, span))?
+            .filter(|ordering| *ordering == Ordering::Equal),
+        (Value::Char(lhs), Value::Char(rhs)) => Some(lhs.cmp(rhs)),
+        (Value::Byte(lhs), Value::Byte(rhs)) => Some(lhs.cmp(rhs)),
+        _ => return Err(invalid_operands(&left, operator, &right, span)),
+    };
+    Ok(Value::Logic(Some(match operator {
+        BinaryOperator::Equal => ordering == Some(Ordering::Equal),
+        BinaryOperator::NotEqual => ordering != Some(Ordering::Equal),
+        BinaryOperator::LessThan => ordering == Some(Ordering::Less),
+        BinaryOperator::LessThanEqual => {
+            matches!(ordering, Some(Ordering::Less | Ordering::Equal))
+        }
+        BinaryOperator::GreaterThan => ordering == Some(Ordering::Greater),
+        BinaryOperator::GreaterThanEqual => {
+            matches!(ordering, Some(Ordering::Greater | Ordering::Equal))
+        }
+        _ => unreachable!("{:?} isn't a comparison", operator),
+    })))
+}
+
+fn invalid_operands(
+    left: &Value,
+    operator: BinaryOperator,
+    right: &Value,
+    span: Span,
+) -> InternalParserError {
+    InternalParserError::InvalidOperands {
+        operator: operator.symbol(),
+        left: left.type_name(),
+        right: right.type_name(),
+        start: span.start,
+        end: span.end,
+    }
+}
+
+fn mismatched_set_types(error: MismatchedTypes, span: Span) -> InternalParserError {
+    InternalParserError::MismatchedSetTypes {
+        error,
+        start: span.start,
+        end: span.end,
+    }
+}
@@ -0,0 +1,1311 @@
+use logos::Logos;
+use rug::ops::Pow;
+
+use crate::{
+    ast::*, number::MAX_EXACT_BITS, parser_errors::InternalParserError,
+    units::Unit,
+};
+
+/// The result type consumed by the LALRPOP generated parser. Every token is
+/// paired with the byte offsets of its first and one-past-last characters
+/// within the source text.
+pub type Spanned<Tok, Loc, Error> = Result<(Loc, Tok, Loc), Error>;
+
+/// The tokens of the Balsa language.
+///
+/// Tokens which only ever have a single lexeme (e.g. keywords, punctuation,
+/// and delimiters) carry their corresponding AST node, while tokens whose
+/// lexeme varies (e.g. identifiers and literals) carry the lexeme itself,
+/// leaving its interpretation to the parser.
+#[derive(Logos, Clone, Debug, PartialEq)]
+pub enum Token<'input> {
+    //////////////
+    // Keywords //
+    //////////////
+    #[token("as", |_| Keyword::As)]
+    #[token("assert", |_| Keyword::Assert)]
+    #[token("break", |_| Keyword::Break)]
+    #[token("const", |_| Keyword::Const)]
+    #[token("continue", |_| Keyword::Continue)]
+    #[token("else", |_| Keyword::Else)]
+    #[token("enum", |_| Keyword::Enum)]
+    #[token("false", |_| Keyword::False)]
+    #[token("False", |_| Keyword::False)]
+    #[token("fn", |_| Keyword::Fn)]
+    #[token("for", |_| Keyword::For)]
+    #[token("if", |_| Keyword::If)]
+    #[token("impl", |_| Keyword::Impl)]
+    #[token("in", |_| Keyword::In)]
+    #[token("loop", |_| Keyword::Loop)]
+    #[token("match", |_| Keyword::Match)]
+    #[token("missing", |_| Keyword::Missing)]
+    #[token("Missing", |_| Keyword::Missing)]
+    #[token("mod", |_| Keyword::Mod)]
+    #[token("pub", |_| Keyword::Pub)]
+    #[token("return", |_| Keyword::Return)]
+    #[token("self", |_| Keyword::SelfValue)]
+    #[token("Self", |_| Keyword::SelfType)]
+    #[token("static", |_| Keyword::Static)]
+    #[token("struct", |_| Keyword::Struct)]
+    #[token("super", |_| Keyword::Super)]
+    #[token("trait", |_| Keyword::Trait)]
+    #[token("true", |_| Keyword::True)]
+    #[token("True", |_| Keyword::True)]
+    #[token("type", |_| Keyword::Type)]
+    #[token("use", |_| Keyword::Use)]
+    #[token("where", |_| Keyword::Where)]
+    #[token("while", |_| Keyword::While)]
+    // Reserved keywords
+    #[token("abstract", |_| Keyword::Reserved(ReservedKeyword::Abstract))]
+    #[token("async", |_| Keyword::Reserved(ReservedKeyword::Async))]
+    #[token("await", |_| Keyword::Reserved(ReservedKeyword::Await))]
+    #[token("crate", |_| Keyword::Reserved(ReservedKeyword::Crate))]
+    #[token("do", |_| Keyword::Reserved(ReservedKeyword::Do))]
+    #[token("dyn", |_| Keyword::Reserved(ReservedKeyword::Dyn))]
+    #[token("export", |_| Keyword::Reserved(ReservedKeyword::Export))]
+    #[token("extern", |_| Keyword::Reserved(ReservedKeyword::Extern))]
+    #[token("final", |_| Keyword::Reserved(ReservedKeyword::Final))]
+    #[token("import", |_| Keyword::Reserved(ReservedKeyword::Import))]
+    #[token("let", |_| Keyword::Reserved(ReservedKeyword::Let))]
+    #[token("macro", |_| Keyword::Reserved(ReservedKeyword::Macro))]
+    #[token("move", |_| Keyword::Reserved(ReservedKeyword::Move))]
+    #[token("mut", |_| Keyword::Reserved(ReservedKeyword::Mut))]
+    #[token("override", |_| Keyword::Reserved(ReservedKeyword::Override))]
+    #[token("priv", |_| Keyword::Reserved(ReservedKeyword::Priv))]
+    #[token("proc", |_| Keyword::Reserved(ReservedKeyword::Proc))]
+    #[token("ref", |_| Keyword::Reserved(ReservedKeyword::Ref))]
+    #[token("typeof", |_| Keyword::Reserved(ReservedKeyword::Typeof))]
+    #[token("unsafe", |_| Keyword::Reserved(ReservedKeyword::Unsafe))]
+    #[token("unsized", |_| Keyword::Reserved(ReservedKeyword::Unsized))]
+    #[token("virtual", |_| Keyword::Reserved(ReservedKeyword::Virtual))]
+    #[token("yield", |_| Keyword::Reserved(ReservedKeyword::Yield))]
+    Keyword(Keyword),
+
+    /////////////////
+    // Punctuation //
+    /////////////////
+    #[token("+", |_| Punctuation::Plus)]
+    #[token("-", |_| Punctuation::Minus)]
+    #[token("*", |_| Punctuation::Star)]
+    #[token("/", |_| Punctuation::Slash)]
+    #[token("\\", |_| Punctuation::Backslash)]
+    #[token("%", |_| Punctuation::Percent)]
+    #[token("^", |_| Punctuation::Caret)]
+    #[token("!", |_| Punctuation::Not)]
+    #[token("not", |_| Punctuation::Not)]
+    #[token("&", |_| Punctuation::And)]
+    #[token("|", |_| Punctuation::Or)]
+    #[token("&&", |_| Punctuation::AndAnd)]
+    #[token("||", |_| Punctuation::OrOr)]
+    #[token("+=", |_| Punctuation::PlusEquals)]
+    #[token("-=", |_| Punctuation::MinusEquals)]
+    #[token("*=", |_| Punctuation::StarEquals)]
+    #[token("/=", |_| Punctuation::SlashEquals)]
+    #[token("%=", |_| Punctuation::PercentEquals)]
+    #[token("^=", |_| Punctuation::CaretEquals)]
+    #[token("&=", |_| Punctuation::AndEquals)]
+    #[token("|=", |_| Punctuation::OrEquals)]
+    #[token("=", |_| Punctuation::Equals)]
+    #[token("==", |_| Punctuation::DoubleEquals)]
+    #[token("!=", |_| Punctuation::NotEqual)]
+    #[token("≠", |_| Punctuation::NotEqual)]
+    #[token("<", |_| Punctuation::LessThan)]
+    #[token("<=", |_| Punctuation::LessThanEqual)]
+    #[token("≤", |_| Punctuation::LessThanEqual)]
+    #[token(">", |_| Punctuation::GreaterThan)]
+    #[token(">=", |_| Punctuation::GreaterThanEqual)]
+    #[token("≥", |_| Punctuation::GreaterThanEqual)]
+    #[token("_", |_| Punctuation::Underscore)]
+    #[token(".", |_| Punctuation::Dot)]
+    #[token("..", |_| Punctuation::DotDot)]
+    #[token("...", |_| Punctuation::DotDotDot)]
+    #[token("..=", |_| Punctuation::DotDotEquals)]
+    #[token(",", |_| Punctuation::Comma)]
+    #[token(":", |_| Punctuation::Colon)]
+    #[token(":=", |_| Punctuation::ColonEquals)]
+    #[token("≔", |_| Punctuation::ColonEquals)]
+    #[token(";", |_| Punctuation::Semicolon)]
+    #[token("::", |_| Punctuation::PathSeparator)]
+    #[token("->", |_| Punctuation::RightArrow)]
+    #[token("→", |_| Punctuation::RightArrow)]
+    #[token("<-", |_| Punctuation::LeftArrow)]
+    #[token("←", |_| Punctuation::LeftArrow)]
+    #[token("=>", |_| Punctuation::FatRightArrow)]
+    #[token("⇒", |_| Punctuation::FatRightArrow)]
+    #[token("⇐", |_| Punctuation::FatLeftArrow)]
+    #[token("#", |_| Punctuation::Pound)]
+    #[token("$", |_| Punctuation::Dollar)]
+    #[token("?", |_| Punctuation::Question)]
+    #[token("'", |_| Punctuation::Apostrophe)]
+    #[token("\"", |_| Punctuation::Quote)]
+    Punctuation(Punctuation),
+
+    ////////////////
+    // Delimiters //
+    ////////////////
+    #[token("(", |_| Delimiter::LeftParen)]
+    #[token(")", |_| Delimiter::RightParen)]
+    #[token("[", |_| Delimiter::LeftBracket)]
+    #[token("]", |_| Delimiter::RightBracket)]
+    #[token("{", |_| Delimiter::LeftBrace)]
+    #[token("}", |_| Delimiter::RightBrace)]
+    Delimiter(Delimiter),
+
+    //////////////////////////
+    // Mathematical Symbols //
+    //////////////////////////
+    #[token("√", |_| MathematicalSymbol::Root { exponent: 2 })]
+    #[token("∛", |_| MathematicalSymbol::Root { exponent: 3 })]
+    #[token("∜", |_| MathematicalSymbol::Root { exponent: 4 })]
+    #[token("÷", |_| MathematicalSymbol::Division)]
+    #[token("·", |_| MathematicalSymbol::DotOperator)]
+    #[token("⋅", |_| MathematicalSymbol::DotOperator)]
+    #[token("∝", |_| MathematicalSymbol::ProportionalTo)]
+    #[token("∩", |_| MathematicalSymbol::Intersection)]
+    #[token("∪", |_| MathematicalSymbol::Union)]
+    #[token("∫", |_| MathematicalSymbol::Integral)]
+    #[token("∑", |_| MathematicalSymbol::Sum)]
+    #[token("∏", |_| MathematicalSymbol::Product)]
+    #[token("∴", |_| MathematicalSymbol::Therefore)]
+    #[token("∵", |_| MathematicalSymbol::Because)]
+    #[token("≈", |_| MathematicalSymbol::ApproximatelyEqual)]
+    #[token("≉", |_| MathematicalSymbol::NotApproximatelyEqual)]
+    #[token("≡", |_| MathematicalSymbol::IdenticalTo)]
+    #[token("≢", |_| MathematicalSymbol::NotIdenticalTo)]
+    #[token("⊂", |_| MathematicalSymbol::SubsetOf)]
+    #[token("⊄", |_| MathematicalSymbol::NotSubsetOf)]
+    #[token("⊆", |_| MathematicalSymbol::SubsetOfOrEqualTo)]
+    #[token("⊃", |_| MathematicalSymbol::SupersetOf)]
+    #[token("⊅", |_| MathematicalSymbol::NotSupersetOf)]
+    #[token("⊇", |_| MathematicalSymbol::SupersetOfOrEqualTo)]
+    #[token("∈", |_| MathematicalSymbol::ElementOf)]
+    #[token("∉", |_| MathematicalSymbol::NotElementOf)]
+    #[token("∀", |_| MathematicalSymbol::ForAll)]
+    #[token("∃", |_| MathematicalSymbol::Exists)]
+    MathematicalSymbol(MathematicalSymbol),
+
+    ////////////////////////////
+    // Mathematical Constants //
+    ////////////////////////////
+    #[token("pi", |_| MathematicalConstant::Pi { value: std::f64::consts::PI, digits: None })]
+    #[token("π", |_| MathematicalConstant::Pi { value: std::f64::consts::PI, digits: None })]
+    #[token("𝜋", |_| MathematicalConstant::Pi { value: std::f64::consts::PI, digits: None })]
+    #[token("euler", |_| MathematicalConstant::Euler { value: std::f64::consts::E, digits: None })]
+    #[token("ℯ", |_| MathematicalConstant::Euler { value: std::f64::consts::E, digits: None })]
+    #[token("tau", |_| MathematicalConstant::Tau { value: std::f64::consts::TAU, digits: None })]
+    #[token("τ", |_| MathematicalConstant::Tau { value: std::f64::consts::TAU, digits: None })]
+    #[token("𝜏", |_| MathematicalConstant::Tau { value: std::f64::consts::TAU, digits: None })]
+    #[token("golden", |_| MathematicalConstant::GoldenRatio { value: 1.618033988749895, digits: None })]
+    #[token("φ", |_| MathematicalConstant::GoldenRatio { value: 1.618033988749895, digits: None })]
+    #[token("𝜙", |_| MathematicalConstant::GoldenRatio { value: 1.618033988749895, digits: None })]
+    #[token("catalan", |_| MathematicalConstant::Catalan { value: 0.915965594177219, digits: None })]
+    #[token("eulermascheroni", |_| MathematicalConstant::EulerMascheroni { value: 0.5772156649015329, digits: None })]
+    #[token("eulergamma", |_| MathematicalConstant::EulerMascheroni { value: 0.5772156649015329, digits: None })]
+    #[token("γ", |_| MathematicalConstant::EulerMascheroni { value: 0.5772156649015329, digits: None })]
+    #[token("𝛾", |_| MathematicalConstant::EulerMascheroni { value: 0.5772156649015329, digits: None })]
+    // e.g. `pi_40`, `π_100`, `γ_20`
+    #[regex(
+        "(pi|π|𝜋|euler|ℯ|tau|τ|𝜏|golden|φ|𝜙|catalan|eulermascheroni|eulergamma|γ|𝛾)_[0-9]+",
+        precise_mathematical_constant
+    )]
+    #[token("Inf", |_| MathematicalConstant::Infinity { value: f64::INFINITY })]
+    #[token("∞", |_| MathematicalConstant::Infinity { value: f64::INFINITY })]
+    #[token("NaN", |_| MathematicalConstant::NotANumber)]
+    MathematicalConstant(MathematicalConstant),
+
+    ///////////////////////////////////////
+    // Superscript Symbols (e.g. ⁻¹, ⁽²⁾) //
+    ///////////////////////////////////////
+    #[regex("[⁰¹²³⁴⁵⁶⁷⁸⁹]+", superscript_integer_literal)]
+    SuperscriptIntegerLiteral(SuperscriptIntegerLiteral),
+
+    // Superscript letters name a variable exponent or root index (e.g. the
+    // `ⁿ` of `xⁿ` and `ⁿ√x`), and so aren't part of an identifier.
+    #[regex("[ᵃᵇᶜᵈᵉᶠᵍʰⁱʲᵏˡᵐⁿᵒᵖʳˢᵗᵘᵛʷˣʸᶻ]+", |lex| lex.slice())]
+    SuperscriptIdentifier(&'input str),
+
+    #[token("⁺", |_| SuperscriptPunctuation::Plus)]
+    #[token("⁻", |_| SuperscriptPunctuation::Minus)]
+    #[token("⁽", |_| SuperscriptPunctuation::LeftParen)]
+    #[token("⁾", |_| SuperscriptPunctuation::RightParen)]
+    SuperscriptPunctuation(SuperscriptPunctuation),
+
+    /////////////////////////////////////
+    // Subscript Symbols (e.g. ₁, ₍₋₁₎) //
+    /////////////////////////////////////
+    #[token("₀", |_| SubscriptSymbol::Zero)]
+    #[token("₁", |_| SubscriptSymbol::One)]
+    #[token("₂", |_| SubscriptSymbol::Two)]
+    #[token("₃", |_| SubscriptSymbol::Three)]
+    #[token("₄", |_| SubscriptSymbol::Four)]
+    #[token("₅", |_| SubscriptSymbol::Five)]
+    #[token("₆", |_| SubscriptSymbol::Six)]
+    #[token("₇", |_| SubscriptSymbol::Seven)]
+    #[token("₈", |_| SubscriptSymbol::Eight)]
+    #[token("₉", |_| SubscriptSymbol::Nine)]
+    #[token("₊", |_| SubscriptSymbol::Plus)]
+    #[token("₋", |_| SubscriptSymbol::Minus)]
+    #[token("₍", |_| SubscriptSymbol::LeftParen)]
+    #[token("₎", |_| SubscriptSymbol::RightParen)]
+    SubscriptSymbol(SubscriptSymbol),
+
+    /////////////////
+    // Identifiers //
+    /////////////////
+    // IDENTIFIER_OR_KEYWORD :
+    //       XID_Start XID_Continue*
+    //    | _ XID_Continue+
+    //
+    // Identifiers are matched maximally, so keywords and mathematical constants
+    // are only recognized when the whole word matches (e.g. `foobarfor` and
+    // `pie` are identifiers). Subscript digits may also continue an identifier
+    // (e.g. `θ₁`, `a₁₂`, `log₂`), mirroring their conventional use in
+    // mathematical notation. A subscript on its own is a `SubscriptSymbol`.
+    // The middle dot (`·`) is an operator rather than part of an identifier
+    // (e.g. `kg·m`).
+    // The mathematical italic `𝑖` is the imaginary unit rather than part of an
+    // identifier (e.g. `𝑖π`), as are superscript letters (e.g. `xⁿ`).
+    #[regex(
+        r"[[\p{XID_Start}_]--[𝑖ᵃᵇᶜᵈᵉᶠᵍʰⁱʲᵏˡᵐⁿᵒᵖʳˢᵗᵘᵛʷˣʸᶻ]][[\p{XID_Continue}₀-₉]--[·𝑖ᵃᵇᶜᵈᵉᶠᵍʰⁱʲᵏˡᵐⁿᵒᵖʳˢᵗᵘᵛʷˣʸᶻ]]*",
+        |lex| lex.slice()
+    )]
+    Identifier(&'input str),
+
+    // The symbols and operators of a unit of measure following a numeric
+    // literal (e.g. `m`, `/` and `s` in `9.81 m/s²`). These are retagged by the
+    // Lexer from identifiers, `/` and `·`, so that a unit's operators aren't
+    // mistaken for arithmetic.
+    UnitSymbol(&'input str),
+    UnitOperator(UnitOperator),
+
+    //////////////////////
+    // Numeric Literals //
+    //////////////////////
+    #[regex(
+        "0[xX][0-9a-fA-F_]+(u8|u16|u32|u64|u128|usize|i8|i16|i32|i64|i128|isize)?",
+        |lex| lex.slice()
+    )]
+    HexadecimalLiteral(&'input str),
+
+    #[regex(
+        "[0-9][0-9_]*(u8|u16|u32|u64|u128|usize|i8|i16|i32|i64|i128|isize)?",
+        |lex| lex.slice()
+    )]
+    DecimalLiteral(&'input str),
+
+    #[regex(
+        "0[oO][0-7_]+(u8|u16|u32|u64|u128|usize|i8|i16|i32|i64|i128|isize)?",
+        |lex| lex.slice()
+    )]
+    OctalLiteral(&'input str),
+
+    #[regex(
+        "0[bB][01_]+(u8|u16|u32|u64|u128|usize|i8|i16|i32|i64|i128|isize)?",
+        |lex| lex.slice()
+    )]
+    BinaryLiteral(&'input str),
+
+    // NOTE: `DEC_LITERAL .` (e.g. `12.`) is not lexed as a single token, as
+    // doing so would require lookahead to avoid lexing `1..2` as `1.` `.2`.
+    // The parser composes such literals from a DecimalLiteral and a Dot.
+    #[regex(r"[0-9][0-9_]*\.[0-9][0-9_]*([eE][+-]?[0-9_]+)?(f32|f64)?", |lex| lex.slice())]
+    #[regex(r"[0-9][0-9_]*[eE][+-]?[0-9_]+(f32|f64)?", |lex| lex.slice())]
+    #[regex(r"[0-9][0-9_]*(f32|f64)", |lex| lex.slice())]
+    FloatLiteral(&'input str),
+
+    // A (hexadecimal) float literal whose exact value is kept (e.g. `0.1` is
+    // `1/10`), including a decimal literal followed by a decimal point (e.g.
+    // `12.`). These are retagged by the Lexer when it keeps exact floats (see
+    // `Lexer::exact_floats`).
+    ExactFloatLiteral(&'input str),
+
+    // HEX_FLOAT_LITERAL :
+    //    0x (HEX_DIGIT|_)* (. HEX_DIGIT (HEX_DIGIT|_)*)? (p|P) (+|-)? (DEC_DIGIT|_)+ FLOAT_SUFFIX?
+    //
+    // NOTE: The binary exponent is mandatory, as a float suffix would
+    // otherwise be indistinguishable from hexadecimal digits (e.g. `0x1.8f32`).
+    // Literals without one are still lexed here, so that the parser can report
+    // the missing exponent.
+    #[regex(
+        r"0[xX][0-9a-fA-F_]*(\.[0-9a-fA-F][0-9a-fA-F_]*)?[pP][+-]?[0-9_]+(f32|f64)?",
+        |lex| lex.slice()
+    )]
+    #[regex(r"0[xX][0-9a-fA-F_]*\.[0-9a-fA-F][0-9a-fA-F_]*", |lex| lex.slice())]
+    HexadecimalFloatLiteral(&'input str),
+
+    // RATIONAL_LITERAL :
+    //       DEC_LITERAL / DEC_LITERAL r
+    //    | DEC_LITERAL? VULGAR_FRACTION
+    //
+    // Only vulgar fractions (e.g. `¾`) are lexed here, as a quotient (e.g.
+    // `3/4r`) or mixed number (e.g. `1½`) is composed by the Lexer from its
+    // leading decimal literal, which would otherwise be lexed on its own.
+    #[regex("[½⅓⅔¼¾⅕⅖⅗⅘⅙⅚⅐⅛⅜⅝⅞⅑⅒↉]", |lex| lex.slice())]
+    RationalLiteral(&'input str),
+
+    // IMAGINARY_LITERAL :
+    //       (DEC_LITERAL | DEC_LITERAL . DEC_LITERAL) FLOAT_EXPONENT? (i | 𝑖)
+    //    | 𝑖
+    //
+    // NOTE: Integer suffixes take precedence over the imaginary unit (e.g.
+    // `2i8` is an `i8`, while `2i` is imaginary).
+    #[regex(r"[0-9][0-9_]*(\.[0-9][0-9_]*)?([eE][+-]?[0-9_]+)?[i𝑖]", |lex| lex.slice())]
+    #[token("𝑖", |lex| lex.slice())]
+    ImaginaryLiteral(&'input str),
+
+    // NOTE: A decimal point immediately followed by an underscore or an
+    // identifier (e.g. `1._5`, `1.e5`, `1.foo`) doesn't form a float literal,
+    // and is reported as an `InvalidFloatLiteral` by the [`Lexer`].
+    #[regex(r"[0-9][0-9_]*\.[_\p{XID_Start}]", |lex| lex.slice())]
+    InvalidFloatLiteral(&'input str),
+
+    ///////////////////////////////////
+    // Character and String Literals //
+    ///////////////////////////////////
+    // STRING_LITERAL :
+    //    " ( ~[" \ IsolatedCR] | QUOTE_ESCAPE | ASCII_ESCAPE | UNICODE_ESCAPE
+    //      | STRING_CONTINUE )* "
+    //
+    // NOTE: Any character may follow a backslash here, so that malformed
+    // escapes are reported by the parser (pointing at the escape itself)
+    // rather than as an unrecognized token.
+    #[regex(r#""([^"\\]|\\(.|\n))*""#, |lex| lex.slice())]
+    StringLiteral(&'input str),
+
+    // RAW_STRING_LITERAL :
+    //    r RAW_STRING_CONTENT
+    // RAW_STRING_CONTENT :
+    //       " ( ~ IsolatedCR )* (non-greedy) "
+    //    | # RAW_STRING_CONTENT #
+    #[regex(r##"r#*""##, raw_string_literal)]
+    RawStringLiteral(&'input str),
+
+    // BYTE_STRING_LITERAL :
+    //    b" ( ASCII_FOR_STRING | BYTE_ESCAPE | STRING_CONTINUE )* "
+    #[regex(r#"b"([^"\\]|\\(.|\n))*""#, |lex| lex.slice())]
+    ByteStringLiteral(&'input str),
+
+    // RAW_BYTE_STRING_LITERAL :
+    //    br RAW_BYTE_STRING_CONTENT
+    #[regex(r##"br#*""##, raw_string_literal)]
+    RawByteStringLiteral(&'input str),
+
+    // CHAR_LITERAL :
+    //    ' ( ~[' \ \n \r \t] | QUOTE_ESCAPE | ASCII_ESCAPE | UNICODE_ESCAPE ) '
+    //
+    // NOTE: Character and byte literals are composed by the [`Lexer`] from an
+    // Apostrophe (or a `b` Identifier immediately followed by an Apostrophe),
+    // as whether an apostrophe opens a literal or a label (e.g. `'outer`)
+    // depends on what follows it.
+    CharLiteral(&'input str),
+
+    // BYTE_LITERAL :
+    //    b' ( ASCII_FOR_CHAR | BYTE_ESCAPE ) '
+    ByteLiteral(&'input str),
+
+    //////////////
+    // Comments //
+    //////////////
+    #[regex(r"//[^\n]*", line_comment)]
+    #[token("/*", block_comment)]
+    Comment(Comment),
+
+    #[error]
+    #[regex(r"[ \t\n\r\f\v]+", logos::skip)]
+    Error,
+}
+
+fn superscript_integer_literal<'input>(
+    lex: &mut logos::Lexer<'input, Token<'input>>,
+) -> Option<SuperscriptIntegerLiteral> {
+    let mut n: i8 = 0;
+    for superscript_decimal_digit in lex.slice().chars() {
+        let digit = match superscript_decimal_digit {
+            '⁰' => 0,
+            '¹' => 1,
+            '²' => 2,
+            '³' => 3,
+            '⁴' => 4,
+            '⁵' => 5,
+            '⁶' => 6,
+            '⁷' => 7,
+            '⁸' => 8,
+            '⁹' => 9,
+            _ => unreachable!(),
+        };
+        // Superscript exponents which don't fit in an i8 are rejected
+        n = n.checked_mul(10)?.checked_add(digit)?;
+    }
+    Some(SuperscriptIntegerLiteral { n })
+}
+
+/// Returns the identifier spelled by a run of superscript letters (e.g. `ⁿ`
+/// spells `n`).
+pub(crate) fn superscript_identifier(lexeme: &str) -> Identifier {
+    const LETTERS: [(char, char); 25] = [
+        ('ᵃ', 'a'),
+        ('ᵇ', 'b'),
+        ('ᶜ', 'c'),
+        ('ᵈ', 'd'),
+        ('ᵉ', 'e'),
+        ('ᶠ', 'f'),
+        ('ᵍ', 'g'),
+        ('ʰ', 'h'),
+        ('ⁱ', 'i'),
+        ('ʲ', 'j'),
+        ('ᵏ', 'k'),
+        ('ˡ', 'l'),
+        ('ᵐ', 'm'),
+        ('ⁿ', 'n'),
+        ('ᵒ', 'o'),
+        ('ᵖ', 'p'),
+        ('ʳ', 'r'),
+        ('ˢ', 's'),
+        ('ᵗ', 't'),
+        ('ᵘ', 'u'),
+        ('ᵛ', 'v'),
+        ('ʷ', 'w'),
+        ('ˣ', 'x'),
+        ('ʸ', 'y'),
+        ('ᶻ', 'z'),
+    ];
+    let name = lexeme
+        .chars()
+        .map(|superscript| {
+            LETTERS
+                .iter()
+                .find(|(letter, _)| *letter == superscript)
+                .map(|(_, letter)| *letter)
+                .unwrap()
+        })
+        .collect();
+    Identifier { name }
+}
+
+// PRECISE_CONSTANT :
+//    CONSTANT_SYMBOL _ DEC_DIGIT+
+//
+// The digits of the precision suffix are the number of significant decimal
+// digits requested, which saturate at `u32::MAX`. The parser checks that
+// they're within range (see `constant_digits`).
+fn precise_mathematical_constant<'input>(
+    lex: &mut logos::Lexer<'input, Token<'input>>,
+) -> Option<MathematicalConstant> {
+    let (symbol, digits) = lex.slice().rsplit_once('_').unwrap();
+    let digits = Some(digits.parse::<u32>().unwrap_or(u32::MAX));
+    Some(match symbol {
+        "pi" | "π" | "𝜋" => MathematicalConstant::Pi {
+            value: std::f64::consts::PI,
+            digits,
+        },
+        "euler" | "ℯ" => MathematicalConstant::Euler {
+            value: std::f64::consts::E,
+            digits,
+        },
+        "tau" | "τ" | "𝜏" => MathematicalConstant::Tau {
+            value: std::f64::consts::TAU,
+            digits,
+        },
+        "golden" | "φ" | "𝜙" => MathematicalConstant::GoldenRatio {
+            value: 1.618033988749895,
+            digits,
+        },
+        "catalan" => MathematicalConstant::Catalan {
+            value: 0.915965594177219,
+            digits,
+        },
+        "eulermascheroni" | "eulergamma" | "γ" | "𝛾" => {
+            MathematicalConstant::EulerMascheroni {
+                value: 0.5772156649015329,
+                digits,
+            }
+        }
+        _ => unreachable!(),
+    })
+}
+
+/// Consumes the remainder of a raw (byte) string literal, whose opening
+/// `r#*"` has already been lexed, up to and including the closing `"`
+/// followed by the same number of `#`s.
+fn raw_string_literal<'input>(
+    lex: &mut logos::Lexer<'input, Token<'input>>,
+) -> Option<&'input str> {
+    let hashes = lex.slice().matches('#').count();
+    let terminator = format!("\"{}", "#".repeat(hashes));
+    // Unterminated raw strings are left as unrecognized tokens
+    let end = lex.remainder().find(&terminator)?;
+    lex.bump(end + terminator.len());
+    Some(lex.slice())
+}
+
+// LINE_COMMENT :
+//    //   (~[/ !] | //) ~\n*
+//    | //
+// INNER_LINE_DOC :
+//    //! ~[\n]*
+// OUTER_LINE_DOC :
+//    /// (~/ ~[\n]*)?
+fn line_comment<'input>(lex: &mut logos::Lexer<'input, Token<'input>>) -> Comment {
+    let comment = String::from(lex.slice());
+    if comment.starts_with("//!") {
+        Comment::DocComment(DocComment::InnerLineDocComment { comment })
+    } else if comment.starts_with("///") && !comment.starts_with("////") {
+        Comment::DocComment(DocComment::OuterLineDocComment { comment })
+    } else {
+        Comment::LineComment { comment }
+    }
+}
+
+// BLOCK_COMMENT :
+//    /* (~[*!] | ** | BlockCommentOrDoc) (BlockCommentOrDoc | ~*/)* */
+//    | /**/
+//    | /***/
+// INNER_BLOCK_DOC :
+//    /*! ( BlockCommentOrDoc | ~[*/] )* */
+// OUTER_BLOCK_DOC :
+//    /** (~* | BlockCommentOrDoc ) (BlockCommentOrDoc | ~[*/])* */
+//
+// Block comments nest (e.g. `/* a /* b */ c */` is a single comment), which a
+// regular expression can't express, so the comment is scanned from its opening
+// `/*` onwards. An unterminated block comment extends to the end of the input
+// and is unrecognized.
+fn block_comment<'input>(
+    lex: &mut logos::Lexer<'input, Token<'input>>,
+) -> Option<Comment> {
+    let remainder = lex.remainder().as_bytes();
+    let mut depth = 1;
+    let mut i = 0;
+    while depth > 0 {
+        match remainder.get(i..i + 2) {
+            Some(b"/*") => {
+                depth += 1;
+                i += 2;
+            }
+            Some(b"*/") => {
+                depth -= 1;
+                i += 2;
+            }
+            Some(_) => i += 1,
+            None => {
+                lex.bump(remainder.len());
+                return None;
+            }
+        }
+    }
+    lex.bump(i);
+
+    let comment = String::from(lex.slice());
+    let comment = if comment.starts_with("/*!") {
+        Comment::DocComment(DocComment::InnerBlockDocComment { comment })
+    } else if comment.starts_with("/**")
+        && !comment.starts_with("/***")
+        && comment != "/**/"
+    {
+        Comment::DocComment(DocComment::OuterBlockDocComment { comment })
+    } else {
+        Comment::BlockComment { comment }
+    };
+    Some(comment)
+}
+
+/// Returns the digits of an integer literal's lexeme (sans prefix), skipping
+/// underscores and stopping at the integer suffix (e.g. `1_000u32` yields
+/// `1000`).
+pub(crate) fn integer_digits(lexeme: &str) -> impl Iterator<Item = char> + '_ {
+    lexeme
+        .chars()
+        .take_while(|c| *c != 'u' && *c != 'i')
+        .filter(|c| *c != '_')
+}
+
+/// Returns the integer suffix of an integer literal's lexeme (sans prefix),
+/// if any (e.g. `1_000u32` yields `u32`).
+pub(crate) fn integer_suffix(lexeme: &str) -> Option<&str> {
+    lexeme.find(|c| c == 'u' || c == 'i').map(|i| &lexeme[i..])
+}
+
+/// Returns the value of an integer literal's lexeme (sans prefix) in the given
+/// radix. Literals of any width are accepted, as the value is computed with
+/// arbitrary precision.
+pub(crate) fn integer_value(lexeme: &str, radix: u32) -> rug::Integer {
+    let digits = integer_digits(lexeme).collect::<String>();
+    // Literals consisting only of a prefix and underscores (e.g. `0x___`) are 0
+    if digits.is_empty() {
+        return rug::Integer::new();
+    }
+    rug::Integer::from_str_radix(&digits, radix as i32).unwrap()
+}
+
+/// Returns the value of the digits of a float literal ending in a decimal point
+/// (e.g. the `12` of `12.`), which, as in Rust, can't have an integer suffix
+/// (e.g. `12u8.`), where `start` and `end` are the byte range of the literal
+/// within the source text.
+pub(crate) fn unsuffixed_decimal_value(
+    lexeme: &str,
+    start: usize,
+    end: usize,
+) -> Result<rug::Integer, InternalParserError> {
+    match integer_suffix(lexeme) {
+        Some(_) => Err(InternalParserError::SuffixedFloatLiteral { start, end }),
+        None => Ok(integer_value(lexeme, 10)),
+    }
+}
+
+/// Returns the [`Integer`] of the type named by an integer literal's suffix
+/// (e.g. `300u16` yields `Integer::U16 { n: 300 }`), where `start` and `end`
+/// are the byte range of the literal within the source text.
+///
+/// Unsuffixed literals take the first of `i32`, `i64`, `i128` and `u128`
+/// which can represent their value, and are otherwise [`Integer::Big`].
+pub(crate) fn integer_literal(
+    lexeme: &str,
+    radix: u32,
+    start: usize,
+    end: usize,
+) -> Result<Integer, InternalParserError> {
+    let n = integer_value(lexeme, radix);
+    let out_of_range =
+        |ty| InternalParserError::IntegerLiteralOutOfRange { ty, start, end };
+    match integer_suffix(lexeme) {
+        Some("u8") => n
+            .to_u8()
+            .map(|n| Integer::U8 { n })
+            .ok_or_else(|| out_of_range("u8")),
+        Some("u16") => n
+            .to_u16()
+            .map(|n| Integer::U16 { n })
+            .ok_or_else(|| out_of_range("u16")),
+        Some("u32") => n
+            .to_u32()
+            .map(|n| Integer::U32 { n })
+            .ok_or_else(|| out_of_range("u32")),
+        Some("u64") => n
+            .to_u64()
+            .map(|n| Integer::U64 { n })
+            .ok_or_else(|| out_of_range("u64")),
+        Some("u128") => n
+            .to_u128()
+            .map(|n| Integer::U128 { n })
+            .ok_or_else(|| out_of_range("u128")),
+        Some("usize") => n
+            .to_usize()
+            .map(|n| Integer::USize { n })
+            .ok_or_else(|| out_of_range("usize")),
+        Some("i8") => n
+            .to_i8()
+            .map(|n| Integer::I8 { n })
+            .ok_or_else(|| out_of_range("i8")),
+        Some("i16") => n
+            .to_i16()
+            .map(|n| Integer::I16 { n })
+            .ok_or_else(|| out_of_range("i16")),
+        Some("i32") => n
+            .to_i32()
+            .map(|n| Integer::I32 { n })
+            .ok_or_else(|| out_of_range("i32")),
+        Some("i64") => n
+            .to_i64()
+            .map(|n| Integer::I64 { n })
+            .ok_or_else(|| out_of_range("i64")),
+        Some("i128") => n
+            .to_i128()
+            .map(|n| Integer::I128 { n })
+            .ok_or_else(|| out_of_range("i128")),
+        Some("isize") => n
+            .to_isize()
+            .map(|n| Integer::ISize { n })
+            .ok_or_else(|| out_of_range("isize")),
+        Some(suffix) => unreachable!("unexpected integer suffix `{}`", suffix),
+        None => Ok(n
+            .to_i32()
+            .map(|n| Integer::I32 { n })
+            .or_else(|| n.to_i64().map(|n| Integer::I64 { n }))
+            .or_else(|| n.to_i128().map(|n| Integer::I128 { n }))
+            .or_else(|| n.to_u128().map(|n| Integer::U128 { n }))
+            .unwrap_or(Integer::Big { n })),
+    }
+}
+
+/// Returns the [`Float`] of the type named by a (hexadecimal) float literal's
+/// suffix, where unsuffixed literals are `f64`s. The literal is rounded to the
+/// nearest value representable by its type.
+pub(crate) fn float_literal(lexeme: &str) -> Result<Float, InternalParserError> {
+    let (digits, suffix) = split_float_suffix(lexeme);
+    let parts = float_parts(digits)?;
+    Ok(if parts.radix == 16 {
+        // Hexadecimal floats are rounded from their exact binary value, as
+        // `str::parse` only accepts decimal floats
+        let value = |precision| {
+            rug::Float::with_val(precision, &parts.mantissa) << parts.binary_shift()
+        };
+        match suffix {
+            Some("f32") => Float::F32 {
+                f: value(f32::MANTISSA_DIGITS).to_f32(),
+            },
+            _ => Float::F64 {
+                f: value(f64::MANTISSA_DIGITS).to_f64(),
+            },
+        }
+    } else {
+        let digits = digits.replace('_', "");
+        match suffix {
+            Some("f32") => Float::F32 {
+                f: digits.parse().unwrap(),
+            },
+            _ => Float::F64 {
+                f: digits.parse().unwrap(),
+            },
+        }
+    })
+}
+
+/// The numerator and denominator of a vulgar fraction (e.g. `¾`).
+fn vulgar_fraction(fraction: char) -> Option<(u32, u32)> {
+    let fraction = match fraction {
+        '½' => (1, 2),
+        '⅓' => (1, 3),
+        '⅔' => (2, 3),
+        '¼' => (1, 4),
+        '¾' => (3, 4),
+        '⅕' => (1, 5),
+        '⅖' => (2, 5),
+        '⅗' => (3, 5),
+        '⅘' => (4, 5),
+        '⅙' => (1, 6),
+        '⅚' => (5, 6),
+        '⅐' => (1, 7),
+        '⅛' => (1, 8),
+        '⅜' => (3, 8),
+        '⅝' => (5, 8),
+        '⅞' => (7, 8),
+        '⅑' => (1, 9),
+        '⅒' => (1, 10),
+        '↉' => (0, 3),
+        _ => return None,
+    };
+    Some(fraction)
+}
+
+/// Returns the number of significant decimal digits requested by the precision
+/// suffix of a mathematical constant, if any, which must be between 1 and
+/// [`MathematicalConstant::MAX_DIGITS`], where `start` and `end` are the byte
+/// range of the constant within the source text.
+pub(crate) fn constant_digits(
+    digits: Option<u32>,
+    start: usize,
+    end: usize,
+) -> Result<Option<u32>, InternalParserError> {
+    match digits {
+        Some(digits) if digits == 0 || digits > MathematicalConstant::MAX_DIGITS => {
+            Err(InternalParserError::InvalidConstantPrecision { start, end })
+        }
+        digits => Ok(digits),
+    }
+}
+
+/// Returns the exact value of a rational literal, i.e. a quotient of decimal
+/// integers (e.g. `3/4r`) or a (mixed) vulgar fraction (e.g. `¾`, `1½`),
+/// where `start` and `end` are the byte range of the literal within the
+/// source text.
+pub(crate) fn rational_literal(
+    lexeme: &str,
+    start: usize,
+    end: usize,
+) -> Result<rug::Rational, InternalParserError> {
+    if let Some(quotient) = lexeme.strip_suffix('r') {
+        let (numerator, denominator) = quotient.split_once('/').unwrap();
+        let denominator = integer_value(denominator, 10);
+        if denominator == 0 {
+            return Err(InternalParserError::ZeroDenominator { start, end });
+        }
+        return Ok(rug::Rational::from((
+            integer_value(numerator, 10),
+            denominator,
+        )));
+    }
+
+    let fraction = lexeme.chars().next_back().unwrap();
+    let whole = integer_value(&lexeme[..lexeme.len() - fraction.len_utf8()], 10);
+    let (numerator, denominator) = vulgar_fraction(fraction).unwrap();
+    Ok(rug::Rational::from((numerator, denominator)) + whole)
+}
+
+/// Returns the exact value of a float literal retagged as a
+/// [`Token::ExactFloatLiteral`], which may be a decimal literal followed by a
+/// decimal point (e.g. `12.`), where `start` and `end` are the byte range of
+/// the literal within the source text.
+pub(crate) fn exact_float_literal(
+    lexeme: &str,
+    start: usize,
+    end: usize,
+) -> Result<rug::Rational, InternalParserError> {
+    match lexeme.strip_suffix('.') {
+        Some(integer) => {
+            unsuffixed_decimal_value(integer, start, end).map(rug::Rational::from)
+        }
+        None => exact_float_value(lexeme, start, end),
+    }
+}
+
+/// Returns the exact value of a (hexadecimal) float literal, ignoring its
+/// suffix, so that no precision is lost before the literal's type is known
+/// (e.g. `0.1` is exactly `1/10`), where `start` and `end` are the byte range
+/// of the literal within the source text.
+///
+/// Like exact arithmetic, the value may need at most [`MAX_EXACT_BITS`]
+/// (e.g. `1e999999999` is out of range).
+pub(crate) fn exact_float_value(
+    lexeme: &str,
+    start: usize,
+    end: usize,
+) -> Result<rug::Rational, InternalParserError> {
+    let parts = float_parts(split_float_suffix(lexeme).0)?;
+    // Zero is exact whatever its exponent (e.g. `0.0e-99999`)
+    if parts.mantissa == 0 {
+        return Ok(rug::Rational::new());
+    }
+    let (base, exponent) = match parts.radix {
+        16 => (2, parts.exponent - 4 * parts.fraction_digits),
+        _ => (10, parts.exponent - parts.fraction_digits),
+    };
+    let bits = f64::from(parts.mantissa.significant_bits())
+        + exponent.unsigned_abs() as f64 * f64::from(base).log2();
+    if bits > MAX_EXACT_BITS as f64 {
+        return Err(InternalParserError::FloatLiteralOutOfRange { start, end });
+    }
+    let magnitude = exponent.unsigned_abs() as u32;
+    let scale = rug::Rational::from(rug::Integer::from(base).pow(magnitude));
+    Ok(if exponent < 0 {
+        rug::Rational::from(parts.mantissa) / scale
+    } else {
+        rug::Rational::from(parts.mantissa) * scale
+    })
+}
+
+/// Splits a float literal's lexeme into its digits and its float suffix (if
+/// any).
+fn split_float_suffix(lexeme: &str) -> (&str, Option<&str>) {
+    match lexeme
+        .strip_suffix("f32")
+        .or_else(|| lexeme.strip_suffix("f64"))
+    {
+        Some(digits) => (digits, Some(&lexeme[digits.len()..])),
+        None => (lexeme, None),
+    }
+}
+
+/// The components of a float literal, whose value is
+/// `mantissa * radix^-fraction_digits * base^exponent`, where the base of the
+/// exponent is 10 for decimal floats and 2 for hexadecimal floats.
+struct FloatParts {
+    radix: u32,
+    /// The literal's digits, ignoring the radix point
+    mantissa: rug::Integer,
+    /// The number of digits following the radix point
+    fraction_digits: i64,
+    /// The exponent, which saturates at `±i32::MAX` (so that it and the number
+    /// of fraction digits may be combined without overflowing an i64)
+    exponent: i64,
+}
+
+impl FloatParts {
+    /// The power of two by which a hexadecimal float's mantissa is scaled,
+    /// clamped to the range of an i32 (which over- or underflows any float).
+    fn binary_shift(&self) -> i32 {
+        let shift = self.exponent - 4 * self.fraction_digits;
+        shift.clamp(i64::from(i32::MIN), i64::from(i32::MAX)) as i32
+    }
+}
+
+fn float_parts(lexeme: &str) -> Result<FloatParts, InternalParserError> {
+    let (radix, lexeme, exponent_markers) = match lexeme
+        .strip_prefix("0x")
+        .or_else(|| lexeme.strip_prefix("0X"))
+    {
+        Some(lexeme) => (16, lexeme, ['p', 'P']),
+        None => (10, lexeme, ['e', 'E']),
+    };
+    let (significand, exponent) = match lexeme.find(exponent_markers) {
+        Some(i) => (&lexeme[..i], Some(&lexeme[i + 1..])),
+        None => (lexeme, None),
+    };
+    let (integer, fraction) =
+        significand.split_once('.').unwrap_or((significand, ""));
+
+    let exponent = match exponent {
+        // Hexadecimal floats must have a binary exponent (e.g. `0x1.8p0`)
+        None if radix == 16 => {
+            return Err(InternalParserError::InvalidFloatExponent)
+        }
+        // Float exponents (e.g. `e+_`, `p-_`) must contain at least one digit
+        Some(exponent) if !exponent.chars().any(|c| c.is_ascii_digit()) => {
+            return Err(InternalParserError::InvalidFloatExponent)
+        }
+        Some(exponent) => {
+            let magnitude = exponent.trim_start_matches(['+', '-']).replace('_', "");
+            // Exponents beyond the range of an i32 saturate, which over- or
+            // underflows the literal regardless
+            let magnitude =
+                i64::from(magnitude.parse::<i32>().unwrap_or(i32::MAX));
+            if exponent.starts_with('-') {
+                -magnitude
+            } else {
+                magnitude
+            }
+        }
+        None => 0,
+    };
+
+    let digits = format!("{}{}", integer, fraction).replace('_', "");
+    let mantissa = if digits.is_empty() {
+        rug::Integer::new()
+    } else {
+        rug::Integer::from_str_radix(&digits, radix as i32).unwrap()
+    };
+    let fraction_digits = fraction.chars().filter(|c| *c != '_').count() as i64;
+
+    Ok(FloatParts {
+        radix,
+        mantissa,
+        fraction_digits,
+        exponent,
+    })
+}
+
+/// Returns the exact imaginary part of an imaginary literal (e.g. `1.5i` yields
+/// `3/2`), where the imaginary unit on its own (i.e. `𝑖`) is `1`, and `start`
+/// and `end` are the byte range of the literal within the source text.
+pub(crate) fn imaginary_literal(
+    lexeme: &str,
+    start: usize,
+    end: usize,
+) -> Result<rug::Rational, InternalParserError> {
+    let coefficient = lexeme
+        .strip_suffix('i')
+        .or_else(|| lexeme.strip_suffix('𝑖'))
+        .unwrap();
+    if coefficient.is_empty() {
+        return Ok(rug::Rational::from(1));
+    }
+    exact_float_value(coefficient, start, end)
+}
+
+/// The token stream fed to the parser.
+///
+/// Comments are dropped from the stream, since the grammar has no
+/// productions for them (unless kept for the concrete syntax tree, see
+/// [`Lexer::with_comments`]), character and byte literals are composed from their
+/// opening apostrophe, rational literals (e.g. `3/4r`, `1½`) from their leading
+/// decimal literal, and any unrecognized input is surfaced as an
+/// [`InternalParserError::UnrecognizedToken`] (or, for a decimal point
+/// followed by an identifier, an [`InternalParserError::InvalidFloatLiteral`]).
+/// The unit of measure following a numeric literal (e.g. `m/s²` in
+/// `9.81 m/s²`) is retagged as [`Token::UnitSymbol`]s and
+/// [`Token::UnitOperator`]s, unless it's the differential of an enclosing
+/// integral (e.g. the `ds` of `∫(s in 0..1) 2 ds`, rather than decisecond).
+/// Float literals are retagged as [`Token::ExactFloatLiteral`]s when the
+/// Lexer keeps exact floats (see [`Lexer::exact_floats`]).
+pub struct Lexer<'input> {
+    token_stream: logos::Lexer<'input, Token<'input>>,
+    keep_comments: bool,
+    exact_floats: bool,
+    /// The end of the unit following the last numeric literal, if any.
+    unit_end: usize,
+    /// The differentials (e.g. `dx`) of the integrals whose integrands are
+    /// being lexed, innermost last.
+    differentials: Vec<String>,
+}
+
+impl<'input> Lexer<'input> {
+    pub fn new(input: &'input str) -> Self {
+        Self {
+            token_stream: Token::lexer(input),
+            keep_comments: false,
+            exact_floats: false,
+            unit_end: 0,
+            differentials: Vec::new(),
+        }
+    }
+
+    /// A token stream which retains comments, as used to build the lossless
+    /// concrete syntax tree (see [`crate::cst`]).
+    pub fn with_comments(input: &'input str) -> Self {
+        Self {
+            token_stream: Token::lexer(input),
+            keep_comments: true,
+            exact_floats: false,
+            unit_end: 0,
+            differentials: Vec::new(),
+        }
+    }
+
+    /// Whether float literals keep their exact value (e.g. `0.1` is `1/10`)
+    /// rather than being rounded to an f32 or f64, so that no precision is
+    /// lost before their type is known.
+    pub fn exact_floats(mut self, exact_floats: bool) -> Self {
+        self.exact_floats = exact_floats;
+        self
+    }
+
+    /// Extends the current token (a decimal literal) over a following decimal
+    /// point which makes it a float literal (e.g. `12.`), unless it begins a
+    /// range (e.g. `12..`).
+    fn decimal_point(&mut self) -> Option<&'input str> {
+        let remainder = self.token_stream.remainder();
+        if !remainder.starts_with('.') || remainder.starts_with("..") {
+            return None;
+        }
+        self.token_stream.bump(1);
+        Some(self.token_stream.slice())
+    }
+
+    /// Extends the current token (a decimal literal) over the remainder of a
+    /// rational literal, if one follows (e.g. `/4r` or `½` following `3`).
+    fn rational_literal(&mut self) -> Option<&'input str> {
+        let len = rational_literal_len(self.token_stream.remainder())?;
+        self.token_stream.bump(len);
+        Some(self.token_stream.slice())
+    }
+
+    /// Extends the current token (an opening `'` or `b'`) over the remainder
+    /// of a character literal, if one follows.
+    fn char_literal(&mut self, prefix: usize) -> Option<&'input str> {
+        let remainder = &self.token_stream.remainder()[prefix..];
+        let len = char_literal_len(remainder)?;
+        self.token_stream.bump(prefix + len);
+        Some(self.token_stream.slice())
+    }
+}
+
+/// The differential of the integral whose `∫` precedes the remainder (e.g.
+/// `dx` for `(x in 0..1) x² dx`), as named by the variable of its binding.
+fn integral_differential(remainder: &str) -> Option<String> {
+    let mut tokens = Token::lexer(remainder)
+        .filter(|token| !matches!(token, Token::Comment(_)));
+    match (tokens.next()?, tokens.next()?, tokens.next()?) {
+        (
+            Token::Delimiter(Delimiter::LeftParen),
+            Token::Identifier(variable),
+            Token::Keyword(Keyword::In)
+            | Token::MathematicalSymbol(MathematicalSymbol::ElementOf),
+        ) => Some(format!("d{}", variable)),
+        _ => None,
+    }
+}
+
+/// The length of the remainder of a rational literal following its leading
+/// decimal literal, i.e. either a vulgar fraction (e.g. `½`) or a `/`, the
+/// denominator's digits and an `r` suffix (e.g. `/4r`).
+fn rational_literal_len(remainder: &str) -> Option<usize> {
+    if let Some(fraction) = remainder
+        .chars()
+        .next()
+        .filter(|c| vulgar_fraction(*c).is_some())
+    {
+        return Some(fraction.len_utf8());
+    }
+
+    let denominator = remainder.strip_prefix('/')?;
+    if !denominator.starts_with(|c: char| c.is_ascii_digit()) {
+        return None;
+    }
+    let digits = denominator
+        .find(|c: char| !c.is_ascii_digit() && c != '_')
+        .unwrap_or(denominator.len());
+    let suffix = denominator[digits..].strip_prefix('r')?;
+    // The `r` mustn't begin an identifier (e.g. `3/4rad`)
+    if suffix.starts_with(|c: char| c.is_alphanumeric() || c == '_') {
+        return None;
+    }
+    Some(1 + digits + 1)
+}
+
+/// The length of a character literal's contents and closing `'`, i.e. a
+/// single space or a run of non-whitespace characters (and escapes).
+///
+/// The contents aren't validated here, so that empty and multi-codepoint
+/// character literals (e.g. `''`, `'ab'`) are reported by the parser, while
+/// labels (e.g. `'outer: loop`) aren't closed and remain an Apostrophe.
+fn char_literal_len(remainder: &str) -> Option<usize> {
+    if remainder.starts_with(" '") {
+        return Some(2);
+    }
+    let mut chars = remainder.char_indices();
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '\'' => return Some(i + 1),
+            '\\' => match chars.next() {
+                Some((_, escape)) if !escape.is_whitespace() => {}
+                _ => return None,
+            },
+            c if c.is_whitespace() => return None,
+            _ => {}
+        }
+    }
+    None
+}
+
+/// The length of the unit of measure at the start of the remainder following
+/// a numeric literal (e.g. ` m/s²` in `9.81 m/s²`), or 0 if there's none.
+///
+/// A unit is a product (`·`) or quotient (`/`) of known unit symbols, each
+/// optionally raised to a superscript exponent (e.g. `kg·m²/s²`). It may be
+/// separated from its value by whitespace, but is written without spaces, so
+/// that `6 m / s` divides `6 m` by `s`. The differentials of enclosing
+/// integrals aren't unit symbols.
+fn unit_len(remainder: &str, differentials: &[String]) -> usize {
+    let mut run = Vec::new();
+    for (token, span) in Token::lexer(remainder).spanned() {
+        if matches!(run.last(), Some((_, end)) if *end != span.start) {
+            break;
+        }
+        run.push((token, span.end));
+    }
+
+    let mut len = 0;
+    let mut i = 0;
+    while let Some((Token::Identifier(symbol), end)) = run.get(i) {
+        if Unit::from_symbol(symbol).is_none()
+            || differentials.iter().any(|differential| differential == symbol)
+        {
+            break;
+        }
+        len = *end;
+        i += 1;
+
+        // An optional exponent (e.g. `²`, `⁻¹`)
+        let sign = match run.get(i) {
+            Some((
+                Token::SuperscriptPunctuation(SuperscriptPunctuation::Plus),
+                _,
+            ))
+            | Some((
+                Token::SuperscriptPunctuation(SuperscriptPunctuation::Minus),
+                _,
+            )) => 1,
+            _ => 0,
+        };
+        if let Some((Token::SuperscriptIntegerLiteral(_), end)) = run.get(i + sign) {
+            len = *end;
+            i += sign + 1;
+        }
+
+        // An operator, which only belongs to the unit if a unit symbol follows
+        match run.get(i) {
+            Some((Token::Punctuation(Punctuation::Slash), _))
+            | Some((
+                Token::MathematicalSymbol(MathematicalSymbol::DotOperator),
+                _,
+            )) => i += 1,
+            _ => break,
+        }
+    }
+    len
+}
+
+impl<'input> Iterator for Lexer<'input> {
+    type Item = Spanned<Token<'input>, usize, InternalParserError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let token = match self.token_stream.next()? {
+                Token::Comment(_) if !self.keep_comments => continue,
+                Token::Error => {
+                    let span = self.token_stream.span();
+                    return Some(Err(InternalParserError::UnrecognizedToken {
+                        start: span.start,
+                        end: span.end,
+                    }));
+                }
+                Token::InvalidFloatLiteral(_) => {
+                    let span = self.token_stream.span();
+                    return Some(Err(InternalParserError::InvalidFloatLiteral {
+                        start: span.start,
+                        end: span.end,
+                    }));
+                }
+                Token::Punctuation(Punctuation::Apostrophe) => {
+                    self.char_literal(0).map_or(
+                        Token::Punctuation(Punctuation::Apostrophe),
+                        Token::CharLiteral,
+                    )
+                }
+                Token::DecimalLiteral(lexeme)
+                    if lexeme.bytes().all(|b| b.is_ascii_digit() || b == b'_') =>
+                {
+                    self.rational_literal().map_or(
+                        Token::DecimalLiteral(lexeme),
+                        Token::RationalLiteral,
+                    )
+                }
+                Token::Identifier("b")
+                    if self.token_stream.remainder().starts_with('\'') =>
+                {
+                    self.char_literal(1)
+                        .map_or(Token::Identifier("b"), Token::ByteLiteral)
+                }
+                token => token,
+            };
+            let token = match token {
+                Token::FloatLiteral(lexeme)
+                | Token::HexadecimalFloatLiteral(lexeme)
+                    if self.exact_floats =>
+                {
+                    Token::ExactFloatLiteral(lexeme)
+                }
+                Token::DecimalLiteral(lexeme) if self.exact_floats => self
+                    .decimal_point()
+                    .map_or(Token::DecimalLiteral(lexeme), Token::ExactFloatLiteral),
+                token => token,
+            };
+            let span = self.token_stream.span();
+
+            let token = if span.start < self.unit_end {
+                match token {
+                    Token::Identifier(symbol) => Token::UnitSymbol(symbol),
+                    Token::Punctuation(Punctuation::Slash) => {
+                        Token::UnitOperator(UnitOperator::Division)
+                    }
+                    Token::MathematicalSymbol(MathematicalSymbol::DotOperator) => {
+                        Token::UnitOperator(UnitOperator::Multiplication)
+                    }
+                    token => token,
+                }
+            } else {
+                token
+            };
+            match token {
+                Token::MathematicalSymbol(MathematicalSymbol::Integral) => {
+                    if let Some(differential) =
+                        integral_differential(self.token_stream.remainder())
+                    {
+                        self.differentials.push(differential);
+                    }
+                }
+                Token::Identifier(identifier)
+                    if self.differentials.last().map(String::as_str)
+                        == Some(identifier) =>
+                {
+                    self.differentials.pop();
+                }
+                _ => {}
+            }
+            if matches!(
+                token,
+                Token::DecimalLiteral(_)
+                    | Token::HexadecimalLiteral(_)
+                    | Token::OctalLiteral(_)
+                    | Token::BinaryLiteral(_)
+                    | Token::FloatLiteral(_)
+                    | Token::HexadecimalFloatLiteral(_)
+                    | Token::ExactFloatLiteral(_)
+                    | Token::RationalLiteral(_)
+            ) {
+                self.unit_end = span.end
+                    + unit_len(self.token_stream.remainder(), &self.differentials);
+            }
+
+            return Some(Ok((span.start, token, span.end)));
+        }
+    }
+}
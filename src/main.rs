@@ -1,8 +1,18 @@
-#![allow(dead_code, unused_imports)]
+#![allow(dead_code, unused_imports, clippy::enum_variant_names)]
 mod ast;
 mod cli;
+mod cst;
 mod db;
+mod docs;
+mod eval;
+mod lexer;
+mod number;
 mod parser_errors;
+mod quadrature;
+mod resolve;
+mod set;
+mod unescape;
+mod units;
 
 use std::{fs::read_to_string, path::PathBuf};
 
@@ -64,11 +74,15 @@ struct ProgramSource {
 #[macro_use]
 extern crate lalrpop_util;
 
-lalrpop_mod!(pub parser);
+lalrpop_mod!(#[allow(clippy::all, unused_variables)] pub parser);
 
 fn main() -> Result<()> {
-    let cli_args = BalsapopCli::parse();
+    run(BalsapopCli::parse())
+}
 
+/// Compiles the source file of the command line, whose errors are reported as
+/// diagnostics (and so exit with a non-zero status).
+fn run(cli_args: BalsapopCli) -> Result<()> {
     let source_file = match get_source_file(cli_args.source_path) {
         Ok(source_file) => source_file,
         Err(err) => return err,
@@ -82,15 +96,442 @@ fn main() -> Result<()> {
 
     // println!("AST: {:?}", ast);
 
-    let float_exponent = parser::FloatExponentParser::new().parse("e+_");
-    match float_exponent {
-        Ok(_) => println!("Float exponent parsed successfully"),
+    let source = source_file.contents.text.as_str();
+    match compile(source, cli_args.tolerance, cli_args.exact_floats) {
+        Ok(program) => println!("AST: {:#?}", program),
         Err(err) => {
-            println!("Error parsing float exponent: {:?}", err);
+            println!("Error parsing {}: {:?}", source_file.path.display(), err);
             match err {
                 lalrpop_util::ParseError::User {
                     error: parser_errors::InternalParserError::InvalidFloatExponent,
                 } => return Err(parser_errors::InvalidFloatExponent {}.into()),
+                lalrpop_util::ParseError::User {
+                    error:
+                        parser_errors::InternalParserError::InvalidFloatLiteral {
+                            start,
+                            end,
+                        },
+                } => {
+                    return Err(parser_errors::InvalidFloatLiteral {
+                        src: String::from(source),
+                        span: (start, end - start).into(),
+                    }
+                    .into())
+                }
+                lalrpop_util::ParseError::User {
+                    error:
+                        parser_errors::InternalParserError::SuffixedFloatLiteral {
+                            start,
+                            end,
+                        },
+                } => {
+                    return Err(parser_errors::SuffixedFloatLiteral {
+                        src: String::from(source),
+                        span: (start, end - start).into(),
+                    }
+                    .into())
+                }
+                lalrpop_util::ParseError::User {
+                    error:
+                        parser_errors::InternalParserError::UnrecognizedToken {
+                            start,
+                            end,
+                        },
+                } => {
+                    return Err(parser_errors::UnrecognizedToken {
+                        src: String::from(source),
+                        span: (start, end - start).into(),
+                    }
+                    .into())
+                }
+                lalrpop_util::ParseError::User {
+                    error:
+                        parser_errors::InternalParserError::IntegerLiteralOutOfRange {
+                            ty,
+                            start,
+                            end,
+                        },
+                } => {
+                    return Err(parser_errors::IntegerLiteralOutOfRange {
+                        src: String::from(source),
+                        span: (start, end - start).into(),
+                        ty,
+                    }
+                    .into())
+                }
+                lalrpop_util::ParseError::User {
+                    error:
+                        parser_errors::InternalParserError::EmptyCharLiteral {
+                            start,
+                            end,
+                        },
+                } => {
+                    return Err(parser_errors::EmptyCharLiteral {
+                        src: String::from(source),
+                        span: (start, end - start).into(),
+                    }
+                    .into())
+                }
+                lalrpop_util::ParseError::User {
+                    error:
+                        parser_errors::InternalParserError::OverlongCharLiteral {
+                            start,
+                            end,
+                        },
+                } => {
+                    return Err(parser_errors::OverlongCharLiteral {
+                        src: String::from(source),
+                        span: (start, end - start).into(),
+                    }
+                    .into())
+                }
+                lalrpop_util::ParseError::User {
+                    error:
+                        parser_errors::InternalParserError::InvalidEscape {
+                            error,
+                            start,
+                            end,
+                        },
+                } => {
+                    return Err(parser_errors::InvalidEscape {
+                        src: String::from(source),
+                        span: (start, end - start).into(),
+                        error,
+                    }
+                    .into())
+                }
+                lalrpop_util::ParseError::User {
+                    error:
+                        parser_errors::InternalParserError::ZeroDenominator {
+                            start,
+                            end,
+                        },
+                } => {
+                    return Err(parser_errors::ZeroDenominator {
+                        src: String::from(source),
+                        span: (start, end - start).into(),
+                    }
+                    .into())
+                }
+                lalrpop_util::ParseError::User {
+                    error:
+                        parser_errors::InternalParserError::FloatLiteralOutOfRange {
+                            start,
+                            end,
+                        },
+                } => {
+                    return Err(parser_errors::FloatLiteralOutOfRange {
+                        src: String::from(source),
+                        span: (start, end - start).into(),
+                    }
+                    .into())
+                }
+                lalrpop_util::ParseError::User {
+                    error:
+                        parser_errors::InternalParserError::InvalidConstantPrecision {
+                            start,
+                            end,
+                        },
+                } => {
+                    return Err(parser_errors::InvalidConstantPrecision {
+                        src: String::from(source),
+                        span: (start, end - start).into(),
+                    }
+                    .into())
+                }
+                lalrpop_util::ParseError::User {
+                    error:
+                        parser_errors::InternalParserError::IncompatibleUnits {
+                            error,
+                            start,
+                            end,
+                        },
+                } => {
+                    return Err(parser_errors::IncompatibleUnits {
+                        src: String::from(source),
+                        span: (start, end - start).into(),
+                        error,
+                    }
+                    .into())
+                }
+                lalrpop_util::ParseError::User {
+                    error:
+                        parser_errors::InternalParserError::UnitExponentOutOfRange {
+                            start,
+                            end,
+                        },
+                } => {
+                    return Err(parser_errors::UnitExponentOutOfRange {
+                        src: String::from(source),
+                        span: (start, end - start).into(),
+                    }
+                    .into())
+                }
+                lalrpop_util::ParseError::User {
+                    error:
+                        parser_errors::InternalParserError::DivisionByZero { start, end },
+                } => {
+                    return Err(parser_errors::DivisionByZero {
+                        src: String::from(source),
+                        span: (start, end - start).into(),
+                    }
+                    .into())
+                }
+                lalrpop_util::ParseError::User {
+                    error:
+                        parser_errors::InternalParserError::InvalidOperand {
+                            operator,
+                            operand,
+                            start,
+                            end,
+                        },
+                } => {
+                    return Err(parser_errors::InvalidOperand {
+                        src: String::from(source),
+                        span: (start, end - start).into(),
+                        operator,
+                        operand,
+                    }
+                    .into())
+                }
+                lalrpop_util::ParseError::User {
+                    error:
+                        parser_errors::InternalParserError::InvalidOperands {
+                            operator,
+                            left,
+                            right,
+                            start,
+                            end,
+                        },
+                } => {
+                    return Err(parser_errors::InvalidOperands {
+                        src: String::from(source),
+                        span: (start, end - start).into(),
+                        operator,
+                        left,
+                        right,
+                    }
+                    .into())
+                }
+                lalrpop_util::ParseError::User {
+                    error:
+                        parser_errors::InternalParserError::InvalidDomain {
+                            operator,
+                            expected,
+                            start,
+                            end,
+                        },
+                } => {
+                    return Err(parser_errors::InvalidDomain {
+                        src: String::from(source),
+                        span: (start, end - start).into(),
+                        operator,
+                        expected,
+                    }
+                    .into())
+                }
+                lalrpop_util::ParseError::User {
+                    error:
+                        parser_errors::InternalParserError::UnknownFunction {
+                            start,
+                            end,
+                        },
+                } => {
+                    return Err(parser_errors::UnknownFunction {
+                        src: String::from(source),
+                        span: (start, end - start).into(),
+                    }
+                    .into())
+                }
+                lalrpop_util::ParseError::User {
+                    error:
+                        parser_errors::InternalParserError::WrongArgumentCount {
+                            expected,
+                            found,
+                            start,
+                            end,
+                        },
+                } => {
+                    return Err(parser_errors::WrongArgumentCount {
+                        src: String::from(source),
+                        span: (start, end - start).into(),
+                        expected,
+                        found,
+                    }
+                    .into())
+                }
+                lalrpop_util::ParseError::User {
+                    error:
+                        parser_errors::InternalParserError::InvalidIntegrand {
+                            found,
+                            start,
+                            end,
+                        },
+                } => {
+                    return Err(parser_errors::InvalidIntegrand {
+                        src: String::from(source),
+                        span: (start, end - start).into(),
+                        found,
+                    }
+                    .into())
+                }
+                lalrpop_util::ParseError::User {
+                    error:
+                        parser_errors::InternalParserError::NonConvergentIntegral {
+                            start,
+                            end,
+                        },
+                } => {
+                    return Err(parser_errors::NonConvergentIntegral {
+                        src: String::from(source),
+                        span: (start, end - start).into(),
+                    }
+                    .into())
+                }
+                lalrpop_util::ParseError::User {
+                    error:
+                        parser_errors::InternalParserError::InvalidCondition {
+                            found,
+                            start,
+                            end,
+                        },
+                } => {
+                    return Err(parser_errors::InvalidCondition {
+                        src: String::from(source),
+                        span: (start, end - start).into(),
+                        found,
+                    }
+                    .into())
+                }
+                lalrpop_util::ParseError::User {
+                    error:
+                        parser_errors::InternalParserError::FailedAssertion {
+                            start,
+                            end,
+                        },
+                } => {
+                    return Err(parser_errors::FailedAssertion {
+                        src: String::from(source),
+                        span: (start, end - start).into(),
+                    }
+                    .into())
+                }
+                lalrpop_util::ParseError::User {
+                    error:
+                        parser_errors::InternalParserError::MismatchedSetTypes {
+                            error,
+                            start,
+                            end,
+                        },
+                } => {
+                    return Err(parser_errors::MismatchedSetTypes {
+                        src: String::from(source),
+                        span: (start, end - start).into(),
+                        error,
+                    }
+                    .into())
+                }
+                lalrpop_util::ParseError::User {
+                    error:
+                        parser_errors::InternalParserError::UnknownTrait { start, end },
+                } => {
+                    return Err(parser_errors::UnknownTrait {
+                        src: String::from(source),
+                        span: (start, end - start).into(),
+                    }
+                    .into())
+                }
+                lalrpop_util::ParseError::User {
+                    error:
+                        parser_errors::InternalParserError::MissingTraitMethod {
+                            start,
+                            end,
+                        },
+                } => {
+                    return Err(parser_errors::MissingTraitMethod {
+                        src: String::from(source),
+                        span: (start, end - start).into(),
+                    }
+                    .into())
+                }
+                lalrpop_util::ParseError::User {
+                    error:
+                        parser_errors::InternalParserError::UnknownTraitMethod {
+                            start,
+                            end,
+                        },
+                } => {
+                    return Err(parser_errors::UnknownTraitMethod {
+                        src: String::from(source),
+                        span: (start, end - start).into(),
+                    }
+                    .into())
+                }
+                lalrpop_util::ParseError::User {
+                    error:
+                        parser_errors::InternalParserError::DuplicateMethod {
+                            start,
+                            end,
+                        },
+                } => {
+                    return Err(parser_errors::DuplicateMethod {
+                        src: String::from(source),
+                        span: (start, end - start).into(),
+                    }
+                    .into())
+                }
+                lalrpop_util::ParseError::User {
+                    error:
+                        parser_errors::InternalParserError::DuplicateImpl { start, end },
+                } => {
+                    return Err(parser_errors::DuplicateImpl {
+                        src: String::from(source),
+                        span: (start, end - start).into(),
+                    }
+                    .into())
+                }
+                lalrpop_util::ParseError::User {
+                    error:
+                        parser_errors::InternalParserError::UnknownMethod { start, end },
+                } => {
+                    return Err(parser_errors::UnknownMethod {
+                        src: String::from(source),
+                        span: (start, end - start).into(),
+                    }
+                    .into())
+                }
+                lalrpop_util::ParseError::User {
+                    error:
+                        parser_errors::InternalParserError::AmbiguousMethod {
+                            start,
+                            end,
+                        },
+                } => {
+                    return Err(parser_errors::AmbiguousMethod {
+                        src: String::from(source),
+                        span: (start, end - start).into(),
+                    }
+                    .into())
+                }
+                lalrpop_util::ParseError::UnrecognizedToken {
+                    token: (start, _, end),
+                    expected,
+                } => {
+                    return Err(parser_errors::UnexpectedToken {
+                        src: String::from(source),
+                        span: (start, end - start).into(),
+                        expected: expected.join(", "),
+                    }
+                    .into())
+                }
+                lalrpop_util::ParseError::UnrecognizedEOF { location, expected } => {
+                    return Err(parser_errors::UnexpectedToken {
+                        src: String::from(source),
+                        span: (location, 0).into(),
+                        expected: expected.join(", "),
+                    }
+                    .into())
+                }
                 _ => {
                     println!("Other error");
                 }
@@ -107,6 +548,33 @@ fn main() -> Result<()> {
     Ok(())
 }
 
+/// Compiles the source of a program: parses it, attaches its doc comments as
+/// found in its concrete syntax tree (see [`cst::build`]), resolves its method
+/// calls (see [`resolve::check`]), and evaluates its constant expressions (see
+/// [`eval::check`]), whose integrals are computed to within the tolerance.
+/// Float literals keep their exact value if `exact_floats` is set (see
+/// [`lexer::Lexer::exact_floats`]).
+pub(crate) fn compile(
+    source: &str,
+    tolerance: f64,
+    exact_floats: bool,
+) -> Result<
+    Block,
+    lalrpop_util::ParseError<
+        usize,
+        lexer::Token<'_>,
+        parser_errors::InternalParserError,
+    >,
+> {
+    let mut program = parser::ProgramParser::new()
+        .parse(lexer::Lexer::new(source).exact_floats(exact_floats))?;
+    docs::attach_doc_comments(&cst::build(source, &program), &mut program);
+    resolve::check(&program)
+        .and_then(|_| eval::check(&program, tolerance))
+        .map_err(|error| lalrpop_util::ParseError::User { error })?;
+    Ok(program)
+}
+
 pub(crate) fn get_source_file(
     source_path: Option<PathBuf>,
 ) -> Result<Box<SourceFile>, Result<(), miette::ErrReport>> {
@@ -147,6 +615,20 @@ mod cli_usage_test_suite {
     use super::*;
     use pretty_assertions::assert_eq;
 
+    /// Compiles a source with the default tolerance, rounding float literals.
+    fn compile(
+        source: &str,
+    ) -> Result<
+        Block,
+        lalrpop_util::ParseError<
+            usize,
+            lexer::Token<'_>,
+            parser_errors::InternalParserError,
+        >,
+    > {
+        super::compile(source, quadrature::DEFAULT_TOLERANCE, false)
+    }
+
     #[test]
     fn test_get_source_file() {
         let source_file =
@@ -165,2046 +647,6643 @@ mod cli_usage_test_suite {
         let source_file = get_source_file(None);
         assert!(source_file.is_err());
     }
-}
 
-// TODO Have sections like the following
-// NonTerminals (e.g. Expression, Statement, etc.)
-#[cfg(test)]
-mod parser_test_suite {
-    use super::*;
-    use crate::parser_errors::InternalParserError::*;
-    use lalrpop_util::ParseError;
-    use pretty_assertions::assert_eq;
+    #[test]
+    fn test_parse_example_source_file() {
+        let source_file =
+            get_source_file(Some(PathBuf::from("examples/hello.balsapop"))).unwrap();
+        let program = compile(&source_file.contents.text).unwrap();
+        assert_eq!(program.statements.len(), 1);
+        assert!(program.value.is_none());
+    }
 
     #[test]
-    fn test_parse_string_literals() {
-        // let ast = parser::LiteralExpressionParser::new()
-        //     .parse(r#""Hello""#)
-        //     .unwrap();
-        // assert_eq!(ast, ast::LiteralExpression::String {
-        //     s: "Hello".to_string(),
-        // });
+    fn test_compile_units_example_source_file() {
+        let source_file =
+            get_source_file(Some(PathBuf::from("examples/units.balsapop"))).unwrap();
+        let program = compile(&source_file.contents.text).unwrap();
+        assert_eq!(program.statements.len(), 6);
+    }
 
-        // let ast = parser::LiteralExpressionParser::new()
-        //     .parse(r#""Hello, world!""#)
-        //     .unwrap();
-        // assert_eq!(ast, ast::LiteralExpression::String {
-        //     s: "Hello, world!".to_string(),
-        // });
+    #[test]
+    fn test_tolerance_flag() {
+        let cli = BalsapopCli::try_parse_from(["balsapopc", "a.balsapop"]).unwrap();
+        assert_eq!(cli.tolerance, quadrature::DEFAULT_TOLERANCE);
+        let cli = BalsapopCli::try_parse_from([
+            "balsapopc",
+            "a.balsapop",
+            "--tolerance",
+            "1e-300",
+        ])
+        .unwrap();
+        assert_eq!(cli.tolerance, 1e-300);
+        for tolerance in ["0", "-1e-6", "inf", "tight"] {
+            assert!(BalsapopCli::try_parse_from([
+                "balsapopc",
+                "a.balsapop",
+                "--tolerance",
+                tolerance
+            ])
+            .is_err());
+        }
 
-        // Character literals
-        // assert_eq!(parser::CharLiteralParser::new().parse('a'), Ok('a'));
-        // assert_eq!(parser::CharLiteralParser::new().parse('z'), Ok('z'));
-        // assert_eq!(parser::CharLiteralParser::new().parse('A'), Ok('A'));
-        // assert_eq!(parser::CharLiteralParser::new().parse('Z'), Ok('Z'));
+        // Integrals are computed to within the tolerance while compiling
+        let source = "x := ∫(x in 0..1) sqrt(x) dx;";
+        assert!(super::compile(source, 1e-4, false).is_ok());
+        assert_eq!(
+            super::compile(source, 1e-300, false),
+            Err(lalrpop_util::ParseError::User {
+                error: parser_errors::InternalParserError::NonConvergentIntegral {
+                    start: 5,
+                    end: 30
+                },
+            })
+        );
     }
 
     #[test]
-    fn test_parse_integer_literals() {
-        //     let ast =
-        // parser::LiteralExpressionParser::new().parse("22").unwrap();
-        //     assert_eq!(ast, ast::LiteralExpression::Integer { n: 22 });
+    fn test_exact_floats_flag() {
+        let cli = BalsapopCli::try_parse_from(["balsapopc", "a.balsapop"]).unwrap();
+        assert!(!cli.exact_floats);
+        let cli = BalsapopCli::try_parse_from([
+            "balsapopc",
+            "a.balsapop",
+            "--exact-floats",
+        ])
+        .unwrap();
+        assert!(cli.exact_floats);
 
-        //     let ast =
-        // parser::LiteralExpressionParser::new().parse("0").unwrap();
-        //     assert_eq!(ast, ast::LiteralExpression::Integer { n: 0 });
+        // Exact floats are evaluated exactly while compiling, and so can't be
+        // divided by zero
+        let source = "x := 1 / 0.0;";
+        assert!(super::compile(source, quadrature::DEFAULT_TOLERANCE, false).is_ok());
+        assert_eq!(
+            super::compile(source, quadrature::DEFAULT_TOLERANCE, true),
+            Err(lalrpop_util::ParseError::User {
+                error: parser_errors::InternalParserError::DivisionByZero {
+                    start: 5,
+                    end: 12
+                },
+            })
+        );
+    }
 
-        //     let ast =
-        // parser::LiteralExpressionParser::new().parse("-22").unwrap();
-        //     assert_eq!(ast, ast::LiteralExpression::Integer { n: -22 });
+    #[test]
+    fn test_run_reports_mismatched_set_types() {
+        let run = |name: &str, source: &str| {
+            let path = std::env::temp_dir().join(name);
+            std::fs::write(&path, source).unwrap();
+            let cli = BalsapopCli::try_parse_from([
+                PathBuf::from("balsapopc"),
+                path.clone(),
+            ])
+            .unwrap();
+            let result = super::run(cli);
+            std::fs::remove_file(path).unwrap();
+            result
+        };
 
-        // Intermediate Non-Terminals (e.g. DecimalDigitOrUnderscore, etc.)
-        // These need to return chars as they are used within other
-        // nonterminals which filter out the underscores
+        assert!(run("balsapop_set.balsapop", "x := {1, 2};").is_ok());
+        for (name, source) in [
+            ("balsapop_set_literal.balsapop", "x := {1, true};"),
+            ("balsapop_set_builder.balsapop", "x := {y | y ∈ [1, true]};"),
+        ] {
+            // An error of `main`, which exits with a non-zero status
+            let report = run(name, source).unwrap_err();
+            assert!(report
+                .downcast_ref::<parser_errors::MismatchedSetTypes>()
+                .is_some());
+        }
+    }
 
-        // Integer Literals
+    #[test]
+    fn test_compile_big_operators_example_source_file() {
+        let source_file =
+            get_source_file(Some(PathBuf::from("examples/big_operators.balsapop")))
+                .unwrap();
+        let program = compile(&source_file.contents.text).unwrap();
+        assert_eq!(program.statements.len(), 5);
+    }
+
+    #[test]
+    fn test_compile_evaluates_big_operators() {
+        let error = |error| Err(lalrpop_util::ParseError::User { error });
         assert_eq!(
-            parser::IntegerLiteralParser::new().parse("0x_____u8"),
-            Ok(0x0)
+            compile("x := ∑(i in 1..=3) 1/0;"),
+            error(parser_errors::InternalParserError::DivisionByZero {
+                start: 21,
+                end: 24
+            })
         );
         assert_eq!(
-            parser::IntegerLiteralParser::new().parse("0X_____u16"),
-            Ok(0x0)
+            compile("fn f(n: Int) { ∑(i in 1..=n) i } x := ∑(i in 1..=2.5) i;"),
+            error(parser_errors::InternalParserError::InvalidDomain {
+                operator: "∑",
+                expected: "a range of integers",
+                start: 53,
+                end: 56
+            })
         );
+
+        // The bounds of a big operator needn't be constant, nor its range short
+        assert!(compile("fn f(n: Int) { ∏(k in 1..=n) 1/k }").is_ok());
+        assert!(compile("x := ∑(i in 1..=10^12) i;").is_ok());
+        assert!(compile("x := ∏(i in 1..=2^20) 2^(2^20);").is_ok());
+    }
+
+    #[test]
+    fn test_compile_checks_units() {
+        let incompatible = |start, end| {
+            Err(lalrpop_util::ParseError::User {
+                error: parser_errors::InternalParserError::IncompatibleUnits {
+                    error: units::IncompatibleDimensions {
+                        left: units::Unit::from_symbol("m").unwrap().dimension,
+                        right: units::Unit::from_symbol("s").unwrap().dimension,
+                    },
+                    start,
+                    end,
+                },
+            })
+        };
+        assert_eq!(compile("x := 1 m + 1 s;"), incompatible(5, 14));
         assert_eq!(
-            parser::IntegerLiteralParser::new().parse("0x_1_2_3_4_5_6_7_u32"),
-            Ok(0x1234567)
+            compile("d := 1 m; t := 1 s; v := d / t; x := d + t;"),
+            incompatible(37, 42)
         );
         assert_eq!(
-            parser::IntegerLiteralParser::new().parse("0X_1_2_3_4_5_6_7_u64"),
-            Ok(0x1234567)
+            compile("fn f() { t := 2 s; 1 m - t }"),
+            incompatible(19, 26)
         );
+
+        // Expressions which aren't constant are left to run time
+        assert!(compile("fn f(t: Float) { 1 m + t }").is_ok());
+        assert!(compile("x := 1 m; x = 1 s; y := x + 1 s;").is_ok());
+        assert!(compile("fn f() {} x := 1 m + f();").is_ok());
+    }
+
+    #[test]
+    fn test_compile_evaluates_constants() {
+        let error = |error| Err(lalrpop_util::ParseError::User { error });
         assert_eq!(
-            parser::IntegerLiteralParser::new().parse("0x_a_b_c_d_e_f_u128"),
-            Ok(0xabcdef)
+            compile("x := 1 % (2 - 2);"),
+            error(parser_errors::InternalParserError::DivisionByZero {
+                start: 5,
+                end: 16
+            })
         );
         assert_eq!(
-            parser::IntegerLiteralParser::new().parse("0X_a_b_c_d_e_f_usize"),
-            Ok(0xabcdef)
+            compile("x := -true;"),
+            error(parser_errors::InternalParserError::InvalidOperand {
+                operator: "-",
+                operand: "logic",
+                start: 5,
+                end: 10
+            })
         );
         assert_eq!(
-            parser::IntegerLiteralParser::new().parse("0x_A_B_C_D_E_F_i8"),
-            Ok(0xABCDEF)
+            compile("fn f() { nosuch(1) }"),
+            error(parser_errors::InternalParserError::UnknownFunction {
+                start: 9,
+                end: 18
+            })
         );
         assert_eq!(
-            parser::IntegerLiteralParser::new().parse("0XA_B_C_D_E_F_i16"),
-            Ok(0xABCDEF)
+            compile("x := sin(1, 2);"),
+            error(parser_errors::InternalParserError::WrongArgumentCount {
+                expected: 1,
+                found: 2,
+                start: 5,
+                end: 14
+            })
         );
-        // TODO Error("Expected a digit, but found '_'. Integer literals cannot start
-        // assert_eq!(
-        //     parser::IntegerLiteralParser::new().parse("0X_A_B_C_D_E_F_i16"),
-        //     Ok(0xABCDEF)
-        // );
-        assert_eq!(parser::IntegerLiteralParser::new().parse("22i32"), Ok(22));
-        assert_eq!(parser::IntegerLiteralParser::new().parse("0i64"), Ok(0));
+
         assert_eq!(
-            parser::IntegerLiteralParser::new().parse("1_2_3_i128"),
-            Ok(123)
+            compile("enum Shape { Circle(Float) } x := Shape::Square(1.0);"),
+            error(parser_errors::InternalParserError::UnknownFunction {
+                start: 34,
+                end: 52
+            })
         );
-        // TODO Error("Expected a digit, but found '_'. Integer literals cannot start
-        // with an underscore.") assert_eq!(
-        //     parser::IntegerLiteralParser::new().parse("_1_2_3_i128"),
-        //     Ok(123)
-        // );
-        assert_eq!(
-            parser::IntegerLiteralParser::new().parse("1_000isize"),
-            Ok(1_000)
+
+        // Calls of declared functions, tuple structs, variants and associated
+        // functions, and ranges, aren't constant
+        assert!(compile("struct Meters(Float); x := Meters(1.0);").is_ok());
+        assert!(compile("fn sin(x: Float) { x } y := sin(1, 2);").is_ok());
+        assert!(
+            compile("enum Shape { Circle(Float) } x := Shape::Circle(1.0);").is_ok()
+        );
+        assert!(compile(
+            "struct Circle { r: Int }
+            impl Circle { fn new(r: Int) -> Circle { Circle { r } } }
+            c := Circle::new(1);"
+        )
+        .is_ok());
+        assert!(compile("r := 1..10;").is_ok());
+    }
+
+    #[test]
+    fn test_compile_checks_assertions() {
+        let error = |error| Err(lalrpop_util::ParseError::User { error });
+        assert!(compile("assert ∀ x ∈ 1..=3: x > 0;").is_ok());
+        assert_eq!(
+            compile("assert ∃ x ∈ {1, 2}: x > 5;"),
+            error(parser_errors::InternalParserError::FailedAssertion {
+                start: 7,
+                end: 30
+            })
         );
+        // A missing condition doesn't hold
         assert_eq!(
-            parser::IntegerLiteralParser::new().parse("1_234_567u8"),
-            Ok(1_234_567)
+            compile("xs := {1, 2}; assert ∀ x ∈ xs: x < 2 || missing;"),
+            error(parser_errors::InternalParserError::FailedAssertion {
+                start: 21,
+                end: 51
+            })
         );
         assert_eq!(
-            parser::IntegerLiteralParser::new().parse("1_234_567_890u16"),
-            Ok(1_234_567_890)
+            compile("assert ∑(i in 1..=3) i;"),
+            error(parser_errors::InternalParserError::InvalidCondition {
+                found: "number",
+                start: 7,
+                end: 24
+            })
         );
+
+        // Assertions which aren't constant are left to run time
+        assert!(compile("fn f(xs: Set<Int>) { assert ∀ x ∈ xs: x > 0; }").is_ok());
+        assert!(compile("fn f(n: Int) { assert ∃ k ∈ 1..=n: k² == n; }").is_ok());
+    }
+}
+
+// TODO Have sections like the following
+// NonTerminals (e.g. Expression, Statement, etc.)
+#[cfg(test)]
+mod parser_test_suite {
+    use super::*;
+    use crate::lexer::Lexer;
+    use crate::parser_errors::InternalParserError::*;
+    use crate::units::Unit;
+    use lalrpop_util::ParseError;
+    use pretty_assertions::assert_eq;
+    use rug::ops::Pow;
+
+    /// The expression as a fully parenthesized s-expression (e.g.
+    /// `(+ 1 (* 2 3))`), sans grouping parentheses.
+    fn sexp(expression: &Expression) -> String {
+        match expression {
+            Expression::Literal {
+                literal: LiteralExpression::NumericLiteral(n),
+                ..
+            } => number::Number::from(n).to_string(),
+            Expression::Literal {
+                literal: LiteralExpression::Quantity { value, unit },
+                ..
+            } => format!("{}{}", number::Number::from(value), unit.dimension),
+            Expression::Literal {
+                literal: LiteralExpression::Logic(logic),
+                ..
+            } => format!("{:?}", logic),
+            Expression::Literal { literal, .. } => format!("{:?}", literal),
+            Expression::Identifier { identifier, .. } => identifier.name.clone(),
+            Expression::SelfValue { .. } => String::from("self"),
+            Expression::Path { segments, .. } => sexp_path(segments),
+            Expression::Grouped { expression, .. } => sexp(expression),
+            Expression::Unary {
+                operator, operand, ..
+            } => format!("({} {})", operator.symbol(), sexp(operand)),
+            Expression::Binary {
+                left,
+                operator,
+                right,
+                ..
+            } => {
+                format!("({} {} {})", operator.symbol(), sexp(left), sexp(right))
+            }
+            Expression::Root {
+                index, radicand, ..
+            } => format!("(√ {} {})", sexp(index), sexp(radicand)),
+            Expression::Set { elements, .. } => {
+                let elements: Vec<_> = elements.iter().map(sexp).collect();
+                format!("{{{}}}", elements.join(" "))
+            }
+            Expression::List { elements, .. } => {
+                let elements: Vec<_> = elements.iter().map(sexp).collect();
+                format!("[{}]", elements.join(" "))
+            }
+            Expression::Comprehension {
+                kind,
+                element,
+                clauses,
+                ..
+            } => {
+                let clauses: Vec<_> = clauses
+                    .iter()
+                    .map(|clause| match clause {
+                        Clause::Generator(binding) => format!(
+                            "{} ∈ {}",
+                            binding.variable.name,
+                            sexp(&binding.domain)
+                        ),
+                        Clause::Filter(condition) => sexp(condition),
+                    })
+                    .collect();
+                let (open, close) = match kind {
+                    ComprehensionKind::Set => ("{", "}"),
+                    ComprehensionKind::List => ("[", "]"),
+                };
+                format!(
+                    "{}{} | {}{}",
+                    open,
+                    sexp(element),
+                    clauses.join(", "),
+                    close
+                )
+            }
+            Expression::Call {
+                function,
+                arguments,
+                ..
+            } => {
+                let arguments: Vec<_> = arguments.iter().map(sexp).collect();
+                format!("({} {})", sexp(function), arguments.join(" "))
+            }
+            Expression::Struct { path, fields, .. } => {
+                let fields: Vec<_> = fields
+                    .iter()
+                    .map(|field| {
+                        format!("{}: {}", field.name.name, sexp(&field.value))
+                    })
+                    .collect();
+                format!("({} {{{}}})", sexp_path(path), fields.join(", "))
+            }
+            Expression::Field { base, field, .. } => match field {
+                FieldName::Named(name) => {
+                    format!("(. {} {})", sexp(base), name.name)
+                }
+                FieldName::Index(index) => format!("(. {} {})", sexp(base), index),
+            },
+            Expression::MethodCall {
+                receiver,
+                method,
+                arguments,
+                ..
+            } => {
+                let operands: Vec<_> = std::iter::once(receiver.as_ref())
+                    .chain(arguments)
+                    .map(sexp)
+                    .collect();
+                format!("(.{} {})", method.name, operands.join(" "))
+            }
+            Expression::Integral {
+                binding, integrand, ..
+            } => format!(
+                "(∫ {} {} {})",
+                binding.variable.name,
+                sexp(&binding.domain),
+                sexp(integrand)
+            ),
+            Expression::Quantifier {
+                quantifier,
+                binding,
+                predicate,
+                ..
+            } => format!(
+                "({} {} {} {})",
+                quantifier.symbol(),
+                binding.variable.name,
+                sexp(&binding.domain),
+                sexp(predicate)
+            ),
+            Expression::BigOperator {
+                operator,
+                binding,
+                body,
+                ..
+            } => format!(
+                "({} {} {} {})",
+                operator.symbol(),
+                binding.variable.name,
+                sexp(&binding.domain),
+                sexp(body)
+            ),
+            Expression::Assignment {
+                target,
+                operator,
+                value,
+                ..
+            } => {
+                let operator = match operator {
+                    AssignmentOperator::Assign => String::from("="),
+                    AssignmentOperator::Compound(operator) => {
+                        format!("{}=", operator.symbol())
+                    }
+                };
+                format!("({} {} {})", operator, target.name, sexp(value))
+            }
+        }
+    }
+
+    /// Parses an expression, rendered as an s-expression.
+    fn parse_expression(source: &str) -> String {
+        sexp(
+            &parser::ExpressionParser::new()
+                .parse(Lexer::new(source))
+                .unwrap(),
+        )
+    }
+
+    /// A block's statements, followed by its value if any (e.g.
+    /// `(:= a 3) (+= a 1); a`).
+    fn sexp_block(block: &Block) -> String {
+        let mut parts: Vec<_> = block
+            .statements
+            .iter()
+            .map(|statement| match statement {
+                Statement::Declaration {
+                    name,
+                    ty: None,
+                    value,
+                    ..
+                } => format!("(:= {} {})", name.name, sexp(value)),
+                Statement::Declaration {
+                    name,
+                    ty: Some(ty),
+                    value,
+                    ..
+                } => format!(
+                    "(:= (: {} {}) {})",
+                    name.name,
+                    sexp_type(ty),
+                    sexp(value)
+                ),
+                Statement::Expression { expression, .. } => {
+                    format!("{};", sexp(expression))
+                }
+                Statement::Return {
+                    value: Some(value), ..
+                } => format!("(return {});", sexp(value)),
+                Statement::Return { value: None, .. } => String::from("(return);"),
+                Statement::Assertion { condition, .. } => {
+                    format!("(assert {});", sexp(condition))
+                }
+                Statement::Item(item) => sexp_item(item),
+            })
+            .collect();
+        parts.extend(block.value.as_deref().map(sexp));
+        parts.join(" ")
+    }
+
+    /// e.g. `(fn add (a: Int, b: Int) -> Int {(+ a b)})`
+    fn sexp_item(item: &Item) -> String {
+        match item {
+            Item::Function(function) => sexp_function(function),
+            Item::Struct(structure) => format!(
+                "({}struct {}{})",
+                sexp_visibility(structure.visibility),
+                structure.name.name,
+                sexp_fields(&structure.fields)
+            ),
+            Item::Enum(enumeration) => {
+                let variants: Vec<_> = enumeration
+                    .variants
+                    .iter()
+                    .map(|variant| {
+                        format!(
+                            "{}{}",
+                            variant.name.name,
+                            sexp_fields(&variant.fields)
+                        )
+                    })
+                    .collect();
+                format!(
+                    "({}enum {} {{{}}})",
+                    sexp_visibility(enumeration.visibility),
+                    enumeration.name.name,
+                    variants.join(", ")
+                )
+            }
+            Item::Trait(declaration) => {
+                let methods: Vec<_> =
+                    declaration.methods.iter().map(sexp_function).collect();
+                format!(
+                    "({}trait {}{} {{{}}})",
+                    sexp_visibility(declaration.visibility),
+                    declaration.name.name,
+                    sexp_where_clause(&declaration.where_clause),
+                    methods.join(" ")
+                )
+            }
+            Item::Impl(implementation) => {
+                let trait_type = match &implementation.trait_type {
+                    Some(ty) => format!("{} for ", sexp_type(ty)),
+                    None => String::new(),
+                };
+                let methods: Vec<_> =
+                    implementation.methods.iter().map(sexp_function).collect();
+                format!(
+                    "(impl {}{}{} {{{}}})",
+                    trait_type,
+                    sexp_type(&implementation.self_type),
+                    sexp_where_clause(&implementation.where_clause),
+                    methods.join(" ")
+                )
+            }
+        }
+    }
+
+    /// e.g. `(fn add (a: Int, b: Int) -> Int {(+ a b)})`, or
+    /// `(fn area (self) -> Float;)` for a required method
+    fn sexp_function(function: &Function) -> String {
+        let receiver = function.receiver.map(|_| String::from("self"));
+        let parameters: Vec<_> = receiver
+            .into_iter()
+            .chain(function.parameters.iter().map(|parameter| {
+                format!("{}: {}", parameter.name.name, sexp_type(&parameter.ty))
+            }))
+            .collect();
+        let return_type = match &function.return_type {
+            Some(ty) => format!(" -> {}", sexp_type(ty)),
+            None => String::new(),
+        };
+        let body = match &function.body {
+            Some(body) => format!(" {{{}}}", sexp_block(body)),
+            None => String::from(";"),
+        };
+        format!(
+            "({}fn {} ({}){}{}{})",
+            sexp_visibility(function.visibility),
+            function.name.name,
+            parameters.join(", "),
+            return_type,
+            sexp_where_clause(&function.where_clause),
+            body
+        )
+    }
+
+    /// e.g. ` where Self: Area + Display`, or nothing without a where clause
+    fn sexp_where_clause(predicates: &[WherePredicate]) -> String {
+        if predicates.is_empty() {
+            return String::new();
+        }
+        let predicates: Vec<_> = predicates
+            .iter()
+            .map(|predicate| {
+                let bounds: Vec<_> =
+                    predicate.bounds.iter().map(sexp_type).collect();
+                format!("{}: {}", sexp_type(&predicate.ty), bounds.join(" + "))
+            })
+            .collect();
+        format!(" where {}", predicates.join(", "))
+    }
+
+    fn sexp_visibility(visibility: Visibility) -> &'static str {
+        match visibility {
+            Visibility::Private => "",
+            Visibility::Public => "pub ",
+        }
+    }
+
+    /// e.g. ` {x: Int, pub y: Int}`, ` (Int, Int)`, or nothing for a unit struct
+    fn sexp_fields(fields: &Fields) -> String {
+        match fields {
+            Fields::Named(fields) => {
+                let fields: Vec<_> = fields
+                    .iter()
+                    .map(|field| {
+                        format!(
+                            "{}{}: {}",
+                            sexp_visibility(field.visibility),
+                            field.name.name,
+                            sexp_type(&field.ty)
+                        )
+                    })
+                    .collect();
+                format!(" {{{}}}", fields.join(", "))
+            }
+            Fields::Tuple(types) => {
+                let types: Vec<_> = types.iter().map(sexp_type).collect();
+                format!(" ({})", types.join(", "))
+            }
+            Fields::Unit => String::new(),
+        }
+    }
+
+    fn sexp_path(segments: &[Identifier]) -> String {
+        let segments: Vec<_> = segments
+            .iter()
+            .map(|segment| segment.name.as_str())
+            .collect();
+        segments.join("::")
+    }
+
+    fn sexp_type(ty: &Type) -> String {
+        match ty {
+            Type::Named {
+                name, arguments, ..
+            } if arguments.is_empty() => name.name.clone(),
+            Type::Named {
+                name, arguments, ..
+            } => {
+                let arguments: Vec<_> = arguments.iter().map(sexp_type).collect();
+                format!("{}<{}>", name.name, arguments.join(", "))
+            }
+            Type::SelfType { .. } => String::from("Self"),
+        }
+    }
+
+    fn parse_program(source: &str) -> String {
+        sexp_block(
+            &parser::ProgramParser::new()
+                .parse(Lexer::new(source))
+                .unwrap(),
+        )
+    }
+
+    #[test]
+    fn test_parse_expressions() {
+        let parse = parse_expression;
+
+        // Associativity
+        assert_eq!(parse("2 ^ 3 ^ 2"), "(^ 2 (^ 3 2))");
+        assert_eq!(parse("(2 ^ 3) ^ 2"), "(^ (^ 2 3) 2)");
+        assert_eq!(parse("1 - 2 - 3"), "(- (- 1 2) 3)");
+        assert_eq!(parse("8 / 4 / 2 % 3"), "(% (/ (/ 8 4) 2) 3)");
+        assert_eq!(parse("a && b && c"), "(&& (&& a b) c)");
+        assert_eq!(parse("a || b || c"), "(|| (|| a b) c)");
+        assert_eq!(parse("x = y = 1"), "(= x (= y 1))");
+
+        // Precedence
+        assert_eq!(parse("1 + 2 * 3"), "(+ 1 (* 2 3))");
+        assert_eq!(parse("(1 + 2) * 3"), "(* (+ 1 2) 3)");
+        assert_eq!(parse("6 ÷ 2 · 3"), "(* (/ 6 2) 3)");
+        assert_eq!(parse("2 * 3 ^ 2"), "(* 2 (^ 3 2))");
+        assert_eq!(parse("-2 ^ 2"), "(- (^ 2 2))");
+        assert_eq!(parse("2 ^ -1"), "(^ 2 (- 1))");
+        assert_eq!(parse("--x"), "(- (- x))");
+        assert_eq!(parse("-a * b"), "(* (- a) b)");
+        assert_eq!(parse("a & b | c & d"), "(| (& a b) (& c d))");
+        assert_eq!(parse("a + b & c"), "(& (+ a b) c)");
+        assert_eq!(parse("a | b == c"), "(== (| a b) c)");
+        assert_eq!(
+            parse("1 + 2 < 3 * 4 && x != y"),
+            "(&& (< (+ 1 2) (* 3 4)) (!= x y))"
+        );
+        assert_eq!(parse("!a && b || c"), "(|| (&& (! a) b) c)");
+        assert_eq!(parse("a || b && c"), "(|| a (&& b c))");
+        assert_eq!(parse("a || b .. c"), "(.. (|| a b) c)");
+        assert_eq!(parse("0..=n + 1"), "(..= 0 (+ n 1))");
+        assert_eq!(parse("x += 2 * 3"), "(+= x (* 2 3))");
+        assert_eq!(parse("x ^= a .. b"), "(^= x (.. a b))");
+
+        // Operands
+        assert_eq!(parse("3 + 4i"), "(+ 3 0+4i)");
+        assert_eq!(parse("1/2r * ¾"), "(* 1/2 3/4)");
+        assert_eq!(parse("1 m + 1 s"), "(+ 1m 1s)");
+        assert_eq!(parse("9.81 m/s² * 2 s"), "(* 9.81m·s⁻² 2s)");
         assert_eq!(
-            parser::IntegerLiteralParser::new().parse("9_876_543_210u32"),
-            Ok(9_876_543_210)
+            parse("true || missing"),
+            "(|| True { value: true } Missing)"
         );
+
+        // Spans
+        let expression = parser::ExpressionParser::new()
+            .parse(Lexer::new("(1 + 2) * 3"))
+            .unwrap();
+        assert_eq!(expression.span(), Span { start: 0, end: 11 });
+        match expression {
+            Expression::Binary { left, .. } => {
+                assert!(matches!(*left, Expression::Grouped { .. }));
+                assert_eq!(left.span(), Span { start: 0, end: 7 });
+            }
+            expression => {
+                panic!("expected a binary expression, got {:?}", expression)
+            }
+        }
+
+        // Comparisons and ranges don't associate
+        assert!(matches!(
+            parser::ExpressionParser::new().parse(Lexer::new("a < b < c")),
+            Err(ParseError::UnrecognizedToken {
+                token: (6, lexer::Token::Punctuation(Punctuation::LessThan), 7),
+                ..
+            })
+        ));
+        assert!(matches!(
+            parser::ExpressionParser::new().parse(Lexer::new("1..2..3")),
+            Err(ParseError::UnrecognizedToken {
+                token: (4, lexer::Token::Punctuation(Punctuation::DotDot), 6),
+                ..
+            })
+        ));
+        // Only identifiers may be assigned to
+        assert!(matches!(
+            parser::ExpressionParser::new().parse(Lexer::new("1 = 2")),
+            Err(ParseError::UnrecognizedToken {
+                token: (2, lexer::Token::Punctuation(Punctuation::Equals), 3),
+                ..
+            })
+        ));
+        assert!(matches!(
+            parser::ExpressionParser::new().parse(Lexer::new("(1 + 2")),
+            Err(ParseError::UnrecognizedEOF { .. })
+        ));
+    }
+
+    #[test]
+    fn test_parse_big_operators() {
+        let parse = parse_expression;
+
+        assert_eq!(parse("∑(i in 1..=n) i²"), "(∑ i (..= 1 n) (^ i 2))");
+        assert_eq!(parse("∏(k in 1..n) k"), "(∏ k (.. 1 n) k)");
         assert_eq!(
-            parser::IntegerLiteralParser::new()
-                .parse("1_234_567_890_123_456_789_123_456_789u64"),
-            Ok(1_234_567_890_123_456_789_123_456_789)
+            parse("∑(i in 0..(n + 1)) ∑(j in 0..i) i * j"),
+            "(∑ i (.. 0 (+ n 1)) (∑ j (.. 0 i) (* i j)))"
         );
+
+        // The body extends over a product, but not a sum
         assert_eq!(
-            parser::IntegerLiteralParser::new()
-                .parse("9_876_543_210_987_654_321_987_654_321u128"),
-            Ok(9_876_543_210_987_654_321_987_654_321)
+            parse("∑(i in 1..=n) 2 · i + 1"),
+            "(+ (∑ i (..= 1 n) (* 2 i)) 1)"
         );
         assert_eq!(
-            parser::IntegerLiteralParser::new().parse("0o_____usize"),
-            Ok(0o0)
+            parse("1 + ∏(k in 1..=n) k / 2"),
+            "(+ 1 (∏ k (..= 1 n) (/ k 2)))"
         );
+        assert_eq!(parse("2 · (∑(i in 1..=n) i)"), "(* 2 (∑ i (..= 1 n) i))");
+
+        let expression = parser::ExpressionParser::new()
+            .parse(Lexer::new("∑(i in 1..=3) i"))
+            .unwrap();
+        match expression {
+            Expression::BigOperator { binding, span, .. } => {
+                assert_eq!(binding.span, Span { start: 4, end: 14 });
+                assert_eq!(span, Span { start: 0, end: 17 });
+            }
+            expression => {
+                panic!("expected a big operator, got {:?}", expression)
+            }
+        }
+
+        // A big operator's variable is bound by `in`
+        assert!(matches!(
+            parser::ExpressionParser::new().parse(Lexer::new("∑(1..=3) 1")),
+            Err(ParseError::UnrecognizedToken { .. })
+        ));
+        // A big operator which is a factor is parenthesized
+        assert!(matches!(
+            parser::ExpressionParser::new().parse(Lexer::new("2 · ∑(i in 1..=n) i")),
+            Err(ParseError::UnrecognizedToken { .. })
+        ));
+    }
+
+    #[test]
+    fn test_parse_calls() {
+        let parse = parse_expression;
+
+        assert_eq!(parse("sin(x)"), "(sin x)");
+        assert_eq!(parse("f(a, b + 1)"), "(f a (+ b 1))");
+        assert_eq!(parse("f(a, b,)"), "(f a b)");
+        assert_eq!(parse("f()"), "(f )");
         assert_eq!(
-            parser::IntegerLiteralParser::new().parse("0O_____i8"),
-            Ok(0o0)
+            parse("sin(x)² + cos(x)²"),
+            "(+ (^ (sin x) 2) (^ (cos x) 2))"
         );
+        assert_eq!(parse("√abs(x)"), "(√ 2 (abs x))");
+    }
+
+    #[test]
+    fn test_parse_integrals() {
+        let parse = parse_expression;
+
+        assert_eq!(parse("∫(x in 0..1) x² dx"), "(∫ x (.. 0 1) (^ x 2))");
+        assert_eq!(parse("∫(θ in a..b) sin(θ) dθ"), "(∫ θ (.. a b) (sin θ))");
+        // The integrand extends over a product, up to the differential
         assert_eq!(
-            parser::IntegerLiteralParser::new().parse("0o_1_2_3_4_5_6_7_i16"),
-            Ok(0o1234567)
+            parse("∫(x in 0..1) 2 · x dx + 1"),
+            "(+ (∫ x (.. 0 1) (* 2 x)) 1)"
         );
         assert_eq!(
-            parser::IntegerLiteralParser::new().parse("0O_1_2_3_4_5_6_7_i32"),
-            Ok(0o1234567)
+            parse("∫(x in 0..1) ∫(y in 0..x) x · y dy dx"),
+            "(∫ x (.. 0 1) (∫ y (.. 0 x) (* x y)))"
         );
+        // A differential which is also a unit symbol (e.g. decisecond)
+        assert_eq!(parse("∫(s in 0..1) 2 ds"), "(∫ s (.. 0 1) 2)");
         assert_eq!(
-            parser::IntegerLiteralParser::new().parse("0o70i64"),
-            Ok(0o70)
+            parse("∫(m in 0..1) ∫(g in 0..m) 3 dg dm"),
+            "(∫ m (.. 0 1) (∫ g (.. 0 m) 3))"
         );
+
+        let expression = parser::ExpressionParser::new()
+            .parse(Lexer::new("∫(x in 0..1) x dx + 1"))
+            .unwrap();
+        match expression {
+            Expression::Binary { left, .. } => {
+                assert_eq!(left.span(), Span { start: 0, end: 19 });
+            }
+            expression => {
+                panic!("expected a binary expression, got {:?}", expression)
+            }
+        }
+
+        // The differential names the integral's variable
         assert_eq!(
-            parser::IntegerLiteralParser::new().parse("0O70i128"),
-            Ok(0o70)
+            parser::ExpressionParser::new().parse(Lexer::new("∫(x in 0..1) x dy")),
+            Err(ParseError::UnrecognizedToken {
+                token: (17, lexer::Token::Identifier("dy"), 19),
+                expected: vec![String::from("\"dx\"")],
+            })
         );
-        assert!(parser::IntegerLiteralParser::new()
-            .parse("0o8isize")
-            .is_err());
+        assert!(matches!(
+            parser::ExpressionParser::new().parse(Lexer::new("∫(x in 0..1) x")),
+            Err(ParseError::UnrecognizedEOF { .. })
+        ));
+    }
+
+    #[test]
+    fn test_parse_sets() {
+        let parse = parse_expression;
+
+        assert_eq!(parse("{1, 2, 3}"), "{1 2 3}");
+        assert_eq!(parse("{1, 2,}"), "{1 2}");
+        assert_eq!(parse("{}"), "{}");
+        assert_eq!(parse("{{1}, {}}"), "{{1} {}}");
+        assert_eq!(parse("{x + 1, f(x)}"), "{(+ x 1) (f x)}");
+
+        // `∩` binds tighter than `∪`, which binds tighter than relations
+        assert_eq!(parse("A ∪ B ∩ C"), "(∪ A (∩ B C))");
+        assert_eq!(parse("A ∩ B ∪ C"), "(∪ (∩ A B) C)");
+        assert_eq!(parse("A ∩ B ∩ C"), "(∩ (∩ A B) C)");
+        assert_eq!(parse("x ∈ A ∪ B"), "(∈ x (∪ A B))");
+        assert_eq!(parse("x + 1 ∉ {1, 2}"), "(∉ (+ x 1) {1 2})");
+        assert_eq!(parse("A ⊆ B && B ⊂ C"), "(&& (⊆ A B) (⊂ B C))");
+        assert_eq!(parse("A ⊃ B ∪ C"), "(⊃ A (∪ B C))");
+        assert_eq!(parse("A ⊇ B"), "(⊇ A B)");
+        assert_eq!(parse("A ⊄ B"), "(⊄ A B)");
+        assert_eq!(parse("A ⊅ B"), "(⊅ A B)");
+
+        // Relations don't associate
+        assert!(matches!(
+            parser::ExpressionParser::new().parse(Lexer::new("A ⊂ B ⊂ C")),
+            Err(ParseError::UnrecognizedToken {
+                token: (
+                    8,
+                    lexer::Token::MathematicalSymbol(MathematicalSymbol::SubsetOf),
+                    11
+                ),
+                ..
+            })
+        ));
+        assert!(matches!(
+            parser::ExpressionParser::new().parse(Lexer::new("{1, 2")),
+            Err(ParseError::UnrecognizedEOF { .. })
+        ));
+    }
+
+    #[test]
+    fn test_parse_statements() {
+        let parse = parse_program;
+
+        assert_eq!(parse("a := 3;"), "(:= a 3)");
+        assert_eq!(parse("a ≔ 3;"), "(:= a 3)");
+        assert_eq!(parse("a: Int := 3;"), "(:= (: a Int) 3)");
+        assert_eq!(parse("s: Set<Int> := {1, 2};"), "(:= (: s Set<Int>) {1 2})");
+        assert_eq!(
+            parse("m: Map<String, List<Int>> := f();"),
+            "(:= (: m Map<String, List<Int>>) (f ))"
+        );
+        assert_eq!(parse("a := 3; a += 1; a * 2"), "(:= a 3) (+= a 1); (* a 2)");
+        assert_eq!(parse(""), "");
+        assert_eq!(parse("1 + 2"), "(+ 1 2)");
+
+        assert_eq!(
+            parser::ProgramParser::new().parse(Lexer::new("a := 3;\n")),
+            Ok(Block {
+                statements: vec![Statement::Declaration {
+                    name: Identifier {
+                        name: String::from("a")
+                    },
+                    ty: None,
+                    value: Expression::Literal {
+                        literal: LiteralExpression::NumericLiteral(
+                            NumericLiteral::Integer(Integer::I32 { n: 3 })
+                        ),
+                        span: Span { start: 5, end: 6 }
+                    },
+                    span: Span { start: 0, end: 7 }
+                }],
+                value: None,
+                docs: vec![],
+                span: Span { start: 0, end: 7 }
+            })
+        );
+
+        // Only a statement which ends its block may omit its `;`
+        assert!(matches!(
+            parser::ProgramParser::new().parse(Lexer::new("a := 3")),
+            Err(ParseError::UnrecognizedEOF { .. })
+        ));
+        assert!(matches!(
+            parser::ProgramParser::new().parse(Lexer::new("a := 3 b := 4;")),
+            Err(ParseError::UnrecognizedToken { .. })
+        ));
+    }
+
+    #[test]
+    fn test_parse_functions() {
+        let parse = parse_program;
+
         assert_eq!(
-            parser::IntegerLiteralParser::new().parse("0b_____u8"),
-            Ok(0b0)
+            parse("fn add(a: Int, b: Int) -> Int { a + b }"),
+            "(fn add (a: Int, b: Int) -> Int {(+ a b)})"
         );
         assert_eq!(
-            parser::IntegerLiteralParser::new().parse("0B_____u16"),
-            Ok(0b0)
+            parse("fn add(a: Int, b: Int,) → Int { return a + b; }"),
+            "(fn add (a: Int, b: Int) -> Int {(return (+ a b));})"
         );
+        assert_eq!(parse("pub fn main() {}"), "(pub fn main () {})");
         assert_eq!(
-            parser::IntegerLiteralParser::new().parse("0b_1_0_1_0_1_0_1_0_u32"),
-            Ok(0b10101010)
+            parse("fn f(xs: Set<Int>) { if_empty(xs); return; }"),
+            "(fn f (xs: Set<Int>) {(if_empty xs); (return);})"
         );
         assert_eq!(
-            parser::IntegerLiteralParser::new().parse("0B_1_0_1_0_1_0_1_0_u64"),
-            Ok(0b10101010)
+            parse("fn f(x: Int) -> Int { y := x²; fn g(z: Int) -> Int { z + 1 } g(y) }"),
+            "(fn f (x: Int) -> Int {(:= y (^ x 2)) (fn g (z: Int) -> Int {(+ z 1)}) (g y)})"
         );
         assert_eq!(
-            parser::IntegerLiteralParser::new().parse("0b1111_1111_1001_0000_u128"),
-            Ok(0b1111_1111_1001_0000)
+            parse("fn one() -> Int { 1 } a := one();"),
+            "(fn one () -> Int {1}) (:= a (one ))"
         );
         assert_eq!(
-            parser::IntegerLiteralParser::new().parse("0B1111_1111_1001_0000_usize"),
-            Ok(0b1111_1111_1001_0000)
+            parse("fn s() -> Set<Int> { {1, 2} }"),
+            "(fn s () -> Set<Int> {{1 2}})"
         );
 
-        // Integer Sub-Literals (e.g. DecimalLiteral, HexadecimalLiteral, etc.)
+        // Parameters must be typed
+        assert!(matches!(
+            parser::ProgramParser::new().parse(Lexer::new("fn f(x) { x }")),
+            Err(ParseError::UnrecognizedToken { .. })
+        ));
+        // Functions aren't expressions
+        assert!(matches!(
+            parser::ProgramParser::new().parse(Lexer::new("a := fn f() {};")),
+            Err(ParseError::UnrecognizedToken { .. })
+        ));
+    }
+
+    #[test]
+    fn test_parse_structs_and_enums() {
+        let parse = parse_program;
 
-        // - HexadecimalLiteral
-        assert_eq!(
-            parser::HexadecimalLiteralParser::new().parse("0x_____"),
-            Ok(0x0)
-        );
         assert_eq!(
-            parser::HexadecimalLiteralParser::new().parse("0X_____"),
-            Ok(0x0)
+            parse("struct Point { x: Int, pub y: Int, }"),
+            "(struct Point {x: Int, pub y: Int})"
         );
         assert_eq!(
-            parser::HexadecimalLiteralParser::new().parse("0x_1_2_3_4_5_6_7_"),
-            Ok(0x1234567)
+            parse("pub struct Meters(Float);"),
+            "(pub struct Meters (Float))"
         );
+        assert_eq!(parse("struct Origin;"), "(struct Origin)");
+        assert_eq!(parse("struct Empty {}"), "(struct Empty {})");
         assert_eq!(
-            parser::HexadecimalLiteralParser::new().parse("0X_1_2_3_4_5_6_7_"),
-            Ok(0x1234567)
+            parse("enum Shape { Empty, Circle(Float), Rectangle { w: Float, h: Float } }"),
+            "(enum Shape {Empty, Circle (Float), Rectangle {w: Float, h: Float}})"
         );
         assert_eq!(
-            parser::HexadecimalLiteralParser::new().parse("0x_a_b_c_d_e_f_"),
-            Ok(0xabcdef)
+            parse("pub enum Tree { Leaf, Node(Tree, Int, Tree), }"),
+            "(pub enum Tree {Leaf, Node (Tree, Int, Tree)})"
         );
         assert_eq!(
-            parser::HexadecimalLiteralParser::new().parse("0X_a_b_c_d_e_f_"),
-            Ok(0xabcdef)
+            parse("fn f() { struct P; enum E {} }"),
+            "(fn f () {(struct P) (enum E {})})"
         );
+
+        // Unit and tuple structs end with a semicolon
+        assert!(matches!(
+            parser::ProgramParser::new().parse(Lexer::new("struct Meters(Float)")),
+            Err(ParseError::UnrecognizedEOF { .. })
+        ));
+        // Fields must be typed
+        assert!(matches!(
+            parser::ProgramParser::new().parse(Lexer::new("struct P { x }")),
+            Err(ParseError::UnrecognizedToken { .. })
+        ));
+    }
+
+    #[test]
+    fn test_parse_constructions_and_field_accesses() {
+        let parse = parse_expression;
+
+        assert_eq!(parse("Point { x: 1, y: 2 }"), "(Point {x: 1, y: 2})");
         assert_eq!(
-            parser::HexadecimalLiteralParser::new().parse("0x_A_B_C_D_E_F_"),
-            Ok(0xABCDEF)
+            parse("Point { x, y: x + 1, }"),
+            "(Point {x: x, y: (+ x 1)})"
         );
+        assert_eq!(parse("Empty {}"), "(Empty {})");
         assert_eq!(
-            parser::HexadecimalLiteralParser::new().parse("0X_A_B_C_D_E_F_"),
-            Ok(0xABCDEF)
+            parse("Shape::Rectangle { w: 2, h }"),
+            "(Shape::Rectangle {w: 2, h: h})"
         );
-        assert!(parser::HexadecimalLiteralParser::new()
-            .parse("0xG")
+        assert_eq!(parse("Meters(3)"), "(Meters 3)");
+        assert_eq!(parse("Shape::Circle(r²)"), "(Shape::Circle (^ r 2))");
+        assert_eq!(parse("Shape::Empty"), "Shape::Empty");
+        assert_eq!(parse("{Shape::Empty}"), "{Shape::Empty}");
+
+        assert_eq!(parse("p.x"), "(. p x)");
+        assert_eq!(parse("pair.0 + pair.1"), "(+ (. pair 0) (. pair 1))");
+        assert_eq!(parse("a.b.c"), "(. (. a b) c)");
+        assert_eq!(parse("(pair.0).1"), "(. (. pair 0) 1)");
+        assert_eq!(
+            parse("Point { x: 1, y: 2 }.x"),
+            "(. (Point {x: 1, y: 2}) x)"
+        );
+        assert_eq!(parse("f(a).y"), "(. (f a) y)");
+        assert_eq!(parse("{1, 2}.len"), "(. {1 2} len)");
+        // A field access binds tighter than any operator
+        assert_eq!(parse("-p.x²"), "(- (^ (. p x) 2))");
+        assert_eq!(parse("√v.x"), "(√ 2 (. v x))");
+        assert_eq!(parse("p.x ^ 2"), "(^ (. p x) 2)");
+
+        // Literals have no fields (`1.` is a float)
+        assert!(parser::ExpressionParser::new()
+            .parse(Lexer::new("1.x"))
+            .is_err());
+        // A field index is a plain decimal literal
+        for source in ["pair.01", "pair.0u8", "pair.1_0"] {
+            assert!(
+                matches!(
+                    parser::ExpressionParser::new().parse(Lexer::new(source)),
+                    Err(ParseError::UnrecognizedToken { .. })
+                ),
+                "{}",
+                source
+            );
+        }
+        // `0.1` is a float literal
+        assert!(parser::ExpressionParser::new()
+            .parse(Lexer::new("pair.0.1"))
             .is_err());
+    }
+
+    #[test]
+    fn test_parse_traits_and_impls() {
+        let parse = parse_program;
 
-        // - DecimalLiteral
-        assert_eq!(parser::DecimalLiteralParser::new().parse("22"), Ok(22));
-        assert_eq!(parser::DecimalLiteralParser::new().parse("0"), Ok(0));
-        assert_eq!(parser::DecimalLiteralParser::new().parse("1_2_3_"), Ok(123));
         assert_eq!(
-            parser::DecimalLiteralParser::new().parse("1_000"),
-            Ok(1_000)
+            parse("trait Area { fn area(self) -> Float; fn twice(self) -> Float { 2 * self.area() } }"),
+            "(trait Area {(fn area (self) -> Float;) (fn twice (self) -> Float {(* 2 (.area self))})})"
         );
+        assert_eq!(parse("pub trait Empty {}"), "(pub trait Empty {})");
         assert_eq!(
-            parser::DecimalLiteralParser::new().parse("1_234_567"),
-            Ok(1_234_567)
+            parse(
+                "impl Circle { pub fn new(r: Float) -> Self { Circle { r } } \
+                 fn scale(self, k: Float,) -> Circle { Circle { r: self.r * k } } }"
+            ),
+            "(impl Circle {(pub fn new (r: Float) -> Self {(Circle {r: r})}) \
+             (fn scale (self, k: Float) -> Circle {(Circle {r: (* (. self r) k)})})})"
         );
         assert_eq!(
-            parser::DecimalLiteralParser::new().parse("1_234_567_890"),
-            Ok(1_234_567_890)
+            parse("impl Area for Circle where Self: Display { fn area(self) -> Float { 3 * self.r² } }"),
+            "(impl Area for Circle where Self: Display {(fn area (self) -> Float {(* 3 (^ (. self r) 2))})})"
         );
         assert_eq!(
-            parser::DecimalLiteralParser::new().parse("9_876_543_210"),
-            Ok(9_876_543_210)
+            parse("trait Greet where Self: Named { fn greet(self) where Self: Display + Debug, {} }"),
+            "(trait Greet where Self: Named {(fn greet (self) where Self: Display + Debug {})})"
         );
         assert_eq!(
-            parser::DecimalLiteralParser::new()
-                .parse("1_234_567_890_123_456_789_123_456_789"),
-            Ok(1_234_567_890_123_456_789_123_456_789)
+            parse("fn show(x: Int) -> String where Int: Display + Debug { f(x) }"),
+            "(fn show (x: Int) -> String where Int: Display + Debug {(f x)})"
         );
         assert_eq!(
-            parser::DecimalLiteralParser::new()
-                .parse("9_876_543_210_987_654_321_987_654_321"),
-            Ok(9_876_543_210_987_654_321_987_654_321)
+            parse("impl Set<Int> { fn zero() -> Self { {0} } }"),
+            "(impl Set<Int> {(fn zero () -> Self {{0}})})"
         );
-        // TODO assert that this is an error (decimal literal cannot start with an
-        // underscore)
-        // assert_eq!(
-        //     parser::DecimalLiteralParser::new().parse("_1_2_3_"),
-        //     Err(ParseError::User {
-        //         error: InvalidDecimalLiteral,
-        //     })
-        // );
 
-        // - OctalLiteral
-        assert_eq!(parser::OctalLiteralParser::new().parse("0o_____"), Ok(0o0));
-        assert_eq!(parser::OctalLiteralParser::new().parse("0O_____"), Ok(0o0));
+        // The methods of an impl have bodies
+        assert!(matches!(
+            parser::ProgramParser::new().parse(Lexer::new(
+                "impl Area for Circle { fn area(self) -> Float; }"
+            )),
+            Err(ParseError::UnrecognizedToken { .. })
+        ));
+        // Only methods have receivers
+        assert!(matches!(
+            parser::ProgramParser::new().parse(Lexer::new("fn f(self) {}")),
+            Err(ParseError::UnrecognizedToken { .. })
+        ));
+        // The receiver comes first
+        assert!(matches!(
+            parser::ProgramParser::new()
+                .parse(Lexer::new("impl C { fn f(x: Int, self) {} }")),
+            Err(ParseError::UnrecognizedToken { .. })
+        ));
+        // An impl is of a named type
+        assert!(matches!(
+            parser::ProgramParser::new().parse(Lexer::new("impl Self {}")),
+            Err(ParseError::UnrecognizedToken { .. })
+        ));
+    }
+
+    #[test]
+    fn test_parse_method_calls() {
+        let parse = parse_expression;
+
+        assert_eq!(parse("c.area()"), "(.area c)");
+        assert_eq!(parse("c.scale(2, k,)"), "(.scale c 2 k)");
+        assert_eq!(parse("c.scale(2).area()"), "(.area (.scale c 2))");
+        assert_eq!(parse("p.x.norm()"), "(.norm (. p x))");
+        assert_eq!(parse("p.norm().x"), "(. (.norm p) x)");
+        assert_eq!(parse("self.r"), "(. self r)");
+        assert_eq!(parse("Circle { r: 1 }.area()"), "(.area (Circle {r: 1}))");
+        assert_eq!(parse("v.len() + 1"), "(+ (.len v) 1)");
+        assert_eq!(parse("-v.norm()²"), "(- (^ (.norm v) 2))");
+    }
+
+    #[test]
+    fn test_parse_comprehensions() {
+        let parse = parse_expression;
+
         assert_eq!(
-            parser::OctalLiteralParser::new().parse("0o_1_2_3_4_5_6_7_"),
-            Ok(0o1234567)
+            parse("{ x² | x ∈ 1..10, x % 2 == 0 }"),
+            "{(^ x 2) | x ∈ (.. 1 10), (== (% x 2) 0)}"
         );
         assert_eq!(
-            parser::OctalLiteralParser::new().parse("0O_1_2_3_4_5_6_7_"),
-            Ok(0o1234567)
+            parse("[x + y | x ∈ A, y ∈ 0..=x, x != y,]"),
+            "[(+ x y) | x ∈ A, y ∈ (..= 0 x), (!= x y)]"
         );
-        assert_eq!(parser::OctalLiteralParser::new().parse("0o70"), Ok(0o70));
-        assert_eq!(parser::OctalLiteralParser::new().parse("0O70"), Ok(0o70));
-        assert!(parser::OctalLiteralParser::new().parse("0o8").is_err());
+        assert_eq!(parse("{x | x ∈ S ∪ T}"), "{x | x ∈ (∪ S T)}");
+        assert_eq!(parse("[1, 2, 3]"), "[1 2 3]");
+        assert_eq!(parse("[]"), "[]");
+        assert_eq!(parse("[[1], {2}]"), "[[1] {2}]");
 
-        // - BinaryLiteral
-        assert_eq!(parser::BinaryLiteralParser::new().parse("0b_____"), Ok(0b0));
-        assert_eq!(parser::BinaryLiteralParser::new().parse("0B_____"), Ok(0b0));
+        // Only a membership test of a variable which isn't yet bound is a
+        // generator
         assert_eq!(
-            parser::BinaryLiteralParser::new().parse("0b________1"),
-            Ok(0b1)
+            parse("{x | x ∈ A, x ∈ B, 1 ∈ C}"),
+            "{x | x ∈ A, (∈ x B), (∈ 1 C)}"
         );
+
+        // Within braces and brackets, `|` must be parenthesized to be a
+        // bitwise or
+        assert_eq!(parse("{(a | b), c & d}"), "{(| a b) (& c d)}");
+        assert_eq!(parse("[a | a ∈ {b | b ∈ S}]"), "[a | a ∈ {b | b ∈ S}]");
+
+        // A generator may also bind the variable of a big operator
+        assert_eq!(parse("∑(i ∈ 1..=n) i"), "(∑ i (..= 1 n) i)");
+
+        // A comprehension has at least one clause
+        assert!(matches!(
+            parser::ExpressionParser::new().parse(Lexer::new("{x |}")),
+            Err(ParseError::UnrecognizedToken { .. })
+        ));
+    }
+
+    #[test]
+    fn test_parse_quantifiers() {
+        let parse = parse_expression;
+
+        assert_eq!(parse("∀ x ∈ xs: p(x)"), "(∀ x xs (p x))");
         assert_eq!(
-            parser::BinaryLiteralParser::new().parse("0B________1"),
-            Ok(0b1)
+            parse("∃ x ∈ 1..n: f(x) == 0"),
+            "(∃ x (.. 1 n) (== (f x) 0))"
         );
         assert_eq!(
-            parser::BinaryLiteralParser::new().parse("0b1111_1111_1001_0000"),
-            Ok(0b1111_1111_1001_0000)
+            parse("∀ x ∈ S: ∃ y ∈ S: x <= y"),
+            "(∀ x S (∃ y S (<= x y)))"
         );
+        assert_eq!(parse("∀ i in {1, 2}: i > 0"), "(∀ i {1 2} (> i 0))");
+
+        // The predicate extends as far to the right as possible
+        assert_eq!(parse("∀ x ∈ S: x > 0 && p"), "(∀ x S (&& (> x 0) p))");
+        assert_eq!(parse("(∃ x ∈ S: x > 0) || p"), "(|| (∃ x S (> x 0)) p)");
         assert_eq!(
-            parser::BinaryLiteralParser::new().parse("0B1111_1111_1001_0000"),
-            Ok(0b1111_1111_1001_0000)
+            parse("{x | x ∈ S, (∃ y ∈ T: x == y)}"),
+            "{x | x ∈ S, (∃ y T (== x y))}"
         );
+
+        // Quantifiers are the conditions of assertions too
         assert_eq!(
-            parser::BinaryLiteralParser::new()
-                .parse("0b_0_1_1_0_1_1_0_0_1_0_0_0_0_0_0_0_"),
-            Ok(0b0110_1100_1000_0000)
+            parse_program("assert ∀ x ∈ xs: x > 0;"),
+            "(assert (∀ x xs (> x 0)));"
         );
-        assert!(parser::BinaryLiteralParser::new().parse("0b2").is_err());
 
-        // HexadecimalDigitOrUnderscore
-        // 0 | 1 | 2 | 3 | 4 | 5 | 6 | 7 | 8 | 9 | a | b | c | d | e | f | A | B | C
-        // | D | E | F | _
+        // A quantifier which is an operand must be parenthesized
+        assert!(matches!(
+            parser::ExpressionParser::new().parse(Lexer::new("p || ∀ x ∈ S: x")),
+            Err(ParseError::UnrecognizedToken { .. })
+        ));
+    }
+
+    #[test]
+    fn test_parse_superscript_powers_and_roots() {
+        let parse = parse_expression;
+
+        assert_eq!(parse("x²"), "(^ x 2)");
+        assert_eq!(parse("(a+b)⁻¹"), "(^ (+ a b) -1)");
+        assert_eq!(parse("xⁿ"), "(^ x n)");
+        assert_eq!(parse("x⁻ⁿ"), "(^ x (- n))");
+        assert_eq!(parse("2¹⁰"), "(^ 2 10)");
+        assert_eq!(parse("√x"), "(√ 2 x)");
+        assert_eq!(parse("∛(y+1)"), "(√ 3 (+ y 1))");
+        assert_eq!(parse("∜16"), "(√ 4 16)");
+        assert_eq!(parse("ⁿ√x"), "(√ n x)");
+        assert_eq!(parse("³√x"), "(√ 3 x)");
+        assert_eq!(parse("√√x"), "(√ 2 (√ 2 x))");
+
+        // Superscripts bind tighter than roots, negation and `^`
+        assert_eq!(parse("-x²"), "(- (^ x 2))");
+        assert_eq!(parse("√x²"), "(√ 2 (^ x 2))");
+        assert_eq!(parse("√x ^ 2"), "(^ (√ 2 x) 2)");
+        assert_eq!(parse("x² ^ 3"), "(^ (^ x 2) 3)");
+        assert_eq!(parse("2 * x² + 1"), "(+ (* 2 (^ x 2)) 1)");
+        assert_eq!(parse("√x + √y"), "(+ (√ 2 x) (√ 2 y))");
+
+        // The superscript after a unit is the unit's, not a power
+        assert_eq!(parse("3 m²"), "3m²");
+        assert_eq!(parse("(3 m)²"), "(^ 3m 2)");
+
+        // Only a square root may be given an index
+        assert!(matches!(
+            parser::ExpressionParser::new().parse(Lexer::new("³∛x")),
+            Err(ParseError::UnrecognizedToken { .. })
+        ));
+    }
+
+    #[test]
+    fn test_parse_string_literals() {
+        fn parse(
+            source: &str,
+        ) -> Result<
+            LiteralExpression,
+            ParseError<usize, lexer::Token<'_>, parser_errors::InternalParserError>,
+        > {
+            parser::LiteralExpressionParser::new().parse(Lexer::new(source))
+        }
+        fn string(value: &str) -> LiteralExpression {
+            LiteralExpression::String(StringLiteral::String {
+                value: String::from(value),
+            })
+        }
+        fn byte_string(value: &[u8]) -> LiteralExpression {
+            LiteralExpression::String(StringLiteral::ByteString {
+                value: value.to_vec(),
+            })
+        }
+        fn invalid_escape(
+            error: parser_errors::EscapeError,
+            start: usize,
+            end: usize,
+        ) -> Result<
+            LiteralExpression,
+            ParseError<
+                usize,
+                lexer::Token<'static>,
+                parser_errors::InternalParserError,
+            >,
+        > {
+            Err(ParseError::User {
+                error: InvalidEscape { error, start, end },
+            })
+        }
+
+        assert_eq!(parse(r#""Hello""#), Ok(string("Hello")));
+        assert_eq!(parse(r#""Hello, world!""#), Ok(string("Hello, world!")));
+        assert_eq!(parse(r#""""#), Ok(string("")));
+        assert_eq!(parse("\"größe θ₁\""), Ok(string("größe θ₁")));
+
+        // Escapes
         assert_eq!(
-            parser::HexadecimalDigitOrUnderscoreParser::new()
-                .parse("0")
-                .unwrap(),
-            '0'
+            parse(r#""a\nb\tc\rd\\e\0f\'g\"h""#),
+            Ok(string("a\nb\tc\rd\\e\0f'g\"h"))
         );
+        assert_eq!(parse(r#""\x41\x7f""#), Ok(string("A\x7f")));
+        assert_eq!(parse(r#""\u{1F600}""#), Ok(string("😀")));
+        assert_eq!(parse(r#""\u{0_3B8}₁""#), Ok(string("θ₁")));
+        assert_eq!(parse(r#""\u{10FFFF}""#), Ok(string("\u{10FFFF}")));
+
+        // Line continuations skip the newline and the following line's
+        // leading whitespace
         assert_eq!(
-            parser::HexadecimalDigitOrUnderscoreParser::new()
-                .parse("1")
-                .unwrap(),
-            '1'
+            parse("\"Hello, \\\n     world!\""),
+            Ok(string("Hello, world!"))
         );
+        assert_eq!(parse("\"a\\\r\n\tb\""), Ok(string("ab")));
+        assert_eq!(parse("\"a\nb\""), Ok(string("a\nb")));
+
+        // Raw strings
+        assert_eq!(parse(r##"r"a\nb""##), Ok(string(r"a\nb")));
+        assert_eq!(parse(r###"r#"a "b" c"#"###), Ok(string(r#"a "b" c"#)));
+        assert_eq!(parse(r####"r##"a "# b"##"####), Ok(string(r##"a "# b"##)));
+
+        // Byte strings
+        assert_eq!(parse(r#"b"abc""#), Ok(byte_string(b"abc")));
         assert_eq!(
-            parser::HexadecimalDigitOrUnderscoreParser::new()
-                .parse("2")
-                .unwrap(),
-            '2'
+            parse(r#"b"\x00\xFF\n\"""#),
+            Ok(byte_string(b"\x00\xFF\n\""))
         );
+        assert_eq!(parse(r##"br"\x00""##), Ok(byte_string(br"\x00")));
+        assert_eq!(parse(r###"br#"a"b"#"###), Ok(byte_string(br#"a"b"#)));
+
+        // Invalid escapes point at the escape itself
+        use parser_errors::EscapeError::*;
         assert_eq!(
-            parser::HexadecimalDigitOrUnderscoreParser::new()
-                .parse("3")
-                .unwrap(),
-            '3'
+            parse(r#""a\qb""#),
+            invalid_escape(UnknownCharacterEscape, 2, 4)
         );
         assert_eq!(
-            parser::HexadecimalDigitOrUnderscoreParser::new()
-                .parse("4")
-                .unwrap(),
-            '4'
+            parse(r#""\é""#),
+            invalid_escape(UnknownCharacterEscape, 1, 4)
         );
+        assert_eq!(parse(r#""\x4""#), invalid_escape(InvalidHexEscape, 1, 4));
+        assert_eq!(parse(r#""\xG0""#), invalid_escape(InvalidHexEscape, 1, 3));
         assert_eq!(
-            parser::HexadecimalDigitOrUnderscoreParser::new()
-                .parse("5")
-                .unwrap(),
-            '5'
+            parse(r#""\x80""#),
+            invalid_escape(OutOfRangeHexEscape, 1, 5)
         );
         assert_eq!(
-            parser::HexadecimalDigitOrUnderscoreParser::new()
-                .parse("6")
-                .unwrap(),
-            '6'
+            parse(r#""\u41""#),
+            invalid_escape(InvalidUnicodeEscape, 1, 3)
         );
         assert_eq!(
-            parser::HexadecimalDigitOrUnderscoreParser::new()
-                .parse("7")
-                .unwrap(),
-            '7'
+            parse(r#""\u{}""#),
+            invalid_escape(InvalidUnicodeEscape, 1, 5)
         );
         assert_eq!(
-            parser::HexadecimalDigitOrUnderscoreParser::new()
-                .parse("8")
-                .unwrap(),
-            '8'
+            parse(r#""\u{_41}""#),
+            invalid_escape(InvalidUnicodeEscape, 1, 5)
         );
         assert_eq!(
-            parser::HexadecimalDigitOrUnderscoreParser::new()
-                .parse("9")
-                .unwrap(),
-            '9'
+            parse(r#""\u{1234567}""#),
+            invalid_escape(InvalidUnicodeEscape, 1, 12)
         );
         assert_eq!(
-            parser::HexadecimalDigitOrUnderscoreParser::new()
-                .parse("a")
-                .unwrap(),
-            'a'
+            parse(r#""\u{12G}""#),
+            invalid_escape(InvalidUnicodeEscape, 1, 7)
         );
         assert_eq!(
-            parser::HexadecimalDigitOrUnderscoreParser::new()
-                .parse("b")
-                .unwrap(),
-            'b'
+            parse(r#""\u{41""#),
+            invalid_escape(InvalidUnicodeEscape, 1, 6)
         );
         assert_eq!(
-            parser::HexadecimalDigitOrUnderscoreParser::new()
-                .parse("c")
-                .unwrap(),
-            'c'
+            parse(r#""\u{D800}""#),
+            invalid_escape(OutOfRangeUnicodeEscape, 1, 9)
         );
         assert_eq!(
-            parser::HexadecimalDigitOrUnderscoreParser::new()
-                .parse("d")
-                .unwrap(),
-            'd'
+            parse(r#""\u{110000}""#),
+            invalid_escape(OutOfRangeUnicodeEscape, 1, 11)
         );
         assert_eq!(
-            parser::HexadecimalDigitOrUnderscoreParser::new()
-                .parse("e")
-                .unwrap(),
-            'e'
+            parse(r#"b"\u{41}""#),
+            invalid_escape(UnicodeEscapeInByteString, 2, 4)
         );
         assert_eq!(
-            parser::HexadecimalDigitOrUnderscoreParser::new()
-                .parse("f")
-                .unwrap(),
-            'f'
+            parse(r#"b"aé""#),
+            invalid_escape(NonAsciiCharacterInByteString, 3, 5)
         );
         assert_eq!(
-            parser::HexadecimalDigitOrUnderscoreParser::new()
-                .parse("A")
-                .unwrap(),
-            'A'
+            parse(r#"br"aé""#),
+            invalid_escape(NonAsciiCharacterInByteString, 4, 6)
         );
+
+        // Unterminated strings
         assert_eq!(
-            parser::HexadecimalDigitOrUnderscoreParser::new()
-                .parse("B")
-                .unwrap(),
-            'B'
+            parse(r#""abc"#),
+            Err(ParseError::User {
+                error: UnrecognizedToken { start: 0, end: 4 },
+            })
+        );
+        assert!(parse(r##"r#"abc""##).is_err());
+    }
+
+    #[test]
+    fn test_parse_character_literals() {
+        fn parse_char(
+            source: &str,
+        ) -> Result<
+            char,
+            ParseError<usize, lexer::Token<'_>, parser_errors::InternalParserError>,
+        > {
+            parser::CharLiteralParser::new().parse(Lexer::new(source))
+        }
+        fn parse_byte(
+            source: &str,
+        ) -> Result<
+            u8,
+            ParseError<usize, lexer::Token<'_>, parser_errors::InternalParserError>,
+        > {
+            parser::ByteLiteralParser::new().parse(Lexer::new(source))
+        }
+
+        assert_eq!(parse_char("'a'"), Ok('a'));
+        assert_eq!(parse_char("'z'"), Ok('z'));
+        assert_eq!(parse_char("'A'"), Ok('A'));
+        assert_eq!(parse_char("'Z'"), Ok('Z'));
+        assert_eq!(parse_char("' '"), Ok(' '));
+        assert_eq!(parse_char("'θ'"), Ok('θ'));
+        assert_eq!(parse_char("'\"'"), Ok('"'));
+        assert_eq!(parse_char(r"'\n'"), Ok('\n'));
+        assert_eq!(parse_char(r"'\''"), Ok('\''));
+        assert_eq!(parse_char(r"'\\'"), Ok('\\'));
+        assert_eq!(parse_char(r"'\x41'"), Ok('A'));
+        assert_eq!(parse_char(r"'\u{1F600}'"), Ok('😀'));
+
+        assert_eq!(parse_byte("b'x'"), Ok(b'x'));
+        assert_eq!(parse_byte("b' '"), Ok(b' '));
+        assert_eq!(parse_byte(r"b'\n'"), Ok(b'\n'));
+        assert_eq!(parse_byte(r"b'\''"), Ok(b'\''));
+        assert_eq!(parse_byte(r"b'\xFF'"), Ok(b'\xFF'));
+
+        assert_eq!(
+            parser::LiteralExpressionParser::new().parse(Lexer::new("'a'")),
+            Ok(LiteralExpression::Char { value: 'a' })
         );
         assert_eq!(
-            parser::HexadecimalDigitOrUnderscoreParser::new()
-                .parse("C")
-                .unwrap(),
-            'C'
+            parser::LiteralExpressionParser::new().parse(Lexer::new("b'a'")),
+            Ok(LiteralExpression::Byte { value: b'a' })
         );
+
+        // Empty and multi-codepoint literals span the whole literal
         assert_eq!(
-            parser::HexadecimalDigitOrUnderscoreParser::new()
-                .parse("D")
-                .unwrap(),
-            'D'
+            parse_char("''"),
+            Err(ParseError::User {
+                error: EmptyCharLiteral { start: 0, end: 2 },
+            })
         );
         assert_eq!(
-            parser::HexadecimalDigitOrUnderscoreParser::new()
-                .parse("E")
-                .unwrap(),
-            'E'
+            parse_byte("b''"),
+            Err(ParseError::User {
+                error: EmptyCharLiteral { start: 0, end: 3 },
+            })
         );
         assert_eq!(
-            parser::HexadecimalDigitOrUnderscoreParser::new()
-                .parse("F")
-                .unwrap(),
-            'F'
+            parse_char("'ab'"),
+            Err(ParseError::User {
+                error: OverlongCharLiteral { start: 0, end: 4 },
+            })
         );
-
-        // DecimalDigitOrUnderscore
-        // 0 | 1 | 2 | 3 | 4 | 5 | 6 | 7 | 8 | 9 | _
         assert_eq!(
-            parser::DecimalDigitOrUnderscoreParser::new().parse("0"),
-            Ok('0')
+            parse_char("'e\u{301}'"),
+            Err(ParseError::User {
+                error: OverlongCharLiteral { start: 0, end: 5 },
+            })
         );
         assert_eq!(
-            parser::DecimalDigitOrUnderscoreParser::new().parse("1"),
-            Ok('1')
+            parse_char(r"'\n\t'"),
+            Err(ParseError::User {
+                error: OverlongCharLiteral { start: 0, end: 6 },
+            })
         );
         assert_eq!(
-            parser::DecimalDigitOrUnderscoreParser::new().parse("2"),
-            Ok('2')
+            parse_byte("b'ab'"),
+            Err(ParseError::User {
+                error: OverlongCharLiteral { start: 0, end: 5 },
+            })
         );
+
+        // Invalid escapes point at the escape itself
         assert_eq!(
-            parser::DecimalDigitOrUnderscoreParser::new().parse("3"),
-            Ok('3')
+            parse_char(r"'\q'"),
+            Err(ParseError::User {
+                error: InvalidEscape {
+                    error: parser_errors::EscapeError::UnknownCharacterEscape,
+                    start: 1,
+                    end: 3,
+                },
+            })
         );
         assert_eq!(
-            parser::DecimalDigitOrUnderscoreParser::new().parse("4"),
-            Ok('4')
+            parse_char(r"'\x80'"),
+            Err(ParseError::User {
+                error: InvalidEscape {
+                    error: parser_errors::EscapeError::OutOfRangeHexEscape,
+                    start: 1,
+                    end: 5,
+                },
+            })
         );
         assert_eq!(
-            parser::DecimalDigitOrUnderscoreParser::new().parse("5"),
-            Ok('5')
+            parse_byte(r"b'\u{41}'"),
+            Err(ParseError::User {
+                error: InvalidEscape {
+                    error: parser_errors::EscapeError::UnicodeEscapeInByteString,
+                    start: 2,
+                    end: 4,
+                },
+            })
         );
         assert_eq!(
-            parser::DecimalDigitOrUnderscoreParser::new().parse("6"),
-            Ok('6')
+            parse_byte("b'é'"),
+            Err(ParseError::User {
+                error: InvalidEscape {
+                    error: parser_errors::EscapeError::NonAsciiCharacterInByteString,
+                    start: 2,
+                    end: 4,
+                },
+            })
         );
+    }
+
+    #[test]
+    fn test_parse_integer_literals() {
+        //     let ast =
+        // parser::LiteralExpressionParser::new().parse("22").unwrap();
+        //     assert_eq!(ast, ast::LiteralExpression::Integer { n: 22 });
+
+        //     let ast =
+        // parser::LiteralExpressionParser::new().parse("0").unwrap();
+        //     assert_eq!(ast, ast::LiteralExpression::Integer { n: 0 });
+
+        //     let ast =
+        // parser::LiteralExpressionParser::new().parse("-22").unwrap();
+        //     assert_eq!(ast, ast::LiteralExpression::Integer { n: -22 });
+
+        fn big(n: u128) -> rug::Integer {
+            rug::Integer::from(n)
+        }
+
+        // Integer Literals
         assert_eq!(
-            parser::DecimalDigitOrUnderscoreParser::new().parse("7"),
-            Ok('7')
+            parser::IntegerLiteralParser::new().parse(Lexer::new("0x_____u8")),
+            Ok(Integer::U8 { n: 0x0 })
         );
         assert_eq!(
-            parser::DecimalDigitOrUnderscoreParser::new().parse("8"),
-            Ok('8')
+            parser::IntegerLiteralParser::new().parse(Lexer::new("0X_____u16")),
+            Ok(Integer::U16 { n: 0x0 })
         );
         assert_eq!(
-            parser::DecimalDigitOrUnderscoreParser::new().parse("9"),
-            Ok('9')
+            parser::IntegerLiteralParser::new()
+                .parse(Lexer::new("0x_1_2_3_4_5_6_7_u32")),
+            Ok(Integer::U32 { n: 0x1234567 })
         );
         assert_eq!(
-            parser::DecimalDigitOrUnderscoreParser::new().parse("_"),
-            Ok('_')
+            parser::IntegerLiteralParser::new()
+                .parse(Lexer::new("0X_1_2_3_4_5_6_7_u64")),
+            Ok(Integer::U64 { n: 0x1234567 })
         );
-
-        // OctalDigitOrUnderscore
-        // 0 | 1 | 2 | 3 | 4 | 5 | 6 | 7 | _
         assert_eq!(
-            parser::OctalDigitOrUnderscoreParser::new().parse("0"),
-            Ok('0')
+            parser::IntegerLiteralParser::new()
+                .parse(Lexer::new("0x_a_b_c_d_e_f_u128")),
+            Ok(Integer::U128 { n: 0xabcdef })
         );
         assert_eq!(
-            parser::OctalDigitOrUnderscoreParser::new().parse("1"),
-            Ok('1')
+            parser::IntegerLiteralParser::new()
+                .parse(Lexer::new("0X_a_b_c_d_e_f_usize")),
+            Ok(Integer::USize { n: 0xabcdef })
         );
         assert_eq!(
-            parser::OctalDigitOrUnderscoreParser::new().parse("2"),
-            Ok('2')
+            parser::IntegerLiteralParser::new()
+                .parse(Lexer::new("0x_A_B_C_D_E_F_i8")),
+            Err(ParseError::User {
+                error: IntegerLiteralOutOfRange {
+                    ty: "i8",
+                    start: 0,
+                    end: 17,
+                },
+            })
         );
         assert_eq!(
-            parser::OctalDigitOrUnderscoreParser::new().parse("3"),
-            Ok('3')
+            parser::IntegerLiteralParser::new()
+                .parse(Lexer::new("0XA_B_C_D_E_F_i16")),
+            Err(ParseError::User {
+                error: IntegerLiteralOutOfRange {
+                    ty: "i16",
+                    start: 0,
+                    end: 17,
+                },
+            })
         );
+        // TODO Error("Expected a digit, but found '_'. Integer literals cannot start
+        // assert_eq!(
+        //     parser::IntegerLiteralParser::new().parse("0X_A_B_C_D_E_F_i16"),
+        //     Ok(0xABCDEF)
+        // );
         assert_eq!(
-            parser::OctalDigitOrUnderscoreParser::new().parse("4"),
-            Ok('4')
+            parser::IntegerLiteralParser::new().parse(Lexer::new("22i32")),
+            Ok(Integer::I32 { n: 22 })
         );
         assert_eq!(
-            parser::OctalDigitOrUnderscoreParser::new().parse("5"),
-            Ok('5')
+            parser::IntegerLiteralParser::new().parse(Lexer::new("0i64")),
+            Ok(Integer::I64 { n: 0 })
         );
         assert_eq!(
-            parser::OctalDigitOrUnderscoreParser::new().parse("6"),
-            Ok('6')
+            parser::IntegerLiteralParser::new().parse(Lexer::new("1_2_3_i128")),
+            Ok(Integer::I128 { n: 123 })
         );
+        // TODO Error("Expected a digit, but found '_'. Integer literals cannot start
+        // with an underscore.") assert_eq!(
+        //     parser::IntegerLiteralParser::new().parse("_1_2_3_i128"),
+        //     Ok(123)
+        // );
         assert_eq!(
-            parser::OctalDigitOrUnderscoreParser::new().parse("7"),
-            Ok('7')
+            parser::IntegerLiteralParser::new().parse(Lexer::new("1_000isize")),
+            Ok(Integer::ISize { n: 1_000 })
         );
         assert_eq!(
-            parser::OctalDigitOrUnderscoreParser::new().parse("_"),
-            Ok('_')
+            parser::IntegerLiteralParser::new().parse(Lexer::new("1_234_567u8")),
+            Err(ParseError::User {
+                error: IntegerLiteralOutOfRange {
+                    ty: "u8",
+                    start: 0,
+                    end: 11,
+                },
+            })
         );
-
-        // BinaryDigitOrUnderscore
-        // 0 | 1 | _
         assert_eq!(
-            parser::BinaryDigitOrUnderscoreParser::new().parse("0"),
-            Ok('0')
+            parser::IntegerLiteralParser::new()
+                .parse(Lexer::new("1_234_567_890u16")),
+            Err(ParseError::User {
+                error: IntegerLiteralOutOfRange {
+                    ty: "u16",
+                    start: 0,
+                    end: 16,
+                },
+            })
         );
         assert_eq!(
-            parser::BinaryDigitOrUnderscoreParser::new().parse("1"),
-            Ok('1')
+            parser::IntegerLiteralParser::new()
+                .parse(Lexer::new("9_876_543_210u32")),
+            Err(ParseError::User {
+                error: IntegerLiteralOutOfRange {
+                    ty: "u32",
+                    start: 0,
+                    end: 16,
+                },
+            })
         );
         assert_eq!(
-            parser::BinaryDigitOrUnderscoreParser::new().parse("_"),
-            Ok('_')
+            parser::IntegerLiteralParser::new()
+                .parse(Lexer::new("1_234_567_890_123_456_789_123_456_789u64")),
+            Err(ParseError::User {
+                error: IntegerLiteralOutOfRange {
+                    ty: "u64",
+                    start: 0,
+                    end: 40,
+                },
+            })
         );
-    }
-
-    #[test]
-    fn test_parse_float_literals() {
-        // let ast = parser::LiteralExpressionParser::new()
-        //     .parse("22.0")
-        //     .unwrap();
-        // assert_eq!(ast, ast::LiteralExpression::Float { f: 22.0 });
-
-        // let ast = parser::LiteralExpressionParser::new().parse("0.0").unwrap();
-        // assert_eq!(ast, ast::LiteralExpression::Float { f: 0.0 });
-
-        // DEC_LITERAL . DEC_LITERAL FLOAT_EXPONENT?
-        assert_eq!(parser::FloatLiteralParser::new().parse("22.0"), Ok(22.0));
         assert_eq!(
-            parser::FloatLiteralParser::new().parse("22.0e+1"),
-            Ok(22.0e+1)
+            parser::IntegerLiteralParser::new()
+                .parse(Lexer::new("9_876_543_210_987_654_321_987_654_321u128")),
+            Ok(Integer::U128 {
+                n: 9_876_543_210_987_654_321_987_654_321
+            })
         );
         assert_eq!(
-            parser::FloatLiteralParser::new().parse("1234.5e-6"),
-            Ok(1234.5e-6)
+            parser::IntegerLiteralParser::new().parse(Lexer::new("0o_____usize")),
+            Ok(Integer::USize { n: 0o0 })
         );
         assert_eq!(
-            parser::FloatLiteralParser::new().parse("12E+99_"),
-            Ok(12E+99)
+            parser::IntegerLiteralParser::new().parse(Lexer::new("0O_____i8")),
+            Ok(Integer::I8 { n: 0o0 })
         );
-        assert_eq!(parser::FloatLiteralParser::new().parse("12."), Ok(12.0));
-
-        // Invalid Float Exponents
         assert_eq!(
-            parser::FloatExponentParser::new().parse("e+_"),
+            parser::IntegerLiteralParser::new()
+                .parse(Lexer::new("0o_1_2_3_4_5_6_7_i16")),
             Err(ParseError::User {
-                error: InvalidFloatExponent,
+                error: IntegerLiteralOutOfRange {
+                    ty: "i16",
+                    start: 0,
+                    end: 20,
+                },
             })
         );
         assert_eq!(
-            parser::FloatExponentParser::new().parse("E-_"),
-            Err(ParseError::User {
-                error: InvalidFloatExponent,
-            })
+            parser::IntegerLiteralParser::new()
+                .parse(Lexer::new("0O_1_2_3_4_5_6_7_i32")),
+            Ok(Integer::I32 { n: 0o1234567 })
         );
-
-        // Valid Float Exponents
         assert_eq!(
-            parser::FloatExponentParser::new().parse("e+3"),
-            Ok(String::from("e+3"))
+            parser::IntegerLiteralParser::new().parse(Lexer::new("0o70i64")),
+            Ok(Integer::I64 { n: 0o70 })
         );
         assert_eq!(
-            parser::FloatExponentParser::new().parse("E-3"),
-            Ok(String::from("E-3"))
+            parser::IntegerLiteralParser::new().parse(Lexer::new("0O70i128")),
+            Ok(Integer::I128 { n: 0o70 })
         );
+        assert!(parser::IntegerLiteralParser::new()
+            .parse(Lexer::new("0o8isize"))
+            .is_err());
         assert_eq!(
-            parser::FloatExponentParser::new().parse("e+123"),
-            Ok(String::from("e+123"))
+            parser::IntegerLiteralParser::new().parse(Lexer::new("0b_____u8")),
+            Ok(Integer::U8 { n: 0b0 })
         );
         assert_eq!(
-            parser::FloatExponentParser::new().parse("E-123"),
-            Ok(String::from("E-123"))
+            parser::IntegerLiteralParser::new().parse(Lexer::new("0B_____u16")),
+            Ok(Integer::U16 { n: 0b0 })
         );
         assert_eq!(
-            parser::FloatExponentParser::new().parse("e+_0_1_2_3_4_5_6_7_8_9"),
-            Ok(String::from("e+_0_1_2_3_4_5_6_7_8_9"))
+            parser::IntegerLiteralParser::new()
+                .parse(Lexer::new("0b_1_0_1_0_1_0_1_0_u32")),
+            Ok(Integer::U32 { n: 0b10101010 })
         );
         assert_eq!(
-            parser::FloatExponentParser::new().parse("E-_0_1_2_3_4_5_6_7_8_9"),
-            Ok(String::from("E-_0_1_2_3_4_5_6_7_8_9"))
+            parser::IntegerLiteralParser::new()
+                .parse(Lexer::new("0B_1_0_1_0_1_0_1_0_u64")),
+            Ok(Integer::U64 { n: 0b10101010 })
+        );
+        assert_eq!(
+            parser::IntegerLiteralParser::new()
+                .parse(Lexer::new("0b1111_1111_1001_0000_u128")),
+            Ok(Integer::U128 {
+                n: 0b1111_1111_1001_0000
+            })
+        );
+        assert_eq!(
+            parser::IntegerLiteralParser::new()
+                .parse(Lexer::new("0B1111_1111_1001_0000_usize")),
+            Ok(Integer::USize {
+                n: 0b1111_1111_1001_0000
+            })
         );
-        assert!(parser::FloatExponentParser::new().parse("e+").is_err());
-
-        // "+" | "-"
-        assert_eq!(parser::PlusOrMinusSignParser::new().parse("+"), Ok('+'));
-        assert_eq!(parser::PlusOrMinusSignParser::new().parse("-"), Ok('-'));
-    }
-
-    // #[test]
-    // fn test_parse_boolean_literal_expression() {
-    //     let ast =
-    // parser::LiteralExpressionParser::new().parse("true").unwrap();
-    //     assert_eq!(ast, ast::LiteralExpression::Boolean { b: true });
 
-    //     let ast =
-    // parser::LiteralExpressionParser::new().parse("false").unwrap();
-    //     assert_eq!(ast, ast::LiteralExpression::Boolean { b: false });
-    // }
-}
-
-/////////////////////////////////////////////////////////////
-/// Verifies the correctness of the LALRPOP lexer          //
-/// generator when it comes to lexing the source code.     //
-///                                                        //
-/// At first glance, the APIs here may seem a bit          //
-/// odd since they all reference structures including the  //
-/// substring "Parser" and this test suite is intended to  //
-/// test the correctness of the Lexer.                     //
-///                                                        //
-/// While this may be the case, it is a result of the      //
-/// fact that we're using LALRPOP to generate the parser,  //
-/// compared to other parsing methods, since by definition //
-/// an LR(1) parser can accept a superset of the grammars  //
-/// accepted by an LL(1) parser. However, what comes with  //
-/// this is that LALRPOP tightly couples the lexer and     //
-/// parser for ease of use. In the future, this            //
-/// implementation detail may be refactored in favor of    //
-/// using a different lexer (e.g. Logos or something else) //
-/// and decoupling the lexing phase from that of parsing.  //
-/// Regardless, the following test suite is written with   //
-/// the intention of testing the lexing capabilities of    //
-/// the compiler when it comes to producing tokens used    //
-/// within the grammar by the parser to create the AST.    //
-/////////////////////////////////////////////////////////////
-#[cfg(test)]
-mod lexer_test_suite {
-    use super::*;
-    use pretty_assertions::assert_eq;
-
-    // TODO maybe in future expand to deserializing parsed nonterminals into
-    // well-defined data structures housing tokens along with their lexemes
-    // and spans. Similar to how lexing is done in Logos.
-    // In contrast, the current implementation is better for having well-defined,
-    // and simple to reason about action code within the parser
-
-    // TODO maybe add a redundant lexer implementation via Logos
-    // for emitting tokens along with their lexemes, spans, and usage in the
-    // language.
-
-    // TODO refactor lexing to more idiomatic Rust data structures
-    // (e.g. using enums instead of strings)
-    // Example: PlusSignParser::new().parse("+").unwrap() ->
-    // Token::Punctuation::Plus
-
-    #[test]
-    fn test_lex_punctuation() {
-        assert_eq!(
-            parser::PunctuationParser::new().parse("+"),
-            Ok(Punctuation::Plus)
-        );
-        assert_eq!(
-            parser::PunctuationParser::new().parse("-"),
-            Ok(Punctuation::Minus)
-        );
-        assert_eq!(
-            parser::PunctuationParser::new().parse("*"),
-            Ok(Punctuation::Star)
-        );
+        // Boundaries of each suffix's type
         assert_eq!(
-            parser::PunctuationParser::new().parse("/"),
-            Ok(Punctuation::Slash)
+            parser::IntegerLiteralParser::new().parse(Lexer::new("255u8")),
+            Ok(Integer::U8 { n: 255 })
         );
         assert_eq!(
-            parser::PunctuationParser::new().parse("\\"),
-            Ok(Punctuation::Backslash)
+            parser::IntegerLiteralParser::new().parse(Lexer::new("300u8")),
+            Err(ParseError::User {
+                error: IntegerLiteralOutOfRange {
+                    ty: "u8",
+                    start: 0,
+                    end: 5,
+                },
+            })
         );
         assert_eq!(
-            parser::PunctuationParser::new().parse("%"),
-            Ok(Punctuation::Percent)
+            parser::IntegerLiteralParser::new().parse(Lexer::new("127i8")),
+            Ok(Integer::I8 { n: 127 })
         );
         assert_eq!(
-            parser::PunctuationParser::new().parse("^"),
-            Ok(Punctuation::Caret)
+            parser::IntegerLiteralParser::new().parse(Lexer::new("128i8")),
+            Err(ParseError::User {
+                error: IntegerLiteralOutOfRange {
+                    ty: "i8",
+                    start: 0,
+                    end: 5,
+                },
+            })
         );
         assert_eq!(
-            parser::PunctuationParser::new().parse("!"),
-            Ok(Punctuation::Not)
+            parser::IntegerLiteralParser::new().parse(Lexer::new("0xFFFF_FFFFu32")),
+            Ok(Integer::U32 { n: 0xFFFF_FFFF })
         );
         assert_eq!(
-            parser::PunctuationParser::new().parse("not"),
-            Ok(Punctuation::Not)
+            parser::IntegerLiteralParser::new()
+                .parse(Lexer::new("0x1_0000_0000u32")),
+            Err(ParseError::User {
+                error: IntegerLiteralOutOfRange {
+                    ty: "u32",
+                    start: 0,
+                    end: 16,
+                },
+            })
         );
         assert_eq!(
-            parser::PunctuationParser::new().parse("&"),
-            Ok(Punctuation::And)
+            parser::IntegerLiteralParser::new().parse(Lexer::new(
+                "340_282_366_920_938_463_463_374_607_431_768_211_455u128"
+            )),
+            Ok(Integer::U128 { n: u128::MAX })
         );
         assert_eq!(
-            parser::PunctuationParser::new().parse("|"),
-            Ok(Punctuation::Or)
+            parser::IntegerLiteralParser::new().parse(Lexer::new(
+                "340_282_366_920_938_463_463_374_607_431_768_211_456u128"
+            )),
+            Err(ParseError::User {
+                error: IntegerLiteralOutOfRange {
+                    ty: "u128",
+                    start: 0,
+                    end: 55,
+                },
+            })
         );
+
+        // Unsuffixed literals take the first of i32, i64, i128 and u128 which
+        // fits their value
         assert_eq!(
-            parser::PunctuationParser::new().parse("&&"),
-            Ok(Punctuation::AndAnd)
+            parser::IntegerLiteralParser::new().parse(Lexer::new("2_147_483_647")),
+            Ok(Integer::I32 { n: i32::MAX })
         );
         assert_eq!(
-            parser::PunctuationParser::new().parse("||"),
-            Ok(Punctuation::OrOr)
+            parser::IntegerLiteralParser::new().parse(Lexer::new("2_147_483_648")),
+            Ok(Integer::I64 { n: 2_147_483_648 })
         );
         assert_eq!(
-            parser::PunctuationParser::new().parse("+="),
-            Ok(Punctuation::PlusEquals)
+            parser::IntegerLiteralParser::new()
+                .parse(Lexer::new("0x8000_0000_0000_0000")),
+            Ok(Integer::I128 {
+                n: 0x8000_0000_0000_0000
+            })
         );
         assert_eq!(
-            parser::PunctuationParser::new().parse("-="),
-            Ok(Punctuation::MinusEquals)
+            parser::IntegerLiteralParser::new()
+                .parse(Lexer::new("0xFFFF_FFFF_FFFF_FFFF_FFFF_FFFF_FFFF_FFFF")),
+            Ok(Integer::U128 { n: u128::MAX })
         );
+
+        // Unsuffixed literals wider than a u128 are arbitrary-precision
         assert_eq!(
-            parser::PunctuationParser::new().parse("*="),
-            Ok(Punctuation::StarEquals)
+            parser::IntegerLiteralParser::new().parse(Lexer::new(
+                "2_000_000_000_000_000_000_000_000_000_000_000_000_000"
+            )),
+            Ok(Integer::Big {
+                n: rug::Integer::from(2) * rug::Integer::from(10).pow(39)
+            })
         );
         assert_eq!(
-            parser::PunctuationParser::new().parse("/="),
-            Ok(Punctuation::SlashEquals)
+            parser::IntegerLiteralParser::new().parse(Lexer::new(
+                "340_282_366_920_938_463_463_374_607_431_768_211_456"
+            )),
+            Ok(Integer::Big {
+                n: rug::Integer::from(u128::MAX) + 1
+            })
         );
         assert_eq!(
-            parser::PunctuationParser::new().parse("%="),
-            Ok(Punctuation::PercentEquals)
+            parser::IntegerLiteralParser::new()
+                .parse(Lexer::new("0b1_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000")),
+            Ok(Integer::Big {
+                n: rug::Integer::from(1) << 128
+            })
         );
         assert_eq!(
-            parser::PunctuationParser::new().parse("^="),
-            Ok(Punctuation::CaretEquals)
+            parser::IntegerLiteralParser::new().parse(Lexer::new(
+                "0o4_000_000_000_000_000_000_000_000_000_000_000_000_000_000"
+            )),
+            Ok(Integer::Big {
+                n: rug::Integer::from(1) << 128
+            })
         );
         assert_eq!(
-            parser::PunctuationParser::new().parse("&="),
-            Ok(Punctuation::AndEquals)
+            parser::LiteralExpressionParser::new().parse(Lexer::new("42")),
+            Ok(LiteralExpression::NumericLiteral(NumericLiteral::Integer(
+                Integer::I32 { n: 42 }
+            )))
         );
+
+        // Integer Sub-Literals (e.g. DecimalLiteral, HexadecimalLiteral, etc.)
+
+        // - HexadecimalLiteral
         assert_eq!(
-            parser::PunctuationParser::new().parse("|="),
-            Ok(Punctuation::OrEquals)
+            parser::HexadecimalLiteralParser::new().parse(Lexer::new("0x_____")),
+            Ok(big(0x0))
         );
         assert_eq!(
-            parser::PunctuationParser::new().parse("="),
-            Ok(Punctuation::Equals)
+            parser::HexadecimalLiteralParser::new().parse(Lexer::new("0X_____")),
+            Ok(big(0x0))
         );
         assert_eq!(
-            parser::PunctuationParser::new().parse("=="),
-            Ok(Punctuation::DoubleEquals)
+            parser::HexadecimalLiteralParser::new()
+                .parse(Lexer::new("0x_1_2_3_4_5_6_7_")),
+            Ok(big(0x1234567))
         );
         assert_eq!(
-            parser::PunctuationParser::new().parse("!="),
-            Ok(Punctuation::NotEqual)
+            parser::HexadecimalLiteralParser::new()
+                .parse(Lexer::new("0X_1_2_3_4_5_6_7_")),
+            Ok(big(0x1234567))
         );
         assert_eq!(
-            parser::PunctuationParser::new().parse("≠"),
-            Ok(Punctuation::NotEqual)
+            parser::HexadecimalLiteralParser::new()
+                .parse(Lexer::new("0x_a_b_c_d_e_f_")),
+            Ok(big(0xabcdef))
         );
         assert_eq!(
-            parser::PunctuationParser::new().parse("<"),
-            Ok(Punctuation::LessThan)
+            parser::HexadecimalLiteralParser::new()
+                .parse(Lexer::new("0X_a_b_c_d_e_f_")),
+            Ok(big(0xabcdef))
         );
         assert_eq!(
-            parser::PunctuationParser::new().parse("<="),
-            Ok(Punctuation::LessThanEqual)
+            parser::HexadecimalLiteralParser::new()
+                .parse(Lexer::new("0x_A_B_C_D_E_F_")),
+            Ok(big(0xABCDEF))
         );
         assert_eq!(
-            parser::PunctuationParser::new().parse("≤"),
-            Ok(Punctuation::LessThanEqual)
+            parser::HexadecimalLiteralParser::new()
+                .parse(Lexer::new("0X_A_B_C_D_E_F_")),
+            Ok(big(0xABCDEF))
         );
+        assert!(parser::HexadecimalLiteralParser::new()
+            .parse(Lexer::new("0xG"))
+            .is_err());
+
+        // - DecimalLiteral
         assert_eq!(
-            parser::PunctuationParser::new().parse(">"),
-            Ok(Punctuation::GreaterThan)
+            parser::DecimalLiteralParser::new().parse(Lexer::new("22")),
+            Ok(big(22))
         );
         assert_eq!(
-            parser::PunctuationParser::new().parse(">="),
-            Ok(Punctuation::GreaterThanEqual)
+            parser::DecimalLiteralParser::new().parse(Lexer::new("0")),
+            Ok(big(0))
         );
         assert_eq!(
-            parser::PunctuationParser::new().parse("≥"),
-            Ok(Punctuation::GreaterThanEqual)
+            parser::DecimalLiteralParser::new().parse(Lexer::new("1_2_3_")),
+            Ok(big(123))
         );
         assert_eq!(
-            parser::PunctuationParser::new().parse("_"),
-            Ok(Punctuation::Underscore)
+            parser::DecimalLiteralParser::new().parse(Lexer::new("1_000")),
+            Ok(big(1_000))
         );
         assert_eq!(
-            parser::PunctuationParser::new().parse("."),
-            Ok(Punctuation::Dot)
+            parser::DecimalLiteralParser::new().parse(Lexer::new("1_234_567")),
+            Ok(big(1_234_567))
         );
         assert_eq!(
-            parser::PunctuationParser::new().parse(".."),
-            Ok(Punctuation::DotDot)
+            parser::DecimalLiteralParser::new().parse(Lexer::new("1_234_567_890")),
+            Ok(big(1_234_567_890))
         );
         assert_eq!(
-            parser::PunctuationParser::new().parse("..."),
-            Ok(Punctuation::DotDotDot)
+            parser::DecimalLiteralParser::new().parse(Lexer::new("9_876_543_210")),
+            Ok(big(9_876_543_210))
         );
         assert_eq!(
-            parser::PunctuationParser::new().parse("..="),
-            Ok(Punctuation::DotDotEquals)
+            parser::DecimalLiteralParser::new()
+                .parse(Lexer::new("1_234_567_890_123_456_789_123_456_789")),
+            Ok(big(1_234_567_890_123_456_789_123_456_789))
         );
         assert_eq!(
-            parser::PunctuationParser::new().parse(","),
-            Ok(Punctuation::Comma)
+            parser::DecimalLiteralParser::new()
+                .parse(Lexer::new("9_876_543_210_987_654_321_987_654_321")),
+            Ok(big(9_876_543_210_987_654_321_987_654_321))
         );
+        // TODO assert that this is an error (decimal literal cannot start with an
+        // underscore)
+        // assert_eq!(
+        //     parser::DecimalLiteralParser::new().parse("_1_2_3_"),
+        //     Err(ParseError::User {
+        //         error: InvalidDecimalLiteral,
+        //     })
+        // );
+
+        // - OctalLiteral
         assert_eq!(
-            parser::PunctuationParser::new().parse(";"),
-            Ok(Punctuation::Semicolon)
+            parser::OctalLiteralParser::new().parse(Lexer::new("0o_____")),
+            Ok(big(0o0))
         );
         assert_eq!(
-            parser::PunctuationParser::new().parse(":"),
-            Ok(Punctuation::Colon)
+            parser::OctalLiteralParser::new().parse(Lexer::new("0O_____")),
+            Ok(big(0o0))
         );
         assert_eq!(
-            parser::PunctuationParser::new().parse("::"),
-            Ok(Punctuation::PathSeparator)
+            parser::OctalLiteralParser::new().parse(Lexer::new("0o_1_2_3_4_5_6_7_")),
+            Ok(big(0o1234567))
         );
         assert_eq!(
-            parser::PunctuationParser::new().parse("->"),
-            Ok(Punctuation::RightArrow)
+            parser::OctalLiteralParser::new().parse(Lexer::new("0O_1_2_3_4_5_6_7_")),
+            Ok(big(0o1234567))
         );
         assert_eq!(
-            parser::PunctuationParser::new().parse("→"),
-            Ok(Punctuation::RightArrow)
+            parser::OctalLiteralParser::new().parse(Lexer::new("0o70")),
+            Ok(big(0o70))
         );
         assert_eq!(
-            parser::PunctuationParser::new().parse("<-"),
-            Ok(Punctuation::LeftArrow)
+            parser::OctalLiteralParser::new().parse(Lexer::new("0O70")),
+            Ok(big(0o70))
         );
+        assert!(parser::OctalLiteralParser::new()
+            .parse(Lexer::new("0o8"))
+            .is_err());
+
+        // - BinaryLiteral
         assert_eq!(
-            parser::PunctuationParser::new().parse("←"),
-            Ok(Punctuation::LeftArrow)
+            parser::BinaryLiteralParser::new().parse(Lexer::new("0b_____")),
+            Ok(big(0b0))
         );
         assert_eq!(
-            parser::PunctuationParser::new().parse("=>"),
-            Ok(Punctuation::FatRightArrow)
+            parser::BinaryLiteralParser::new().parse(Lexer::new("0B_____")),
+            Ok(big(0b0))
         );
         assert_eq!(
-            parser::PunctuationParser::new().parse("⇒"),
-            Ok(Punctuation::FatRightArrow)
+            parser::BinaryLiteralParser::new().parse(Lexer::new("0b________1")),
+            Ok(big(0b1))
         );
         assert_eq!(
-            parser::PunctuationParser::new().parse("⇐"),
-            Ok(Punctuation::FatLeftArrow)
+            parser::BinaryLiteralParser::new().parse(Lexer::new("0B________1")),
+            Ok(big(0b1))
         );
         assert_eq!(
-            parser::PunctuationParser::new().parse("#"),
-            Ok(Punctuation::Pound)
+            parser::BinaryLiteralParser::new()
+                .parse(Lexer::new("0b1111_1111_1001_0000")),
+            Ok(big(0b1111_1111_1001_0000))
         );
         assert_eq!(
-            parser::PunctuationParser::new().parse("$"),
-            Ok(Punctuation::Dollar)
+            parser::BinaryLiteralParser::new()
+                .parse(Lexer::new("0B1111_1111_1001_0000")),
+            Ok(big(0b1111_1111_1001_0000))
         );
         assert_eq!(
-            parser::PunctuationParser::new().parse("?"),
-            Ok(Punctuation::Question)
+            parser::BinaryLiteralParser::new()
+                .parse(Lexer::new("0b_0_1_1_0_1_1_0_0_1_0_0_0_0_0_0_0_")),
+            Ok(big(0b0110_1100_1000_0000))
         );
+        assert!(parser::BinaryLiteralParser::new()
+            .parse(Lexer::new("0b2"))
+            .is_err());
+
+        // Sub-literals are computed with arbitrary precision
         assert_eq!(
-            parser::PunctuationParser::new().parse("'"),
-            Ok(Punctuation::Apostrophe)
+            parser::DecimalLiteralParser::new().parse(Lexer::new(
+                "2_000_000_000_000_000_000_000_000_000_000_000_000_000"
+            )),
+            Ok(rug::Integer::from(2) * rug::Integer::from(10).pow(39))
         );
         assert_eq!(
-            parser::PunctuationParser::new().parse("\""),
-            Ok(Punctuation::Quote)
+            parser::HexadecimalLiteralParser::new()
+                .parse(Lexer::new("0x1_0000_0000_0000_0000_0000_0000_0000_0000")),
+            Ok(rug::Integer::from(1) << 128)
         );
     }
 
     #[test]
-    fn test_lex_delimiters() {
-        assert_eq!(
-            parser::DelimiterParser::new().parse("("),
-            Ok(Delimiter::LeftParen)
-        );
-        assert_eq!(
-            parser::DelimiterParser::new().parse(")"),
-            Ok(Delimiter::RightParen)
-        );
-        assert_eq!(
-            parser::DelimiterParser::new().parse("["),
-            Ok(Delimiter::LeftBracket)
-        );
+    fn test_parse_float_literals() {
+        // let ast = parser::LiteralExpressionParser::new()
+        //     .parse("22.0")
+        //     .unwrap();
+        // assert_eq!(ast, ast::LiteralExpression::Float { f: 22.0 });
+
+        // let ast = parser::LiteralExpressionParser::new().parse("0.0").unwrap();
+        // assert_eq!(ast, ast::LiteralExpression::Float { f: 0.0 });
+
+        // DEC_LITERAL . DEC_LITERAL FLOAT_EXPONENT?
         assert_eq!(
-            parser::DelimiterParser::new().parse("]"),
-            Ok(Delimiter::RightBracket)
+            parser::FloatLiteralParser::new().parse(Lexer::new("22.0")),
+            Ok(Float::F64 { f: 22.0 })
         );
         assert_eq!(
-            parser::DelimiterParser::new().parse("{"),
-            Ok(Delimiter::LeftBrace)
+            parser::FloatLiteralParser::new().parse(Lexer::new("22.0e+1")),
+            Ok(Float::F64 { f: 22.0e+1 })
         );
         assert_eq!(
-            parser::DelimiterParser::new().parse("}"),
-            Ok(Delimiter::RightBrace)
+            parser::FloatLiteralParser::new().parse(Lexer::new("1234.5e-6")),
+            Ok(Float::F64 { f: 1234.5e-6 })
         );
-    }
-
-    // [A-Za-zªµºÀ-ÖØ-öø-ˁˆ-ˑ ˠ-ˤˬˮͰ-ʹͶͷͻ-ͽͿΆΈ-ΊΌΎ-Ρ Σ-ϵϷ-ҁҊ-ԯԱ-Ֆՙՠ-ֈא-תׯ-ײ ؠ-يٮٯٱ-ۓەۥۦۮۯۺ-ۼۿܐܒ-ܯ ݍ-ޥޱߊ-ߪߴߵߺࠀ-ࠕࠚࠤࠨࡀ-ࡘࡠ-ࡪ ࡰ-ࢇࢉ-ࢎࢠ-ࣉऄ-हऽॐक़-ॡॱ-ঀঅ-ঌ এঐও-নপ-রলশ-হঽৎড়ঢ়য়-ৡৰৱ ৼਅ-ਊਏਐਓ-ਨਪ-ਰਲਲ਼ਵਸ਼ਸਹਖ਼-ੜ ਫ਼ੲ-ੴઅ-ઍએ-ઑઓ-નપ-રલળવ-હ ઽૐૠૡૹଅ-ଌଏଐଓ-ନପ-ରଲଳଵ-ହ ଽଡ଼ଢ଼ୟ-ୡୱஃஅ-ஊஎ-ஐஒ-கஙசஜஞ டணதந-பம-ஹௐఅ-ఌఎ-ఐఒ-నప-హ ఽౘ-ౚౝౠౡಀಅ-ಌಎ-ಐಒ-ನಪ-ಳವ-ಹ ಽೝೞೠೡೱೲഄ-ഌഎ-ഐഒ-ഺഽൎൔ-ൖ ൟ-ൡൺ-ൿඅ-ඖක-නඳ-රලව-ෆก-ะ าเ-ๆກຂຄຆ-ຊຌ-ຣລວ-ະາຽເ-ໄ ໆໜ-ໟༀཀ-ཇཉ-ཬྈ-ྌက-ဪဿၐ-ၕ ၚ-ၝၡၥၦၮ-ၰၵ-ႁႎႠ-ჅჇჍა-ჺ ჼ-ቈቊ-ቍቐ-ቖቘቚ-ቝበ-ኈኊ-ኍነ-ኰ ኲ-ኵኸ-ኾዀዂ-ዅወ-ዖዘ-ጐጒ-ጕጘ-ፚ ᎀ-ᎏᎠ-Ᏽᏸ-ᏽᐁ-ᙬᙯ-ᙿᚁ-ᚚᚠ-ᛪ ᛮ-ᛸᜀ-ᜑᜟ-ᜱᝀ-ᝑᝠ-ᝬᝮ-ᝰក-ឳ ៗៜᠠ-ᡸᢀ-ᢨᢪᢰ-ᣵᤀ-ᤞᥐ-ᥭᥰ-ᥴ ᦀ-ᦫᦰ-ᧉᨀ-ᨖᨠ-ᩔᪧᬅ-ᬳᭅ-ᭌᮃ-ᮠ ᮮᮯᮺ-ᯥᰀ-ᰣᱍ-ᱏᱚ-ᱽᲀ-ᲈᲐ-ᲺᲽ-Ჿ ᳩ-ᳬᳮ-ᳳᳵᳶᳺᴀ-ᶿḀ-ἕἘ-Ἕἠ-ὅ Ὀ-Ὅὐ-ὗὙὛὝὟ-ώᾀ-ᾴᾶ-ᾼιῂ-ῄ ῆ-ῌῐ-ΐῖ-Ίῠ-Ῥῲ-ῴῶ-ῼⁱⁿₐ-ₜ ℂℇℊ-ℓℕ℘-ℝℤΩℨK-ℹℼ-ℿⅅ-ⅉ ⅎⅠ-ↈⰀ-ⳤⳫ-ⳮⳲⳳⴀ-ⴥⴧⴭⴰ-ⵧⵯ ⶀ-ⶖⶠ-ⶦⶨ-ⶮⶰ-ⶶⶸ-ⶾⷀ-ⷆⷈ-ⷎ ⷐ-ⷖⷘ-ⷞ々-〇〡-〩〱-〵〸-〼ぁ-ゖ ゝ-ゟァ-ヺー-ヿㄅ-ㄯㄱ-ㆎㆠ-ㆿㇰ-ㇿ 㐀-䶿一-ꒌꓐ-ꓽꔀ-ꘌꘐ-ꘟꘪꘫꙀ-ꙮꙿ-ꚝ ꚠ-ꛯꜗ-ꜟꜢ-ꞈꞋ-ꟊꟐꟑꟓꟕ-ꟙꟲ-ꠁ ꠃ-ꠅꠇ-ꠊꠌ-ꠢꡀ-ꡳꢂ-ꢳꣲ-ꣷꣻꣽꣾ ꤊ-ꤥꤰ-ꥆꥠ-ꥼꦄ-ꦲꧏꧠ-ꧤꧦ-ꧯꧺ-ꧾ ꨀ-ꨨꩀ-ꩂꩄ-ꩋꩠ-ꩶꩺꩾ-ꪯꪱꪵꪶꪹ-ꪽ ꫀꫂꫛ-ꫝꫠ-ꫪꫲ-ꫴꬁ-ꬆꬉ-ꬎꬑ-ꬖꬠ-ꬦ ꬨ-ꬮꬰ-ꭚꭜ-ꭩꭰ-ꯢ가-힣ힰ-ퟆퟋ-ퟻ 豈-舘並-龎ﬀ-ﬆﬓ-ﬗיִײַ-ﬨשׁ-זּטּ-לּ מּנּסּףּפּצּ-ﮱﯓ-ﱝﱤ-ﴽﵐ-ﶏﶒ-ﷇﷰ-ﷹ ﹱﹳﹷﹹﹻﹽﹿ-ﻼＡ-Ｚａ-ｚｦ-ﾝ-ﾾￂ-ￇ ￊ-ￏￒ-ￗￚ-ￜ𐀀-𐀋𐀍-𐀦𐀨-𐀺𐀼𐀽𐀿-𐁍 𐁐-𐁝𐂀-𐃺𐅀-𐅴𐊀-𐊜𐊠-𐋐𐌀-𐌟𐌭-𐍊 𐍐-𐍵𐎀-𐎝𐎠-𐏃𐏈-𐏏𐏑-𐏕𐐀-𐒝𐒰-𐓓 𐓘-𐓻𐔀-𐔧𐔰-𐕣𐕰-𐕺𐕼-𐖊𐖌-𐖒𐖔𐖕𐖗-𐖡 𐖣-𐖱𐖳-𐖹𐖻𐖼𐘀-𐜶𐝀-𐝕𐝠-𐝧𐞀-𐞅𐞇-𐞰 𐞲-𐞺𐠀-𐠅𐠈𐠊-𐠵𐠷𐠸𐠼𐠿-𐡕𐡠-𐡶𐢀-𐢞 𐣠-𐣲𐣴𐣵𐤀-𐤕𐤠-𐤹𐦀-𐦷𐦾𐦿𐨀𐨐-𐨓𐨕-𐨗 𐨙-𐨵𐩠-𐩼𐪀-𐪜𐫀-𐫇𐫉-𐫤𐬀-𐬵𐭀-𐭕 𐭠-𐭲𐮀-𐮑𐰀-𐱈𐲀-𐲲𐳀-𐳲𐴀-𐴣𐺀-𐺩 𐺰𐺱𐼀-𐼜𐼧𐼰-𐽅𐽰-𐾁𐾰-𐿄𐿠-𐿶𑀃-𑀷 𑁱𑁲𑁵𑂃-𑂯𑃐-𑃨𑄃-𑄦𑅄𑅇𑅐-𑅲𑅶𑆃-𑆲 𑇁-𑇄𑇚𑇜𑈀-𑈑𑈓-𑈫𑈿𑉀𑊀-𑊆𑊈𑊊-𑊍𑊏-𑊝 𑊟-𑊨𑊰-𑋞𑌅-𑌌𑌏𑌐𑌓-𑌨𑌪-𑌰𑌲𑌳𑌵-𑌹 𑌽𑍐𑍝-𑍡𑐀-𑐴𑑇-𑑊𑑟-𑑡𑒀-𑒯𑓄𑓅𑓇𑖀-𑖮 𑗘-𑗛𑘀-𑘯𑙄𑚀-𑚪𑚸𑜀-𑜚𑝀-𑝆𑠀-𑠫𑢠-𑣟 𑣿-𑤆𑤉𑤌-𑤓𑤕𑤖𑤘-𑤯𑤿𑥁𑦠-𑦧𑦪-𑧐𑧡 𑧣𑨀𑨋-𑨲𑨺𑩐𑩜-𑪉𑪝𑪰-𑫸𑰀-𑰈𑰊-𑰮𑱀 𑱲-𑲏𑴀-𑴆𑴈𑴉𑴋-𑴰𑵆𑵠-𑵥𑵧𑵨𑵪-𑶉𑶘 𑻠-𑻲𑼂𑼄-𑼐𑼒-𑼳𑾰𒀀-𒎙𒐀-𒑮𒒀-𒕃𒾐-𒿰 𓀀-𓐯𓑁-𓑆𔐀-𔙆𖠀-𖨸𖩀-𖩞𖩰-𖪾𖫐-𖫭 𖬀-𖬯𖭀-𖭃𖭣-𖭷𖭽-𖮏𖹀-𖹿𖼀-𖽊𖽐𖾓-𖾟 𖿠𖿡𖿣𗀀-𘟷𘠀-𘳕𘴀-𘴈𚿰-𚿳𚿵-𚿻𚿽𚿾𛀀-𛄢 𛄲𛅐-𛅒𛅕𛅤-𛅧𛅰-𛋻𛰀-𛱪𛱰-𛱼𛲀-𛲈𛲐-𛲙 𝐀-𝑔𝑖-𝒜𝒞𝒟𝒢𝒥𝒦𝒩-𝒬𝒮-𝒹𝒻𝒽-𝓃 𝓅-𝔅𝔇-𝔊𝔍-𝔔𝔖-𝔜𝔞-𝔹𝔻-𝔾𝕀-𝕄 𝕆𝕊-𝕐𝕒-𝚥𝚨-𝛀𝛂-𝛚𝛜-𝛺𝛼-𝜔𝜖-𝜴 𝜶-𝝎𝝐-𝝮𝝰-𝞈𝞊-𝞨𝞪-𝟂𝟄-𝟋𝼀-𝼞 𝼥-𝼪𞀰-𞁭𞄀-𞄬𞄷-𞄽𞅎𞊐-𞊭𞋀-𞋫𞓐-𞓫 𞟠-𞟦𞟨-𞟫𞟭𞟮𞟰-𞟾𞠀-𞣄𞤀-𞥃𞥋𞸀-𞸃 𞸅-𞸟𞸡𞸢𞸤𞸧𞸩-𞸲𞸴-𞸷𞸹𞸻𞹂𞹇𞹉𞹋𞹍-𞹏 𞹑𞹒𞹔𞹗𞹙𞹛𞹝𞹟𞹡𞹢𞹤𞹧-𞹪𞹬-𞹲𞹴-𞹷𞹹-𞹼 𞹾𞺀-𞺉𞺋-𞺛𞺡-𞺣𞺥-𞺩𞺫-𞺻𠀀-𪛟𪜀-𫜹 𫝀-𫠝𫠠-𬺡𬺰-𮯠丽-𪘀𰀀-𱍊𱍐-𲎯]
-    // TODO further distinguish the separation between xidstart and xidcontinue
-    // there are 3000+ codepoints in xidcontinue that are not in xidstart
-    #[test]
-    fn test_lex_xid_start() {
-        assert_eq!(parser::XidStartParser::new().parse("a"), Ok('a'));
-        assert_eq!(parser::XidStartParser::new().parse("в"), Ok('в'));
-        assert_eq!(parser::XidStartParser::new().parse("भ"), Ok('भ'));
-        assert_eq!(parser::XidStartParser::new().parse("त"), Ok('त'));
-        assert_eq!(parser::XidStartParser::new().parse("र"), Ok('र'));
-        assert_eq!(parser::XidStartParser::new().parse("П"), Ok('П'));
-        assert_eq!(parser::XidStartParser::new().parse("и"), Ok('и'));
-        assert_eq!(parser::XidStartParser::new().parse("в"), Ok('в'));
-        assert_eq!(parser::XidStartParser::new().parse("е"), Ok('е'));
-        assert_eq!(parser::XidStartParser::new().parse("т"), Ok('т'));
-        assert_eq!(parser::XidStartParser::new().parse("ᄀ"), Ok('ᄀ'));
-        assert_eq!(parser::XidStartParser::new().parse("ᄁ"), Ok('ᄁ'));
-        assert_eq!(parser::XidStartParser::new().parse("ᄊ"), Ok('ᄊ'));
-        assert_eq!(parser::XidStartParser::new().parse("ᇂ"), Ok('ᇂ'));
-        assert_eq!(parser::XidStartParser::new().parse("ᆱ"), Ok('ᆱ'));
-        assert_eq!(parser::XidStartParser::new().parse("æ"), Ok('æ'));
-        assert_eq!(parser::XidStartParser::new().parse("Ä"), Ok('Ä'));
-        assert_eq!(parser::XidStartParser::new().parse("é"), Ok('é'));
-        assert_eq!(parser::XidStartParser::new().parse("ç"), Ok('ç'));
-        assert_eq!(parser::XidStartParser::new().parse("â"), Ok('â'));
-    }
-
-    // [0-9A-Z_a-zªµ·ºÀ-ÖØ-ö ø-ˁˆ-ˑˠ-ˤˬˮ̀-ʹͶͷͻ-ͽͿΆ-Ί ΌΎ-ΡΣ-ϵϷ-ҁ҃-҇Ҋ-ԯԱ-Ֆՙՠ-ֈ֑-ׇֽֿׁׂׅׄ א-תׯ-ײؐ-ؚؠ-٩ٮ-ۓە-ۜ۟-۪ۨ-ۼ ۿܐ-݊ݍ-ޱ߀-ߵߺ߽ࠀ-࠭ࡀ-࡛ࡠ-ࡪ ࡰ-ࢇࢉ-ࢎ࢘-ࣣ࣡-ॣ०-९ॱ-ঃঅ-ঌ এঐও-নপ-রলশ-হ়-ৄেৈো-ৎৗ ড়ঢ়য়-ৣ০-ৱৼ৾ਁ-ਃਅ-ਊਏਐਓ-ਨ ਪ-ਰਲਲ਼ਵਸ਼ਸਹ਼ਾ-ੂੇੈੋ-੍ੑਖ਼-ੜ ਫ਼੦-ੵઁ-ઃઅ-ઍએ-ઑઓ-નપ-રલળ વ-હ઼-ૅે-ૉો-્ૐૠ-ૣ૦-૯ૹ-૿ଁ-ଃ ଅ-ଌଏଐଓ-ନପ-ରଲଳଵ-ହ଼-ୄେୈୋ-୍୕-ୗ ଡ଼ଢ଼ୟ-ୣ୦-୯ୱஂஃஅ-ஊஎ-ஐஒ-கங சஜஞடணதந-பம-ஹா-ூெ-ைொ-் ௐௗ௦-௯ఀ-ఌఎ-ఐఒ-నప-హ఼-ౄె-ైొ-్ౕౖ ౘ-ౚౝౠ-ౣ౦-౯ಀ-ಃಅ-ಌಎ-ಐಒ-ನ ಪ-ಳವ-ಹ಼-ೄೆ-ೈೊ-್ೕೖೝೞೠ-ೣ ೦-೯ೱ-ೳഀ-ഌഎ-ഐഒ-ൄെ-ൈൊ-ൎ ൔ-ൗൟ-ൣ൦-൯ൺ-ൿඁ-ඃඅ-ඖක-න ඳ-රලව-ෆ්ා-ුූෘ-ෟ෦-෯ෲෳก-ฺ เ-๎๐-๙ກຂຄຆ-ຊຌ-ຣລວ-ຽເ-ໄ ໆ່-໎໐-໙ໜ-ໟༀ༘༙༠-༩༹༵༷༾-ཇ ཉ-ཬཱ-྄྆-ྗྙ-ྼ࿆က-၉ၐ-ႝႠ-Ⴥ ჇჍა-ჺჼ-ቈቊ-ቍቐ-ቖቘቚ-ቝበ-ኈ ኊ-ኍነ-ኰኲ-ኵኸ-ኾዀዂ-ዅወ-ዖዘ-ጐ ጒ-ጕጘ-ፚ፝-፟፩-፱ᎀ-ᎏᎠ-Ᏽᏸ-ᏽ ᐁ-ᙬᙯ-ᙿᚁ-ᚚᚠ-ᛪᛮ-ᛸᜀ-᜕ᜟ-᜴ ᝀ-ᝓᝠ-ᝬᝮ-ᝰᝲᝳក-៓ៗៜ៝០-៩--᠙ ᠠ-ᡸᢀ-ᢪᢰ-ᣵᤀ-ᤞᤠ-ᤫᤰ-᤻᥆-ᥭ ᥰ-ᥴᦀ-ᦫᦰ-ᧉ᧐-᧚ᨀ-ᨛᨠ-ᩞ᩠-᩿᩼-᪉ ᪐-᪙ᪧ᪰-᪽ᪿ-ᫎᬀ-ᭌ᭐-᭙᭫-᭳ᮀ-᯳ ᰀ-᰷᱀-᱉ᱍ-ᱽᲀ-ᲈᲐ-ᲺᲽ-Ჿ᳐-᳔᳒-ᳺ ᴀ-ἕἘ-Ἕἠ-ὅὈ-Ὅὐ-ὗὙὛὝὟ-ώ ᾀ-ᾴᾶ-ᾼιῂ-ῄῆ-ῌῐ-ΐῖ-Ίῠ-Ῥ ῲ-ῴῶ-ῼ‿⁀⁔ⁱⁿₐ-ₜ⃐-⃥⃜⃡-⃰ ℂℇℊ-ℓℕ℘-ℝℤΩℨK-ℹℼ-ℿⅅ-ⅉ ⅎⅠ-ↈⰀ-ⳤⳫ-ⳳⴀ-ⴥⴧⴭⴰ-ⵧⵯ⵿-ⶖ ⶠ-ⶦⶨ-ⶮⶰ-ⶶⶸ-ⶾⷀ-ⷆⷈ-ⷎⷐ-ⷖ ⷘ-ⷞⷠ-ⷿ々-〇〡-〯〱-〵〸-〼ぁ-ゖ゙゚ ゝ-ゟァ-ヺー-ヿㄅ-ㄯㄱ-ㆎㆠ-ㆿㇰ-ㇿ 㐀-䶿一-ꒌꓐ-ꓽꔀ-ꘌꘐ-ꘫꙀ-꙯ꙴ-꙽ ꙿ-꛱ꜗ-ꜟꜢ-ꞈꞋ-ꟊꟐꟑꟓꟕ-ꟙꟲ-ꠧ꠬ ꡀ-ꡳꢀ-ꣅ꣐-꣙꣠-ꣷꣻꣽ-꤭ꤰ-꥓ꥠ-ꥼꦀ-꧀ ꧏ-꧙ꧠ-ꧾꨀ-ꨶꩀ-ꩍ꩐-꩙ꩠ-ꩶꩺ-ꫂ ꫛ-ꫝꫠ-ꫯꫲ-꫶ꬁ-ꬆꬉ-ꬎꬑ-ꬖꬠ-ꬦ ꬨ-ꬮꬰ-ꭚꭜ-ꭩꭰ-ꯪ꯬꯭꯰-꯹가-힣ힰ-ퟆ ퟋ-ퟻ豈-舘並-龎ﬀ-ﬆﬓ-ﬗיִ-ﬨשׁ-זּ טּ-לּמּנּסּףּפּצּ-ﮱﯓ-ﱝﱤ-ﴽﵐ-ﶏﶒ-ﷇ ﷰ-ﷹ-︠-︯︳︴﹍-﹏ﹱﹳﹷﹹﹻﹽﹿ-ﻼ ０-９Ａ-Ｚ＿ａ-ｚｦ-ﾾￂ-ￇￊ-ￏￒ-ￗ ￚ-ￜ𐀀-𐀋𐀍-𐀦𐀨-𐀺𐀼𐀽𐀿-𐁍𐁐-𐁝𐂀-𐃺 𐅀-𐅴𐇽𐊀-𐊜𐊠-𐋐𐋠𐌀-𐌟𐌭-𐍊𐍐-𐍺𐎀-𐎝 𐎠-𐏃𐏈-𐏏𐏑-𐏕𐐀-𐒝𐒠-𐒩𐒰-𐓓𐓘-𐓻 𐔀-𐔧𐔰-𐕣𐕰-𐕺𐕼-𐖊𐖌-𐖒𐖔𐖕𐖗-𐖡𐖣-𐖱 𐖳-𐖹𐖻𐖼𐘀-𐜶𐝀-𐝕𐝠-𐝧𐞀-𐞅𐞇-𐞰𐞲-𐞺 𐠀-𐠅𐠈𐠊-𐠵𐠷𐠸𐠼𐠿-𐡕𐡠-𐡶𐢀-𐢞𐣠-𐣲 𐣴𐣵𐤀-𐤕𐤠-𐤹𐦀-𐦷𐦾𐦿𐨀-𐨃𐨅𐨆𐨌-𐨓 𐨕-𐨗𐨙-𐨵𐨸-𐨿𐨺𐩠-𐩼𐪀-𐪜𐫀-𐫇𐫉-𐫦 𐬀-𐬵𐭀-𐭕𐭠-𐭲𐮀-𐮑𐰀-𐱈𐲀-𐲲𐳀-𐳲 𐴀-𐴧𐴰-𐴹𐺀-𐺩𐺫𐺬𐺰𐺱𐻽-𐼜𐼧𐼰-𐽐𐽰-𐾅 𐾰-𐿄𐿠-𐿶𑀀-𑁆𑁦-𑁵𑁿-𑂺𑃂𑃐-𑃨𑃰-𑃹𑄀-𑄴 𑄶-𑄿𑅄-𑅇𑅐-𑅳𑅶𑆀-𑇄𑇉-𑇌𑇎-𑇚𑇜𑈀-𑈑 𑈓-𑈷𑈾-𑉁𑊀-𑊆𑊈𑊊-𑊍𑊏-𑊝𑊟-𑊨𑊰-𑋪 𑋰-𑋹𑌀-𑌃𑌅-𑌌𑌏𑌐𑌓-𑌨𑌪-𑌰𑌲𑌳𑌵-𑌹𑌻-𑍄𑍇𑍈𑍋-𑍍 𑍐𑍗𑍝-𑍣𑍦-𑍬𑍰-𑍴𑐀-𑑊𑑐-𑑙𑑞-𑑡𑒀-𑓅 𑓇𑓐-𑓙𑖀-𑖵𑖸-𑗀𑗘-𑗝𑘀-𑙀𑙄𑙐-𑙙𑚀-𑚸 𑛀-𑛉𑜀-𑜚𑜝-𑜫𑜰-𑜹𑝀-𑝆𑠀-𑠺𑢠-𑣩 𑣿-𑤆𑤉𑤌-𑤓𑤕𑤖𑤘-𑤵𑤷𑤸𑤻-𑥃𑥐-𑥙𑦠-𑦧 𑦪-𑧗𑧚-𑧡𑧣𑧤𑨀-𑨾𑩇𑩐-𑪙𑪝𑪰-𑫸𑰀-𑰈 𑰊-𑰶𑰸-𑱀𑱐-𑱙𑱲-𑲏𑲒-𑲧𑲩-𑲶𑴀-𑴆 𑴈𑴉𑴋-𑴶𑴺𑴼𑴽𑴿-𑵇𑵐-𑵙𑵠-𑵥𑵧𑵨𑵪-𑶎𑶐𑶑𑶓-𑶘 𑶠-𑶩𑻠-𑻶𑼀-𑼐𑼒-𑼺𑼾-𑽂𑽐-𑽙𑾰𒀀-𒎙 𒐀-𒑮𒒀-𒕃𒾐-𒿰𓀀-𓐯𓑀-𓑕𔐀-𔙆𖠀-𖨸 𖩀-𖩞𖩠-𖩩𖩰-𖪾𖫀-𖫉𖫐-𖫭𖫰-𖫴𖬀-𖬶 𖭀-𖭃𖭐-𖭙𖭣-𖭷𖭽-𖮏𖹀-𖹿𖼀-𖽊𖽏-𖾇𖾏-𖾟 𖿠𖿡𖿣𖿤𖿰𖿱𗀀-𘟷𘠀-𘳕𘴀-𘴈𚿰-𚿳𚿵-𚿻 𚿽𚿾𛀀-𛄢𛄲𛅐-𛅒𛅕𛅤-𛅧𛅰-𛋻𛰀-𛱪𛱰-𛱼 𛲀-𛲈𛲐-𛲙𛲝𛲞𜼀-𜼭𜼰-𜽆𝅥-𝅩𝅭-𝅲𝅻-𝆂𝆅-𝆋𝆪-𝆭𝉂-𝉄 𝐀-𝑔𝑖-𝒜𝒞𝒟𝒢𝒥𝒦𝒩-𝒬𝒮-𝒹𝒻𝒽-𝓃 𝓅-𝔅𝔇-𝔊𝔍-𝔔𝔖-𝔜𝔞-𝔹𝔻-𝔾𝕀-𝕄 𝕆𝕊-𝕐𝕒-𝚥𝚨-𝛀𝛂-𝛚𝛜-𝛺𝛼-𝜔𝜖-𝜴 𝜶-𝝎𝝐-𝝮𝝰-𝞈𝞊-𝞨𝞪-𝟂𝟄-𝟋𝟎-𝟿𝨀-𝨶𝨻-𝩬𝩵𝪄𝪛-𝪟𝪡-𝪯 𝼀-𝼞𝼥-𝼪𞀀-𞀆𞀈-𞀘𞀛-𞀡𞀣𞀤𞀦-𞀪𞀰-𞁭𞂏 𞄀-𞄬𞄰-𞄽𞅀-𞅉𞅎𞊐-𞊮𞋀-𞋹𞓐-𞓹𞟠-𞟦 𞟨-𞟫𞟭𞟮𞟰-𞟾𞠀-𞣄𞣐-𞣖𞤀-𞥋𞥐-𞥙𞸀-𞸃 𞸅-𞸟𞸡𞸢𞸤𞸧𞸩-𞸲𞸴-𞸷𞸹𞸻𞹂𞹇𞹉𞹋𞹍-𞹏 𞹑𞹒𞹔𞹗𞹙𞹛𞹝𞹟𞹡𞹢𞹤𞹧-𞹪𞹬-𞹲𞹴-𞹷𞹹-𞹼 𞹾𞺀-𞺉𞺋-𞺛𞺡-𞺣𞺥-𞺩𞺫-𞺻🯰-🯹𠀀-𪛟 𪜀-𫜹𫝀-𫠝𫠠-𬺡𬺰-𮯠丽-𪘀𰀀-𱍊𱍐-𲎯-]
-    #[test]
-    fn test_lex_xid_continue() {
-        assert_eq!(parser::XidContinueParser::new().parse("a"), Ok('a'));
-        assert_eq!(parser::XidContinueParser::new().parse("_"), Ok('_'));
-        assert_eq!(parser::XidContinueParser::new().parse("в"), Ok('в'));
-        assert_eq!(parser::XidContinueParser::new().parse("भ"), Ok('भ'));
-        assert_eq!(parser::XidContinueParser::new().parse("त"), Ok('त'));
-        assert_eq!(parser::XidContinueParser::new().parse("र"), Ok('र'));
-        assert_eq!(parser::XidContinueParser::new().parse("П"), Ok('П'));
-        assert_eq!(parser::XidContinueParser::new().parse("и"), Ok('и'));
-        assert_eq!(parser::XidContinueParser::new().parse("в"), Ok('в'));
-        assert_eq!(parser::XidContinueParser::new().parse("е"), Ok('е'));
-        assert_eq!(parser::XidContinueParser::new().parse("т"), Ok('т'));
-        assert_eq!(parser::XidContinueParser::new().parse("ᄀ"), Ok('ᄀ'));
-        assert_eq!(parser::XidContinueParser::new().parse("ᄁ"), Ok('ᄁ'));
-        assert_eq!(parser::XidContinueParser::new().parse("ᄊ"), Ok('ᄊ'));
-        assert_eq!(parser::XidContinueParser::new().parse("ᇂ"), Ok('ᇂ'));
-        assert_eq!(parser::XidContinueParser::new().parse("ᆱ"), Ok('ᆱ'));
-        assert_eq!(parser::XidContinueParser::new().parse("æ"), Ok('æ'));
-        assert_eq!(parser::XidContinueParser::new().parse("Ä"), Ok('Ä'));
-        assert_eq!(parser::XidContinueParser::new().parse("é"), Ok('é'));
-        assert_eq!(parser::XidContinueParser::new().parse("ç"), Ok('ç'));
-        assert_eq!(parser::XidContinueParser::new().parse("â"), Ok('â'));
-    }
-
-    #[test]
-    fn test_lex_identifiers() {
         assert_eq!(
-            parser::IdentifierParser::new().parse("foo"),
-            Ok(Identifier {
-                name: String::from("foo"),
-            })
+            parser::FloatLiteralParser::new().parse(Lexer::new("12E+99_")),
+            Ok(Float::F64 { f: 12E+99 })
         );
         assert_eq!(
-            parser::IdentifierParser::new().parse("foo_bar"),
-            Ok(Identifier {
-                name: String::from("foo_bar"),
-            })
+            parser::FloatLiteralParser::new().parse(Lexer::new("12.")),
+            Ok(Float::F64 { f: 12.0 })
         );
+        // but not with an integer suffix, as in Rust
         assert_eq!(
-            parser::IdentifierParser::new().parse("_ident"),
-            Ok(Identifier {
-                name: String::from("_ident"),
+            parser::FloatLiteralParser::new().parse(Lexer::new("12u8.")),
+            Err(ParseError::User {
+                error: SuffixedFloatLiteral { start: 0, end: 5 },
             })
         );
         assert_eq!(
-            parser::IdentifierParser::new().parse("Москва"),
-            Ok(Identifier {
-                name: String::from("Москва"),
+            parser::FloatLiteralParser::new().parse(Lexer::new("1_0i32.")),
+            Err(ParseError::User {
+                error: SuffixedFloatLiteral { start: 0, end: 7 },
             })
         );
+
+        // DEC_LITERAL FLOAT_EXPONENT
         assert_eq!(
-            parser::IdentifierParser::new().parse("東京"),
-            Ok(Identifier {
-                name: String::from("東京"),
-            })
+            parser::FloatLiteralParser::new().parse(Lexer::new("1e+3")),
+            Ok(Float::F64 { f: 1e+3 })
         );
         assert_eq!(
-            parser::IdentifierParser::new().parse("المملكة"),
-            Ok(Identifier {
-                name: String::from("المملكة"),
-            })
+            parser::FloatLiteralParser::new().parse(Lexer::new("1E-3")),
+            Ok(Float::F64 { f: 1E-3 })
         );
         assert_eq!(
-            parser::IdentifierParser::new().parse("Привет"),
-            Ok(Identifier {
-                name: String::from("Привет"),
-            })
+            parser::FloatLiteralParser::new().parse(Lexer::new("1e123")),
+            Ok(Float::F64 { f: 1e123 })
         );
         assert_eq!(
-            parser::IdentifierParser::new().parse("你好世界"),
-            Ok(Identifier {
-                name: String::from("你好世界"),
-            })
+            parser::FloatLiteralParser::new().parse(Lexer::new("1e+_0_1_2")),
+            Ok(Float::F64 { f: 1e+012 })
         );
-    }
 
-    #[test]
-    fn test_lex_numeric_literals() {
-        // Integer Literal Prefix Terminals
+        // DEC_LITERAL (. DEC_LITERAL)? FLOAT_EXPONENT? FLOAT_SUFFIX
         assert_eq!(
-            parser::BinaryLiteralPrefixParser::new().parse("0b"),
-            Ok(String::from("0b"))
+            parser::FloatLiteralParser::new().parse(Lexer::new("2.5f32")),
+            Ok(Float::F32 { f: 2.5 })
         );
         assert_eq!(
-            parser::BinaryLiteralPrefixParser::new().parse("0B"),
-            Ok(String::from("0B"))
+            parser::FloatLiteralParser::new().parse(Lexer::new("1_000.0e-3f64")),
+            Ok(Float::F64 { f: 1.0 })
         );
+
+        // Invalid Float Exponents
         assert_eq!(
-            parser::OctalLiteralPrefixParser::new().parse("0o"),
-            Ok(String::from("0o"))
+            parser::FloatLiteralParser::new().parse(Lexer::new("1e+_")),
+            Err(ParseError::User {
+                error: InvalidFloatExponent,
+            })
         );
         assert_eq!(
-            parser::OctalLiteralPrefixParser::new().parse("0O"),
-            Ok(String::from("0O"))
+            parser::FloatLiteralParser::new().parse(Lexer::new("2.0E-_")),
+            Err(ParseError::User {
+                error: InvalidFloatExponent,
+            })
         );
+        assert!(parser::FloatLiteralParser::new()
+            .parse(Lexer::new("1e+"))
+            .is_err());
         assert_eq!(
-            parser::HexadecimalLiteralPrefixParser::new().parse("0x"),
-            Ok(String::from("0x"))
+            parser::FloatLiteralParser::new().parse(Lexer::new("0x1.8f32")),
+            Err(ParseError::User {
+                error: InvalidFloatExponent,
+            })
         );
         assert_eq!(
-            parser::HexadecimalLiteralPrefixParser::new().parse("0X"),
-            Ok(String::from("0X"))
+            parser::FloatLiteralParser::new().parse(Lexer::new("0x1p+_")),
+            Err(ParseError::User {
+                error: InvalidFloatExponent,
+            })
         );
 
-        // Integer Literal Suffix Terminals
-
-        // - Unsigned Integer Literal Suffix Terminals
-        assert_eq!(
-            parser::IntegerSuffixParser::new().parse("u8"),
-            Ok(String::from("u8"))
-        );
-        assert_eq!(
-            parser::IntegerSuffixParser::new().parse("u16"),
-            Ok(String::from("u16"))
-        );
+        // Suffixes
         assert_eq!(
-            parser::IntegerSuffixParser::new().parse("u32"),
-            Ok(String::from("u32"))
+            parser::FloatLiteralParser::new().parse(Lexer::new("1.5f32")),
+            Ok(Float::F32 { f: 1.5 })
         );
         assert_eq!(
-            parser::IntegerSuffixParser::new().parse("u64"),
-            Ok(String::from("u64"))
+            parser::FloatLiteralParser::new().parse(Lexer::new("2e10f64")),
+            Ok(Float::F64 { f: 2e10 })
         );
         assert_eq!(
-            parser::IntegerSuffixParser::new().parse("u128"),
-            Ok(String::from("u128"))
+            parser::FloatLiteralParser::new().parse(Lexer::new("0.1f32")),
+            Ok(Float::F32 { f: 0.1 })
         );
         assert_eq!(
-            parser::IntegerSuffixParser::new().parse("usize"),
-            Ok(String::from("usize"))
+            parser::FloatLiteralParser::new().parse(Lexer::new("1f32")),
+            Ok(Float::F32 { f: 1.0 })
         );
 
-        // - Signed Integer Literal Suffix Terminals
+        // HEX_FLOAT_LITERAL
         assert_eq!(
-            parser::IntegerSuffixParser::new().parse("i8"),
-            Ok(String::from("i8"))
+            parser::FloatLiteralParser::new().parse(Lexer::new("0x1.8p3")),
+            Ok(Float::F64 { f: 12.0 })
         );
         assert_eq!(
-            parser::IntegerSuffixParser::new().parse("i16"),
-            Ok(String::from("i16"))
+            parser::FloatLiteralParser::new().parse(Lexer::new("0X1P-2")),
+            Ok(Float::F64 { f: 0.25 })
         );
         assert_eq!(
-            parser::IntegerSuffixParser::new().parse("i32"),
-            Ok(String::from("i32"))
+            parser::FloatLiteralParser::new().parse(Lexer::new("0xA.Bp0f32")),
+            Ok(Float::F32 { f: 10.6875 })
         );
         assert_eq!(
-            parser::IntegerSuffixParser::new().parse("i64"),
-            Ok(String::from("i64"))
+            parser::FloatLiteralParser::new()
+                .parse(Lexer::new("0x1.fffffffffffffp1023")),
+            Ok(Float::F64 { f: f64::MAX })
         );
         assert_eq!(
-            parser::IntegerSuffixParser::new().parse("i128"),
-            Ok(String::from("i128"))
+            parser::FloatLiteralParser::new().parse(Lexer::new("0x1p1024")),
+            Ok(Float::F64 { f: f64::INFINITY })
         );
+
+        // Exponents far beyond the range of a float over- or underflow it
         assert_eq!(
-            parser::IntegerSuffixParser::new().parse("isize"),
-            Ok(String::from("isize"))
+            parser::FloatLiteralParser::new()
+                .parse(Lexer::new("0x1.8p-2147483647")),
+            Ok(Float::F64 { f: 0.0 })
         );
-
-        // Digit Terminals
-
-        // - Binary Digit Terminals
-        assert_eq!(parser::BinaryDigitParser::new().parse("0"), Ok('0'));
-        assert_eq!(parser::BinaryDigitParser::new().parse("1"), Ok('1'));
-
-        // - Octal Digit Terminals
-        assert_eq!(parser::OctalDigitParser::new().parse("0"), Ok('0'));
-        assert_eq!(parser::OctalDigitParser::new().parse("1"), Ok('1'));
-        assert_eq!(parser::OctalDigitParser::new().parse("2"), Ok('2'));
-        assert_eq!(parser::OctalDigitParser::new().parse("3"), Ok('3'));
-        assert_eq!(parser::OctalDigitParser::new().parse("4"), Ok('4'));
-        assert_eq!(parser::OctalDigitParser::new().parse("5"), Ok('5'));
-        assert_eq!(parser::OctalDigitParser::new().parse("6"), Ok('6'));
-        assert_eq!(parser::OctalDigitParser::new().parse("7"), Ok('7'));
-
-        // - Decimal Digit Terminals
-        assert_eq!(parser::DecimalDigitParser::new().parse("0"), Ok('0'));
-        assert_eq!(parser::DecimalDigitParser::new().parse("1"), Ok('1'));
-        assert_eq!(parser::DecimalDigitParser::new().parse("2"), Ok('2'));
-        assert_eq!(parser::DecimalDigitParser::new().parse("3"), Ok('3'));
-        assert_eq!(parser::DecimalDigitParser::new().parse("4"), Ok('4'));
-        assert_eq!(parser::DecimalDigitParser::new().parse("5"), Ok('5'));
-        assert_eq!(parser::DecimalDigitParser::new().parse("6"), Ok('6'));
-        assert_eq!(parser::DecimalDigitParser::new().parse("7"), Ok('7'));
-        assert_eq!(parser::DecimalDigitParser::new().parse("8"), Ok('8'));
-        assert_eq!(parser::DecimalDigitParser::new().parse("9"), Ok('9'));
-
-        // - Hexadecimal Digit Terminals
-        assert_eq!(parser::HexadecimalDigitParser::new().parse("0"), Ok('0'));
-        assert_eq!(parser::HexadecimalDigitParser::new().parse("1"), Ok('1'));
-        assert_eq!(parser::HexadecimalDigitParser::new().parse("2"), Ok('2'));
-        assert_eq!(parser::HexadecimalDigitParser::new().parse("3"), Ok('3'));
-        assert_eq!(parser::HexadecimalDigitParser::new().parse("4"), Ok('4'));
-        assert_eq!(parser::HexadecimalDigitParser::new().parse("5"), Ok('5'));
-        assert_eq!(parser::HexadecimalDigitParser::new().parse("6"), Ok('6'));
-        assert_eq!(parser::HexadecimalDigitParser::new().parse("7"), Ok('7'));
-        assert_eq!(parser::HexadecimalDigitParser::new().parse("8"), Ok('8'));
-        assert_eq!(parser::HexadecimalDigitParser::new().parse("9"), Ok('9'));
-        assert_eq!(parser::HexadecimalDigitParser::new().parse("a"), Ok('a'));
-        assert_eq!(parser::HexadecimalDigitParser::new().parse("b"), Ok('b'));
-        assert_eq!(parser::HexadecimalDigitParser::new().parse("c"), Ok('c'));
-        assert_eq!(parser::HexadecimalDigitParser::new().parse("d"), Ok('d'));
-        assert_eq!(parser::HexadecimalDigitParser::new().parse("e"), Ok('e'));
-        assert_eq!(parser::HexadecimalDigitParser::new().parse("f"), Ok('f'));
-        assert_eq!(parser::HexadecimalDigitParser::new().parse("A"), Ok('A'));
-        assert_eq!(parser::HexadecimalDigitParser::new().parse("B"), Ok('B'));
-        assert_eq!(parser::HexadecimalDigitParser::new().parse("C"), Ok('C'));
-        assert_eq!(parser::HexadecimalDigitParser::new().parse("D"), Ok('D'));
-        assert_eq!(parser::HexadecimalDigitParser::new().parse("E"), Ok('E'));
-        assert_eq!(parser::HexadecimalDigitParser::new().parse("F"), Ok('F'));
-
-        /////////////////////////////
-        // Floating Point Literals //
-        /////////////////////////////
-
-        // Float Literal Suffix Terminals
         assert_eq!(
-            parser::FloatSuffixParser::new().parse("f32"),
-            Ok(String::from("f32"))
+            parser::FloatLiteralParser::new().parse(Lexer::new("0x1.8p2147483647")),
+            Ok(Float::F64 { f: f64::INFINITY })
         );
         assert_eq!(
-            parser::FloatSuffixParser::new().parse("f64"),
-            Ok(String::from("f64"))
+            parser::FloatLiteralParser::new()
+                .parse(Lexer::new("1.25e-99999999999")),
+            Ok(Float::F64 { f: 0.0 })
         );
 
-        // Float Literal Exponent Symbol Terminals
-        assert_eq!(parser::ExponentialSymbolParser::new().parse("e"), Ok('e'));
-        assert_eq!(parser::ExponentialSymbolParser::new().parse("E"), Ok('E'));
-    }
-
-    #[test]
-    fn test_lex_mathematical_constants() {
-        ////////////////////////////
-        // Mathematical Constants //
-        ////////////////////////////
-
-        // Pi Terminals (π)
+        // A decimal point must be followed by a digit (or nothing at all)
         assert_eq!(
-            parser::PiParser::new().parse("pi"),
-            Ok(MathematicalConstant::Pi {
-                value: std::f64::consts::PI,
+            parser::FloatLiteralParser::new().parse(Lexer::new("1._5")),
+            Err(ParseError::User {
+                error: InvalidFloatLiteral { start: 0, end: 3 },
             })
         );
         assert_eq!(
-            parser::PiParser::new().parse("π"),
-            Ok(MathematicalConstant::Pi {
-                value: std::f64::consts::PI,
+            parser::FloatLiteralParser::new().parse(Lexer::new("1.foo")),
+            Err(ParseError::User {
+                error: InvalidFloatLiteral { start: 0, end: 3 },
             })
         );
         assert_eq!(
-            parser::PiParser::new().parse("𝜋"),
-            Ok(MathematicalConstant::Pi {
-                value: std::f64::consts::PI,
+            parser::FloatLiteralParser::new().parse(Lexer::new("1.e5")),
+            Err(ParseError::User {
+                error: InvalidFloatLiteral { start: 0, end: 3 },
             })
         );
 
-        // Euler's Number Terminals (e)
+        // Exact float values, kept by a Lexer which keeps exact floats
+        let exact = |source| Lexer::new(source).exact_floats(true);
         assert_eq!(
-            parser::EulerParser::new().parse("ℯ"),
-            Ok(MathematicalConstant::Euler {
-                value: std::f64::consts::E,
+            parser::FloatLiteralParser::new().parse(exact("0.1")),
+            Ok(Float::Exact {
+                value: rug::Rational::from((1, 10))
             })
         );
         assert_eq!(
-            parser::EulerParser::new().parse("euler"),
-            Ok(MathematicalConstant::Euler {
-                value: std::f64::consts::E,
+            parser::FloatLiteralParser::new()
+                .parse(exact("1_234.5e-3f32")),
+            Ok(Float::Exact {
+                value: rug::Rational::from((12_345, 10_000))
             })
         );
-
-        // Tau Terminals (τ)
         assert_eq!(
-            parser::TauParser::new().parse("tau"),
-            Ok(MathematicalConstant::Tau {
-                value: std::f64::consts::TAU,
+            parser::FloatLiteralParser::new().parse(exact("2.5e2")),
+            Ok(Float::Exact {
+                value: rug::Rational::from(250)
             })
         );
         assert_eq!(
-            parser::TauParser::new().parse("τ"),
-            Ok(MathematicalConstant::Tau {
-                value: std::f64::consts::TAU,
+            parser::FloatLiteralParser::new().parse(exact("12.")),
+            Ok(Float::Exact {
+                value: rug::Rational::from(12)
             })
         );
         assert_eq!(
-            parser::TauParser::new().parse("𝜏"),
-            Ok(MathematicalConstant::Tau {
-                value: std::f64::consts::TAU,
+            parser::FloatLiteralParser::new().parse(exact("12usize.")),
+            Err(ParseError::User {
+                error: SuffixedFloatLiteral { start: 0, end: 8 },
             })
         );
-
-        // Catalan's Constant Terminals (γ)
         assert_eq!(
-            parser::CatalanParser::new().parse("catalan"),
-            Ok(MathematicalConstant::Catalan {
-                value: 0.91596559417721901505460351493238411077414937428167,
+            parser::FloatLiteralParser::new().parse(exact("0x1.8p-3")),
+            Ok(Float::Exact {
+                value: rug::Rational::from((3, 16))
             })
         );
-
-        // Golden Ratio Terminals (φ)
         assert_eq!(
-            parser::GoldenRatioParser::new().parse("golden"),
-            Ok(MathematicalConstant::GoldenRatio {
-                value: 1.618033988749895,
+            parser::FloatLiteralParser::new()
+                .parse(exact("0.00e-2147483647")),
+            Ok(Float::Exact {
+                value: rug::Rational::new()
             })
         );
         assert_eq!(
-            parser::GoldenRatioParser::new().parse("φ"),
-            Ok(MathematicalConstant::GoldenRatio {
-                value: 1.618033988749895,
+            parser::FloatLiteralParser::new()
+                .parse(exact("0x0.0p-99999999999")),
+            Ok(Float::Exact {
+                value: rug::Rational::new()
             })
         );
         assert_eq!(
-            parser::GoldenRatioParser::new().parse("𝜙"),
-            Ok(MathematicalConstant::GoldenRatio {
-                value: 1.618033988749895,
+            parser::FloatLiteralParser::new()
+                .parse(exact("0.1000000000000000000000000000000000000001")),
+            Ok(Float::Exact {
+                value: rug::Rational::from((
+                    rug::Integer::from(10).pow(39) + 1,
+                    rug::Integer::from(10).pow(40)
+                ))
             })
         );
 
-        // Euler Mascheroni Constant Terminals (γ)
         assert_eq!(
-            parser::EulerMascheroniParser::new().parse("eulermascheroni"),
-            Ok(MathematicalConstant::EulerMascheroni {
-                value: 0.5772156649015329,
-            })
+            parser::LiteralExpressionParser::new().parse(Lexer::new("2.5")),
+            Ok(LiteralExpression::NumericLiteral(NumericLiteral::Float(
+                Float::F64 { f: 2.5 }
+            )))
         );
+    }
+
+    #[test]
+    fn test_parse_quantity_literals() {
+        fn unit(symbol: &str) -> Unit {
+            Unit::from_symbol(symbol).unwrap()
+        }
+        fn parse(
+            source: &str,
+        ) -> Result<
+            LiteralExpression,
+            ParseError<usize, lexer::Token<'_>, parser_errors::InternalParserError>,
+        > {
+            parser::LiteralExpressionParser::new().parse(Lexer::new(source))
+        }
+
         assert_eq!(
-            parser::EulerMascheroniParser::new().parse("eulergamma"),
-            Ok(MathematicalConstant::EulerMascheroni {
-                value: 0.5772156649015329,
+            parse("9.81 m/s²"),
+            Ok(LiteralExpression::Quantity {
+                value: NumericLiteral::Float(Float::F64 { f: 9.81 }),
+                unit: unit("m")
+                    .checked_div(unit("s").checked_powi(2).unwrap())
+                    .unwrap(),
             })
         );
         assert_eq!(
-            parser::EulerMascheroniParser::new().parse("γ"),
-            Ok(MathematicalConstant::EulerMascheroni {
-                value: 0.5772156649015329,
+            parse("3 kg·m"),
+            Ok(LiteralExpression::Quantity {
+                value: NumericLiteral::Integer(Integer::I32 { n: 3 }),
+                unit: unit("kg").checked_mul(unit("m")).unwrap(),
             })
         );
         assert_eq!(
-            parser::EulerMascheroniParser::new().parse("𝛾"),
-            Ok(MathematicalConstant::EulerMascheroni {
-                value: 0.5772156649015329,
+            parse("3kg⋅m⁻¹"),
+            Ok(LiteralExpression::Quantity {
+                value: NumericLiteral::Integer(Integer::I32 { n: 3 }),
+                unit: unit("kg")
+                    .checked_mul(unit("m").checked_powi(-1).unwrap())
+                    .unwrap(),
             })
         );
-
-        // Infinity Terminals (∞)
         assert_eq!(
-            parser::InfinityParser::new().parse("Inf"),
-            Ok(MathematicalConstant::Infinity {
-                value: std::f64::INFINITY,
+            parse("1 kg·m²/s²"),
+            Ok(LiteralExpression::Quantity {
+                value: NumericLiteral::Integer(Integer::I32 { n: 1 }),
+                unit: unit("kg")
+                    .checked_mul(unit("m").checked_powi(2).unwrap())
+                    .and_then(|numerator| numerator
+                        .checked_div(unit("s").checked_powi(2)?))
+                    .unwrap(),
             })
         );
-        assert_eq!(
-            parser::InfinityParser::new().parse("∞"),
-            Ok(MathematicalConstant::Infinity {
-                value: std::f64::INFINITY,
-            })
+
+        // Prefixed and derived units
+        let kilometre = match parse("5 km") {
+            Ok(LiteralExpression::Quantity { unit, .. }) => unit,
+            result => panic!("expected a quantity, found {:?}", result),
+        };
+        assert_eq!(kilometre.scale, 1000.0);
+        assert_eq!(kilometre.dimension, unit("m").dimension);
+        let newton = match parse("2 N") {
+            Ok(LiteralExpression::Quantity { unit, .. }) => unit,
+            result => panic!("expected a quantity, found {:?}", result),
+        };
+        assert_eq!(newton.dimension.to_string(), "kg·m·s⁻²");
+        assert_eq!(
+            newton.dimension,
+            unit("kg")
+                .checked_mul(unit("m"))
+                .and_then(
+                    |numerator| numerator.checked_div(unit("s").checked_powi(2)?)
+                )
+                .unwrap()
+                .dimension
         );
 
-        // Not a Number Terminals (NaN)
-        assert_eq!(
-            parser::NotANumberParser::new().parse("NaN"),
-            Ok(MathematicalConstant::NotANumber)
+        // Units are written without spaces, and only consist of known unit
+        // symbols
+        assert!(matches!(
+            parse("6 m / s"),
+            Err(ParseError::UnrecognizedToken {
+                token: (4, lexer::Token::Punctuation(Punctuation::Slash), 5),
+                ..
+            })
+        ));
+        assert_eq!(
+            parse("3 apples"),
+            Err(ParseError::UnrecognizedToken {
+                token: (2, lexer::Token::Identifier("apples"), 8),
+                expected: vec![
+                    String::from("\".\""),
+                    String::from("\"unit symbol\"")
+                ],
+            })
         );
+
+        // The exponents of a unit must fit in a byte
+        let out_of_range = |start, end| {
+            Err(ParseError::User {
+                error: parser_errors::InternalParserError::UnitExponentOutOfRange {
+                    start,
+                    end,
+                },
+            })
+        };
+        assert_eq!(parse("1 m¹²⁷·m"), out_of_range(2, 13));
+        assert_eq!(parse("1 m¹⁰⁰·m¹⁰⁰"), out_of_range(2, 22));
+        assert_eq!(parse("1 m⁻¹²⁷/m¹⁰"), out_of_range(2, 20));
+        assert_eq!(parse("1 N¹⁰⁰"), out_of_range(2, 11));
+        assert!(parse("1 m¹²⁷").is_ok());
     }
 
     #[test]
-    fn test_lex_superscript_numeric_literals() {
-        // SuperscriptIntegerLiteral
-        assert_eq!(
-            parser::SuperscriptIntegerLiteralParser::new().parse("¹"),
-            Ok(SuperscriptIntegerLiteral { n: 1 })
-        );
+    fn test_parse_rational_literals() {
+        fn parse(
+            source: &str,
+        ) -> Result<
+            rug::Rational,
+            ParseError<usize, lexer::Token<'_>, parser_errors::InternalParserError>,
+        > {
+            parser::RationalLiteralParser::new().parse(Lexer::new(source))
+        }
+        fn rational(numerator: i32, denominator: i32) -> rug::Rational {
+            rug::Rational::from((numerator, denominator))
+        }
+
+        assert_eq!(parse("3/4r"), Ok(rational(3, 4)));
+        assert_eq!(parse("6/8r"), Ok(rational(3, 4)));
+        assert_eq!(parse("4/2r"), Ok(rational(2, 1)));
+        assert_eq!(parse("1_000/3r"), Ok(rational(1000, 3)));
         assert_eq!(
-            parser::SuperscriptIntegerLiteralParser::new().parse("¹²"),
-            Ok(SuperscriptIntegerLiteral { n: 12 })
+            parse("1/340282366920938463463374607431768211456r"),
+            Ok(rug::Rational::from((1, rug::Integer::from(2).pow(128))))
         );
+        assert_eq!(parse("0/5r"), Ok(rational(0, 1)));
 
-        // SuperscriptDecimalDigit = "⁰" | "¹" | "²" | "³" | "⁴" | "⁵" | "⁶" | "⁷" |
-        // "⁸" | "⁹"
+        // Vulgar fractions and mixed numbers
+        assert_eq!(parse("¾"), Ok(rational(3, 4)));
+        assert_eq!(parse("⅒"), Ok(rational(1, 10)));
+        assert_eq!(parse("↉"), Ok(rational(0, 1)));
+        assert_eq!(parse("1½"), Ok(rational(3, 2)));
+        assert_eq!(parse("12⅔"), Ok(rational(38, 3)));
+
+        // Rational literals are numeric literals, and may have a unit
         assert_eq!(
-            parser::SuperscriptDecimalDigitParser::new().parse("⁰"),
-            Ok(SuperscriptDecimalDigit { digit: 0 })
+            parser::LiteralExpressionParser::new().parse(Lexer::new("3/4r")),
+            Ok(LiteralExpression::NumericLiteral(NumericLiteral::Rational(
+                rational(3, 4)
+            )))
         );
         assert_eq!(
-            parser::SuperscriptDecimalDigitParser::new().parse("¹"),
-            Ok(SuperscriptDecimalDigit { digit: 1 })
+            parser::LiteralExpressionParser::new().parse(Lexer::new("1½ h")),
+            Ok(LiteralExpression::Quantity {
+                value: NumericLiteral::Rational(rational(3, 2)),
+                unit: Unit::from_symbol("h").unwrap(),
+            })
         );
+
+        // Zero denominators
         assert_eq!(
-            parser::SuperscriptDecimalDigitParser::new().parse("²"),
-            Ok(SuperscriptDecimalDigit { digit: 2 })
+            parse("1/0r"),
+            Err(ParseError::User {
+                error: ZeroDenominator { start: 0, end: 4 }
+            })
         );
         assert_eq!(
-            parser::SuperscriptDecimalDigitParser::new().parse("³"),
-            Ok(SuperscriptDecimalDigit { digit: 3 })
+            parse("0/0_0r"),
+            Err(ParseError::User {
+                error: ZeroDenominator { start: 0, end: 6 }
+            })
         );
-        assert_eq!(
-            parser::SuperscriptDecimalDigitParser::new().parse("⁴"),
-            Ok(SuperscriptDecimalDigit { digit: 4 })
+    }
+
+    #[test]
+    fn test_parse_imaginary_literals() {
+        fn parse(
+            source: &str,
+        ) -> Result<
+            rug::Rational,
+            ParseError<usize, lexer::Token<'_>, parser_errors::InternalParserError>,
+        > {
+            parser::ImaginaryLiteralParser::new().parse(Lexer::new(source))
+        }
+
+        assert_eq!(parse("2i"), Ok(rug::Rational::from(2)));
+        assert_eq!(parse("1.5𝑖"), Ok(rug::Rational::from((3, 2))));
+        assert_eq!(parse("0.1i"), Ok(rug::Rational::from((1, 10))));
+        assert_eq!(parse("1_000i"), Ok(rug::Rational::from(1000)));
+        assert_eq!(parse("2.5e2i"), Ok(rug::Rational::from(250)));
+        assert_eq!(parse("𝑖"), Ok(rug::Rational::from(1)));
+        assert_eq!(
+            parse("1e_i"),
+            Err(ParseError::User {
+                error: InvalidFloatExponent
+            })
         );
+        // Exact values are capped like exact powers, rather than computed
         assert_eq!(
-            parser::SuperscriptDecimalDigitParser::new().parse("⁵"),
-            Ok(SuperscriptDecimalDigit { digit: 5 })
+            parse("1e999999999i"),
+            Err(ParseError::User {
+                error: FloatLiteralOutOfRange { start: 0, end: 12 }
+            })
         );
         assert_eq!(
-            parser::SuperscriptDecimalDigitParser::new().parse("⁶"),
-            Ok(SuperscriptDecimalDigit { digit: 6 })
+            parse("1.5e-999999999i"),
+            Err(ParseError::User {
+                error: FloatLiteralOutOfRange { start: 0, end: 15 }
+            })
         );
+        assert_eq!(parse("0e999999999i"), Ok(rug::Rational::new()));
         assert_eq!(
-            parser::SuperscriptDecimalDigitParser::new().parse("⁷"),
-            Ok(SuperscriptDecimalDigit { digit: 7 })
+            parse("1e300000i"),
+            Ok(rug::Rational::from(rug::Integer::from(10).pow(300_000)))
         );
+
         assert_eq!(
-            parser::SuperscriptDecimalDigitParser::new().parse("⁸"),
-            Ok(SuperscriptDecimalDigit { digit: 8 })
+            parser::LiteralExpressionParser::new().parse(Lexer::new("4i")),
+            Ok(LiteralExpression::NumericLiteral(
+                NumericLiteral::Imaginary(rug::Rational::from(4))
+            ))
         );
+        // Integer suffixes aren't imaginary
         assert_eq!(
-            parser::SuperscriptDecimalDigitParser::new().parse("⁹"),
-            Ok(SuperscriptDecimalDigit { digit: 9 })
+            parser::LiteralExpressionParser::new().parse(Lexer::new("2i8")),
+            Ok(LiteralExpression::NumericLiteral(NumericLiteral::Integer(
+                Integer::I8 { n: 2 }
+            )))
         );
     }
 
     #[test]
-    fn test_lex_superscript_punctuation() {
-        // SuperscriptPunctuation = "⁺" | "⁻" | "⁽" | "⁾"
+    fn test_parse_mathematical_constants() {
         assert_eq!(
-            parser::SuperscriptPunctuationParser::new().parse("⁺"),
-            Ok(SuperscriptPunctuation::Plus)
+            parser::MathematicalConstantParser::new().parse(Lexer::new("π")),
+            Ok(MathematicalConstant::Pi {
+                value: std::f64::consts::PI,
+                digits: None,
+            })
         );
         assert_eq!(
-            parser::SuperscriptPunctuationParser::new().parse("⁻"),
-            Ok(SuperscriptPunctuation::Minus)
+            parser::MathematicalConstantParser::new().parse(Lexer::new("π_100")),
+            Ok(MathematicalConstant::Pi {
+                value: std::f64::consts::PI,
+                digits: Some(100),
+            })
         );
         assert_eq!(
-            parser::SuperscriptPunctuationParser::new().parse("⁽"),
-            Ok(SuperscriptPunctuation::LeftParen)
+            parser::MathematicalConstantParser::new()
+                .parse(Lexer::new("catalan_30")),
+            Ok(MathematicalConstant::Catalan {
+                value: 0.915_965_594_177_219,
+                digits: Some(30),
+            })
         );
         assert_eq!(
-            parser::SuperscriptPunctuationParser::new().parse("⁾"),
-            Ok(SuperscriptPunctuation::RightParen)
+            parser::LiteralExpressionParser::new().parse(Lexer::new("∞")),
+            Ok(LiteralExpression::NumericLiteral(
+                NumericLiteral::MathematicalConstant(
+                    MathematicalConstant::Infinity {
+                        value: f64::INFINITY,
+                    }
+                )
+            ))
         );
+
+        // A precision suffix requests between 1 and `MAX_DIGITS` digits
+        let invalid = |start, end| {
+            Err(ParseError::User {
+                error: InvalidConstantPrecision { start, end },
+            })
+        };
+        let parse = |source| {
+            parser::MathematicalConstantParser::new().parse(Lexer::new(source))
+        };
+        assert_eq!(parse("π_0"), invalid(0, 4));
+        assert_eq!(parse("catalan_0"), invalid(0, 9));
+        assert_eq!(parse("pi_4000000000"), invalid(0, 13));
+        assert_eq!(parse("pi_99999999999"), invalid(0, 14));
+        assert_eq!(parse("pi_10001"), invalid(0, 8));
+        assert!(parse("pi_10000").is_ok());
+
+        // Constants computed to the requested number of digits
+        fn digits(source: &str) -> String {
+            let constant = parser::MathematicalConstantParser::new()
+                .parse(Lexer::new(source))
+                .unwrap();
+            let digits = match constant {
+                MathematicalConstant::Pi { digits, .. }
+                | MathematicalConstant::Euler { digits, .. }
+                | MathematicalConstant::EulerMascheroni { digits, .. }
+                | MathematicalConstant::Tau { digits, .. }
+                | MathematicalConstant::Catalan { digits, .. }
+                | MathematicalConstant::GoldenRatio { digits, .. } => {
+                    digits.unwrap()
+                }
+                _ => unreachable!(),
+            };
+            constant
+                .to_digits(digits)
+                .to_string_radix(10, Some(digits as usize))
+        }
+        assert_eq!(digits("pi_40"), "3.141592653589793238462643383279502884197");
+        assert_eq!(
+            digits("π_100"),
+            "3.141592653589793238462643383279502884197169399375105820974944592307816406286208998628034825342117068"
+        );
+        assert_eq!(digits("τ_30"), "6.28318530717958647692528676656");
+        assert_eq!(digits("ℯ_30"), "2.71828182845904523536028747135");
+        assert_eq!(digits("γ_30"), "5.77215664901532860606512090082e-1");
+        assert_eq!(digits("φ_30"), "1.61803398874989484820458683437");
+        assert_eq!(digits("catalan_30"), "9.15965594177219015054603514932e-1");
+        assert!(MathematicalConstant::NotANumber.to_digits(10).is_nan());
+        assert!(MathematicalConstant::Infinity {
+            value: f64::INFINITY
+        }
+        .to_digits(10)
+        .is_infinite());
     }
 
-    #[test]
-    fn test_lex_subscript_symbols() {}
+    // #[test]
+    // fn test_parse_boolean_literal_expression() {
+    //     let ast =
+    // parser::LiteralExpressionParser::new().parse("true").unwrap();
+    //     assert_eq!(ast, ast::LiteralExpression::Boolean { b: true });
+
+    //     let ast =
+    // parser::LiteralExpressionParser::new().parse("false").unwrap();
+    //     assert_eq!(ast, ast::LiteralExpression::Boolean { b: false });
+    // }
+}
+
+/////////////////////////////////////////////////////////////
+/// Verifies the correctness of the Logos lexer when it    //
+/// comes to lexing the source code.                       //
+///                                                        //
+/// Each test lexes a snippet of source code and asserts   //
+/// the kinds of the tokens produced along with their      //
+/// byte spans, independently of the parser. A handful of  //
+/// composite symbols (e.g. `¹²√`), which are assembled by //
+/// the parser from multiple tokens, are verified through  //
+/// their corresponding nonterminals.                      //
+/////////////////////////////////////////////////////////////
+#[cfg(test)]
+mod lexer_test_suite {
+    use super::*;
+    use crate::{
+        lexer::{Lexer, Token},
+        parser_errors::InternalParserError,
+    };
+    use logos::{Logos, Span};
+    use pretty_assertions::assert_eq;
+
+    /// Lexes the given source code into its tokens and their spans.
+    fn lex(source: &str) -> Vec<(Token<'_>, Span)> {
+        Token::lexer(source).spanned().collect()
+    }
 
     #[test]
-    fn test_lex_mathematical_symbols() {
-        // Powers (e.g. 2²)
+    fn test_lex_punctuation() {
         assert_eq!(
-            parser::MathematicalSymbolParser::new().parse("¹²"),
-            Ok(MathematicalSymbol::Power { exponent: 12 })
+            lex("+"),
+            vec![(Token::Punctuation(Punctuation::Plus), 0..1)]
         );
         assert_eq!(
-            parser::MathematicalSymbolParser::new().parse("⁻⁵³"),
-            Ok(MathematicalSymbol::Power { exponent: -53 })
+            lex("-"),
+            vec![(Token::Punctuation(Punctuation::Minus), 0..1)]
         );
         assert_eq!(
-            parser::MathematicalSymbolParser::new().parse("⁺⁵³"),
-            Ok(MathematicalSymbol::Power { exponent: 53 })
+            lex("*"),
+            vec![(Token::Punctuation(Punctuation::Star), 0..1)]
+        );
+        assert_eq!(
+            lex("/"),
+            vec![(Token::Punctuation(Punctuation::Slash), 0..1)]
+        );
+        assert_eq!(
+            lex("\\"),
+            vec![(Token::Punctuation(Punctuation::Backslash), 0..1)]
+        );
+        assert_eq!(
+            lex("%"),
+            vec![(Token::Punctuation(Punctuation::Percent), 0..1)]
+        );
+        assert_eq!(
+            lex("^"),
+            vec![(Token::Punctuation(Punctuation::Caret), 0..1)]
+        );
+        assert_eq!(lex("!"), vec![(Token::Punctuation(Punctuation::Not), 0..1)]);
+        assert_eq!(
+            lex("not"),
+            vec![(Token::Punctuation(Punctuation::Not), 0..3)]
+        );
+        assert_eq!(lex("&"), vec![(Token::Punctuation(Punctuation::And), 0..1)]);
+        assert_eq!(lex("|"), vec![(Token::Punctuation(Punctuation::Or), 0..1)]);
+        assert_eq!(
+            lex("&&"),
+            vec![(Token::Punctuation(Punctuation::AndAnd), 0..2)]
+        );
+        assert_eq!(
+            lex("||"),
+            vec![(Token::Punctuation(Punctuation::OrOr), 0..2)]
+        );
+        assert_eq!(
+            lex("+="),
+            vec![(Token::Punctuation(Punctuation::PlusEquals), 0..2)]
+        );
+        assert_eq!(
+            lex("-="),
+            vec![(Token::Punctuation(Punctuation::MinusEquals), 0..2)]
+        );
+        assert_eq!(
+            lex("*="),
+            vec![(Token::Punctuation(Punctuation::StarEquals), 0..2)]
+        );
+        assert_eq!(
+            lex("/="),
+            vec![(Token::Punctuation(Punctuation::SlashEquals), 0..2)]
+        );
+        assert_eq!(
+            lex("%="),
+            vec![(Token::Punctuation(Punctuation::PercentEquals), 0..2)]
+        );
+        assert_eq!(
+            lex("^="),
+            vec![(Token::Punctuation(Punctuation::CaretEquals), 0..2)]
+        );
+        assert_eq!(
+            lex("&="),
+            vec![(Token::Punctuation(Punctuation::AndEquals), 0..2)]
+        );
+        assert_eq!(
+            lex("|="),
+            vec![(Token::Punctuation(Punctuation::OrEquals), 0..2)]
+        );
+        assert_eq!(
+            lex("="),
+            vec![(Token::Punctuation(Punctuation::Equals), 0..1)]
+        );
+        assert_eq!(
+            lex("=="),
+            vec![(Token::Punctuation(Punctuation::DoubleEquals), 0..2)]
+        );
+        assert_eq!(
+            lex("!="),
+            vec![(Token::Punctuation(Punctuation::NotEqual), 0..2)]
+        );
+        assert_eq!(
+            lex("≠"),
+            vec![(Token::Punctuation(Punctuation::NotEqual), 0..3)]
+        );
+        assert_eq!(
+            lex("<"),
+            vec![(Token::Punctuation(Punctuation::LessThan), 0..1)]
+        );
+        assert_eq!(
+            lex("<="),
+            vec![(Token::Punctuation(Punctuation::LessThanEqual), 0..2)]
+        );
+        assert_eq!(
+            lex("≤"),
+            vec![(Token::Punctuation(Punctuation::LessThanEqual), 0..3)]
+        );
+        assert_eq!(
+            lex(">"),
+            vec![(Token::Punctuation(Punctuation::GreaterThan), 0..1)]
+        );
+        assert_eq!(
+            lex(">="),
+            vec![(Token::Punctuation(Punctuation::GreaterThanEqual), 0..2)]
+        );
+        assert_eq!(
+            lex("≥"),
+            vec![(Token::Punctuation(Punctuation::GreaterThanEqual), 0..3)]
+        );
+        assert_eq!(
+            lex("_"),
+            vec![(Token::Punctuation(Punctuation::Underscore), 0..1)]
+        );
+        assert_eq!(lex("."), vec![(Token::Punctuation(Punctuation::Dot), 0..1)]);
+        assert_eq!(
+            lex(".."),
+            vec![(Token::Punctuation(Punctuation::DotDot), 0..2)]
+        );
+        assert_eq!(
+            lex("..."),
+            vec![(Token::Punctuation(Punctuation::DotDotDot), 0..3)]
+        );
+        assert_eq!(
+            lex("..="),
+            vec![(Token::Punctuation(Punctuation::DotDotEquals), 0..3)]
+        );
+        assert_eq!(
+            lex(","),
+            vec![(Token::Punctuation(Punctuation::Comma), 0..1)]
+        );
+        assert_eq!(
+            lex(";"),
+            vec![(Token::Punctuation(Punctuation::Semicolon), 0..1)]
+        );
+        assert_eq!(
+            lex(":"),
+            vec![(Token::Punctuation(Punctuation::Colon), 0..1)]
+        );
+        assert_eq!(
+            lex("::"),
+            vec![(Token::Punctuation(Punctuation::PathSeparator), 0..2)]
+        );
+        assert_eq!(
+            lex("->"),
+            vec![(Token::Punctuation(Punctuation::RightArrow), 0..2)]
+        );
+        assert_eq!(
+            lex("→"),
+            vec![(Token::Punctuation(Punctuation::RightArrow), 0..3)]
+        );
+        assert_eq!(
+            lex("<-"),
+            vec![(Token::Punctuation(Punctuation::LeftArrow), 0..2)]
+        );
+        assert_eq!(
+            lex("←"),
+            vec![(Token::Punctuation(Punctuation::LeftArrow), 0..3)]
+        );
+        assert_eq!(
+            lex("=>"),
+            vec![(Token::Punctuation(Punctuation::FatRightArrow), 0..2)]
+        );
+        assert_eq!(
+            lex("⇒"),
+            vec![(Token::Punctuation(Punctuation::FatRightArrow), 0..3)]
+        );
+        assert_eq!(
+            lex("⇐"),
+            vec![(Token::Punctuation(Punctuation::FatLeftArrow), 0..3)]
+        );
+        assert_eq!(
+            lex("#"),
+            vec![(Token::Punctuation(Punctuation::Pound), 0..1)]
+        );
+        assert_eq!(
+            lex("$"),
+            vec![(Token::Punctuation(Punctuation::Dollar), 0..1)]
+        );
+        assert_eq!(
+            lex("?"),
+            vec![(Token::Punctuation(Punctuation::Question), 0..1)]
+        );
+        assert_eq!(
+            lex("'"),
+            vec![(Token::Punctuation(Punctuation::Apostrophe), 0..1)]
+        );
+        assert_eq!(
+            lex("\""),
+            vec![(Token::Punctuation(Punctuation::Quote), 0..1)]
         );
+    }
 
-        // Roots (e.g. ²√4 = 2)
+    #[test]
+    fn test_lex_delimiters() {
         assert_eq!(
-            parser::MathematicalSymbolParser::new().parse("√"),
-            Ok(MathematicalSymbol::Root { exponent: 2 })
+            lex("("),
+            vec![(Token::Delimiter(Delimiter::LeftParen), 0..1)]
         );
         assert_eq!(
-            parser::MathematicalSymbolParser::new().parse("²√"),
-            Ok(MathematicalSymbol::Root { exponent: 2 })
+            lex(")"),
+            vec![(Token::Delimiter(Delimiter::RightParen), 0..1)]
         );
         assert_eq!(
-            parser::MathematicalSymbolParser::new().parse("¹²√"),
-            Ok(MathematicalSymbol::Root { exponent: 12 })
+            lex("["),
+            vec![(Token::Delimiter(Delimiter::LeftBracket), 0..1)]
         );
         assert_eq!(
-            parser::MathematicalSymbolParser::new().parse("⁻⁵³√"),
-            Ok(MathematicalSymbol::Root { exponent: -53 })
+            lex("]"),
+            vec![(Token::Delimiter(Delimiter::RightBracket), 0..1)]
         );
         assert_eq!(
-            parser::MathematicalSymbolParser::new().parse("⁺⁵³√"),
-            Ok(MathematicalSymbol::Root { exponent: 53 })
+            lex("{"),
+            vec![(Token::Delimiter(Delimiter::LeftBrace), 0..1)]
+        );
+        assert_eq!(
+            lex("}"),
+            vec![(Token::Delimiter(Delimiter::RightBrace), 0..1)]
+        );
+    }
+
+    // [A-Za-zªµºÀ-ÖØ-öø-ˁˆ-ˑ ˠ-ˤˬˮͰ-ʹͶͷͻ-ͽͿΆΈ-ΊΌΎ-Ρ Σ-ϵϷ-ҁҊ-ԯԱ-Ֆՙՠ-ֈא-תׯ-ײ ؠ-يٮٯٱ-ۓەۥۦۮۯۺ-ۼۿܐܒ-ܯ ݍ-ޥޱߊ-ߪߴߵߺࠀ-ࠕࠚࠤࠨࡀ-ࡘࡠ-ࡪ ࡰ-ࢇࢉ-ࢎࢠ-ࣉऄ-हऽॐक़-ॡॱ-ঀঅ-ঌ এঐও-নপ-রলশ-হঽৎড়ঢ়য়-ৡৰৱ ৼਅ-ਊਏਐਓ-ਨਪ-ਰਲਲ਼ਵਸ਼ਸਹਖ਼-ੜ ਫ਼ੲ-ੴઅ-ઍએ-ઑઓ-નપ-રલળવ-હ ઽૐૠૡૹଅ-ଌଏଐଓ-ନପ-ରଲଳଵ-ହ ଽଡ଼ଢ଼ୟ-ୡୱஃஅ-ஊஎ-ஐஒ-கஙசஜஞ டணதந-பம-ஹௐఅ-ఌఎ-ఐఒ-నప-హ ఽౘ-ౚౝౠౡಀಅ-ಌಎ-ಐಒ-ನಪ-ಳವ-ಹ ಽೝೞೠೡೱೲഄ-ഌഎ-ഐഒ-ഺഽൎൔ-ൖ ൟ-ൡൺ-ൿඅ-ඖක-නඳ-රලව-ෆก-ะ าเ-ๆກຂຄຆ-ຊຌ-ຣລວ-ະາຽເ-ໄ ໆໜ-ໟༀཀ-ཇཉ-ཬྈ-ྌက-ဪဿၐ-ၕ ၚ-ၝၡၥၦၮ-ၰၵ-ႁႎႠ-ჅჇჍა-ჺ ჼ-ቈቊ-ቍቐ-ቖቘቚ-ቝበ-ኈኊ-ኍነ-ኰ ኲ-ኵኸ-ኾዀዂ-ዅወ-ዖዘ-ጐጒ-ጕጘ-ፚ ᎀ-ᎏᎠ-Ᏽᏸ-ᏽᐁ-ᙬᙯ-ᙿᚁ-ᚚᚠ-ᛪ ᛮ-ᛸᜀ-ᜑᜟ-ᜱᝀ-ᝑᝠ-ᝬᝮ-ᝰក-ឳ ៗៜᠠ-ᡸᢀ-ᢨᢪᢰ-ᣵᤀ-ᤞᥐ-ᥭᥰ-ᥴ ᦀ-ᦫᦰ-ᧉᨀ-ᨖᨠ-ᩔᪧᬅ-ᬳᭅ-ᭌᮃ-ᮠ ᮮᮯᮺ-ᯥᰀ-ᰣᱍ-ᱏᱚ-ᱽᲀ-ᲈᲐ-ᲺᲽ-Ჿ ᳩ-ᳬᳮ-ᳳᳵᳶᳺᴀ-ᶿḀ-ἕἘ-Ἕἠ-ὅ Ὀ-Ὅὐ-ὗὙὛὝὟ-ώᾀ-ᾴᾶ-ᾼιῂ-ῄ ῆ-ῌῐ-ΐῖ-Ίῠ-Ῥῲ-ῴῶ-ῼⁱⁿₐ-ₜ ℂℇℊ-ℓℕ℘-ℝℤΩℨK-ℹℼ-ℿⅅ-ⅉ ⅎⅠ-ↈⰀ-ⳤⳫ-ⳮⳲⳳⴀ-ⴥⴧⴭⴰ-ⵧⵯ ⶀ-ⶖⶠ-ⶦⶨ-ⶮⶰ-ⶶⶸ-ⶾⷀ-ⷆⷈ-ⷎ ⷐ-ⷖⷘ-ⷞ々-〇〡-〩〱-〵〸-〼ぁ-ゖ ゝ-ゟァ-ヺー-ヿㄅ-ㄯㄱ-ㆎㆠ-ㆿㇰ-ㇿ 㐀-䶿一-ꒌꓐ-ꓽꔀ-ꘌꘐ-ꘟꘪꘫꙀ-ꙮꙿ-ꚝ ꚠ-ꛯꜗ-ꜟꜢ-ꞈꞋ-ꟊꟐꟑꟓꟕ-ꟙꟲ-ꠁ ꠃ-ꠅꠇ-ꠊꠌ-ꠢꡀ-ꡳꢂ-ꢳꣲ-ꣷꣻꣽꣾ ꤊ-ꤥꤰ-ꥆꥠ-ꥼꦄ-ꦲꧏꧠ-ꧤꧦ-ꧯꧺ-ꧾ ꨀ-ꨨꩀ-ꩂꩄ-ꩋꩠ-ꩶꩺꩾ-ꪯꪱꪵꪶꪹ-ꪽ ꫀꫂꫛ-ꫝꫠ-ꫪꫲ-ꫴꬁ-ꬆꬉ-ꬎꬑ-ꬖꬠ-ꬦ ꬨ-ꬮꬰ-ꭚꭜ-ꭩꭰ-ꯢ가-힣ힰ-ퟆퟋ-ퟻ 豈-舘並-龎ﬀ-ﬆﬓ-ﬗיִײַ-ﬨשׁ-זּטּ-לּ מּנּסּףּפּצּ-ﮱﯓ-ﱝﱤ-ﴽﵐ-ﶏﶒ-ﷇﷰ-ﷹ ﹱﹳﹷﹹﹻﹽﹿ-ﻼＡ-Ｚａ-ｚｦ-ﾝ-ﾾￂ-ￇ ￊ-ￏￒ-ￗￚ-ￜ𐀀-𐀋𐀍-𐀦𐀨-𐀺𐀼𐀽𐀿-𐁍 𐁐-𐁝𐂀-𐃺𐅀-𐅴𐊀-𐊜𐊠-𐋐𐌀-𐌟𐌭-𐍊 𐍐-𐍵𐎀-𐎝𐎠-𐏃𐏈-𐏏𐏑-𐏕𐐀-𐒝𐒰-𐓓 𐓘-𐓻𐔀-𐔧𐔰-𐕣𐕰-𐕺𐕼-𐖊𐖌-𐖒𐖔𐖕𐖗-𐖡 𐖣-𐖱𐖳-𐖹𐖻𐖼𐘀-𐜶𐝀-𐝕𐝠-𐝧𐞀-𐞅𐞇-𐞰 𐞲-𐞺𐠀-𐠅𐠈𐠊-𐠵𐠷𐠸𐠼𐠿-𐡕𐡠-𐡶𐢀-𐢞 𐣠-𐣲𐣴𐣵𐤀-𐤕𐤠-𐤹𐦀-𐦷𐦾𐦿𐨀𐨐-𐨓𐨕-𐨗 𐨙-𐨵𐩠-𐩼𐪀-𐪜𐫀-𐫇𐫉-𐫤𐬀-𐬵𐭀-𐭕 𐭠-𐭲𐮀-𐮑𐰀-𐱈𐲀-𐲲𐳀-𐳲𐴀-𐴣𐺀-𐺩 𐺰𐺱𐼀-𐼜𐼧𐼰-𐽅𐽰-𐾁𐾰-𐿄𐿠-𐿶𑀃-𑀷 𑁱𑁲𑁵𑂃-𑂯𑃐-𑃨𑄃-𑄦𑅄𑅇𑅐-𑅲𑅶𑆃-𑆲 𑇁-𑇄𑇚𑇜𑈀-𑈑𑈓-𑈫𑈿𑉀𑊀-𑊆𑊈𑊊-𑊍𑊏-𑊝 𑊟-𑊨𑊰-𑋞𑌅-𑌌𑌏𑌐𑌓-𑌨𑌪-𑌰𑌲𑌳𑌵-𑌹 𑌽𑍐𑍝-𑍡𑐀-𑐴𑑇-𑑊𑑟-𑑡𑒀-𑒯𑓄𑓅𑓇𑖀-𑖮 𑗘-𑗛𑘀-𑘯𑙄𑚀-𑚪𑚸𑜀-𑜚𑝀-𑝆𑠀-𑠫𑢠-𑣟 𑣿-𑤆𑤉𑤌-𑤓𑤕𑤖𑤘-𑤯𑤿𑥁𑦠-𑦧𑦪-𑧐𑧡 𑧣𑨀𑨋-𑨲𑨺𑩐𑩜-𑪉𑪝𑪰-𑫸𑰀-𑰈𑰊-𑰮𑱀 𑱲-𑲏𑴀-𑴆𑴈𑴉𑴋-𑴰𑵆𑵠-𑵥𑵧𑵨𑵪-𑶉𑶘 𑻠-𑻲𑼂𑼄-𑼐𑼒-𑼳𑾰𒀀-𒎙𒐀-𒑮𒒀-𒕃𒾐-𒿰 𓀀-𓐯𓑁-𓑆𔐀-𔙆𖠀-𖨸𖩀-𖩞𖩰-𖪾𖫐-𖫭 𖬀-𖬯𖭀-𖭃𖭣-𖭷𖭽-𖮏𖹀-𖹿𖼀-𖽊𖽐𖾓-𖾟 𖿠𖿡𖿣𗀀-𘟷𘠀-𘳕𘴀-𘴈𚿰-𚿳𚿵-𚿻𚿽𚿾𛀀-𛄢 𛄲𛅐-𛅒𛅕𛅤-𛅧𛅰-𛋻𛰀-𛱪𛱰-𛱼𛲀-𛲈𛲐-𛲙 𝐀-𝑔𝑖-𝒜𝒞𝒟𝒢𝒥𝒦𝒩-𝒬𝒮-𝒹𝒻𝒽-𝓃 𝓅-𝔅𝔇-𝔊𝔍-𝔔𝔖-𝔜𝔞-𝔹𝔻-𝔾𝕀-𝕄 𝕆𝕊-𝕐𝕒-𝚥𝚨-𝛀𝛂-𝛚𝛜-𝛺𝛼-𝜔𝜖-𝜴 𝜶-𝝎𝝐-𝝮𝝰-𝞈𝞊-𝞨𝞪-𝟂𝟄-𝟋𝼀-𝼞 𝼥-𝼪𞀰-𞁭𞄀-𞄬𞄷-𞄽𞅎𞊐-𞊭𞋀-𞋫𞓐-𞓫 𞟠-𞟦𞟨-𞟫𞟭𞟮𞟰-𞟾𞠀-𞣄𞤀-𞥃𞥋𞸀-𞸃 𞸅-𞸟𞸡𞸢𞸤𞸧𞸩-𞸲𞸴-𞸷𞸹𞸻𞹂𞹇𞹉𞹋𞹍-𞹏 𞹑𞹒𞹔𞹗𞹙𞹛𞹝𞹟𞹡𞹢𞹤𞹧-𞹪𞹬-𞹲𞹴-𞹷𞹹-𞹼 𞹾𞺀-𞺉𞺋-𞺛𞺡-𞺣𞺥-𞺩𞺫-𞺻𠀀-𪛟𪜀-𫜹 𫝀-𫠝𫠠-𬺡𬺰-𮯠丽-𪘀𰀀-𱍊𱍐-𲎯]
+    #[test]
+    fn test_lex_xid_start() {
+        assert_eq!(lex("a"), vec![(Token::Identifier("a"), 0..1)]);
+        assert_eq!(lex("в"), vec![(Token::Identifier("в"), 0..2)]);
+        assert_eq!(lex("भ"), vec![(Token::Identifier("भ"), 0..3)]);
+        assert_eq!(lex("त"), vec![(Token::Identifier("त"), 0..3)]);
+        assert_eq!(lex("र"), vec![(Token::Identifier("र"), 0..3)]);
+        assert_eq!(lex("П"), vec![(Token::Identifier("П"), 0..2)]);
+        assert_eq!(lex("и"), vec![(Token::Identifier("и"), 0..2)]);
+        assert_eq!(lex("в"), vec![(Token::Identifier("в"), 0..2)]);
+        assert_eq!(lex("е"), vec![(Token::Identifier("е"), 0..2)]);
+        assert_eq!(lex("т"), vec![(Token::Identifier("т"), 0..2)]);
+        assert_eq!(lex("ᄀ"), vec![(Token::Identifier("ᄀ"), 0..3)]);
+        assert_eq!(lex("ᄁ"), vec![(Token::Identifier("ᄁ"), 0..3)]);
+        assert_eq!(lex("ᄊ"), vec![(Token::Identifier("ᄊ"), 0..3)]);
+        assert_eq!(lex("ᇂ"), vec![(Token::Identifier("ᇂ"), 0..3)]);
+        assert_eq!(lex("ᆱ"), vec![(Token::Identifier("ᆱ"), 0..3)]);
+        assert_eq!(lex("æ"), vec![(Token::Identifier("æ"), 0..2)]);
+        assert_eq!(lex("Ä"), vec![(Token::Identifier("Ä"), 0..2)]);
+        assert_eq!(lex("é"), vec![(Token::Identifier("é"), 0..2)]);
+        assert_eq!(lex("ç"), vec![(Token::Identifier("ç"), 0..2)]);
+        assert_eq!(lex("â"), vec![(Token::Identifier("â"), 0..2)]);
+        assert_eq!(lex("℘"), vec![(Token::Identifier("℘"), 0..3)]);
+
+        // XID_Continue characters which aren't XID_Start can't begin an
+        // identifier
+        assert_eq!(lex("٣"), vec![(Token::Error, 0..2)]);
+        assert_eq!(
+            lex("\u{0301}a"),
+            vec![(Token::Error, 0..2), (Token::Identifier("a"), 2..3)]
+        );
+    }
+
+    // [0-9A-Z_a-zªµ·ºÀ-ÖØ-ö ø-ˁˆ-ˑˠ-ˤˬˮ̀-ʹͶͷͻ-ͽͿΆ-Ί ΌΎ-ΡΣ-ϵϷ-ҁ҃-҇Ҋ-ԯԱ-Ֆՙՠ-ֈ֑-ׇֽֿׁׂׅׄ א-תׯ-ײؐ-ؚؠ-٩ٮ-ۓە-ۜ۟-۪ۨ-ۼ ۿܐ-݊ݍ-ޱ߀-ߵߺ߽ࠀ-࠭ࡀ-࡛ࡠ-ࡪ ࡰ-ࢇࢉ-ࢎ࢘-ࣣ࣡-ॣ०-९ॱ-ঃঅ-ঌ এঐও-নপ-রলশ-হ়-ৄেৈো-ৎৗ ড়ঢ়য়-ৣ০-ৱৼ৾ਁ-ਃਅ-ਊਏਐਓ-ਨ ਪ-ਰਲਲ਼ਵਸ਼ਸਹ਼ਾ-ੂੇੈੋ-੍ੑਖ਼-ੜ ਫ਼੦-ੵઁ-ઃઅ-ઍએ-ઑઓ-નપ-રલળ વ-હ઼-ૅે-ૉો-્ૐૠ-ૣ૦-૯ૹ-૿ଁ-ଃ ଅ-ଌଏଐଓ-ନପ-ରଲଳଵ-ହ଼-ୄେୈୋ-୍୕-ୗ ଡ଼ଢ଼ୟ-ୣ୦-୯ୱஂஃஅ-ஊஎ-ஐஒ-கங சஜஞடணதந-பம-ஹா-ூெ-ைொ-் ௐௗ௦-௯ఀ-ఌఎ-ఐఒ-నప-హ఼-ౄె-ైొ-్ౕౖ ౘ-ౚౝౠ-ౣ౦-౯ಀ-ಃಅ-ಌಎ-ಐಒ-ನ ಪ-ಳವ-ಹ಼-ೄೆ-ೈೊ-್ೕೖೝೞೠ-ೣ ೦-೯ೱ-ೳഀ-ഌഎ-ഐഒ-ൄെ-ൈൊ-ൎ ൔ-ൗൟ-ൣ൦-൯ൺ-ൿඁ-ඃඅ-ඖක-න ඳ-රලව-ෆ්ා-ුූෘ-ෟ෦-෯ෲෳก-ฺ เ-๎๐-๙ກຂຄຆ-ຊຌ-ຣລວ-ຽເ-ໄ ໆ່-໎໐-໙ໜ-ໟༀ༘༙༠-༩༹༵༷༾-ཇ ཉ-ཬཱ-྄྆-ྗྙ-ྼ࿆က-၉ၐ-ႝႠ-Ⴥ ჇჍა-ჺჼ-ቈቊ-ቍቐ-ቖቘቚ-ቝበ-ኈ ኊ-ኍነ-ኰኲ-ኵኸ-ኾዀዂ-ዅወ-ዖዘ-ጐ ጒ-ጕጘ-ፚ፝-፟፩-፱ᎀ-ᎏᎠ-Ᏽᏸ-ᏽ ᐁ-ᙬᙯ-ᙿᚁ-ᚚᚠ-ᛪᛮ-ᛸᜀ-᜕ᜟ-᜴ ᝀ-ᝓᝠ-ᝬᝮ-ᝰᝲᝳក-៓ៗៜ៝០-៩--᠙ ᠠ-ᡸᢀ-ᢪᢰ-ᣵᤀ-ᤞᤠ-ᤫᤰ-᤻᥆-ᥭ ᥰ-ᥴᦀ-ᦫᦰ-ᧉ᧐-᧚ᨀ-ᨛᨠ-ᩞ᩠-᩿᩼-᪉ ᪐-᪙ᪧ᪰-᪽ᪿ-ᫎᬀ-ᭌ᭐-᭙᭫-᭳ᮀ-᯳ ᰀ-᰷᱀-᱉ᱍ-ᱽᲀ-ᲈᲐ-ᲺᲽ-Ჿ᳐-᳔᳒-ᳺ ᴀ-ἕἘ-Ἕἠ-ὅὈ-Ὅὐ-ὗὙὛὝὟ-ώ ᾀ-ᾴᾶ-ᾼιῂ-ῄῆ-ῌῐ-ΐῖ-Ίῠ-Ῥ ῲ-ῴῶ-ῼ‿⁀⁔ⁱⁿₐ-ₜ⃐-⃥⃜⃡-⃰ ℂℇℊ-ℓℕ℘-ℝℤΩℨK-ℹℼ-ℿⅅ-ⅉ ⅎⅠ-ↈⰀ-ⳤⳫ-ⳳⴀ-ⴥⴧⴭⴰ-ⵧⵯ⵿-ⶖ ⶠ-ⶦⶨ-ⶮⶰ-ⶶⶸ-ⶾⷀ-ⷆⷈ-ⷎⷐ-ⷖ ⷘ-ⷞⷠ-ⷿ々-〇〡-〯〱-〵〸-〼ぁ-ゖ゙゚ ゝ-ゟァ-ヺー-ヿㄅ-ㄯㄱ-ㆎㆠ-ㆿㇰ-ㇿ 㐀-䶿一-ꒌꓐ-ꓽꔀ-ꘌꘐ-ꘫꙀ-꙯ꙴ-꙽ ꙿ-꛱ꜗ-ꜟꜢ-ꞈꞋ-ꟊꟐꟑꟓꟕ-ꟙꟲ-ꠧ꠬ ꡀ-ꡳꢀ-ꣅ꣐-꣙꣠-ꣷꣻꣽ-꤭ꤰ-꥓ꥠ-ꥼꦀ-꧀ ꧏ-꧙ꧠ-ꧾꨀ-ꨶꩀ-ꩍ꩐-꩙ꩠ-ꩶꩺ-ꫂ ꫛ-ꫝꫠ-ꫯꫲ-꫶ꬁ-ꬆꬉ-ꬎꬑ-ꬖꬠ-ꬦ ꬨ-ꬮꬰ-ꭚꭜ-ꭩꭰ-ꯪ꯬꯭꯰-꯹가-힣ힰ-ퟆ ퟋ-ퟻ豈-舘並-龎ﬀ-ﬆﬓ-ﬗיִ-ﬨשׁ-זּ טּ-לּמּנּסּףּפּצּ-ﮱﯓ-ﱝﱤ-ﴽﵐ-ﶏﶒ-ﷇ ﷰ-ﷹ-︠-︯︳︴﹍-﹏ﹱﹳﹷﹹﹻﹽﹿ-ﻼ ０-９Ａ-Ｚ＿ａ-ｚｦ-ﾾￂ-ￇￊ-ￏￒ-ￗ ￚ-ￜ𐀀-𐀋𐀍-𐀦𐀨-𐀺𐀼𐀽𐀿-𐁍𐁐-𐁝𐂀-𐃺 𐅀-𐅴𐇽𐊀-𐊜𐊠-𐋐𐋠𐌀-𐌟𐌭-𐍊𐍐-𐍺𐎀-𐎝 𐎠-𐏃𐏈-𐏏𐏑-𐏕𐐀-𐒝𐒠-𐒩𐒰-𐓓𐓘-𐓻 𐔀-𐔧𐔰-𐕣𐕰-𐕺𐕼-𐖊𐖌-𐖒𐖔𐖕𐖗-𐖡𐖣-𐖱 𐖳-𐖹𐖻𐖼𐘀-𐜶𐝀-𐝕𐝠-𐝧𐞀-𐞅𐞇-𐞰𐞲-𐞺 𐠀-𐠅𐠈𐠊-𐠵𐠷𐠸𐠼𐠿-𐡕𐡠-𐡶𐢀-𐢞𐣠-𐣲 𐣴𐣵𐤀-𐤕𐤠-𐤹𐦀-𐦷𐦾𐦿𐨀-𐨃𐨅𐨆𐨌-𐨓 𐨕-𐨗𐨙-𐨵𐨸-𐨿𐨺𐩠-𐩼𐪀-𐪜𐫀-𐫇𐫉-𐫦 𐬀-𐬵𐭀-𐭕𐭠-𐭲𐮀-𐮑𐰀-𐱈𐲀-𐲲𐳀-𐳲 𐴀-𐴧𐴰-𐴹𐺀-𐺩𐺫𐺬𐺰𐺱𐻽-𐼜𐼧𐼰-𐽐𐽰-𐾅 𐾰-𐿄𐿠-𐿶𑀀-𑁆𑁦-𑁵𑁿-𑂺𑃂𑃐-𑃨𑃰-𑃹𑄀-𑄴 𑄶-𑄿𑅄-𑅇𑅐-𑅳𑅶𑆀-𑇄𑇉-𑇌𑇎-𑇚𑇜𑈀-𑈑 𑈓-𑈷𑈾-𑉁𑊀-𑊆𑊈𑊊-𑊍𑊏-𑊝𑊟-𑊨𑊰-𑋪 𑋰-𑋹𑌀-𑌃𑌅-𑌌𑌏𑌐𑌓-𑌨𑌪-𑌰𑌲𑌳𑌵-𑌹𑌻-𑍄𑍇𑍈𑍋-𑍍 𑍐𑍗𑍝-𑍣𑍦-𑍬𑍰-𑍴𑐀-𑑊𑑐-𑑙𑑞-𑑡𑒀-𑓅 𑓇𑓐-𑓙𑖀-𑖵𑖸-𑗀𑗘-𑗝𑘀-𑙀𑙄𑙐-𑙙𑚀-𑚸 𑛀-𑛉𑜀-𑜚𑜝-𑜫𑜰-𑜹𑝀-𑝆𑠀-𑠺𑢠-𑣩 𑣿-𑤆𑤉𑤌-𑤓𑤕𑤖𑤘-𑤵𑤷𑤸𑤻-𑥃𑥐-𑥙𑦠-𑦧 𑦪-𑧗𑧚-𑧡𑧣𑧤𑨀-𑨾𑩇𑩐-𑪙𑪝𑪰-𑫸𑰀-𑰈 𑰊-𑰶𑰸-𑱀𑱐-𑱙𑱲-𑲏𑲒-𑲧𑲩-𑲶𑴀-𑴆 𑴈𑴉𑴋-𑴶𑴺𑴼𑴽𑴿-𑵇𑵐-𑵙𑵠-𑵥𑵧𑵨𑵪-𑶎𑶐𑶑𑶓-𑶘 𑶠-𑶩𑻠-𑻶𑼀-𑼐𑼒-𑼺𑼾-𑽂𑽐-𑽙𑾰𒀀-𒎙 𒐀-𒑮𒒀-𒕃𒾐-𒿰𓀀-𓐯𓑀-𓑕𔐀-𔙆𖠀-𖨸 𖩀-𖩞𖩠-𖩩𖩰-𖪾𖫀-𖫉𖫐-𖫭𖫰-𖫴𖬀-𖬶 𖭀-𖭃𖭐-𖭙𖭣-𖭷𖭽-𖮏𖹀-𖹿𖼀-𖽊𖽏-𖾇𖾏-𖾟 𖿠𖿡𖿣𖿤𖿰𖿱𗀀-𘟷𘠀-𘳕𘴀-𘴈𚿰-𚿳𚿵-𚿻 𚿽𚿾𛀀-𛄢𛄲𛅐-𛅒𛅕𛅤-𛅧𛅰-𛋻𛰀-𛱪𛱰-𛱼 𛲀-𛲈𛲐-𛲙𛲝𛲞𜼀-𜼭𜼰-𜽆𝅥-𝅩𝅭-𝅲𝅻-𝆂𝆅-𝆋𝆪-𝆭𝉂-𝉄 𝐀-𝑔𝑖-𝒜𝒞𝒟𝒢𝒥𝒦𝒩-𝒬𝒮-𝒹𝒻𝒽-𝓃 𝓅-𝔅𝔇-𝔊𝔍-𝔔𝔖-𝔜𝔞-𝔹𝔻-𝔾𝕀-𝕄 𝕆𝕊-𝕐𝕒-𝚥𝚨-𝛀𝛂-𝛚𝛜-𝛺𝛼-𝜔𝜖-𝜴 𝜶-𝝎𝝐-𝝮𝝰-𝞈𝞊-𝞨𝞪-𝟂𝟄-𝟋𝟎-𝟿𝨀-𝨶𝨻-𝩬𝩵𝪄𝪛-𝪟𝪡-𝪯 𝼀-𝼞𝼥-𝼪𞀀-𞀆𞀈-𞀘𞀛-𞀡𞀣𞀤𞀦-𞀪𞀰-𞁭𞂏 𞄀-𞄬𞄰-𞄽𞅀-𞅉𞅎𞊐-𞊮𞋀-𞋹𞓐-𞓹𞟠-𞟦 𞟨-𞟫𞟭𞟮𞟰-𞟾𞠀-𞣄𞣐-𞣖𞤀-𞥋𞥐-𞥙𞸀-𞸃 𞸅-𞸟𞸡𞸢𞸤𞸧𞸩-𞸲𞸴-𞸷𞸹𞸻𞹂𞹇𞹉𞹋𞹍-𞹏 𞹑𞹒𞹔𞹗𞹙𞹛𞹝𞹟𞹡𞹢𞹤𞹧-𞹪𞹬-𞹲𞹴-𞹷𞹹-𞹼 𞹾𞺀-𞺉𞺋-𞺛𞺡-𞺣𞺥-𞺩𞺫-𞺻🯰-🯹𠀀-𪛟 𪜀-𫜹𫝀-𫠝𫠠-𬺡𬺰-𮯠丽-𪘀𰀀-𱍊𱍐-𲎯-]
+    #[test]
+    fn test_lex_xid_continue() {
+        assert_eq!(lex("a"), vec![(Token::Identifier("a"), 0..1)]);
+        assert_eq!(
+            lex("_"),
+            vec![(Token::Punctuation(Punctuation::Underscore), 0..1)]
+        );
+        assert_eq!(lex("в"), vec![(Token::Identifier("в"), 0..2)]);
+        assert_eq!(lex("भ"), vec![(Token::Identifier("भ"), 0..3)]);
+        assert_eq!(lex("त"), vec![(Token::Identifier("त"), 0..3)]);
+        assert_eq!(lex("र"), vec![(Token::Identifier("र"), 0..3)]);
+        assert_eq!(lex("П"), vec![(Token::Identifier("П"), 0..2)]);
+        assert_eq!(lex("и"), vec![(Token::Identifier("и"), 0..2)]);
+        assert_eq!(lex("в"), vec![(Token::Identifier("в"), 0..2)]);
+        assert_eq!(lex("е"), vec![(Token::Identifier("е"), 0..2)]);
+        assert_eq!(lex("т"), vec![(Token::Identifier("т"), 0..2)]);
+        assert_eq!(lex("ᄀ"), vec![(Token::Identifier("ᄀ"), 0..3)]);
+        assert_eq!(lex("ᄁ"), vec![(Token::Identifier("ᄁ"), 0..3)]);
+        assert_eq!(lex("ᄊ"), vec![(Token::Identifier("ᄊ"), 0..3)]);
+        assert_eq!(lex("ᇂ"), vec![(Token::Identifier("ᇂ"), 0..3)]);
+        assert_eq!(lex("ᆱ"), vec![(Token::Identifier("ᆱ"), 0..3)]);
+        assert_eq!(lex("æ"), vec![(Token::Identifier("æ"), 0..2)]);
+        assert_eq!(lex("Ä"), vec![(Token::Identifier("Ä"), 0..2)]);
+        assert_eq!(lex("é"), vec![(Token::Identifier("é"), 0..2)]);
+        assert_eq!(lex("ç"), vec![(Token::Identifier("ç"), 0..2)]);
+        assert_eq!(lex("â"), vec![(Token::Identifier("â"), 0..2)]);
+
+        // XID_Continue characters which aren't XID_Start
+        assert_eq!(lex("a1"), vec![(Token::Identifier("a1"), 0..2)]);
+        assert_eq!(lex("_0"), vec![(Token::Identifier("_0"), 0..2)]);
+        assert_eq!(lex("x٣"), vec![(Token::Identifier("x٣"), 0..3)]);
+        assert_eq!(
+            lex("e\u{0301}"),
+            vec![(Token::Identifier("e\u{0301}"), 0..3)]
+        );
+        assert_eq!(lex("a‿b"), vec![(Token::Identifier("a‿b"), 0..5)]);
+    }
+
+    #[test]
+    fn test_lex_identifiers() {
+        assert_eq!(lex("foo"), vec![(Token::Identifier("foo"), 0..3)]);
+        assert_eq!(lex("foo_bar"), vec![(Token::Identifier("foo_bar"), 0..7)]);
+        assert_eq!(lex("_ident"), vec![(Token::Identifier("_ident"), 0..6)]);
+        assert_eq!(lex("Москва"), vec![(Token::Identifier("Москва"), 0..12)]);
+        assert_eq!(lex("東京"), vec![(Token::Identifier("東京"), 0..6)]);
+        assert_eq!(lex("المملكة"), vec![(Token::Identifier("المملكة"), 0..14)]);
+        assert_eq!(lex("Привет"), vec![(Token::Identifier("Привет"), 0..12)]);
+        assert_eq!(
+            lex("你好世界"),
+            vec![(Token::Identifier("你好世界"), 0..12)]
+        );
+        assert_eq!(lex("größe"), vec![(Token::Identifier("größe"), 0..7)]);
+        assert_eq!(lex("θ₁"), vec![(Token::Identifier("θ₁"), 0..5)]);
+
+        // Keywords and constants are only recognized when the whole word matches
+        assert_eq!(
+            lex("foobarfor"),
+            vec![(Token::Identifier("foobarfor"), 0..9)]
         );
+        assert_eq!(lex("format"), vec![(Token::Identifier("format"), 0..6)]);
+        assert_eq!(lex("notable"), vec![(Token::Identifier("notable"), 0..7)]);
+        assert_eq!(lex("pie"), vec![(Token::Identifier("pie"), 0..3)]);
+        assert_eq!(lex("if_"), vec![(Token::Identifier("if_"), 0..3)]);
         assert_eq!(
-            parser::MathematicalSymbolParser::new().parse("∛"),
-            Ok(MathematicalSymbol::Root { exponent: 3 })
+            lex("for x in xs"),
+            vec![
+                (Token::Keyword(Keyword::For), 0..3),
+                (Token::Identifier("x"), 4..5),
+                (Token::Keyword(Keyword::In), 6..8),
+                (Token::Identifier("xs"), 9..11),
+            ]
         );
         assert_eq!(
-            parser::MathematicalSymbolParser::new().parse("∜"),
-            Ok(MathematicalSymbol::Root { exponent: 4 })
+            parser::IdentifierParser::new().parse(Lexer::new("foobarfor")),
+            Ok(Identifier {
+                name: String::from("foobarfor"),
+            })
         );
+        assert!(parser::IdentifierParser::new()
+            .parse(Lexer::new("for"))
+            .is_err());
+    }
 
-        // Fractions (e.g. 1//2 = ½)
+    #[test]
+    fn test_lex_numeric_literals() {
+        // Integer Literal Prefixes
+        assert_eq!(lex("0b1010"), vec![(Token::BinaryLiteral("0b1010"), 0..6)]);
+        assert_eq!(lex("0B1010"), vec![(Token::BinaryLiteral("0B1010"), 0..6)]);
+        assert_eq!(lex("0o777"), vec![(Token::OctalLiteral("0o777"), 0..5)]);
+        assert_eq!(lex("0O777"), vec![(Token::OctalLiteral("0O777"), 0..5)]);
+        assert_eq!(
+            lex("0xdead_BEEF"),
+            vec![(Token::HexadecimalLiteral("0xdead_BEEF"), 0..11)]
+        );
+        assert_eq!(
+            lex("0XDEAD_beef"),
+            vec![(Token::HexadecimalLiteral("0XDEAD_beef"), 0..11)]
+        );
+        assert_eq!(
+            lex("1_234_567"),
+            vec![(Token::DecimalLiteral("1_234_567"), 0..9)]
+        );
 
-        // General Mathematical Symbols (e.g. ÷, ∫, etc.)
+        // Integer Literal Suffixes
+
+        // - Unsigned Integer Literal Suffixes
+        assert_eq!(lex("1u8"), vec![(Token::DecimalLiteral("1u8"), 0..3)]);
+        assert_eq!(lex("1u16"), vec![(Token::DecimalLiteral("1u16"), 0..4)]);
+        assert_eq!(lex("1u32"), vec![(Token::DecimalLiteral("1u32"), 0..4)]);
+        assert_eq!(lex("1u64"), vec![(Token::DecimalLiteral("1u64"), 0..4)]);
+        assert_eq!(lex("1u128"), vec![(Token::DecimalLiteral("1u128"), 0..5)]);
+        assert_eq!(lex("1usize"), vec![(Token::DecimalLiteral("1usize"), 0..6)]);
+
+        // - Signed Integer Literal Suffixes
+        assert_eq!(lex("1i8"), vec![(Token::DecimalLiteral("1i8"), 0..3)]);
+        assert_eq!(lex("1i16"), vec![(Token::DecimalLiteral("1i16"), 0..4)]);
+        assert_eq!(lex("1i32"), vec![(Token::DecimalLiteral("1i32"), 0..4)]);
+        assert_eq!(lex("1i64"), vec![(Token::DecimalLiteral("1i64"), 0..4)]);
+        assert_eq!(lex("1i128"), vec![(Token::DecimalLiteral("1i128"), 0..5)]);
+        assert_eq!(lex("1isize"), vec![(Token::DecimalLiteral("1isize"), 0..6)]);
         assert_eq!(
-            parser::MathematicalSymbolParser::new().parse("÷"),
-            Ok(MathematicalSymbol::Division)
+            lex("0x_ff_u8"),
+            vec![(Token::HexadecimalLiteral("0x_ff_u8"), 0..8)]
         );
+
+        // Digits outside of a literal's radix end the literal
         assert_eq!(
-            parser::MathematicalSymbolParser::new().parse("∝"),
-            Ok(MathematicalSymbol::ProportionalTo)
+            lex("0b2"),
+            vec![
+                (Token::DecimalLiteral("0"), 0..1),
+                (Token::Identifier("b2"), 1..3),
+            ]
         );
-        // assert_eq!(
-        //     parser::MathematicalSymbolParser::new().parse("∠"),
-        //     Ok(MathematicalSymbol::Angle)
-        // );
-        // assert_eq!(
-        //     parser::MathematicalSymbolParser::new().parse("∧"),
-        //     Ok(MathematicalSymbol::LogicalAnd)
-        // );
-        // assert_eq!(
-        //     parser::MathematicalSymbolParser::new().parse("∨"),
-        //     Ok(MathematicalSymbol::LogicalOr)
-        // );
         assert_eq!(
-            parser::MathematicalSymbolParser::new().parse("∩"),
-            Ok(MathematicalSymbol::Intersection)
+            lex("0o8"),
+            vec![
+                (Token::DecimalLiteral("0"), 0..1),
+                (Token::Identifier("o8"), 1..3),
+            ]
         );
+
+        /////////////////////////////
+        // Floating Point Literals //
+        /////////////////////////////
+
+        assert_eq!(lex("22.0"), vec![(Token::FloatLiteral("22.0"), 0..4)]);
         assert_eq!(
-            parser::MathematicalSymbolParser::new().parse("∪"),
-            Ok(MathematicalSymbol::Union)
+            lex("1234.5e-6"),
+            vec![(Token::FloatLiteral("1234.5e-6"), 0..9)]
         );
+        assert_eq!(lex("12E+99_"), vec![(Token::FloatLiteral("12E+99_"), 0..7)]);
+
+        // Float Literal Suffixes
+        assert_eq!(lex("1.5f32"), vec![(Token::FloatLiteral("1.5f32"), 0..6)]);
+        assert_eq!(lex("1.5f64"), vec![(Token::FloatLiteral("1.5f64"), 0..6)]);
+        assert_eq!(lex("1f32"), vec![(Token::FloatLiteral("1f32"), 0..4)]);
         assert_eq!(
-            parser::MathematicalSymbolParser::new().parse("∫"),
-            Ok(MathematicalSymbol::Integral)
+            lex("0x1.8p3"),
+            vec![(Token::HexadecimalFloatLiteral("0x1.8p3"), 0..7)]
         );
         assert_eq!(
-            parser::MathematicalSymbolParser::new().parse("∴"),
-            Ok(MathematicalSymbol::Therefore)
+            lex("0xAP-1f32"),
+            vec![(Token::HexadecimalFloatLiteral("0xAP-1f32"), 0..9)]
         );
+        // Hexadecimal floats without an exponent are rejected by the parser
         assert_eq!(
-            parser::MathematicalSymbolParser::new().parse("∵"),
-            Ok(MathematicalSymbol::Because)
+            lex("0x1.8"),
+            vec![(Token::HexadecimalFloatLiteral("0x1.8"), 0..5)]
         );
         assert_eq!(
-            parser::MathematicalSymbolParser::new().parse("≈"),
-            Ok(MathematicalSymbol::ApproximatelyEqual)
+            lex("0x1..0xF"),
+            vec![
+                (Token::HexadecimalLiteral("0x1"), 0..3),
+                (Token::Punctuation(Punctuation::DotDot), 3..5),
+                (Token::HexadecimalLiteral("0xF"), 5..8),
+            ]
         );
         assert_eq!(
-            parser::MathematicalSymbolParser::new().parse("≉"),
-            Ok(MathematicalSymbol::NotApproximatelyEqual)
+            lex("1._5"),
+            vec![
+                (Token::InvalidFloatLiteral("1._"), 0..3),
+                (Token::DecimalLiteral("5"), 3..4),
+            ]
         );
+        assert_eq!(lex("1.x"), vec![(Token::InvalidFloatLiteral("1.x"), 0..3)]);
+
+        // `DEC_LITERAL .` is composed by the parser, so ranges aren't mistaken
+        // for floats
         assert_eq!(
-            parser::MathematicalSymbolParser::new().parse("≡"),
-            Ok(MathematicalSymbol::IdenticalTo)
+            lex("12."),
+            vec![
+                (Token::DecimalLiteral("12"), 0..2),
+                (Token::Punctuation(Punctuation::Dot), 2..3),
+            ]
         );
         assert_eq!(
-            parser::MathematicalSymbolParser::new().parse("≢"),
-            Ok(MathematicalSymbol::NotIdenticalTo)
+            lex("1..5"),
+            vec![
+                (Token::DecimalLiteral("1"), 0..1),
+                (Token::Punctuation(Punctuation::DotDot), 1..3),
+                (Token::DecimalLiteral("5"), 3..4),
+            ]
         );
+    }
+
+    #[test]
+    fn test_lex_mathematical_constants() {
+        ////////////////////////////
+        // Mathematical Constants //
+        ////////////////////////////
+
+        // Pi Terminals (π)
         assert_eq!(
-            parser::MathematicalSymbolParser::new().parse("⊂"),
-            Ok(MathematicalSymbol::SubsetOf)
+            lex("pi"),
+            vec![(
+                Token::MathematicalConstant(MathematicalConstant::Pi {
+                    value: std::f64::consts::PI,
+                    digits: None,
+                }),
+                0..2
+            )]
+        );
+        assert_eq!(
+            lex("π"),
+            vec![(
+                Token::MathematicalConstant(MathematicalConstant::Pi {
+                    value: std::f64::consts::PI,
+                    digits: None,
+                }),
+                0..2
+            )]
+        );
+        assert_eq!(
+            lex("𝜋"),
+            vec![(
+                Token::MathematicalConstant(MathematicalConstant::Pi {
+                    value: std::f64::consts::PI,
+                    digits: None,
+                }),
+                0..4
+            )]
         );
+
+        // Euler's Number Terminals (e)
         assert_eq!(
-            parser::MathematicalSymbolParser::new().parse("⊃"),
-            Ok(MathematicalSymbol::SupersetOf)
+            lex("ℯ"),
+            vec![(
+                Token::MathematicalConstant(MathematicalConstant::Euler {
+                    value: std::f64::consts::E,
+                    digits: None,
+                }),
+                0..3
+            )]
+        );
+        assert_eq!(
+            lex("euler"),
+            vec![(
+                Token::MathematicalConstant(MathematicalConstant::Euler {
+                    value: std::f64::consts::E,
+                    digits: None,
+                }),
+                0..5
+            )]
         );
+
+        // Tau Terminals (τ)
         assert_eq!(
-            parser::MathematicalSymbolParser::new().parse("⊆"),
-            Ok(MathematicalSymbol::SubsetOfOrEqualTo)
+            lex("tau"),
+            vec![(
+                Token::MathematicalConstant(MathematicalConstant::Tau {
+                    value: std::f64::consts::TAU,
+                    digits: None,
+                }),
+                0..3
+            )]
+        );
+        assert_eq!(
+            lex("τ"),
+            vec![(
+                Token::MathematicalConstant(MathematicalConstant::Tau {
+                    value: std::f64::consts::TAU,
+                    digits: None,
+                }),
+                0..2
+            )]
+        );
+        assert_eq!(
+            lex("𝜏"),
+            vec![(
+                Token::MathematicalConstant(MathematicalConstant::Tau {
+                    value: std::f64::consts::TAU,
+                    digits: None,
+                }),
+                0..4
+            )]
         );
+
+        // Catalan's Constant Terminals (γ)
         assert_eq!(
-            parser::MathematicalSymbolParser::new().parse("⊇"),
-            Ok(MathematicalSymbol::SupersetOfOrEqualTo)
+            lex("catalan"),
+            vec![(
+                Token::MathematicalConstant(MathematicalConstant::Catalan {
+                    value: 0.915_965_594_177_219,
+                    digits: None,
+                }),
+                0..7
+            )]
         );
-        // assert_eq!(
-        //     parser::MathematicalSymbolParser::new().parse("⊕"),
-        //     Ok(MathematicalSymbol::Plus)
-        // );
-        // assert_eq!(
-        //     parser::MathematicalSymbolParser::new().parse("⊖"),
-        //     Ok(MathematicalSymbol::Minus)
-        // );
-        // assert_eq!(
-        //     parser::MathematicalSymbolParser::new().parse("⊗"),
-        //     Ok(MathematicalSymbol::Multiplication)
-        // );
-        // assert_eq!(
-        //     parser::MathematicalSymbolParser::new().parse("⊘"),
-        //     Ok(MathematicalSymbol::Division)
-        // );
-        // assert_eq!(
-        //     parser::MathematicalSymbolParser::new().parse("⊙"),
-        //     Ok(MathematicalSymbol::Circle)
-        // );
-        // assert_eq!(
-        //     parser::MathematicalSymbolParser::new().parse("⊥"),
-        //     Ok(MathematicalSymbol::Perpendicular)
-        // );
-        // assert_eq!(
-        //     parser::MathematicalSymbolParser::new().parse("⋅"),
-        //     Ok(MathematicalSymbol::Dot)
-        // );
-        // assert_eq!(
-        //     parser::MathematicalSymbolParser::new().parse("⋯"),
-        //     Ok(MathematicalSymbol::HorizontalEllipsis)
-        // );
-        // assert_eq!(
-        //     parser::MathematicalSymbolParser::new().parse("⌈"),
-        //     Ok(MathematicalSymbol::LeftCeiling)
-        // );
-        // assert_eq!(
-        //     parser::MathematicalSymbolParser::new().parse("⌉"),
-        //     Ok(MathematicalSymbol::RightCeiling)
-        // );
-        // assert_eq!(
-        //     parser::MathematicalSymbolParser::new().parse("⌊"),
-        //     Ok(MathematicalSymbol::LeftFloor)
-        // );
-        // assert_eq!(
-        //     parser::MathematicalSymbolParser::new().parse("⌋"),
-        //     Ok(MathematicalSymbol::RightFloor)
-        // );
-        // assert_eq!(
-        //     parser::MathematicalSymbolParser::new().parse("〈"),
-        //     Ok(MathematicalSymbol::LeftPointingAngleBracket)
-        // );
-        // assert_eq!(
-        //     parser::MathematicalSymbolParser::new().parse("〉"),
-        //     Ok(MathematicalSymbol::RightPointingAngleBracket)
-        // );
 
-        // TODO refactor to SuperScriptSymbol (e.g. ², ³, ⁴, ⁵, ⁶, ⁷, ⁸, ⁹)
-        // and SubScriptSymbol (e.g. ₀, ₁, ₂, ₃, ₄, ₅, ₆, ₇, ₈, ₉)
-        // and PowerParser (e.g. 10² -> 10^2, 10⁴³ -> 10^43
+        // Golden Ratio Terminals (φ)
+        assert_eq!(
+            lex("golden"),
+            vec![(
+                Token::MathematicalConstant(MathematicalConstant::GoldenRatio {
+                    value: 1.618033988749895,
+                    digits: None,
+                }),
+                0..6
+            )]
+        );
+        assert_eq!(
+            lex("φ"),
+            vec![(
+                Token::MathematicalConstant(MathematicalConstant::GoldenRatio {
+                    value: 1.618033988749895,
+                    digits: None,
+                }),
+                0..2
+            )]
+        );
+        assert_eq!(
+            lex("𝜙"),
+            vec![(
+                Token::MathematicalConstant(MathematicalConstant::GoldenRatio {
+                    value: 1.618033988749895,
+                    digits: None,
+                }),
+                0..4
+            )]
+        );
+
+        // Euler Mascheroni Constant Terminals (γ)
+        assert_eq!(
+            lex("eulermascheroni"),
+            vec![(
+                Token::MathematicalConstant(MathematicalConstant::EulerMascheroni {
+                    value: 0.5772156649015329,
+                    digits: None,
+                }),
+                0..15
+            )]
+        );
+        assert_eq!(
+            lex("eulergamma"),
+            vec![(
+                Token::MathematicalConstant(MathematicalConstant::EulerMascheroni {
+                    value: 0.5772156649015329,
+                    digits: None,
+                }),
+                0..10
+            )]
+        );
+        assert_eq!(
+            lex("γ"),
+            vec![(
+                Token::MathematicalConstant(MathematicalConstant::EulerMascheroni {
+                    value: 0.5772156649015329,
+                    digits: None,
+                }),
+                0..2
+            )]
+        );
+        assert_eq!(
+            lex("𝛾"),
+            vec![(
+                Token::MathematicalConstant(MathematicalConstant::EulerMascheroni {
+                    value: 0.5772156649015329,
+                    digits: None,
+                }),
+                0..4
+            )]
+        );
+
+        // Infinity Terminals (∞)
+        assert_eq!(
+            lex("Inf"),
+            vec![(
+                Token::MathematicalConstant(MathematicalConstant::Infinity {
+                    value: f64::INFINITY,
+                }),
+                0..3
+            )]
+        );
+        assert_eq!(
+            lex("∞"),
+            vec![(
+                Token::MathematicalConstant(MathematicalConstant::Infinity {
+                    value: f64::INFINITY,
+                }),
+                0..3
+            )]
+        );
+
+        // Not a Number Terminals (NaN)
+        assert_eq!(
+            lex("NaN"),
+            vec![(
+                Token::MathematicalConstant(MathematicalConstant::NotANumber),
+                0..3
+            )]
+        );
+
+        // Precision suffixes
+        assert_eq!(
+            lex("pi_40"),
+            vec![(
+                Token::MathematicalConstant(MathematicalConstant::Pi {
+                    value: std::f64::consts::PI,
+                    digits: Some(40),
+                }),
+                0..5
+            )]
+        );
+        assert_eq!(
+            lex("π_100"),
+            vec![(
+                Token::MathematicalConstant(MathematicalConstant::Pi {
+                    value: std::f64::consts::PI,
+                    digits: Some(100),
+                }),
+                0..6
+            )]
+        );
+        assert_eq!(
+            lex("γ_20"),
+            vec![(
+                Token::MathematicalConstant(MathematicalConstant::EulerMascheroni {
+                    value: 0.577_215_664_901_532_9,
+                    digits: Some(20),
+                }),
+                0..5
+            )]
+        );
+        // The parser checks the range of the digits (see `constant_digits`)
+        assert_eq!(
+            lex("pi_0"),
+            vec![(
+                Token::MathematicalConstant(MathematicalConstant::Pi {
+                    value: std::f64::consts::PI,
+                    digits: Some(0),
+                }),
+                0..4
+            )]
+        );
+        assert_eq!(
+            lex("pi_99999999999"),
+            vec![(
+                Token::MathematicalConstant(MathematicalConstant::Pi {
+                    value: std::f64::consts::PI,
+                    digits: Some(u32::MAX),
+                }),
+                0..14
+            )]
+        );
+        assert_eq!(lex("pi_"), vec![(Token::Identifier("pi_"), 0..3)]);
+        assert_eq!(lex("pi_x"), vec![(Token::Identifier("pi_x"), 0..4)]);
+        assert_eq!(lex("pie_40"), vec![(Token::Identifier("pie_40"), 0..6)]);
+    }
+
+    #[test]
+    fn test_lex_superscript_numeric_literals() {
+        // SuperscriptIntegerLiteral
+        assert_eq!(
+            lex("¹"),
+            vec![(
+                Token::SuperscriptIntegerLiteral(SuperscriptIntegerLiteral { n: 1 }),
+                0..2
+            )]
+        );
+        assert_eq!(
+            lex("¹²"),
+            vec![(
+                Token::SuperscriptIntegerLiteral(SuperscriptIntegerLiteral {
+                    n: 12
+                }),
+                0..4
+            )]
+        );
+
+        // SuperscriptDecimalDigit = "⁰" | "¹" | "²" | "³" | "⁴" | "⁵" | "⁶" | "⁷" |
+        // "⁸" | "⁹"
+        assert_eq!(
+            lex("⁰"),
+            vec![(
+                Token::SuperscriptIntegerLiteral(SuperscriptIntegerLiteral { n: 0 }),
+                0..3
+            )]
+        );
+        assert_eq!(
+            lex("¹"),
+            vec![(
+                Token::SuperscriptIntegerLiteral(SuperscriptIntegerLiteral { n: 1 }),
+                0..2
+            )]
+        );
+        assert_eq!(
+            lex("²"),
+            vec![(
+                Token::SuperscriptIntegerLiteral(SuperscriptIntegerLiteral { n: 2 }),
+                0..2
+            )]
+        );
+        assert_eq!(
+            lex("³"),
+            vec![(
+                Token::SuperscriptIntegerLiteral(SuperscriptIntegerLiteral { n: 3 }),
+                0..2
+            )]
+        );
+        assert_eq!(
+            lex("⁴"),
+            vec![(
+                Token::SuperscriptIntegerLiteral(SuperscriptIntegerLiteral { n: 4 }),
+                0..3
+            )]
+        );
+        assert_eq!(
+            lex("⁵"),
+            vec![(
+                Token::SuperscriptIntegerLiteral(SuperscriptIntegerLiteral { n: 5 }),
+                0..3
+            )]
+        );
+        assert_eq!(
+            lex("⁶"),
+            vec![(
+                Token::SuperscriptIntegerLiteral(SuperscriptIntegerLiteral { n: 6 }),
+                0..3
+            )]
+        );
+        assert_eq!(
+            lex("⁷"),
+            vec![(
+                Token::SuperscriptIntegerLiteral(SuperscriptIntegerLiteral { n: 7 }),
+                0..3
+            )]
+        );
+        assert_eq!(
+            lex("⁸"),
+            vec![(
+                Token::SuperscriptIntegerLiteral(SuperscriptIntegerLiteral { n: 8 }),
+                0..3
+            )]
+        );
+        assert_eq!(
+            lex("⁹"),
+            vec![(
+                Token::SuperscriptIntegerLiteral(SuperscriptIntegerLiteral { n: 9 }),
+                0..3
+            )]
+        );
+    }
+
+    #[test]
+    fn test_lex_superscript_punctuation() {
+        // SuperscriptPunctuation = "⁺" | "⁻" | "⁽" | "⁾"
+        assert_eq!(
+            lex("⁺"),
+            vec![(
+                Token::SuperscriptPunctuation(SuperscriptPunctuation::Plus),
+                0..3
+            )]
+        );
+        assert_eq!(
+            lex("⁻"),
+            vec![(
+                Token::SuperscriptPunctuation(SuperscriptPunctuation::Minus),
+                0..3
+            )]
+        );
+        assert_eq!(
+            lex("⁽"),
+            vec![(
+                Token::SuperscriptPunctuation(SuperscriptPunctuation::LeftParen),
+                0..3
+            )]
+        );
+        assert_eq!(
+            lex("⁾"),
+            vec![(
+                Token::SuperscriptPunctuation(SuperscriptPunctuation::RightParen),
+                0..3
+            )]
+        );
+    }
+
+    #[test]
+    fn test_lex_superscript_identifiers() {
+        assert_eq!(lex("ⁿ"), vec![(Token::SuperscriptIdentifier("ⁿ"), 0..3)]);
+        assert_eq!(lex("ᵃᵇ"), vec![(Token::SuperscriptIdentifier("ᵃᵇ"), 0..6)]);
+        // Superscript letters end an identifier, rather than continuing it
+        assert_eq!(
+            lex("xⁿ"),
+            vec![
+                (Token::Identifier("x"), 0..1),
+                (Token::SuperscriptIdentifier("ⁿ"), 1..4)
+            ]
+        );
+        assert_eq!(
+            lex("x⁻ⁿ"),
+            vec![
+                (Token::Identifier("x"), 0..1),
+                (
+                    Token::SuperscriptPunctuation(SuperscriptPunctuation::Minus),
+                    1..4
+                ),
+                (Token::SuperscriptIdentifier("ⁿ"), 4..7)
+            ]
+        );
+        assert_eq!(
+            lexer::superscript_identifier("ᵃⁿ"),
+            Identifier {
+                name: String::from("an")
+            }
+        );
+    }
+
+    #[test]
+    fn test_lex_units() {
+        fn tokens(source: &str) -> Vec<(usize, Token<'_>, usize)> {
+            Lexer::new(source).map(Result::unwrap).collect()
+        }
+        assert_eq!(
+            tokens("9.81 m/s²"),
+            vec![
+                (0, Token::FloatLiteral("9.81"), 4),
+                (5, Token::UnitSymbol("m"), 6),
+                (6, Token::UnitOperator(UnitOperator::Division), 7),
+                (7, Token::UnitSymbol("s"), 8),
+                (
+                    8,
+                    Token::SuperscriptIntegerLiteral(SuperscriptIntegerLiteral {
+                        n: 2
+                    }),
+                    10
+                ),
+            ]
+        );
+        assert_eq!(
+            tokens("3 kg·m"),
+            vec![
+                (0, Token::DecimalLiteral("3"), 1),
+                (2, Token::UnitSymbol("kg"), 4),
+                (4, Token::UnitOperator(UnitOperator::Multiplication), 6),
+                (6, Token::UnitSymbol("m"), 7),
+            ]
+        );
+
+        // The middle dot isn't part of an identifier
+        assert_eq!(
+            tokens("x·y"),
+            vec![
+                (0, Token::Identifier("x"), 1),
+                (
+                    1,
+                    Token::MathematicalSymbol(MathematicalSymbol::DotOperator),
+                    3
+                ),
+                (3, Token::Identifier("y"), 4),
+            ]
+        );
+
+        // An operator is only part of a unit if a unit symbol follows it
+        assert_eq!(
+            tokens("3 m/x"),
+            vec![
+                (0, Token::DecimalLiteral("3"), 1),
+                (2, Token::UnitSymbol("m"), 3),
+                (3, Token::Punctuation(Punctuation::Slash), 4),
+                (4, Token::Identifier("x"), 5),
+            ]
+        );
+        assert_eq!(
+            tokens("6 m / s"),
+            vec![
+                (0, Token::DecimalLiteral("6"), 1),
+                (2, Token::UnitSymbol("m"), 3),
+                (4, Token::Punctuation(Punctuation::Slash), 5),
+                (6, Token::Identifier("s"), 7),
+            ]
+        );
+
+        // Identifiers which aren't units, or which don't follow a number, are
+        // left as is
+        assert_eq!(
+            tokens("3 x"),
+            vec![
+                (0, Token::DecimalLiteral("3"), 1),
+                (2, Token::Identifier("x"), 3),
+            ]
+        );
+        assert_eq!(tokens("m"), vec![(0, Token::Identifier("m"), 1)]);
+
+        // The differential of an enclosing integral isn't a unit (e.g. `ds`
+        // rather than decisecond), but is once the integral ends
+        let integral = tokens("∫(s in 0..1) 2 ds + 1 ds");
+        assert_eq!(
+            integral[integral.len() - 5..],
+            [
+                (15, Token::DecimalLiteral("2"), 16),
+                (17, Token::Identifier("ds"), 19),
+                (20, Token::Punctuation(Punctuation::Plus), 21),
+                (22, Token::DecimalLiteral("1"), 23),
+                (24, Token::UnitSymbol("ds"), 26),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_lex_imaginary_literals() {
+        fn tokens(source: &str) -> Vec<(usize, Token<'_>, usize)> {
+            Lexer::new(source).map(Result::unwrap).collect()
+        }
+        assert_eq!(tokens("2i"), vec![(0, Token::ImaginaryLiteral("2i"), 2)]);
+        assert_eq!(
+            tokens("1.5𝑖"),
+            vec![(0, Token::ImaginaryLiteral("1.5𝑖"), 7)]
+        );
+        assert_eq!(tokens("2i8"), vec![(0, Token::DecimalLiteral("2i8"), 3)]);
+        assert_eq!(
+            tokens("3 + 4i"),
+            vec![
+                (0, Token::DecimalLiteral("3"), 1),
+                (2, Token::Punctuation(Punctuation::Plus), 3),
+                (4, Token::ImaginaryLiteral("4i"), 6),
+            ]
+        );
+
+        // The imaginary unit `𝑖` isn't part of an identifier, while `i` is
+        assert_eq!(
+            tokens("𝑖π"),
+            vec![
+                (0, Token::ImaginaryLiteral("𝑖"), 4),
+                (
+                    4,
+                    Token::MathematicalConstant(MathematicalConstant::Pi {
+                        value: std::f64::consts::PI,
+                        digits: None
+                    }),
+                    6
+                ),
+            ]
+        );
+        assert_eq!(tokens("iπ"), vec![(0, Token::Identifier("iπ"), 3)]);
+        assert_eq!(tokens("i"), vec![(0, Token::Identifier("i"), 1)]);
+    }
+
+    #[test]
+    fn test_lex_rational_literals() {
+        fn tokens(source: &str) -> Vec<(usize, Token<'_>, usize)> {
+            Lexer::new(source).map(Result::unwrap).collect()
+        }
+        assert_eq!(tokens("3/4r"), vec![(0, Token::RationalLiteral("3/4r"), 4)]);
+        assert_eq!(tokens("¾"), vec![(0, Token::RationalLiteral("¾"), 2)]);
+        assert_eq!(tokens("1½"), vec![(0, Token::RationalLiteral("1½"), 3)]);
+        assert_eq!(
+            tokens("2 ½"),
+            vec![
+                (0, Token::DecimalLiteral("2"), 1),
+                (2, Token::RationalLiteral("½"), 4),
+            ]
+        );
+
+        // Quotients without the `r` suffix are divisions
+        assert_eq!(
+            tokens("3/4"),
+            vec![
+                (0, Token::DecimalLiteral("3"), 1),
+                (1, Token::Punctuation(Punctuation::Slash), 2),
+                (2, Token::DecimalLiteral("4"), 3),
+            ]
+        );
+        assert_eq!(
+            tokens("3/4rad"),
+            vec![
+                (0, Token::DecimalLiteral("3"), 1),
+                (1, Token::Punctuation(Punctuation::Slash), 2),
+                (2, Token::DecimalLiteral("4"), 3),
+                (3, Token::Identifier("rad"), 6),
+            ]
+        );
+        assert_eq!(
+            tokens("3u8/4r"),
+            vec![
+                (0, Token::DecimalLiteral("3u8"), 3),
+                (3, Token::Punctuation(Punctuation::Slash), 4),
+                (4, Token::DecimalLiteral("4"), 5),
+                (5, Token::Identifier("r"), 6),
+            ]
+        );
+        assert_eq!(
+            tokens("x½"),
+            vec![
+                (0, Token::Identifier("x"), 1),
+                (1, Token::RationalLiteral("½"), 3),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_lex_subscript_symbols() {
+        // SubscriptSymbol = "₀" | "₁" | "₂" | "₃" | "₄" | "₅" | "₆" | "₇" | "₈" |
+        //                   "₉" | "₊" | "₋" | "₍" | "₎"
+        assert_eq!(
+            lex("₀"),
+            vec![(Token::SubscriptSymbol(SubscriptSymbol::Zero), 0..3)]
+        );
+        assert_eq!(
+            lex("₁"),
+            vec![(Token::SubscriptSymbol(SubscriptSymbol::One), 0..3)]
+        );
+        assert_eq!(
+            lex("₂"),
+            vec![(Token::SubscriptSymbol(SubscriptSymbol::Two), 0..3)]
+        );
+        assert_eq!(
+            lex("₃"),
+            vec![(Token::SubscriptSymbol(SubscriptSymbol::Three), 0..3)]
+        );
+        assert_eq!(
+            lex("₄"),
+            vec![(Token::SubscriptSymbol(SubscriptSymbol::Four), 0..3)]
+        );
+        assert_eq!(
+            lex("₅"),
+            vec![(Token::SubscriptSymbol(SubscriptSymbol::Five), 0..3)]
+        );
+        assert_eq!(
+            lex("₆"),
+            vec![(Token::SubscriptSymbol(SubscriptSymbol::Six), 0..3)]
+        );
+        assert_eq!(
+            lex("₇"),
+            vec![(Token::SubscriptSymbol(SubscriptSymbol::Seven), 0..3)]
+        );
+        assert_eq!(
+            lex("₈"),
+            vec![(Token::SubscriptSymbol(SubscriptSymbol::Eight), 0..3)]
+        );
+        assert_eq!(
+            lex("₉"),
+            vec![(Token::SubscriptSymbol(SubscriptSymbol::Nine), 0..3)]
+        );
+        assert_eq!(
+            lex("₊"),
+            vec![(Token::SubscriptSymbol(SubscriptSymbol::Plus), 0..3)]
+        );
+        assert_eq!(
+            lex("₋"),
+            vec![(Token::SubscriptSymbol(SubscriptSymbol::Minus), 0..3)]
+        );
+        assert_eq!(
+            lex("₍"),
+            vec![(Token::SubscriptSymbol(SubscriptSymbol::LeftParen), 0..3)]
+        );
+        assert_eq!(
+            lex("₎"),
+            vec![(Token::SubscriptSymbol(SubscriptSymbol::RightParen), 0..3)]
+        );
+
+        // Only subscript digits continue an identifier; signs and parentheses
+        // are lexed separately
+        assert_eq!(lex("x₁"), vec![(Token::Identifier("x₁"), 0..4)]);
+        assert_eq!(lex("a₁₂"), vec![(Token::Identifier("a₁₂"), 0..7)]);
+        assert_eq!(lex("log₂"), vec![(Token::Identifier("log₂"), 0..6)]);
+        assert_eq!(
+            lex("x₍₋₁₎"),
+            vec![
+                (Token::Identifier("x"), 0..1),
+                (Token::SubscriptSymbol(SubscriptSymbol::LeftParen), 1..4),
+                (Token::SubscriptSymbol(SubscriptSymbol::Minus), 4..7),
+                (Token::SubscriptSymbol(SubscriptSymbol::One), 7..10),
+                (Token::SubscriptSymbol(SubscriptSymbol::RightParen), 10..13),
+            ]
+        );
+        assert_eq!(
+            lex("₁x"),
+            vec![
+                (Token::SubscriptSymbol(SubscriptSymbol::One), 0..3),
+                (Token::Identifier("x"), 3..4),
+            ]
+        );
+
+        assert_eq!(
+            parser::SubscriptSymbolParser::new().parse(Lexer::new("₉")),
+            Ok(SubscriptSymbol::Nine)
+        );
+        assert_eq!(
+            parser::IdentifierParser::new().parse(Lexer::new("a₁₂")),
+            Ok(Identifier {
+                name: String::from("a₁₂")
+            })
+        );
+    }
+
+    #[test]
+    fn test_lex_mathematical_symbols() {
+        // Powers (e.g. 2²)
+        assert_eq!(
+            parser::MathematicalSymbolParser::new().parse(Lexer::new("¹²")),
+            Ok(MathematicalSymbol::Power { exponent: 12 })
+        );
+        assert_eq!(
+            parser::MathematicalSymbolParser::new().parse(Lexer::new("⁻⁵³")),
+            Ok(MathematicalSymbol::Power { exponent: -53 })
+        );
+        assert_eq!(
+            parser::MathematicalSymbolParser::new().parse(Lexer::new("⁺⁵³")),
+            Ok(MathematicalSymbol::Power { exponent: 53 })
+        );
+
+        // Roots (e.g. ²√4 = 2)
+        assert_eq!(
+            lex("√"),
+            vec![(
+                Token::MathematicalSymbol(MathematicalSymbol::Root { exponent: 2 }),
+                0..3
+            )]
+        );
+        assert_eq!(
+            parser::MathematicalSymbolParser::new().parse(Lexer::new("²√")),
+            Ok(MathematicalSymbol::Root { exponent: 2 })
+        );
+        assert_eq!(
+            parser::MathematicalSymbolParser::new().parse(Lexer::new("¹²√")),
+            Ok(MathematicalSymbol::Root { exponent: 12 })
+        );
+        assert_eq!(
+            parser::MathematicalSymbolParser::new().parse(Lexer::new("⁻⁵³√")),
+            Ok(MathematicalSymbol::Root { exponent: -53 })
+        );
+        assert_eq!(
+            parser::MathematicalSymbolParser::new().parse(Lexer::new("⁺⁵³√")),
+            Ok(MathematicalSymbol::Root { exponent: 53 })
+        );
+        assert_eq!(
+            lex("∛"),
+            vec![(
+                Token::MathematicalSymbol(MathematicalSymbol::Root { exponent: 3 }),
+                0..3
+            )]
+        );
+        assert_eq!(
+            lex("∜"),
+            vec![(
+                Token::MathematicalSymbol(MathematicalSymbol::Root { exponent: 4 }),
+                0..3
+            )]
+        );
+
+        // Indexed roots are lexed as a superscript literal followed by a radical
+        assert_eq!(
+            lex("¹²√"),
+            vec![
+                (
+                    Token::SuperscriptIntegerLiteral(SuperscriptIntegerLiteral {
+                        n: 12,
+                    }),
+                    0..4
+                ),
+                (
+                    Token::MathematicalSymbol(MathematicalSymbol::Root {
+                        exponent: 2,
+                    }),
+                    4..7
+                ),
+            ]
+        );
+
+        // Fractions (e.g. 1//2 = ½)
+
+        // General Mathematical Symbols (e.g. ÷, ∫, etc.)
+        assert_eq!(
+            lex("÷"),
+            vec![(
+                Token::MathematicalSymbol(MathematicalSymbol::Division),
+                0..2
+            )]
+        );
+        assert_eq!(
+            lex("∝"),
+            vec![(
+                Token::MathematicalSymbol(MathematicalSymbol::ProportionalTo),
+                0..3
+            )]
+        );
+        // assert_eq!(
+        //     parser::MathematicalSymbolParser::new().parse("∠"),
+        //     Ok(MathematicalSymbol::Angle)
+        // );
+        // assert_eq!(
+        //     parser::MathematicalSymbolParser::new().parse("∧"),
+        //     Ok(MathematicalSymbol::LogicalAnd)
+        // );
+        // assert_eq!(
+        //     parser::MathematicalSymbolParser::new().parse("∨"),
+        //     Ok(MathematicalSymbol::LogicalOr)
+        // );
+        assert_eq!(
+            lex("∩"),
+            vec![(
+                Token::MathematicalSymbol(MathematicalSymbol::Intersection),
+                0..3
+            )]
+        );
+        assert_eq!(
+            lex("∪"),
+            vec![(Token::MathematicalSymbol(MathematicalSymbol::Union), 0..3)]
+        );
+        assert_eq!(
+            lex("∫"),
+            vec![(
+                Token::MathematicalSymbol(MathematicalSymbol::Integral),
+                0..3
+            )]
+        );
+        assert_eq!(
+            lex("∑"),
+            vec![(Token::MathematicalSymbol(MathematicalSymbol::Sum), 0..3)]
+        );
+        assert_eq!(
+            lex("∏"),
+            vec![(Token::MathematicalSymbol(MathematicalSymbol::Product), 0..3)]
+        );
+        assert_eq!(
+            lex("∴"),
+            vec![(
+                Token::MathematicalSymbol(MathematicalSymbol::Therefore),
+                0..3
+            )]
+        );
+        assert_eq!(
+            lex("∵"),
+            vec![(Token::MathematicalSymbol(MathematicalSymbol::Because), 0..3)]
+        );
+        assert_eq!(
+            lex("≈"),
+            vec![(
+                Token::MathematicalSymbol(MathematicalSymbol::ApproximatelyEqual),
+                0..3
+            )]
+        );
+        assert_eq!(
+            lex("≉"),
+            vec![(
+                Token::MathematicalSymbol(MathematicalSymbol::NotApproximatelyEqual),
+                0..3
+            )]
+        );
+        assert_eq!(
+            lex("≡"),
+            vec![(
+                Token::MathematicalSymbol(MathematicalSymbol::IdenticalTo),
+                0..3
+            )]
+        );
+        assert_eq!(
+            lex("≢"),
+            vec![(
+                Token::MathematicalSymbol(MathematicalSymbol::NotIdenticalTo),
+                0..3
+            )]
+        );
+        assert_eq!(
+            lex("⊂"),
+            vec![(
+                Token::MathematicalSymbol(MathematicalSymbol::SubsetOf),
+                0..3
+            )]
+        );
+        assert_eq!(
+            lex("⊃"),
+            vec![(
+                Token::MathematicalSymbol(MathematicalSymbol::SupersetOf),
+                0..3
+            )]
+        );
+        assert_eq!(
+            lex("⊆"),
+            vec![(
+                Token::MathematicalSymbol(MathematicalSymbol::SubsetOfOrEqualTo),
+                0..3
+            )]
+        );
+        assert_eq!(
+            lex("⊇"),
+            vec![(
+                Token::MathematicalSymbol(MathematicalSymbol::SupersetOfOrEqualTo),
+                0..3
+            )]
+        );
+        assert_eq!(
+            lex("∈"),
+            vec![(
+                Token::MathematicalSymbol(MathematicalSymbol::ElementOf),
+                0..3
+            )]
+        );
+        assert_eq!(
+            lex("∉"),
+            vec![(
+                Token::MathematicalSymbol(MathematicalSymbol::NotElementOf),
+                0..3
+            )]
+        );
+        assert_eq!(
+            lex("∀"),
+            vec![(Token::MathematicalSymbol(MathematicalSymbol::ForAll), 0..3)]
+        );
+        assert_eq!(
+            lex("∃"),
+            vec![(Token::MathematicalSymbol(MathematicalSymbol::Exists), 0..3)]
+        );
+        // assert_eq!(
+        //     parser::MathematicalSymbolParser::new().parse("⊕"),
+        //     Ok(MathematicalSymbol::Plus)
+        // );
+        // assert_eq!(
+        //     parser::MathematicalSymbolParser::new().parse("⊖"),
+        //     Ok(MathematicalSymbol::Minus)
+        // );
+        // assert_eq!(
+        //     parser::MathematicalSymbolParser::new().parse("⊗"),
+        //     Ok(MathematicalSymbol::Multiplication)
+        // );
+        // assert_eq!(
+        //     parser::MathematicalSymbolParser::new().parse("⊘"),
+        //     Ok(MathematicalSymbol::Division)
+        // );
+        // assert_eq!(
+        //     parser::MathematicalSymbolParser::new().parse("⊙"),
+        //     Ok(MathematicalSymbol::Circle)
+        // );
+        // assert_eq!(
+        //     parser::MathematicalSymbolParser::new().parse("⊥"),
+        //     Ok(MathematicalSymbol::Perpendicular)
+        // );
+        // assert_eq!(
+        //     parser::MathematicalSymbolParser::new().parse("⋅"),
+        //     Ok(MathematicalSymbol::Dot)
+        // );
+        // assert_eq!(
+        //     parser::MathematicalSymbolParser::new().parse("⋯"),
+        //     Ok(MathematicalSymbol::HorizontalEllipsis)
+        // );
+        // assert_eq!(
+        //     parser::MathematicalSymbolParser::new().parse("⌈"),
+        //     Ok(MathematicalSymbol::LeftCeiling)
+        // );
+        // assert_eq!(
+        //     parser::MathematicalSymbolParser::new().parse("⌉"),
+        //     Ok(MathematicalSymbol::RightCeiling)
+        // );
+        // assert_eq!(
+        //     parser::MathematicalSymbolParser::new().parse("⌊"),
+        //     Ok(MathematicalSymbol::LeftFloor)
+        // );
+        // assert_eq!(
+        //     parser::MathematicalSymbolParser::new().parse("⌋"),
+        //     Ok(MathematicalSymbol::RightFloor)
+        // );
+        // assert_eq!(
+        //     parser::MathematicalSymbolParser::new().parse("〈"),
+        //     Ok(MathematicalSymbol::LeftPointingAngleBracket)
+        // );
+        // assert_eq!(
+        //     parser::MathematicalSymbolParser::new().parse("〉"),
+        //     Ok(MathematicalSymbol::RightPointingAngleBracket)
+        // );
+
+        // TODO refactor to SuperScriptSymbol (e.g. ², ³, ⁴, ⁵, ⁶, ⁷, ⁸, ⁹)
+        // and PowerParser (e.g. 10² -> 10^2, 10⁴³ -> 10^43
+    }
+
+    #[test]
+    fn test_lex_character_and_string_literals() {
+        assert_eq!(
+            lex(r#""Hello""#),
+            vec![(Token::StringLiteral(r#""Hello""#), 0..7)]
+        );
+        assert_eq!(
+            lex(r#""a \"b\" c""#),
+            vec![(Token::StringLiteral(r#""a \"b\" c""#), 0..11)]
+        );
+        assert_eq!(
+            lex("\"a\\\n b\""),
+            vec![(Token::StringLiteral("\"a\\\n b\""), 0..7)]
+        );
+        // Malformed escapes are still lexed as part of the string literal
+        assert_eq!(
+            lex(r#""\q""#),
+            vec![(Token::StringLiteral(r#""\q""#), 0..4)]
+        );
+        assert_eq!(
+            lex(r#""a" "b""#),
+            vec![
+                (Token::StringLiteral(r#""a""#), 0..3),
+                (Token::StringLiteral(r#""b""#), 4..7),
+            ]
+        );
+
+        assert_eq!(
+            lex(r##"r"a\b""##),
+            vec![(Token::RawStringLiteral(r##"r"a\b""##), 0..6)]
+        );
+        assert_eq!(
+            lex(r###"r#"a"#"b"# c"###),
+            vec![
+                (Token::RawStringLiteral(r###"r#"a"#"###), 0..6),
+                (Token::StringLiteral(r#""b""#), 6..9),
+                (Token::Punctuation(Punctuation::Pound), 9..10),
+                (Token::Identifier("c"), 11..12),
+            ]
+        );
+        assert_eq!(
+            lex(r#"b"abc""#),
+            vec![(Token::ByteStringLiteral(r#"b"abc""#), 0..6)]
+        );
+        assert_eq!(
+            lex(r###"br#"a"b"#"###),
+            vec![(Token::RawByteStringLiteral(r###"br#"a"b"#"###), 0..9)]
+        );
+
+        // `r`, `b`, and `br` are otherwise ordinary identifiers
+        assert_eq!(
+            lex("r b br rb"),
+            vec![
+                (Token::Identifier("r"), 0..1),
+                (Token::Identifier("b"), 2..3),
+                (Token::Identifier("br"), 4..6),
+                (Token::Identifier("rb"), 7..9),
+            ]
+        );
+
+        // Character and byte literals are composed from their opening
+        // apostrophe by the `Lexer`
+        fn tokens(source: &str) -> Vec<(usize, Token<'_>, usize)> {
+            Lexer::new(source).map(Result::unwrap).collect()
+        }
+        assert_eq!(tokens("'a'"), vec![(0, Token::CharLiteral("'a'"), 3)]);
+        assert_eq!(tokens("' '"), vec![(0, Token::CharLiteral("' '"), 3)]);
+        assert_eq!(tokens(r"'\''"), vec![(0, Token::CharLiteral(r"'\''"), 4)]);
+        assert_eq!(
+            tokens(r"'\u{1F600}'"),
+            vec![(0, Token::CharLiteral(r"'\u{1F600}'"), 11)]
+        );
+        assert_eq!(tokens("b'x'"), vec![(0, Token::ByteLiteral("b'x'"), 4)]);
+        assert_eq!(
+            tokens("'a' 'b'"),
+            vec![
+                (0, Token::CharLiteral("'a'"), 3),
+                (4, Token::CharLiteral("'b'"), 7),
+            ]
+        );
+        // Empty and multi-codepoint literals are reported by the parser
+        assert_eq!(tokens("''"), vec![(0, Token::CharLiteral("''"), 2)]);
+        assert_eq!(tokens("'ab'"), vec![(0, Token::CharLiteral("'ab'"), 4)]);
+
+        // Apostrophes which aren't closed within the same word are label
+        // punctuation
+        assert_eq!(
+            tokens("'outer: loop { break 'outer; }"),
+            vec![
+                (0, Token::Punctuation(Punctuation::Apostrophe), 1),
+                (1, Token::Identifier("outer"), 6),
+                (6, Token::Punctuation(Punctuation::Colon), 7),
+                (8, Token::Keyword(Keyword::Loop), 12),
+                (13, Token::Delimiter(Delimiter::LeftBrace), 14),
+                (15, Token::Keyword(Keyword::Break), 20),
+                (21, Token::Punctuation(Punctuation::Apostrophe), 22),
+                (22, Token::Identifier("outer"), 27),
+                (27, Token::Punctuation(Punctuation::Semicolon), 28),
+                (29, Token::Delimiter(Delimiter::RightBrace), 30),
+            ]
+        );
+        assert_eq!(
+            tokens("b 'a"),
+            vec![
+                (0, Token::Identifier("b"), 1),
+                (2, Token::Punctuation(Punctuation::Apostrophe), 3),
+                (3, Token::Identifier("a"), 4),
+            ]
+        );
+        assert_eq!(
+            lex("'"),
+            vec![(Token::Punctuation(Punctuation::Apostrophe), 0..1)]
+        );
+        assert_eq!(
+            lex("\""),
+            vec![(Token::Punctuation(Punctuation::Quote), 0..1)]
+        );
+    }
+
+    #[test]
+    fn test_lex_keywords() {
+        assert_eq!(lex("as"), vec![(Token::Keyword(Keyword::As), 0..2)]);
+        assert_eq!(lex("assert"), vec![(Token::Keyword(Keyword::Assert), 0..6)]);
+        assert_eq!(lex("break"), vec![(Token::Keyword(Keyword::Break), 0..5)]);
+        assert_eq!(lex("const"), vec![(Token::Keyword(Keyword::Const), 0..5)]);
+        assert_eq!(
+            lex("continue"),
+            vec![(Token::Keyword(Keyword::Continue), 0..8)]
+        );
+        assert_eq!(
+            lex("crate"),
+            vec![(
+                Token::Keyword(Keyword::Reserved(ReservedKeyword::Crate)),
+                0..5
+            )]
+        );
+        assert_eq!(lex("else"), vec![(Token::Keyword(Keyword::Else), 0..4)]);
+        assert_eq!(lex("enum"), vec![(Token::Keyword(Keyword::Enum), 0..4)]);
+        assert_eq!(
+            lex("extern"),
+            vec![(
+                Token::Keyword(Keyword::Reserved(ReservedKeyword::Extern)),
+                0..6
+            )]
+        );
+        assert_eq!(lex("false"), vec![(Token::Keyword(Keyword::False), 0..5)]);
+        assert_eq!(lex("False"), vec![(Token::Keyword(Keyword::False), 0..5)]);
+        assert_eq!(lex("fn"), vec![(Token::Keyword(Keyword::Fn), 0..2)]);
+        assert_eq!(lex("for"), vec![(Token::Keyword(Keyword::For), 0..3)]);
+        assert_eq!(lex("if"), vec![(Token::Keyword(Keyword::If), 0..2)]);
+        assert_eq!(lex("impl"), vec![(Token::Keyword(Keyword::Impl), 0..4)]);
+        assert_eq!(lex("in"), vec![(Token::Keyword(Keyword::In), 0..2)]);
+        assert_eq!(lex("loop"), vec![(Token::Keyword(Keyword::Loop), 0..4)]);
+        assert_eq!(lex("match"), vec![(Token::Keyword(Keyword::Match), 0..5)]);
+        assert_eq!(
+            lex("missing"),
+            vec![(Token::Keyword(Keyword::Missing), 0..7)]
+        );
+        assert_eq!(
+            lex("Missing"),
+            vec![(Token::Keyword(Keyword::Missing), 0..7)]
+        );
+        assert_eq!(lex("mod"), vec![(Token::Keyword(Keyword::Mod), 0..3)]);
+        assert_eq!(lex("pub"), vec![(Token::Keyword(Keyword::Pub), 0..3)]);
+        assert_eq!(lex("return"), vec![(Token::Keyword(Keyword::Return), 0..6)]);
+        assert_eq!(
+            lex("self"),
+            vec![(Token::Keyword(Keyword::SelfValue), 0..4)]
+        );
+        assert_eq!(lex("Self"), vec![(Token::Keyword(Keyword::SelfType), 0..4)]);
+        assert_eq!(lex("static"), vec![(Token::Keyword(Keyword::Static), 0..6)]);
+        assert_eq!(lex("struct"), vec![(Token::Keyword(Keyword::Struct), 0..6)]);
+        assert_eq!(lex("super"), vec![(Token::Keyword(Keyword::Super), 0..5)]);
+        assert_eq!(lex("trait"), vec![(Token::Keyword(Keyword::Trait), 0..5)]);
+        assert_eq!(lex("true"), vec![(Token::Keyword(Keyword::True), 0..4)]);
+        assert_eq!(lex("True"), vec![(Token::Keyword(Keyword::True), 0..4)]);
+        assert_eq!(lex("type"), vec![(Token::Keyword(Keyword::Type), 0..4)]);
+        assert_eq!(lex("use"), vec![(Token::Keyword(Keyword::Use), 0..3)]);
+        assert_eq!(lex("where"), vec![(Token::Keyword(Keyword::Where), 0..5)]);
+        assert_eq!(lex("while"), vec![(Token::Keyword(Keyword::While), 0..5)]);
+    }
+
+    #[test]
+    fn text_lex_reserved_keywords() {
+        assert_eq!(
+            lex("abstract"),
+            vec![(
+                Token::Keyword(Keyword::Reserved(ReservedKeyword::Abstract)),
+                0..8
+            )]
+        );
+        assert_eq!(
+            lex("async"),
+            vec![(
+                Token::Keyword(Keyword::Reserved(ReservedKeyword::Async)),
+                0..5
+            )]
+        );
+        assert_eq!(
+            lex("await"),
+            vec![(
+                Token::Keyword(Keyword::Reserved(ReservedKeyword::Await)),
+                0..5
+            )]
+        );
+        assert_eq!(
+            lex("crate"),
+            vec![(
+                Token::Keyword(Keyword::Reserved(ReservedKeyword::Crate)),
+                0..5
+            )]
+        );
+        assert_eq!(
+            lex("do"),
+            vec![(Token::Keyword(Keyword::Reserved(ReservedKeyword::Do)), 0..2)]
+        );
+        assert_eq!(
+            lex("dyn"),
+            vec![(
+                Token::Keyword(Keyword::Reserved(ReservedKeyword::Dyn)),
+                0..3
+            )]
+        );
+        assert_eq!(
+            lex("export"),
+            vec![(
+                Token::Keyword(Keyword::Reserved(ReservedKeyword::Export)),
+                0..6
+            )]
+        );
+        assert_eq!(
+            lex("extern"),
+            vec![(
+                Token::Keyword(Keyword::Reserved(ReservedKeyword::Extern)),
+                0..6
+            )]
+        );
+        assert_eq!(
+            lex("final"),
+            vec![(
+                Token::Keyword(Keyword::Reserved(ReservedKeyword::Final)),
+                0..5
+            )]
+        );
+        assert_eq!(
+            lex("import"),
+            vec![(
+                Token::Keyword(Keyword::Reserved(ReservedKeyword::Import)),
+                0..6
+            )]
+        );
+        assert_eq!(
+            lex("let"),
+            vec![(
+                Token::Keyword(Keyword::Reserved(ReservedKeyword::Let)),
+                0..3
+            )]
+        );
+        assert_eq!(
+            lex("macro"),
+            vec![(
+                Token::Keyword(Keyword::Reserved(ReservedKeyword::Macro)),
+                0..5
+            )]
+        );
+        assert_eq!(
+            lex("move"),
+            vec![(
+                Token::Keyword(Keyword::Reserved(ReservedKeyword::Move)),
+                0..4
+            )]
+        );
+        assert_eq!(
+            lex("mut"),
+            vec![(
+                Token::Keyword(Keyword::Reserved(ReservedKeyword::Mut)),
+                0..3
+            )]
+        );
+        assert_eq!(
+            lex("override"),
+            vec![(
+                Token::Keyword(Keyword::Reserved(ReservedKeyword::Override)),
+                0..8
+            )]
+        );
+        assert_eq!(
+            lex("priv"),
+            vec![(
+                Token::Keyword(Keyword::Reserved(ReservedKeyword::Priv)),
+                0..4
+            )]
+        );
+        assert_eq!(
+            lex("proc"),
+            vec![(
+                Token::Keyword(Keyword::Reserved(ReservedKeyword::Proc)),
+                0..4
+            )]
+        );
+        assert_eq!(
+            lex("ref"),
+            vec![(
+                Token::Keyword(Keyword::Reserved(ReservedKeyword::Ref)),
+                0..3
+            )]
+        );
+        assert_eq!(
+            lex("typeof"),
+            vec![(
+                Token::Keyword(Keyword::Reserved(ReservedKeyword::Typeof)),
+                0..6
+            )]
+        );
+        assert_eq!(
+            lex("unsafe"),
+            vec![(
+                Token::Keyword(Keyword::Reserved(ReservedKeyword::Unsafe)),
+                0..6
+            )]
+        );
+        assert_eq!(
+            lex("unsized"),
+            vec![(
+                Token::Keyword(Keyword::Reserved(ReservedKeyword::Unsized)),
+                0..7
+            )]
+        );
+        assert_eq!(
+            lex("virtual"),
+            vec![(
+                Token::Keyword(Keyword::Reserved(ReservedKeyword::Virtual)),
+                0..7
+            )]
+        );
+        assert_eq!(
+            lex("yield"),
+            vec![(
+                Token::Keyword(Keyword::Reserved(ReservedKeyword::Yield)),
+                0..5
+            )]
+        );
+    }
+
+    #[test]
+    fn test_lex_comments() {
+        // Line comments
+        assert_eq!(
+            lex("//"),
+            vec![(
+                Token::Comment(Comment::LineComment {
+                    comment: String::from("//"),
+                }),
+                0..2
+            )]
+        );
+
+        assert_eq!(
+            lex("//   - Only a comment"),
+            vec![(
+                Token::Comment(Comment::LineComment {
+                    comment: String::from("//   - Only a comment"),
+                }),
+                0..21
+            )]
+        );
+        assert_eq!(
+            lex("// Hello, world!"),
+            vec![(
+                Token::Comment(Comment::LineComment {
+                    comment: String::from("// Hello, world!"),
+                }),
+                0..16
+            )]
+        );
+        assert_eq!(
+            lex("// Hello, \tworld!"),
+            vec![(
+                Token::Comment(Comment::LineComment {
+                    comment: String::from("// Hello, \tworld!"),
+                }),
+                0..17
+            )]
+        );
+        assert_eq!(
+            lex(
+                "// This is an incredibly long line comment that should be parsed \
+                 correctly. It should also be parsed correctly if it contains a \
+                 newline"
+            ),
+            vec![(
+                Token::Comment(Comment::LineComment {
+                    comment: String::from(
+                        "// This is an incredibly long line comment that should be \
+                     parsed correctly. It should also be parsed correctly if it \
+                     contains a newline"
+                    ),
+                }),
+                0..135
+            )]
+        );
+        assert_eq!(
+            lex("// Hello, \nworld!"),
+            vec![
+                (
+                    Token::Comment(Comment::LineComment {
+                        comment: String::from("// Hello, ")
+                    }),
+                    0..10
+                ),
+                (Token::Identifier("world"), 11..16),
+                (Token::Punctuation(Punctuation::Not), 16..17),
+            ]
+        );
+
+        // Block comments
+        assert_eq!(
+            lex("/* */"),
+            vec![(
+                Token::Comment(Comment::BlockComment {
+                    comment: String::from("/* */"),
+                }),
+                0..5
+            )]
+        );
+        assert_eq!(
+            lex("/**/"),
+            vec![(
+                Token::Comment(Comment::BlockComment {
+                    comment: String::from("/**/"),
+                }),
+                0..4
+            )]
+        );
+        assert_eq!(
+            lex("/***/"),
+            vec![(
+                Token::Comment(Comment::BlockComment {
+                    comment: String::from("/***/"),
+                }),
+                0..5
+            )]
+        );
+        assert_eq!(
+            lex("/*   - Only a comment */"),
+            vec![(
+                Token::Comment(Comment::BlockComment {
+                    comment: String::from("/*   - Only a comment */"),
+                }),
+                0..24
+            )]
+        );
+        assert_eq!(
+            lex("/*** - Only a comment */"),
+            vec![(
+                Token::Comment(Comment::BlockComment {
+                    comment: String::from("/*** - Only a comment */"),
+                }),
+                0..24
+            )]
+        );
+        assert_eq!(
+            lex("/* Hello, world! */"),
+            vec![(
+                Token::Comment(Comment::BlockComment {
+                    comment: String::from("/* Hello, world! */"),
+                }),
+                0..19
+            )]
+        );
+        assert_eq!(
+            lex("/* Hello, \tworld! */"),
+            vec![(
+                Token::Comment(Comment::BlockComment {
+                    comment: String::from("/* Hello, \tworld! */"),
+                }),
+                0..20
+            )]
+        );
+        assert_eq!(
+            lex(
+                "/* This is an incredibly long\nblock comment that should be \
+                 parsed correctly.\nIt should also be parsed correctly if it \
+                 contains a newline */"
+            ),
+            vec![(Token::Comment(Comment::BlockComment {
+                comment: String::from(
+                    "/* This is an incredibly long\nblock comment that should be \
+                     parsed correctly.\nIt should also be parsed correctly if it \
+                     contains a newline */"
+                ),
+            }), 0..139)]
+        );
+        // Block comments nest, and may contain asterisks and slashes
+        assert_eq!(
+            lex("/* In Balsapop /* we can /* nest comments */ */ */"),
+            vec![(
+                Token::Comment(Comment::BlockComment {
+                    comment: String::from(
+                        "/* In Balsapop /* we can /* nest comments */ */ */"
+                    ),
+                }),
+                0..50
+            )]
+        );
+        assert_eq!(
+            lex("/* a /* b */ c */ d"),
+            vec![
+                (
+                    Token::Comment(Comment::BlockComment {
+                        comment: String::from("/* a /* b */ c */"),
+                    }),
+                    0..17
+                ),
+                (Token::Identifier("d"), 18..19),
+            ]
+        );
+        assert_eq!(
+            lex("/* 2 * 3 / 4 **/"),
+            vec![(
+                Token::Comment(Comment::BlockComment {
+                    comment: String::from("/* 2 * 3 / 4 **/"),
+                }),
+                0..16
+            )]
+        );
+
+        // Unterminated block comments extend to the end of the input
+        assert_eq!(
+            Lexer::new("a /* b /* c */").collect::<Vec<_>>(),
+            vec![
+                Ok((0, Token::Identifier("a"), 1)),
+                Err(InternalParserError::UnrecognizedToken { start: 2, end: 14 }),
+            ]
+        );
+        assert_eq!(
+            Lexer::new("/*/").collect::<Vec<_>>(),
+            vec![Err(InternalParserError::UnrecognizedToken {
+                start: 0,
+                end: 3
+            })]
+        );
+
+        // Doc comments (e.g. outer line doc comments, inner line doc comments, outer
+        // block doc comments, inner block doc comments)
+
+        // - Outer line doc comments
+        assert_eq!(
+            lex("///"),
+            vec![(
+                Token::Comment(Comment::DocComment(
+                    DocComment::OuterLineDocComment {
+                        comment: String::from("///"),
+                    }
+                )),
+                0..3
+            )]
+        );
+        assert_eq!(
+            lex("///  - Outer line doc (exactly 3 slashes)"),
+            vec![(
+                Token::Comment(Comment::DocComment(
+                    DocComment::OuterLineDocComment {
+                        comment: String::from(
+                            "///  - Outer line doc (exactly 3 slashes)"
+                        ),
+                    }
+                )),
+                0..41
+            )]
+        );
+        assert_eq!(
+            lex("/// Hello, world!"),
+            vec![(
+                Token::Comment(Comment::DocComment(
+                    DocComment::OuterLineDocComment {
+                        comment: String::from("/// Hello, world!"),
+                    }
+                )),
+                0..17
+            )]
+        );
+        assert_eq!(
+            lex("/// Hello, \tworld!"),
+            vec![(
+                Token::Comment(Comment::DocComment(
+                    DocComment::OuterLineDocComment {
+                        comment: String::from("/// Hello, \tworld!"),
+                    }
+                )),
+                0..18
+            )]
+        );
+        assert_eq!(
+            lex("/// This is an incredibly long line comment that should be parsed \
+                 correctly. It should also be parsed correctly if it contains a \
+                 newline"),
+            vec![(Token::Comment(Comment::DocComment(DocComment::OuterLineDocComment {
+                comment: String::from(
+                    "/// This is an incredibly long line comment that should be \
+                     parsed correctly. It should also be parsed correctly if it \
+                     contains a newline"
+                ),
+            })), 0..136)]
+        );
+        assert_eq!(
+            lex("/// Hello, \nworld!"),
+            vec![
+                (
+                    Token::Comment(Comment::DocComment(
+                        DocComment::OuterLineDocComment {
+                            comment: String::from("/// Hello, ")
+                        }
+                    )),
+                    0..11
+                ),
+                (Token::Identifier("world"), 12..17),
+                (Token::Punctuation(Punctuation::Not), 17..18),
+            ]
+        );
+        assert_eq!(
+            lex("//// Hello, \nworld!"),
+            vec![
+                (
+                    Token::Comment(Comment::LineComment {
+                        comment: String::from("//// Hello, ")
+                    }),
+                    0..12
+                ),
+                (Token::Identifier("world"), 13..18),
+                (Token::Punctuation(Punctuation::Not), 18..19),
+            ]
+        );
+
+        // - Inner line doc comments
+        assert_eq!(
+            lex("//!"),
+            vec![(
+                Token::Comment(Comment::DocComment(
+                    DocComment::InnerLineDocComment {
+                        comment: String::from("//!"),
+                    }
+                )),
+                0..3
+            )]
+        );
+        assert_eq!(
+            lex("//! Hello, world!"),
+            vec![(
+                Token::Comment(Comment::DocComment(
+                    DocComment::InnerLineDocComment {
+                        comment: String::from("//! Hello, world!"),
+                    }
+                )),
+                0..17
+            )]
+        );
+        assert_eq!(
+            lex("//! Hello, \tworld!"),
+            vec![(
+                Token::Comment(Comment::DocComment(
+                    DocComment::InnerLineDocComment {
+                        comment: String::from("//! Hello, \tworld!"),
+                    }
+                )),
+                0..18
+            )]
+        );
+        assert_eq!(
+            lex("//! This is an incredibly long line comment that should be parsed \
+                 correctly. It should also be parsed correctly if it contains a \
+                 newline"),
+            vec![(Token::Comment(Comment::DocComment(DocComment::InnerLineDocComment {
+                comment: String::from(
+                    "//! This is an incredibly long line comment that should be \
+                     parsed correctly. It should also be parsed correctly if it \
+                     contains a newline"
+                ),
+            })), 0..136)]
+        );
+        assert_eq!(
+            lex("//! Hello, \nworld!"),
+            vec![
+                (
+                    Token::Comment(Comment::DocComment(
+                        DocComment::InnerLineDocComment {
+                            comment: String::from("//! Hello, ")
+                        }
+                    )),
+                    0..11
+                ),
+                (Token::Identifier("world"), 12..17),
+                (Token::Punctuation(Punctuation::Not), 17..18),
+            ]
+        );
+
+        // - Outer block doc comments
+        assert_eq!(
+            lex("/** Outer block doc (exactly 2 asterisks) */"),
+            vec![(
+                Token::Comment(Comment::DocComment(
+                    DocComment::OuterBlockDocComment {
+                        comment: String::from(
+                            "/** Outer block doc (exactly 2 asterisks) */"
+                        ),
+                    }
+                )),
+                0..44
+            )]
+        );
+        assert_eq!(
+            lex("/** a /* b */ c */"),
+            vec![(
+                Token::Comment(Comment::DocComment(
+                    DocComment::OuterBlockDocComment {
+                        comment: String::from("/** a /* b */ c */"),
+                    }
+                )),
+                0..18
+            )]
+        );
+
+        // - Inner block doc comments
+        assert_eq!(
+            lex("/*! Inner block doc */"),
+            vec![(
+                Token::Comment(Comment::DocComment(
+                    DocComment::InnerBlockDocComment {
+                        comment: String::from("/*! Inner block doc */"),
+                    }
+                )),
+                0..22
+            )]
+        );
+    }
+
+    #[test]
+    fn test_lex_unrecognized_tokens() {
+        assert_eq!(
+            Lexer::new("@").collect::<Vec<_>>(),
+            vec![Err(InternalParserError::UnrecognizedToken {
+                start: 0,
+                end: 1
+            })]
+        );
+        assert_eq!(
+            Lexer::new("a ~ b").collect::<Vec<_>>(),
+            vec![
+                Ok((0, Token::Identifier("a"), 1)),
+                Err(InternalParserError::UnrecognizedToken { start: 2, end: 3 }),
+                Ok((4, Token::Identifier("b"), 5)),
+            ]
+        );
+
+        // Comments never reach the parser
+        assert_eq!(
+            Lexer::new("a // b").collect::<Vec<_>>(),
+            vec![Ok((0, Token::Identifier("a"), 1))]
+        );
+    }
+
+    #[test]
+    fn test_lex_logic_literals() {
+        assert_eq!(
+            parser::LogicLiteralParser::new().parse(Lexer::new("true")),
+            Ok(LogicLiteral::True { value: true })
+        );
+        assert_eq!(
+            parser::LogicLiteralParser::new().parse(Lexer::new("True")),
+            Ok(LogicLiteral::True { value: true })
+        );
+        assert_eq!(
+            parser::LogicLiteralParser::new().parse(Lexer::new("false")),
+            Ok(LogicLiteral::False { value: false })
+        );
+        assert_eq!(
+            parser::LogicLiteralParser::new().parse(Lexer::new("False")),
+            Ok(LogicLiteral::False { value: false })
+        );
+        assert_eq!(
+            parser::LogicLiteralParser::new().parse(Lexer::new("missing")),
+            Ok(LogicLiteral::Missing)
+        );
+        assert_eq!(
+            parser::LogicLiteralParser::new().parse(Lexer::new("Missing")),
+            Ok(LogicLiteral::Missing)
+        );
+    }
+
+    // fn test_lex_paths() {
+    // Simple path segments
+    // assert_eq!(
+    //     parser::SimplePathSegmentParser::new().parse("a"),
+    //     Ok(PathSegment::Simple(SimplePathSegment {
+    //         name: String::from("a"),
+    //     }))
+    // );
+    // assert_eq!(
+    //     parser::SimplePathSegmentParser::new().parse("super"),
+    //     Ok(SimplePathSegment::SuperSegment {
+    //         segment: String::from("super"),
+    //     })
+    // );
+    // assert_eq!(
+    //     parser::SimplePathSegmentParser::new().parse("self"),
+    //     Ok(SimplePathSegment::SelfValueSegment {
+    //         segment: String::from("self"),
+    //     })
+    // );
+    // assert_eq!(
+    //     parser::SimplePathSegmentParser::new().parse("crate"),
+    //     Ok(SimplePathSegment::CrateSegment {
+    //         segment: String::from("crate"),
+    //     })
+    // );
+    // }
+}
+
+#[cfg(test)]
+mod cst_test_suite {
+    use super::*;
+    use crate::cst::{self, SyntaxElement, SyntaxKind};
+    use pretty_assertions::assert_eq;
+
+    /// The kinds and text of the tokens of the given source code's CST.
+    fn tokens(source: &str) -> Vec<(SyntaxKind, String)> {
+        cst::parse(source)
+            .descendants_with_tokens()
+            .filter_map(SyntaxElement::into_token)
+            .map(|token| (token.kind(), String::from(token.text())))
+            .collect()
+    }
+
+    #[test]
+    fn test_cst_round_trips_source() {
+        for source in [
+            "",
+            "   ",
+            "a := 3;",
+            "a\t:=\r\n  3 ;\n",
+            "//! Module docs\n/// Item docs\nfn f(x) { /* body */ x₁ }\n",
+            "\x0B\x0C'a' b'b' 'outer: loop {}",
+            "(1 + [2, {3}])) (( § 1.f",
+            "r#\"raw \"string\"\"# // trailing comment",
+            "//! Docs\n/// Item\nfn f(x: Int) -> Int {\n\t/* body */ x + 1\n}\r\n",
+        ] {
+            assert_eq!(cst::parse(source).to_string(), source);
+        }
+    }
+
+    #[test]
+    fn test_cst_trivia() {
+        assert_eq!(
+            tokens("a\t\t= \r\n3"),
+            vec![
+                (SyntaxKind::Identifier, String::from("a")),
+                (SyntaxKind::Tab, String::from("\t\t")),
+                (SyntaxKind::Punctuation, String::from("=")),
+                (SyntaxKind::Space, String::from(" ")),
+                (SyntaxKind::CarriageReturn, String::from("\r")),
+                (SyntaxKind::Newline, String::from("\n")),
+                (SyntaxKind::DecimalLiteral, String::from("3")),
+            ]
+        );
+        assert_eq!(SyntaxKind::Tab.whitespace(), Some(Whitespace::Tab));
+        assert_eq!(
+            SyntaxKind::CarriageReturn.whitespace(),
+            Some(Whitespace::CarriageReturn)
+        );
+        assert_eq!(SyntaxKind::Identifier.whitespace(), None);
+        assert!(SyntaxKind::OuterLineDocComment.is_trivia());
+        assert!(!SyntaxKind::Keyword.is_trivia());
+
+        let source = "//! Inner\n/// Outer\n// Line\n/* Block */ x";
+        let comments: Vec<Comment> = cst::parse(source)
+            .descendants_with_tokens()
+            .filter_map(SyntaxElement::into_token)
+            .filter_map(|token| cst::comment(&token))
+            .collect();
+        assert_eq!(
+            comments,
+            vec![
+                Comment::DocComment(DocComment::InnerLineDocComment {
+                    comment: String::from("//! Inner"),
+                }),
+                Comment::DocComment(DocComment::OuterLineDocComment {
+                    comment: String::from("/// Outer"),
+                }),
+                Comment::LineComment {
+                    comment: String::from("// Line"),
+                },
+                Comment::BlockComment {
+                    comment: String::from("/* Block */"),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_cst_nodes() {
+        /// The kinds and text of the nodes of the given source code's CST.
+        fn nodes(source: &str) -> Vec<(SyntaxKind, String)> {
+            cst::parse(source)
+                .descendants()
+                .map(|node| (node.kind(), node.to_string()))
+                .collect()
+        }
+
+        assert_eq!(
+            nodes("fn f(x: Int) -> Int { y := x + 1; y }"),
+            vec![
+                (
+                    SyntaxKind::SourceFile,
+                    String::from("fn f(x: Int) -> Int { y := x + 1; y }")
+                ),
+                (
+                    SyntaxKind::Function,
+                    String::from("fn f(x: Int) -> Int { y := x + 1; y }")
+                ),
+                (SyntaxKind::Parameter, String::from("x: Int")),
+                (SyntaxKind::NamedType, String::from("Int")),
+                (SyntaxKind::NamedType, String::from("Int")),
+                (SyntaxKind::Block, String::from("{ y := x + 1; y }")),
+                (SyntaxKind::Declaration, String::from("y := x + 1;")),
+                (SyntaxKind::BinaryExpression, String::from("x + 1")),
+                (SyntaxKind::IdentifierExpression, String::from("x")),
+                (SyntaxKind::LiteralExpression, String::from("1")),
+                (SyntaxKind::IdentifierExpression, String::from("y")),
+            ]
+        );
+        assert_eq!(
+            nodes("assert ∀ x ∈ xs: x > 0;"),
+            vec![
+                (SyntaxKind::SourceFile, String::from("assert ∀ x ∈ xs: x > 0;")),
+                (SyntaxKind::Assertion, String::from("assert ∀ x ∈ xs: x > 0;")),
+                (
+                    SyntaxKind::QuantifierExpression,
+                    String::from("∀ x ∈ xs: x > 0")
+                ),
+                (SyntaxKind::Binding, String::from("x ∈ xs")),
+                (SyntaxKind::IdentifierExpression, String::from("xs")),
+                (SyntaxKind::BinaryExpression, String::from("x > 0")),
+                (SyntaxKind::IdentifierExpression, String::from("x")),
+                (SyntaxKind::LiteralExpression, String::from("0")),
+            ]
+        );
+
+        // A source file which doesn't parse is an error node of its tokens
+        assert_eq!(
+            nodes(") (a"),
+            vec![
+                (SyntaxKind::SourceFile, String::from(") (a")),
+                (SyntaxKind::ErrorNode, String::from(") (a")),
+            ]
+        );
+        // Unrecognized input is kept as error tokens
+        assert_eq!(
+            tokens("§ 1.f"),
+            vec![
+                (SyntaxKind::Error, String::from("§")),
+                (SyntaxKind::Space, String::from(" ")),
+                (SyntaxKind::Error, String::from("1.f")),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_cst_trivia_belongs_to_nodes() {
+        let source = "//! Module\nx := 1; // one\n\n/// Doc\n// Note\nfn f() {}\n";
+        let root = cst::parse(source);
+        let children: Vec<(SyntaxKind, String)> = root
+            .children_with_tokens()
+            .map(|element| (element.kind(), element.to_string()))
+            .collect();
+        // The comments preceding an item belong to it, unlike the whitespace
+        // preceding them
+        assert_eq!(
+            children,
+            vec![
+                (SyntaxKind::InnerLineDocComment, String::from("//! Module")),
+                (SyntaxKind::Newline, String::from("\n")),
+                (SyntaxKind::Declaration, String::from("x := 1;")),
+                (SyntaxKind::Space, String::from(" ")),
+                (SyntaxKind::LineComment, String::from("// one")),
+                (SyntaxKind::Newline, String::from("\n\n")),
+                (
+                    SyntaxKind::Function,
+                    String::from("/// Doc\n// Note\nfn f() {}")
+                ),
+                (SyntaxKind::Newline, String::from("\n")),
+            ]
+        );
+
+        // Trivia within a node belong to the innermost node enclosing them
+        let root = cst::parse("f(x /* first */, y)");
+        let call = root.first_child().unwrap();
+        assert_eq!(call.kind(), SyntaxKind::CallExpression);
+        assert!(call
+            .children_with_tokens()
+            .any(|element| element.kind() == SyntaxKind::BlockComment));
+    }
+}
+
+#[cfg(test)]
+mod docs_test_suite {
+    use super::*;
+    use crate::docs::attach_doc_comments;
+    use pretty_assertions::assert_eq;
+
+    fn outer(comment: &str) -> DocComment {
+        DocComment::OuterLineDocComment {
+            comment: String::from(comment),
+        }
+    }
+
+    fn inner(comment: &str) -> DocComment {
+        DocComment::InnerLineDocComment {
+            comment: String::from(comment),
+        }
+    }
+
+    /// The program of the given source code, with its doc comments attached.
+    fn parse(source: &str) -> Block {
+        let mut program = parser::ProgramParser::new()
+            .parse(lexer::Lexer::new(source))
+            .unwrap();
+        attach_doc_comments(&cst::build(source, &program), &mut program);
+        program
+    }
+
+    /// The items of a program, by their position.
+    fn items(program: &Block) -> Vec<&Item> {
+        program
+            .statements
+            .iter()
+            .filter_map(|statement| match statement {
+                Statement::Item(item) => Some(item),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_attach_doc_comments() {
+        let source = "\
+//! The crate.
+/*! More about the crate. */
+
+/// Adds one.
+// Not documentation
+/// Really.
+pub fn add_one(x: Int) -> Int {
+    //! Inner documentation of `add_one`.
+    x + 1
+}
+
+/** A point. */
+struct Point {
+    /// The abscissa.
+    x: Float,
+    y: Float,
+}
+
+/// A shape.
+enum Shape {
+    /// Nothing at all.
+    Empty,
+    Circle(Float),
+}
+
+/// Measures a shape.
+trait Area {
+    /// The area of the shape.
+    fn area(self) -> Float;
+}
+
+impl Area for Point {
+    //! Points have no area.
+
+    fn area(self) -> Float {
+        /// A nested function.
+        fn zero() -> Float { 0.0 }
+        zero()
+    }
+}
+
+/// Dropped, as it documents a statement.
+a := 3;
+";
+        let program = parse(source);
+        assert_eq!(
+            program.docs,
+            vec![
+                inner("//! The crate."),
+                DocComment::InnerBlockDocComment {
+                    comment: String::from("/*! More about the crate. */"),
+                },
+            ]
+        );
+        match &items(&program)[..] {
+            [Item::Function(add_one), Item::Struct(point), Item::Enum(shape), Item::Trait(area), Item::Impl(implementation)] =>
+            {
+                assert_eq!(
+                    add_one.docs,
+                    vec![
+                        outer("/// Adds one."),
+                        outer("/// Really."),
+                        inner("//! Inner documentation of `add_one`."),
+                    ]
+                );
+                assert_eq!(
+                    point.docs,
+                    vec![DocComment::OuterBlockDocComment {
+                        comment: String::from("/** A point. */"),
+                    }]
+                );
+                match &point.fields {
+                    Fields::Named(fields) => {
+                        assert_eq!(fields[0].docs, vec![outer("/// The abscissa.")]);
+                        assert_eq!(fields[1].docs, vec![]);
+                    }
+                    fields => panic!("expected named fields, got {:?}", fields),
+                }
+                assert_eq!(shape.docs, vec![outer("/// A shape.")]);
+                assert_eq!(
+                    shape.variants[0].docs,
+                    vec![outer("/// Nothing at all.")]
+                );
+                assert_eq!(shape.variants[1].docs, vec![]);
+                assert_eq!(area.docs, vec![outer("/// Measures a shape.")]);
+                assert_eq!(
+                    area.methods[0].docs,
+                    vec![outer("/// The area of the shape.")]
+                );
+                assert_eq!(
+                    implementation.docs,
+                    vec![inner("//! Points have no area.")]
+                );
+                let method = &implementation.methods[0];
+                assert_eq!(method.docs, vec![]);
+                match &items(method.body.as_ref().unwrap())[..] {
+                    [Item::Function(zero)] => {
+                        assert_eq!(zero.docs, vec![outer("/// A nested function.")])
+                    }
+                    items => panic!("expected a nested function, got {:?}", items),
+                }
+            }
+            items => panic!("unexpected items {:?}", items),
+        }
+    }
+
+    #[test]
+    fn test_compile_attaches_doc_comments() {
+        let program = compile(
+            "//! Module docs\n/// Item docs\nfn f() {}\n",
+            quadrature::DEFAULT_TOLERANCE,
+        )
+        .unwrap();
+        assert_eq!(program.docs, vec![inner("//! Module docs")]);
+        match &items(&program)[..] {
+            [Item::Function(function)] => {
+                assert_eq!(function.docs, vec![outer("/// Item docs")])
+            }
+            items => panic!("expected a function, got {:?}", items),
+        }
+    }
+}
+
+#[cfg(test)]
+mod units_test_suite {
+    use crate::units::{
+        Dimension, ExponentOutOfRange, IncompatibleDimensions, Quantity, Unit,
+    };
+    use pretty_assertions::assert_eq;
+
+    fn unit(symbol: &str) -> Unit {
+        Unit::from_symbol(symbol).unwrap()
+    }
+
+    #[test]
+    fn test_unit_symbols() {
+        assert_eq!(unit("m").scale, 1.0);
+        assert_eq!(unit("kg").scale, 1.0);
+        assert_eq!(unit("g").scale, 1e-3);
+        assert_eq!(unit("mm").scale, 1e-3);
+        assert_eq!(unit("μs").scale, 1e-6);
+        assert_eq!(unit("min").scale, 60.0);
+        assert_eq!(unit("h").scale, 3600.0);
+        assert_eq!(unit("hPa").scale, 100.0);
+        assert_eq!(unit("mol").dimension.to_string(), "mol");
+        assert_eq!(unit("cd").dimension.to_string(), "cd");
+        assert_eq!(unit("J").dimension.to_string(), "kg·m²·s⁻²");
+        assert_eq!(
+            unit("Hz").dimension,
+            unit("s").checked_powi(-1).unwrap().dimension
+        );
+        assert_eq!(
+            unit("W").dimension,
+            unit("J").checked_div(unit("s")).unwrap().dimension
+        );
+        assert_eq!(
+            unit("Ω").dimension,
+            unit("V").checked_div(unit("A")).unwrap().dimension
+        );
+        assert_eq!(Unit::from_symbol("apples"), None);
+        assert_eq!(Unit::from_symbol("kkm"), None);
+        assert_eq!(Dimension::DIMENSIONLESS.to_string(), "1");
+        assert!(unit("m")
+            .checked_div(unit("km"))
+            .unwrap()
+            .dimension
+            .is_dimensionless());
+    }
+
+    #[test]
+    fn test_quantity_arithmetic() {
+        let metres = Quantity::new(500.0, unit("m"));
+        let kilometres = Quantity::new(1.0, unit("km"));
+        let seconds = Quantity::new(10.0, unit("s"));
+
+        assert_eq!(
+            kilometres.checked_add(metres),
+            Ok(Quantity::new(1.5, unit("km")))
+        );
+        assert_eq!(
+            metres.checked_sub(kilometres),
+            Ok(Quantity::new(-500.0, unit("m")))
+        );
+
+        // Only quantities of the same dimension may be added
+        let error = metres.checked_add(seconds).unwrap_err();
+        assert_eq!(
+            error,
+            IncompatibleDimensions {
+                left: unit("m").dimension,
+                right: unit("s").dimension,
+            }
+        );
+        assert_eq!(error.to_string(), "cannot add `s` to `m`");
+
+        // Multiplying and dividing combines dimensions
+        let velocity = metres.checked_div(seconds).unwrap();
+        assert_eq!(velocity.value, 50.0);
+        assert_eq!(velocity.unit.dimension.to_string(), "m·s⁻¹");
+        let acceleration = velocity.checked_div(seconds).unwrap();
+        assert_eq!(acceleration.unit.dimension.to_string(), "m·s⁻²");
+        let force = Quantity::new(2.0, unit("kg"))
+            .checked_mul(acceleration)
+            .unwrap();
+        assert_eq!(force.unit.dimension, unit("N").dimension);
+        assert_eq!(
+            force
+                .checked_add(Quantity::new(1.0, unit("N")))
+                .unwrap()
+                .value,
+            11.0
+        );
+        assert_eq!(
+            seconds.checked_powi(2).unwrap().unit.dimension.to_string(),
+            "s²"
+        );
+
+        // Combining dimensions whose exponents overflow is an error
+        let metre = unit("m");
+        assert_eq!(
+            metre.checked_powi(127).unwrap().checked_mul(metre),
+            Err(ExponentOutOfRange)
+        );
+        assert_eq!(
+            metre.checked_powi(-128).unwrap().checked_div(metre),
+            Err(ExponentOutOfRange)
+        );
+        assert_eq!(unit("N").checked_powi(100), Err(ExponentOutOfRange));
+        assert_eq!(metre.dimension.checked_powi(-128).unwrap().root(-1), None);
+        assert_eq!(
+            Quantity::new(1.0, metre.checked_powi(100).unwrap()).checked_powi(2),
+            Err(ExponentOutOfRange)
+        );
+        assert_eq!(kilometres.si_value(), 1000.0);
+    }
+}
+
+#[cfg(test)]
+mod number_test_suite {
+    use super::*;
+    use crate::number::Number;
+    use pretty_assertions::assert_eq;
+
+    fn integer(n: i32) -> Number {
+        Number::Integer(rug::Integer::from(n))
+    }
+
+    fn rational(numerator: i32, denominator: i32) -> Number {
+        Number::rational(rug::Rational::from((numerator, denominator)))
+    }
+
+    #[test]
+    fn test_exact_arithmetic() {
+        // Integer division is exact
+        assert_eq!(integer(1).checked_div(integer(3)), Some(rational(1, 3)));
+        assert_eq!(integer(6).checked_div(integer(3)), Some(integer(2)));
+        assert_eq!(integer(1).checked_div(integer(0)), None);
+
+        // Rationals stay exact, and are integers when their denominator is 1
+        assert_eq!(rational(1, 3) + rational(2, 3), integer(1));
+        assert_eq!(rational(3, 4) - integer(1), rational(-1, 4));
+        assert_eq!(rational(2, 3) * integer(3), integer(2));
+        assert_eq!(
+            rational(1, 10) + rational(2, 10),
+            rational(3, 10),
+            "unlike 0.1 + 0.2"
+        );
+        assert_eq!(-rational(1, 2), rational(-1, 2));
+        assert_eq!(rational(1, 2).to_string(), "1/2");
+
+        // Floats are only produced by combining with a float, or explicitly
+        assert_eq!(rational(1, 2) + Number::Float(0.25), Number::Float(0.75));
+        assert_eq!(
+            integer(1).checked_div(Number::Float(0.0)),
+            Some(Number::Float(f64::INFINITY))
+        );
+        assert_eq!(rational(1, 4).to_f64(), 0.25);
+        assert!(rational(1, 3).is_exact());
+        assert!(!Number::Float(1.0).is_exact());
+
+        // Integer powers are exact, unless they're too large to compute exactly
+        assert_eq!(rational(2, 3).pow(integer(-3)), rational(27, 8));
+        assert_eq!(
+            integer(2).pow(integer(1 << 20)),
+            Number::Integer(rug::Integer::from(1) << (1u32 << 20))
+        );
+        let huge = integer(2_000_000_000);
+        assert_eq!(integer(-1).pow(huge.clone()), integer(1));
+        assert_eq!(integer(10).pow(huge.clone()), Number::Float(f64::INFINITY));
+        assert_eq!(rational(1, 10).pow(huge), Number::Float(0.0));
+        assert_eq!(integer(0).checked_pow(integer(-1)), None);
+        assert_eq!(integer(0).checked_pow(integer(2)), Some(integer(0)));
+        assert_eq!(
+            Number::Float(0.0).checked_pow(integer(-1)),
+            Some(Number::Float(f64::INFINITY))
+        );
+
+        // As are sums, differences, products and quotients
+        let big = integer(2).pow(integer(1 << 20));
+        assert_eq!(big.clone() * integer(3), Number::Float(f64::INFINITY));
+        assert_eq!(big.clone() + integer(1), Number::Float(f64::INFINITY));
+        assert_eq!(-big.clone() - integer(1), Number::Float(f64::NEG_INFINITY));
+        assert_eq!(
+            integer(1).checked_div(big),
+            Some(Number::Float(0.0))
+        );
+
+        // Literals
+        assert_eq!(
+            Number::from(&NumericLiteral::Rational(rug::Rational::from((3, 4)))),
+            rational(3, 4)
+        );
+
+        // Constants with a precision suffix are exact to their digits
+        let pi = |digits| {
+            Number::from(&NumericLiteral::MathematicalConstant(
+                MathematicalConstant::Pi {
+                    value: std::f64::consts::PI,
+                    digits,
+                },
+            ))
+        };
+        assert_eq!(pi(None), Number::Float(std::f64::consts::PI));
+        let precise = pi(Some(100));
+        assert!(precise.is_exact());
+        assert_eq!(precise.to_f64(), std::f64::consts::PI);
+        match precise - Number::Float(0.0) {
+            Number::Float(f) => assert_eq!(f, std::f64::consts::PI),
+            number => panic!("expected a float, got {:?}", number),
+        }
+        match pi(Some(100)) - pi(Some(120)) {
+            Number::Rational(error) => assert!(
+                error.abs()
+                    < rug::Rational::from((
+                        1,
+                        rug::Integer::from(rug::Integer::u_pow_u(10, 99))
+                    ))
+            ),
+            number => panic!("expected a rational, got {:?}", number),
+        }
+        assert_eq!(
+            Number::from(&NumericLiteral::Float(Float::Exact {
+                value: rug::Rational::from((1, 10))
+            })),
+            rational(1, 10)
+        );
+        assert_eq!(
+            Number::from(&NumericLiteral::Integer(Integer::U8 { n: 255 })),
+            integer(255)
+        );
+    }
+
+    #[test]
+    fn test_complex_arithmetic() {
+        fn complex(real: f64, imag: f64) -> Number {
+            Number::Complex(rug::Complex::with_val(53, (real, imag)))
+        }
+        fn imaginary(imag: i32) -> Number {
+            Number::from(&NumericLiteral::Imaginary(rug::Rational::from(imag)))
+        }
+
+        // Numbers only become complex when combined with a complex number
+        assert_eq!(integer(3) + imaginary(4), complex(3.0, 4.0));
+        assert_eq!(imaginary(1) * imaginary(1), complex(-1.0, 0.0));
+        assert_eq!(
+            (integer(3) + imaginary(4)) * (integer(3) - imaginary(4)),
+            complex(25.0, 0.0)
+        );
+        assert_eq!(
+            integer(1).checked_div(integer(1) + imaginary(1)),
+            Some(complex(0.5, -0.5))
+        );
+        assert_eq!(rational(1, 2) - imaginary(2), complex(0.5, -2.0));
+        assert_eq!(-complex(1.0, -1.0), complex(-1.0, 1.0));
+        assert_eq!(integer(2).pow(integer(10)), integer(1024));
+        assert_eq!(rational(2, 3).pow(integer(-2)), rational(9, 4));
+        assert_eq!(imaginary(1).pow(integer(2)), complex(-1.0, 0.0));
+
+        // Complex numbers keep the greater precision of their operands
+        let precise = Number::Complex(rug::Complex::with_val(200, (1, 0)));
+        match precise + rational(1, 3) {
+            Number::Complex(z) => assert_eq!(z.prec(), (200, 200)),
+            number => panic!("expected a complex number, got {:?}", number),
+        }
+
+        // and exact operands aren't rounded to an f64's precision
+        let large = integer(2).pow(integer(100)) + integer(1);
+        match large + imaginary(1) {
+            Number::Complex(z) => {
+                assert_eq!(z.prec(), (101, 101));
+                assert_eq!(*z.real(), (rug::Integer::from(1) << 100u32) + 1u32);
+            }
+            number => panic!("expected a complex number, got {:?}", number),
+        }
+        let pi = Number::from(&NumericLiteral::MathematicalConstant(
+            MathematicalConstant::Pi {
+                value: std::f64::consts::PI,
+                digits: Some(100),
+            },
+        ));
+        match pi + imaginary(1) {
+            Number::Complex(z) => assert!(z.prec().0 > 53),
+            number => panic!("expected a complex number, got {:?}", number),
+        }
+
+        // ℯ^(𝑖π) = -1
+        let e = Number::from(&NumericLiteral::MathematicalConstant(
+            MathematicalConstant::Euler {
+                value: std::f64::consts::E,
+                digits: None,
+            },
+        ));
+        let pi = Number::from(&NumericLiteral::MathematicalConstant(
+            MathematicalConstant::Pi {
+                value: std::f64::consts::PI,
+                digits: None,
+            },
+        ));
+        for z in [e.pow(imaginary(1) * pi.clone()), (imaginary(1) * pi).exp()] {
+            match z {
+                Number::Complex(z) => {
+                    assert!((z.real().to_f64() + 1.0).abs() < 1e-15);
+                    assert!(z.imag().to_f64().abs() < 1e-15);
+                }
+                number => panic!("expected a complex number, got {:?}", number),
+            }
+        }
+
+        assert_eq!(complex(3.0, 4.0).to_string(), "3+4i");
+        assert_eq!(complex(0.5, -2.0).to_string(), "0.5-2i");
+        assert_eq!(complex(3.0, 0.0).to_f64(), 3.0);
+        assert!(complex(3.0, 4.0).to_f64().is_nan());
+        assert!(!complex(3.0, 0.0).is_exact());
+    }
+}
+
+#[cfg(test)]
+mod quadrature_test_suite {
+    use crate::quadrature::{integrate, QuadratureError, DEFAULT_TOLERANCE};
+
+    fn integral(f: fn(f64) -> f64, a: f64, b: f64) -> f64 {
+        integrate(|x| Ok::<_, ()>(f(x)), a, b, DEFAULT_TOLERANCE).unwrap()
+    }
+
+    #[test]
+    fn test_integrate() {
+        // Polynomials of degree up to 22 are integrated exactly
+        assert!((integral(|x| x.powi(7), 0.0, 2.0) - 32.0).abs() < 1e-12);
+        assert!((integral(f64::cos, 0.0, 10.0) - 10f64.sin()).abs() < 1e-10);
+        assert!((integral(|x| 1.0 / x, 1.0, 1e6) - 1e6f64.ln()).abs() < 1e-9);
+        assert!((integral(f64::sqrt, 0.0, 1.0) - 2.0 / 3.0).abs() < 1e-10);
+        assert!((integral(|x| x.exp(), 1.0, 0.0) + 1f64.exp() - 1.0).abs() < 1e-10);
+        assert_eq!(integral(f64::exp, 2.0, 2.0), 0.0);
+
+        // Improper integrals
+        let lorentzian = |x: f64| 1.0 / (1.0 + x * x);
+        assert!(
+            (integral(lorentzian, f64::NEG_INFINITY, f64::INFINITY)
+                - std::f64::consts::PI)
+                .abs()
+                < 1e-10
+        );
+        assert!(
+            (integral(lorentzian, 0.0, f64::INFINITY) - std::f64::consts::FRAC_PI_2)
+                .abs()
+                < 1e-10
+        );
+        assert!((integral(f64::exp, f64::NEG_INFINITY, 0.0) - 1.0).abs() < 1e-10);
+
+        // Divergent integrals and failing integrands
+        assert_eq!(
+            integrate(|x| Ok::<_, ()>(1.0 / x), 0.0, 1.0, DEFAULT_TOLERANCE),
+            Err(QuadratureError::NotConverged)
+        );
+        assert_eq!(
+            integrate(
+                |x| if x < 0.5 { Ok(x) } else { Err("undefined") },
+                0.0,
+                1.0,
+                DEFAULT_TOLERANCE
+            ),
+            Err(QuadratureError::Integrand("undefined"))
+        );
+    }
+}
+
+#[cfg(test)]
+mod eval_test_suite {
+    use super::*;
+    use crate::eval::{evaluate, Environment, Value};
+    use crate::lexer::Lexer;
+    use crate::number::Number;
+    use crate::parser_errors::InternalParserError;
+    use crate::set::{MismatchedTypes, Set};
+    use crate::units::{IncompatibleDimensions, Quantity, Unit};
+    use pretty_assertions::assert_eq;
+
+    fn eval(source: &str) -> Result<Value, InternalParserError> {
+        evaluate(
+            &parser::ExpressionParser::new()
+                .parse(Lexer::new(source))
+                .unwrap(),
+        )
     }
 
-    #[test]
-    fn test_lex_character_and_string_literals() {
+    fn integer(n: i32) -> Value {
+        Value::Number(Number::Integer(rug::Integer::from(n)))
+    }
 
-        // // Apostrophe
-        // assert_eq!(parser::ApostropheParser::new().parse("'"), Ok('\''));
+    fn rational(numerator: i32, denominator: i32) -> Value {
+        Value::Number(Number::rational(rug::Rational::from((
+            numerator,
+            denominator,
+        ))))
+    }
 
-        // // Quote
-        // assert_eq!(parser::QuoteParser::new().parse("\""), Ok('"'));
+    fn logic(value: Option<bool>) -> Value {
+        Value::Logic(value)
     }
 
     #[test]
-    fn test_lex_keywords() {
-        assert_eq!(parser::KeywordParser::new().parse("as"), Ok(Keyword::As));
-        assert_eq!(
-            parser::KeywordParser::new().parse("break"),
-            Ok(Keyword::Break)
-        );
+    fn test_evaluate_exact_floats() {
+        let exact = |source| {
+            evaluate(
+                &parser::ExpressionParser::new()
+                    .parse(Lexer::new(source).exact_floats(true))
+                    .unwrap(),
+            )
+        };
+        // Exact floats stay exact, unlike rounded ones
+        assert_eq!(exact("0.1 + 0.2"), Ok(rational(3, 10)));
+        assert_ne!(eval("0.1 + 0.2"), Ok(rational(3, 10)));
+        assert_eq!(exact("0.1 + 0.2 == 0.3"), Ok(logic(Some(true))));
+        assert_eq!(exact("12. / 8"), Ok(rational(3, 2)));
+        assert_eq!(exact("0x1.8p-3 + 2.5e1f32"), Ok(rational(403, 16)));
+        // Ranges aren't float literals
+        assert_eq!(exact("∑(i in 1..=3) i"), Ok(integer(6)));
+    }
+
+    #[test]
+    fn test_evaluate_arithmetic() {
+        assert_eq!(eval("1 + 2 * 3"), Ok(integer(7)));
+        assert_eq!(eval("(1 + 2) * 3"), Ok(integer(9)));
+        assert_eq!(eval("2 ^ 3 ^ 2"), Ok(integer(512)));
+        assert_eq!(eval("(2 ^ 3) ^ 2"), Ok(integer(64)));
+        assert_eq!(eval("-2 ^ 2"), Ok(integer(-4)));
+        assert_eq!(eval("2 ^ -1"), Ok(rational(1, 2)));
+        assert_eq!(eval("1 / 3 + 1 / 6"), Ok(rational(1, 2)));
+        assert_eq!(eval("6 ÷ 4 · 2"), Ok(integer(3)));
+        assert_eq!(eval("7 % 3"), Ok(integer(1)));
+        assert_eq!(eval("-7 % 3"), Ok(integer(-1)));
+        assert_eq!(eval("7/2r % 1"), Ok(rational(1, 2)));
+        assert_eq!(eval("0.5 + 1/4r"), Ok(Value::Number(Number::Float(0.75))));
+        assert_eq!(eval("12 & 10 | 1"), Ok(integer(9)));
+        assert_eq!(
+            eval("3 + 4i"),
+            Ok(Value::Number(Number::Complex(rug::Complex::with_val(
+                53,
+                (3, 4)
+            ))))
+        );
+        match eval("ℯ ^ (𝑖 * π)") {
+            Ok(Value::Number(Number::Complex(z))) => {
+                assert!((z.real().to_f64() + 1.0).abs() < 1e-15);
+                assert!(z.imag().to_f64().abs() < 1e-15);
+            }
+            value => panic!("expected a complex number, got {:?}", value),
+        }
+
         assert_eq!(
-            parser::KeywordParser::new().parse("const"),
-            Ok(Keyword::Const)
+            eval("1 / (2 - 2)"),
+            Err(InternalParserError::DivisionByZero { start: 0, end: 11 })
         );
         assert_eq!(
-            parser::KeywordParser::new().parse("continue"),
-            Ok(Keyword::Continue)
+            eval("1 / 0.0"),
+            Ok(Value::Number(Number::Float(f64::INFINITY)))
         );
         assert_eq!(
-            parser::KeywordParser::new().parse("crate"),
-            Ok(Keyword::Reserved(ReservedKeyword::Crate))
+            eval("1 % 0"),
+            Err(InternalParserError::DivisionByZero { start: 0, end: 5 })
         );
+        // A negative power of zero divides by zero too
         assert_eq!(
-            parser::KeywordParser::new().parse("else"),
-            Ok(Keyword::Else)
+            eval("0^-1"),
+            Err(InternalParserError::DivisionByZero { start: 0, end: 4 })
         );
         assert_eq!(
-            parser::KeywordParser::new().parse("enum"),
-            Ok(Keyword::Enum)
+            eval("0⁻¹"),
+            Err(InternalParserError::DivisionByZero { start: 0, end: 6 })
         );
         assert_eq!(
-            parser::KeywordParser::new().parse("extern"),
-            Ok(Keyword::Reserved(ReservedKeyword::Extern))
+            eval("0.0^-1"),
+            Ok(Value::Number(Number::Float(f64::INFINITY)))
         );
+    }
+
+    #[test]
+    fn test_evaluate_big_operators() {
+        assert_eq!(eval("∑(i in 1..=10) i"), Ok(integer(55)));
+        assert_eq!(eval("∑(i in 1..=10) i²"), Ok(integer(385)));
+        assert_eq!(eval("∑(i in 1..10) i"), Ok(integer(45)));
+        assert_eq!(eval("∏(k in 1..=5) k"), Ok(integer(120)));
+        assert_eq!(eval("∑(k in 0..=3) 1/2r ^ k"), Ok(rational(15, 8)));
+        assert_eq!(eval("∑(i in 1..=3) ∑(j in 1..=i) i · j"), Ok(integer(25)));
+        assert_eq!(eval("∑(i in -2..=(1 + 1)) i"), Ok(integer(0)));
+        // The empty sum and product
+        assert_eq!(eval("∑(i in 1..1) i"), Ok(integer(0)));
+        assert_eq!(eval("∏(i in 3..=1) i"), Ok(integer(1)));
+        // Precise constants are computed once, rather than for each term
+        assert_eq!(eval("∑(k in 1..=1000) π_10000"), eval("1000 · π_10000"));
+        // Ranges too long to iterate at compile time aren't constant
         assert_eq!(
-            parser::KeywordParser::new().parse("false"),
-            Ok(Keyword::False)
+            eval("∑(i in 1..=10^12) i"),
+            Err(InternalParserError::NotConstant { start: 0, end: 21 })
         );
         assert_eq!(
-            parser::KeywordParser::new().parse("False"),
-            Ok(Keyword::False)
+            eval("∑(i in 1..=2^10) ∏(j in 1..=2^11) 1"),
+            Err(InternalParserError::NotConstant { start: 19, end: 39 })
         );
-        assert_eq!(parser::KeywordParser::new().parse("fn"), Ok(Keyword::Fn));
-        assert_eq!(parser::KeywordParser::new().parse("for"), Ok(Keyword::For));
-        assert_eq!(parser::KeywordParser::new().parse("if"), Ok(Keyword::If));
+        // As are results too large to compute exactly
         assert_eq!(
-            parser::KeywordParser::new().parse("impl"),
-            Ok(Keyword::Impl)
+            eval("∏(i in 1..=2^20) 2^(2^20)"),
+            Err(InternalParserError::NotConstant { start: 0, end: 27 })
         );
-        assert_eq!(parser::KeywordParser::new().parse("in"), Ok(Keyword::In));
         assert_eq!(
-            parser::KeywordParser::new().parse("loop"),
-            Ok(Keyword::Loop)
+            eval("∏(k in 1..=1000000) k"),
+            Err(InternalParserError::NotConstant { start: 0, end: 23 })
         );
+        // The bound variable is out of scope after the big operator
         assert_eq!(
-            parser::KeywordParser::new().parse("match"),
-            Ok(Keyword::Match)
+            eval("∑(i in 1..=3) i + i"),
+            Err(InternalParserError::NotConstant { start: 20, end: 21 })
         );
+        let metre = Unit::from_symbol("m").unwrap();
         assert_eq!(
-            parser::KeywordParser::new().parse("missing"),
-            Ok(Keyword::Missing)
+            eval("∑(i in 1..=3) 2 m"),
+            Ok(Value::Quantity(Quantity::new(6.0, metre)))
         );
+
         assert_eq!(
-            parser::KeywordParser::new().parse("Missing"),
-            Ok(Keyword::Missing)
+            eval("∑(i in 1..=2.5) i"),
+            Err(InternalParserError::InvalidDomain {
+                operator: "∑",
+                expected: "a range of integers",
+                start: 13,
+                end: 16
+            })
         );
-        assert_eq!(parser::KeywordParser::new().parse("mod"), Ok(Keyword::Mod));
-        assert_eq!(parser::KeywordParser::new().parse("pub"), Ok(Keyword::Pub));
         assert_eq!(
-            parser::KeywordParser::new().parse("return"),
-            Ok(Keyword::Return)
+            eval("∏(k in 5) k"),
+            Err(InternalParserError::InvalidDomain {
+                operator: "∏",
+                expected: "a range of integers",
+                start: 9,
+                end: 10
+            })
         );
+    }
+
+    #[test]
+    fn test_evaluate_calls() {
+        assert_eq!(eval("sin(0)"), Ok(Value::Number(Number::Float(0.0))));
+        assert_eq!(eval("ln(1)"), Ok(Value::Number(Number::Float(0.0))));
+        assert_eq!(eval("sqrt(9/4r)"), Ok(rational(3, 2)));
+        assert_eq!(eval("abs(-3/2r)"), Ok(rational(3, 2)));
+        assert_eq!(eval("abs(3 + 4i)"), Ok(Value::Number(Number::Float(5.0))));
+        match eval("sin(π/6)² + cos(π/6)²") {
+            Ok(Value::Number(Number::Float(x))) => assert!((x - 1.0).abs() < 1e-15),
+            value => panic!("expected a float, got {:?}", value),
+        }
+        match eval("exp(𝑖 · π)") {
+            Ok(Value::Number(Number::Complex(z))) => {
+                assert!((z.real().to_f64() + 1.0).abs() < 1e-15);
+                assert!(z.imag().to_f64().abs() < 1e-15);
+            }
+            value => panic!("expected a complex number, got {:?}", value),
+        }
+        let metre = Unit::from_symbol("m").unwrap();
         assert_eq!(
-            parser::KeywordParser::new().parse("self"),
-            Ok(Keyword::SelfValue)
+            eval("sqrt(9 m²)"),
+            Ok(Value::Quantity(Quantity::new(3.0, metre)))
         );
         assert_eq!(
-            parser::KeywordParser::new().parse("Self"),
-            Ok(Keyword::SelfType)
+            eval("abs(-2 m)"),
+            Ok(Value::Quantity(Quantity::new(2.0, metre)))
         );
+
         assert_eq!(
-            parser::KeywordParser::new().parse("static"),
-            Ok(Keyword::Static)
+            eval("foo(1)"),
+            Err(InternalParserError::UnknownFunction { start: 0, end: 6 })
         );
+        // Without declarations, no path resolves
         assert_eq!(
-            parser::KeywordParser::new().parse("struct"),
-            Ok(Keyword::Struct)
+            eval("Shape::Circle(1)"),
+            Err(InternalParserError::UnknownFunction { start: 0, end: 16 })
         );
         assert_eq!(
-            parser::KeywordParser::new().parse("super"),
-            Ok(Keyword::Super)
+            eval("sin(1, 2)"),
+            Err(InternalParserError::WrongArgumentCount {
+                expected: 1,
+                found: 2,
+                start: 0,
+                end: 9
+            })
         );
         assert_eq!(
-            parser::KeywordParser::new().parse("trait"),
-            Ok(Keyword::Trait)
+            eval("sin(true)"),
+            Err(InternalParserError::InvalidOperand {
+                operator: "sin",
+                operand: "logic",
+                start: 0,
+                end: 9
+            })
         );
         assert_eq!(
-            parser::KeywordParser::new().parse("true"),
-            Ok(Keyword::True)
+            eval("sin(2 m)"),
+            Err(InternalParserError::InvalidOperand {
+                operator: "sin",
+                operand: "quantity",
+                start: 0,
+                end: 8
+            })
         );
+    }
+
+    #[test]
+    fn test_evaluate_integrals() {
+        let float = |source: &str| match eval(source) {
+            Ok(Value::Number(Number::Float(x))) => x,
+            value => panic!("expected a float, got {:?}", value),
+        };
+
+        assert!((float("∫(x in 0..π) sin(x) dx") - 2.0).abs() < 1e-10);
+        assert!((float("∫(x in 0..1) x² dx") - 1.0 / 3.0).abs() < 1e-10);
+        assert!((float("∫(x in 1..0) x dx") + 0.5).abs() < 1e-10);
+        assert!((float("∫(x in 0..2) ∫(y in 0..x) 1 dy dx") - 2.0).abs() < 1e-10);
+        assert!((float("∫(x in 0..∞) ℯ^-x dx") - 1.0).abs() < 1e-10);
+        assert!(
+            (float("∫(x in -∞..∞) exp(-x²) dx") - std::f64::consts::PI.sqrt()).abs()
+                < 1e-10
+        );
+        assert!((float("∑(n in 1..=3) ∫(x in 0..1) n · x dx") - 3.0).abs() < 1e-10);
+        assert_eq!(float("∫(x in 1..1) x dx"), 0.0);
+
+        // The tolerance is configurable
+        let integral = parser::ExpressionParser::new()
+            .parse(Lexer::new("∫(x in 0..1) sqrt(x) dx"))
+            .unwrap();
+        match Environment::default()
+            .with_tolerance(1e-4)
+            .evaluate(&integral)
+        {
+            Ok(Value::Number(Number::Float(x))) => {
+                assert!((x - 2.0 / 3.0).abs() < 1e-4)
+            }
+            value => panic!("expected a float, got {:?}", value),
+        }
         assert_eq!(
-            parser::KeywordParser::new().parse("True"),
-            Ok(Keyword::True)
+            Environment::default()
+                .with_tolerance(1e-300)
+                .evaluate(&integral),
+            Err(InternalParserError::NonConvergentIntegral { start: 0, end: 25 })
         );
+
         assert_eq!(
-            parser::KeywordParser::new().parse("type"),
-            Ok(Keyword::Type)
+            eval("∫(x in 0..1) 1/x dx"),
+            Err(InternalParserError::NonConvergentIntegral { start: 0, end: 21 })
         );
-        assert_eq!(parser::KeywordParser::new().parse("use"), Ok(Keyword::Use));
         assert_eq!(
-            parser::KeywordParser::new().parse("where"),
-            Ok(Keyword::Where)
+            eval("∫(x in \"a\"..1) x dx"),
+            Err(InternalParserError::InvalidDomain {
+                operator: "∫",
+                expected: "a range of real numbers",
+                start: 9,
+                end: 12
+            })
         );
         assert_eq!(
-            parser::KeywordParser::new().parse("while"),
-            Ok(Keyword::While)
+            eval("∫(x in 0..1) (x < 1) dx"),
+            Err(InternalParserError::InvalidIntegrand {
+                found: "logic",
+                start: 15,
+                end: 22
+            })
         );
     }
 
     #[test]
-    fn text_lex_reserved_keywords() {
-        assert_eq!(
-            parser::KeywordParser::new().parse("abstract"),
-            Ok(Keyword::Reserved(ReservedKeyword::Abstract))
+    fn test_evaluate_sets() {
+        let set = |elements: &[i32]| {
+            let mut set = Set::new();
+            for n in elements {
+                set.insert(integer(*n)).unwrap();
+            }
+            Value::Set(set)
+        };
+
+        assert_eq!(eval("{1, 2, 3}"), Ok(set(&[1, 2, 3])));
+        assert_eq!(eval("{3, 1, 2}"), Ok(set(&[1, 2, 3])));
+        assert_eq!(eval("{1, 1.0, 2/2r, 1 + 1}"), Ok(set(&[1, 2])));
+        assert_eq!(eval("{}"), Ok(set(&[])));
+        assert_eq!(eval("{1, 2} ∪ {2, 3}"), Ok(set(&[1, 2, 3])));
+        assert_eq!(eval("{1, 2} ∩ {2, 3}"), Ok(set(&[2])));
+        assert_eq!(eval("{1, 2} ∩ {3}"), Ok(set(&[])));
+        assert_eq!(eval("{1} ∪ {2} ∩ {2, 3}"), Ok(set(&[1, 2])));
+        assert_eq!(eval("{} ∪ {1}"), Ok(set(&[1])));
+
+        assert_eq!(eval("2 ∈ {1, 2, 3}"), Ok(logic(Some(true))));
+        assert_eq!(eval("2.0 ∈ {1, 2, 3}"), Ok(logic(Some(true))));
+        assert_eq!(eval("4 ∈ {1, 2, 3}"), Ok(logic(Some(false))));
+        assert_eq!(eval("4 ∉ {1, 2, 3}"), Ok(logic(Some(true))));
+        assert_eq!(eval("1 ∈ {}"), Ok(logic(Some(false))));
+        assert_eq!(eval("{1} ∈ {{1}, {2}}"), Ok(logic(Some(true))));
+        assert_eq!(eval("1000 m ∈ {1 km}"), Ok(logic(Some(true))));
+
+        assert_eq!(eval("{1} ⊂ {1, 2}"), Ok(logic(Some(true))));
+        assert_eq!(eval("{1, 2} ⊂ {1, 2}"), Ok(logic(Some(false))));
+        assert_eq!(eval("{1, 2} ⊆ {2, 1}"), Ok(logic(Some(true))));
+        assert_eq!(eval("{1, 3} ⊆ {1, 2}"), Ok(logic(Some(false))));
+        assert_eq!(eval("{1, 3} ⊄ {1, 2}"), Ok(logic(Some(true))));
+        assert_eq!(eval("{1, 2} ⊃ {2}"), Ok(logic(Some(true))));
+        assert_eq!(eval("{1, 2} ⊇ {1, 2}"), Ok(logic(Some(true))));
+        assert_eq!(eval("{1, 2} ⊅ {1, 2}"), Ok(logic(Some(true))));
+        assert_eq!(eval("{} ⊂ {true}"), Ok(logic(Some(true))));
+        assert_eq!(eval("{1, 2} == {2, 1}"), Ok(logic(Some(true))));
+        assert_eq!(eval("{1, 2} != {1}"), Ok(logic(Some(true))));
+        assert_eq!(eval("{'a'} == {}"), Ok(logic(Some(false))));
+
+        // A set's elements are all of the same type
+        assert_eq!(
+            eval("{1, true}"),
+            Err(InternalParserError::MismatchedSetTypes {
+                error: MismatchedTypes {
+                    expected: "number",
+                    found: "logic"
+                },
+                start: 4,
+                end: 8
+            })
         );
         assert_eq!(
-            parser::KeywordParser::new().parse("async"),
-            Ok(Keyword::Reserved(ReservedKeyword::Async))
+            eval("\"a\" ∈ {1}"),
+            Err(InternalParserError::MismatchedSetTypes {
+                error: MismatchedTypes {
+                    expected: "number",
+                    found: "string"
+                },
+                start: 0,
+                end: 11
+            })
         );
         assert_eq!(
-            parser::KeywordParser::new().parse("await"),
-            Ok(Keyword::Reserved(ReservedKeyword::Await))
+            eval("{1} ∪ {'a'}"),
+            Err(InternalParserError::MismatchedSetTypes {
+                error: MismatchedTypes {
+                    expected: "number",
+                    found: "char"
+                },
+                start: 0,
+                end: 13
+            })
         );
         assert_eq!(
-            parser::KeywordParser::new().parse("crate"),
-            Ok(Keyword::Reserved(ReservedKeyword::Crate))
+            eval("{1} < {1, 2}"),
+            Err(InternalParserError::InvalidOperands {
+                operator: "<",
+                left: "set",
+                right: "set",
+                start: 0,
+                end: 12
+            })
         );
         assert_eq!(
-            parser::KeywordParser::new().parse("do"),
-            Ok(Keyword::Reserved(ReservedKeyword::Do))
+            eval("1 ∪ {1}"),
+            Err(InternalParserError::InvalidOperands {
+                operator: "∪",
+                left: "number",
+                right: "set",
+                start: 0,
+                end: 9
+            })
         );
         assert_eq!(
-            parser::KeywordParser::new().parse("dyn"),
-            Ok(Keyword::Reserved(ReservedKeyword::Dyn))
+            eval("1 ∈ 1"),
+            Err(InternalParserError::InvalidOperands {
+                operator: "∈",
+                left: "number",
+                right: "number",
+                start: 0,
+                end: 7
+            })
         );
+    }
+
+    #[test]
+    fn test_evaluate_quantifiers() {
+        assert_eq!(eval("∀ x ∈ {1, 2, 3}: x > 0"), Ok(logic(Some(true))));
+        assert_eq!(eval("∀ x ∈ {1, 2, 3}: x > 1"), Ok(logic(Some(false))));
+        assert_eq!(eval("∃ x ∈ 1..5: x² == 9"), Ok(logic(Some(true))));
+        assert_eq!(eval("∃ x ∈ 1..3: x² == 9"), Ok(logic(Some(false))));
         assert_eq!(
-            parser::KeywordParser::new().parse("export"),
-            Ok(Keyword::Reserved(ReservedKeyword::Export))
+            eval("∀ x ∈ 1..=3: ∃ y ∈ [3, 2, 1]: x + y == 4"),
+            Ok(logic(Some(true)))
         );
         assert_eq!(
-            parser::KeywordParser::new().parse("extern"),
-            Ok(Keyword::Reserved(ReservedKeyword::Extern))
+            eval("{x | x ∈ 2..=10, (∀ d ∈ 2..x: x % d != 0)}"),
+            eval("{2, 3, 5, 7}")
         );
+
+        // Over an empty domain, `∀` holds and `∃` doesn't
+        assert_eq!(eval("∀ x ∈ {}: false"), Ok(logic(Some(true))));
+        assert_eq!(eval("∃ x ∈ []: true"), Ok(logic(Some(false))));
+
+        // `missing` unless decided by some value
+        assert_eq!(eval("∀ x ∈ {1, 2}: x < 2 || missing"), Ok(logic(None)));
         assert_eq!(
-            parser::KeywordParser::new().parse("final"),
-            Ok(Keyword::Reserved(ReservedKeyword::Final))
+            eval("∀ x ∈ {1, 2}: x > 1 && missing"),
+            Ok(logic(Some(false)))
         );
+        assert_eq!(eval("∃ x ∈ {1, 2}: x > 1 && missing"), Ok(logic(None)));
         assert_eq!(
-            parser::KeywordParser::new().parse("import"),
-            Ok(Keyword::Reserved(ReservedKeyword::Import))
+            eval("∃ x ∈ {1, 2}: x < 2 || missing"),
+            Ok(logic(Some(true)))
         );
-        assert_eq!(
-            parser::KeywordParser::new().parse("let"),
-            Ok(Keyword::Reserved(ReservedKeyword::Let))
+
+        // The predicate isn't evaluated beyond the deciding value
+        assert_eq!(eval("∃ x ∈ [1, \"a\"]: x == 1"), Ok(logic(Some(true))));
+        assert_eq!(eval("∃ n ∈ 1..10^100: n > 5"), Ok(logic(Some(true))));
+        // Nor beyond the iterations of a constant expression
+        assert_eq!(
+            eval("∀ n ∈ 1..10^15: n > 0"),
+            Err(InternalParserError::NotConstant { start: 10, end: 18 })
+        );
+        assert_eq!(
+            eval("∀ x ∈ [1, \"a\"]: x == 1"),
+            Err(InternalParserError::InvalidOperands {
+                operator: "==",
+                left: "string",
+                right: "number",
+                start: 20,
+                end: 26
+            })
         );
+
         assert_eq!(
-            parser::KeywordParser::new().parse("macro"),
-            Ok(Keyword::Reserved(ReservedKeyword::Macro))
+            eval("∀ x ∈ 3: true"),
+            Err(InternalParserError::InvalidDomain {
+                operator: "∀",
+                expected: "a range of integers, a set or a list",
+                start: 10,
+                end: 11
+            })
         );
         assert_eq!(
-            parser::KeywordParser::new().parse("move"),
-            Ok(Keyword::Reserved(ReservedKeyword::Move))
+            eval("∃ x ∈ {1, 2}: x + 1"),
+            Err(InternalParserError::InvalidCondition {
+                found: "number",
+                start: 18,
+                end: 23
+            })
         );
+    }
+
+    #[test]
+    fn test_evaluate_comprehensions() {
+        let set = |elements: &[i32]| {
+            let mut set = Set::new();
+            for n in elements {
+                set.insert(integer(*n)).unwrap();
+            }
+            Value::Set(set)
+        };
+        let list = |elements: &[i32]| {
+            Value::List(elements.iter().map(|n| integer(*n)).collect())
+        };
+
         assert_eq!(
-            parser::KeywordParser::new().parse("mut"),
-            Ok(Keyword::Reserved(ReservedKeyword::Mut))
+            eval("{ x² | x ∈ 1..10, x % 2 == 0 }"),
+            Ok(set(&[4, 16, 36, 64]))
         );
+        assert_eq!(eval("[x % 3 | x ∈ 1..=6]"), Ok(list(&[1, 2, 0, 1, 2, 0])));
+        assert_eq!(eval("{x % 3 | x ∈ 1..=6}"), Ok(set(&[0, 1, 2])));
         assert_eq!(
-            parser::KeywordParser::new().parse("override"),
-            Ok(Keyword::Reserved(ReservedKeyword::Override))
+            eval("[10·x + y | x ∈ 1..=2, y ∈ {1, 2}, x != y]"),
+            Ok(list(&[12, 21]))
         );
+        assert_eq!(eval("[y | x ∈ [3, 1], y ∈ 0..x]"), Ok(list(&[0, 1, 2, 0])));
+        assert_eq!(eval("{x | x ∈ 1..10, x > 20}"), Ok(set(&[])));
+        assert_eq!(eval("[x | x ∈ 1..3, missing]"), Ok(list(&[])));
+        assert_eq!(eval("[1, 1 + 1, 3]"), Ok(list(&[1, 2, 3])));
+        assert_eq!(eval("{x | x ∈ {1, 2, 3}, x ∈ {2, 3, 4}}"), Ok(set(&[2, 3])));
+        assert_eq!(eval("∑(i ∈ 1..=4) i"), Ok(integer(10)));
+
         assert_eq!(
-            parser::KeywordParser::new().parse("priv"),
-            Ok(Keyword::Reserved(ReservedKeyword::Priv))
+            eval("{x | x ∈ 1..3, x + 1}"),
+            Err(InternalParserError::InvalidCondition {
+                found: "number",
+                start: 17,
+                end: 22
+            })
         );
         assert_eq!(
-            parser::KeywordParser::new().parse("proc"),
-            Ok(Keyword::Reserved(ReservedKeyword::Proc))
+            eval("{x | x ∈ 3}"),
+            Err(InternalParserError::InvalidDomain {
+                operator: "∈",
+                expected: "a range of integers, a set or a list",
+                start: 11,
+                end: 12
+            })
         );
         assert_eq!(
-            parser::KeywordParser::new().parse("ref"),
-            Ok(Keyword::Reserved(ReservedKeyword::Ref))
+            eval("[x | x ∈ 1.5..3]"),
+            Err(InternalParserError::InvalidDomain {
+                operator: "∈",
+                expected: "a range of integers, a set or a list",
+                start: 11,
+                end: 14
+            })
         );
+        // Domains too long to iterate at compile time aren't constant
         assert_eq!(
-            parser::KeywordParser::new().parse("typeof"),
-            Ok(Keyword::Reserved(ReservedKeyword::Typeof))
+            eval("{x | x ∈ 0..10^12}"),
+            Err(InternalParserError::NotConstant { start: 11, end: 19 })
         );
         assert_eq!(
-            parser::KeywordParser::new().parse("unsafe"),
-            Ok(Keyword::Reserved(ReservedKeyword::Unsafe))
+            eval("{x | x ∈ [1, true]}"),
+            Err(InternalParserError::MismatchedSetTypes {
+                error: MismatchedTypes {
+                    expected: "number",
+                    found: "logic"
+                },
+                start: 1,
+                end: 2
+            })
         );
-        assert_eq!(
-            parser::KeywordParser::new().parse("unsized"),
-            Ok(Keyword::Reserved(ReservedKeyword::Unsized))
+    }
+
+    #[test]
+    fn test_evaluate_powers_and_roots() {
+        assert_eq!(eval("2²"), Ok(integer(4)));
+        assert_eq!(eval("2¹⁰ - 1"), Ok(integer(1023)));
+        assert_eq!(eval("-2²"), Ok(integer(-4)));
+        assert_eq!(eval("(2/3r)⁻²"), Ok(rational(9, 4)));
+        assert_eq!(eval("√4"), Ok(integer(2)));
+        assert_eq!(eval("√(9/4r)"), Ok(rational(3, 2)));
+        assert_eq!(eval("∛(8/27r)"), Ok(rational(2, 3)));
+        assert_eq!(eval("∜81"), Ok(integer(3)));
+        assert_eq!(eval("⁵√32"), Ok(integer(2)));
+        assert_eq!(eval("∛(-8)"), Ok(integer(-2)));
+        assert_eq!(eval("√3²"), Ok(integer(3)));
+        assert_eq!(eval("√2"), Ok(Value::Number(Number::Float(2f64.sqrt()))));
+        assert_eq!(
+            eval("√(-4)"),
+            Ok(Value::Number(Number::Complex(rug::Complex::with_val(
+                53,
+                (0, 2)
+            ))))
+        );
+        let metre = Unit::from_symbol("m").unwrap();
+        assert_eq!(
+            eval("√(4 m²)"),
+            Ok(Value::Quantity(Quantity::new(2.0, metre)))
+        );
+        assert_eq!(
+            eval("(3 m)²"),
+            Ok(Value::Quantity(Quantity::new(
+                9.0,
+                metre.checked_powi(2).unwrap()
+            )))
         );
+
         assert_eq!(
-            parser::KeywordParser::new().parse("virtual"),
-            Ok(Keyword::Reserved(ReservedKeyword::Virtual))
+            eval("√(2 m)"),
+            Err(InternalParserError::InvalidOperands {
+                operator: "√",
+                left: "number",
+                right: "quantity",
+                start: 0,
+                end: 8
+            })
         );
         assert_eq!(
-            parser::KeywordParser::new().parse("yield"),
-            Ok(Keyword::Reserved(ReservedKeyword::Yield))
+            eval("⁰√2"),
+            Err(InternalParserError::DivisionByZero { start: 0, end: 7 })
         );
     }
 
     #[test]
-    fn test_lex_comments() {
-        // Line comments
+    fn test_evaluate_quantities() {
+        let metre = Unit::from_symbol("m").unwrap();
+        let second = Unit::from_symbol("s").unwrap();
+
         assert_eq!(
-            parser::LineCommentParser::new().parse("//"),
-            Ok(Comment::LineComment {
-                comment: String::from("//"),
-            })
+            eval("1 km + 500 m"),
+            Ok(Value::Quantity(Quantity::new(
+                1.5,
+                Unit::from_symbol("km").unwrap()
+            )))
         );
-
         assert_eq!(
-            parser::LineCommentParser::new().parse("//   - Only a comment"),
-            Ok(Comment::LineComment {
-                comment: String::from("//   - Only a comment"),
-            })
+            eval("2 m * 3 s"),
+            Ok(Value::Quantity(Quantity::new(
+                6.0,
+                metre.checked_mul(second).unwrap()
+            )))
         );
         assert_eq!(
-            parser::LineCommentParser::new().parse("// Hello, world!"),
-            Ok(Comment::LineComment {
-                comment: String::from("// Hello, world!"),
-            })
+            eval("6 m / 2"),
+            Ok(Value::Quantity(Quantity::new(3.0, metre)))
         );
         assert_eq!(
-            parser::LineCommentParser::new().parse("// Hello, \tworld!"),
-            Ok(Comment::LineComment {
-                comment: String::from("// Hello, \tworld!"),
-            })
+            eval("(2 m) ^ 2"),
+            Ok(Value::Quantity(Quantity::new(
+                4.0,
+                metre.checked_powi(2).unwrap()
+            )))
         );
         assert_eq!(
-            parser::LineCommentParser::new().parse(
-                "// This is an incredibly long line comment that should be parsed \
-                 correctly. It should also be parsed correctly if it contains a \
-                 newline"
-            ),
-            Ok(Comment::LineComment {
-                comment: String::from(
-                    "// This is an incredibly long line comment that should be \
-                     parsed correctly. It should also be parsed correctly if it \
-                     contains a newline"
-                ),
-            })
+            eval("-(2 m)"),
+            Ok(Value::Quantity(Quantity::new(-2.0, metre)))
         );
-        assert!(parser::LineCommentParser::new()
-            .parse("// Hello, \nworld!")
-            .is_err());
+        assert_eq!(eval("1 m < 1 km"), Ok(logic(Some(true))));
+        assert_eq!(eval("1000 m == 1 km"), Ok(logic(Some(true))));
 
-        // Block comments
+        // Multiplying quantities whose exponents overflow is an error
         assert_eq!(
-            parser::BlockCommentParser::new().parse("/* */"),
-            Ok(Comment::BlockComment {
-                comment: String::from("/* */"),
-            })
+            eval("(1 m¹⁰⁰) * (1 m¹⁰⁰)"),
+            Err(InternalParserError::UnitExponentOutOfRange { start: 0, end: 29 })
         );
         assert_eq!(
-            parser::BlockCommentParser::new().parse("/**/"),
-            Ok(Comment::BlockComment {
-                comment: String::from("/**/"),
+            eval("(1 m¹⁰⁰) ^ 2"),
+            Err(InternalParserError::UnitExponentOutOfRange { start: 0, end: 17 })
+        );
+
+        // Adding metres to seconds is an error
+        let incompatible = |start, end| InternalParserError::IncompatibleUnits {
+            error: IncompatibleDimensions {
+                left: metre.dimension,
+                right: second.dimension,
+            },
+            start,
+            end,
+        };
+        assert_eq!(eval("1 m + 1 s"), Err(incompatible(0, 9)));
+        assert_eq!(eval("2 * (1 m - 1 s)"), Err(incompatible(5, 14)));
+        assert_eq!(eval("1 m < 1 s"), Err(incompatible(0, 9)));
+        assert_eq!(
+            eval("1 m + 1"),
+            Err(InternalParserError::IncompatibleUnits {
+                error: IncompatibleDimensions {
+                    left: metre.dimension,
+                    right: Unit::DIMENSIONLESS.dimension,
+                },
+                start: 0,
+                end: 7,
             })
         );
         assert_eq!(
-            parser::BlockCommentParser::new().parse("/***/"),
-            Ok(Comment::BlockComment {
-                comment: String::from("/***/"),
+            eval("(2 m) ^ 1.5"),
+            Err(InternalParserError::InvalidOperands {
+                operator: "^",
+                left: "quantity",
+                right: "number",
+                start: 0,
+                end: 11,
             })
         );
-        assert_eq!(
-            parser::BlockCommentParser::new().parse("/*   - Only a comment */"),
-            Ok(Comment::BlockComment {
-                comment: String::from("/*   - Only a comment */"),
+    }
+
+    #[test]
+    fn test_evaluate_logic() {
+        assert_eq!(eval("1 < 2 && 2 <= 2"), Ok(logic(Some(true))));
+        assert_eq!(eval("1/2r == 0.5"), Ok(logic(Some(true))));
+        assert_eq!(eval("0.1 + 0.2 == 0.3"), Ok(logic(Some(false))));
+        assert_eq!(eval("1/10r + 2/10r == 3/10r"), Ok(logic(Some(true))));
+        assert_eq!(eval("NaN == NaN"), Ok(logic(Some(false))));
+        assert_eq!(eval("𝑖 * 𝑖 == -1"), Ok(logic(Some(true))));
+        assert_eq!(eval("\"a\" != \"b\" && 'a' < 'b'"), Ok(logic(Some(true))));
+
+        // Three-valued logic
+        assert_eq!(eval("missing && false"), Ok(logic(Some(false))));
+        assert_eq!(eval("missing && true"), Ok(logic(None)));
+        assert_eq!(eval("missing || true"), Ok(logic(Some(true))));
+        assert_eq!(eval("missing || false"), Ok(logic(None)));
+        assert_eq!(eval("!missing"), Ok(logic(None)));
+        assert_eq!(eval("missing == missing"), Ok(logic(None)));
+        assert_eq!(eval("true & false | true"), Ok(logic(Some(true))));
+
+        // `&&` and `||` short-circuit
+        assert_eq!(eval("false && 1 / 0 == 1"), Ok(logic(Some(false))));
+        assert_eq!(eval("true || 1 / 0 == 1"), Ok(logic(Some(true))));
+    }
+
+    #[test]
+    fn test_evaluate_errors() {
+        assert_eq!(
+            eval("1 + true"),
+            Err(InternalParserError::InvalidOperands {
+                operator: "+",
+                left: "number",
+                right: "logic",
+                start: 0,
+                end: 8,
             })
         );
         assert_eq!(
-            parser::BlockCommentParser::new().parse("/*** - Only a comment */"),
-            Ok(Comment::BlockComment {
-                comment: String::from("/*** - Only a comment */"),
+            eval("2i < 3"),
+            Err(InternalParserError::InvalidOperands {
+                operator: "<",
+                left: "complex",
+                right: "number",
+                start: 0,
+                end: 6,
             })
         );
         assert_eq!(
-            parser::BlockCommentParser::new().parse("/* Hello, world! */"),
-            Ok(Comment::BlockComment {
-                comment: String::from("/* Hello, world! */"),
+            eval("\"a\" == b\"a\""),
+            Err(InternalParserError::InvalidOperands {
+                operator: "==",
+                left: "string",
+                right: "byte string",
+                start: 0,
+                end: 11,
             })
         );
         assert_eq!(
-            parser::BlockCommentParser::new().parse("/* Hello, \tworld! */"),
-            Ok(Comment::BlockComment {
-                comment: String::from("/* Hello, \tworld! */"),
+            eval("1 + -true"),
+            Err(InternalParserError::InvalidOperand {
+                operator: "-",
+                operand: "logic",
+                start: 4,
+                end: 9,
             })
         );
         assert_eq!(
-            parser::BlockCommentParser::new().parse(
-                "/* This is an incredibly long\nblock comment that should be \
-                 parsed correctly.\nIt should also be parsed correctly if it \
-                 contains a newline */"
-            ),
-            Ok(Comment::BlockComment {
-                comment: String::from(
-                    "/* This is an incredibly long\nblock comment that should be \
-                     parsed correctly.\nIt should also be parsed correctly if it \
-                     contains a newline */"
-                ),
-            })
+            eval("1 + x"),
+            Err(InternalParserError::NotConstant { start: 4, end: 5 })
         );
-        // TODO: Add implementation for nested block comments in the grammar
-        // assert_eq!(
-        //     parser::BlockCommentParser::new()
-        //         .parse("/* In Balsapop /* we can /* nest comments */ */ */"),
-        //     Ok(Comment::BlockComment {
-        //         comment: String::from(
-        //             "/* In Balsapop /* we can /* nest comments */ */ */"
-        //         ),
-        //     })
-        // );
+        assert_eq!(
+            eval("x = 1"),
+            Err(InternalParserError::NotConstant { start: 0, end: 5 })
+        );
+        assert_eq!(
+            eval("1..2"),
+            Err(InternalParserError::NotConstant { start: 0, end: 4 })
+        );
+        assert_eq!(
+            eval("Point { x: 1 }"),
+            Err(InternalParserError::NotConstant { start: 0, end: 14 })
+        );
+        assert_eq!(
+            eval("Shape::Empty"),
+            Err(InternalParserError::NotConstant { start: 0, end: 12 })
+        );
+        assert_eq!(
+            eval("{1}.len"),
+            Err(InternalParserError::NotConstant { start: 0, end: 7 })
+        );
+    }
+}
 
-        // Doc comments (e.g. outer line doc comments, inner line doc comments, outer
-        // block doc comments, inner block doc comments)
+#[cfg(test)]
+mod resolve_test_suite {
+    use super::*;
+    use crate::lexer::Lexer;
+    use crate::parser_errors::InternalParserError;
+    use crate::resolve;
+    use pretty_assertions::assert_eq;
 
-        // - Outer line doc comments
+    fn check(source: &str) -> Result<(), InternalParserError> {
+        resolve::check(
+            &parser::ProgramParser::new()
+                .parse(Lexer::new(source))
+                .unwrap(),
+        )
+        .map(|_| ())
+    }
+
+    /// The resolved method calls of a source, in order, as the source of each
+    /// call and of the method which it calls.
+    fn resolved(source: &str) -> Vec<(&str, &str)> {
+        let program = parser::ProgramParser::new()
+            .parse(Lexer::new(source))
+            .unwrap();
+        let mut calls = resolve::check(&program)
+            .unwrap()
+            .into_iter()
+            .map(|(call, method)| (call, method.span))
+            .collect::<Vec<_>>();
+        calls.sort_by_key(|(call, _)| call.start);
+        calls
+            .into_iter()
+            .map(|(call, method)| {
+                (
+                    &source[call.start..call.end],
+                    &source[method.start..method.end],
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_resolve_methods() {
+        let shapes = "
+            trait Area {
+                fn area(self) -> Float;
+                fn twice(self) -> Float { 2 * self.area() }
+            }
+            struct Circle { r: Float }
+            impl Circle {
+                fn new(r: Float) -> Circle { Circle { r } }
+                fn diameter(self) -> Float { 2 * self.r }
+            }
+            impl Area for Circle {
+                fn area(self) -> Float { 3 * self.r² }
+            }
+            a := Circle { r: 1 }.twice();
+            d := Circle { r: 1 }.diameter();
+        ";
+        assert_eq!(check(shapes), Ok(()));
+
+        // An impl may define a default method itself
         assert_eq!(
-            parser::OuterLineDocCommentParser::new().parse("///"),
-            Ok(Comment::DocComment(DocComment::OuterLineDocComment {
-                comment: String::from("///"),
-            }))
+            check(
+                "trait A { fn f(self) -> Int { 1 } } \
+                 impl A for C { fn f(self) -> Int { 2 } } \
+                 x := C {}.f();"
+            ),
+            Ok(())
         );
+        // An inherent method takes precedence over those of traits
         assert_eq!(
-            parser::OuterLineDocCommentParser::new()
-                .parse("///  - Outer line doc (exactly 3 slashes)"),
-            Ok(Comment::DocComment(DocComment::OuterLineDocComment {
-                comment: String::from("///  - Outer line doc (exactly 3 slashes)"),
-            }))
+            check(
+                "trait A { fn name(self) -> Int { 1 } } trait B { fn name(self) -> Int { 2 } } \
+                 impl A for C {} impl B for C {} impl C { fn name(self) -> Int { 3 } } \
+                 n := C {}.name();"
+            ),
+            Ok(())
         );
+        // `self` has the methods of the traits which bound `Self`
         assert_eq!(
-            parser::OuterLineDocCommentParser::new().parse("/// Hello, world!"),
-            Ok(Comment::DocComment(DocComment::OuterLineDocComment {
-                comment: String::from("/// Hello, world!"),
-            }))
+            check(
+                "trait Named { fn name(self) -> Int; } \
+                 trait Greet where Self: Named { fn greet(self) -> Int { self.name() } }"
+            ),
+            Ok(())
         );
         assert_eq!(
-            parser::OuterLineDocCommentParser::new().parse("/// Hello, \tworld!"),
-            Ok(Comment::DocComment(DocComment::OuterLineDocComment {
-                comment: String::from("/// Hello, \tworld!"),
-            }))
+            check(
+                "trait Named { fn name(self) -> Int; } \
+                 trait Greet { fn greet(self) -> Int where Self: Named { self.name() } }"
+            ),
+            Ok(())
         );
+        // Calls on variables resolve through the types of their declarations
         assert_eq!(
-            parser::OuterLineDocCommentParser::new().parse(
-                "/// This is an incredibly long line comment that should be parsed \
-                 correctly. It should also be parsed correctly if it contains a \
-                 newline"
+            resolved(
+                "struct C { r: Int } impl C { fn area(self) -> Int { self.r } } \
+                 c := C { r: 1 }; a := c.area();"
             ),
-            Ok(Comment::DocComment(DocComment::OuterLineDocComment {
-                comment: String::from(
-                    "/// This is an incredibly long line comment that should be \
-                     parsed correctly. It should also be parsed correctly if it \
-                     contains a newline"
-                ),
-            }))
+            vec![("c.area()", "fn area(self) -> Int { self.r }")]
         );
-        assert!(parser::OuterLineDocCommentParser::new()
-            .parse("/// Hello, \nworld!")
-            .is_err());
-        assert!(parser::OuterLineDocCommentParser::new()
-            .parse("//// Hello, \nworld!")
-            .is_err());
-
-        // - Inner line doc comments
         assert_eq!(
-            parser::InnerLineDocCommentParser::new().parse("//!"),
-            Ok(Comment::DocComment(DocComment::InnerLineDocComment {
-                comment: String::from("//!"),
-            }))
+            resolved(
+                "struct C; trait A { fn f(self) -> Int; } \
+                 impl A for C { fn f(self) -> Int { 1 } } \
+                 fn g(c: C) -> Int { c.f() }"
+            ),
+            vec![("c.f()", "fn f(self) -> Int { 1 }")]
         );
         assert_eq!(
-            parser::InnerLineDocCommentParser::new().parse("//! Hello, world!"),
-            Ok(Comment::DocComment(DocComment::InnerLineDocComment {
-                comment: String::from("//! Hello, world!"),
-            }))
+            resolved(
+                "struct C; impl C { fn f(self) -> Int { 1 } \
+                 fn g(other: Self) -> Int { d: C := other; d.f() + (other).f() } }"
+            ),
+            vec![
+                ("d.f()", "fn f(self) -> Int { 1 }"),
+                ("(other).f()", "fn f(self) -> Int { 1 }")
+            ]
         );
         assert_eq!(
-            parser::InnerLineDocCommentParser::new().parse("//! Hello, \tworld!"),
-            Ok(Comment::DocComment(DocComment::InnerLineDocComment {
-                comment: String::from("//! Hello, \tworld!"),
-            }))
+            resolved(
+                "trait A { fn f(self) -> Int { 1 } } trait B where Self: A { \
+                 fn g(self, other: Self) -> Int { self.f() + other.f() } }"
+            ),
+            vec![
+                ("self.f()", "fn f(self) -> Int { 1 }"),
+                ("other.f()", "fn f(self) -> Int { 1 }")
+            ]
         );
+        // Variables which are redeclared, or shadowed by the variable of a big
+        // operator, aren't resolved through their former types
         assert_eq!(
-            parser::InnerLineDocCommentParser::new().parse(
-                "//! This is an incredibly long line comment that should be parsed \
-                 correctly. It should also be parsed correctly if it contains a \
-                 newline"
+            check(
+                "struct C; impl C { fn f(self) -> Int { 1 } } \
+                 c := C {}; s := ∑(c in 1..=3) c.g(); c := 1; c.g();"
             ),
-            Ok(Comment::DocComment(DocComment::InnerLineDocComment {
-                comment: String::from(
-                    "//! This is an incredibly long line comment that should be \
-                     parsed correctly. It should also be parsed correctly if it \
-                     contains a newline"
-                ),
-            }))
+            Ok(())
+        );
+        // The receivers of other calls aren't resolved without their types
+        assert_eq!(check("struct C; x := f().g();"), Ok(()));
+        // Nor are those of constructions of types which aren't declared by the
+        // source file, even if they have impls
+        assert_eq!(
+            check("impl C { fn f(self) -> Int { 1 } } x := C {}.g();"),
+            Ok(())
+        );
+        assert_eq!(
+            resolved("impl C { fn f(self) -> Int { 1 } } x := C {}.f();"),
+            vec![]
+        );
+        // Nor are those of parameters whose types aren't structs or enums of
+        // the source file
+        assert_eq!(check("fn f(c: Circle) -> Int { c.area() }"), Ok(()));
+        // Items within functions are resolved too
+        assert_eq!(
+            check("fn f() -> Int { impl C { fn g(self) -> Int { 1 } } C {}.g() }"),
+            Ok(())
         );
-        assert!(parser::InnerLineDocCommentParser::new()
-            .parse("//! Hello, \nworld!")
-            .is_err());
-
-        // - Outer block doc comments
-        // /**  - Outer block doc (exactly) 2 asterisks */
     }
 
     #[test]
-    fn test_lex_logic_literals() {
+    fn test_resolve_errors() {
         assert_eq!(
-            parser::LogicLiteralParser::new().parse("true"),
-            Ok(LogicLiteral::True { value: true })
+            check("struct C; impl Area for C {}"),
+            Err(InternalParserError::UnknownTrait { start: 15, end: 19 })
         );
         assert_eq!(
-            parser::LogicLiteralParser::new().parse("True"),
-            Ok(LogicLiteral::True { value: true })
+            check("fn f() where Int: Display {}"),
+            Err(InternalParserError::UnknownTrait { start: 18, end: 25 })
         );
         assert_eq!(
-            parser::LogicLiteralParser::new().parse("false"),
-            Ok(LogicLiteral::False { value: false })
+            check("trait Area { fn area(self) -> Int; } impl Area for C {}"),
+            Err(InternalParserError::MissingTraitMethod { start: 37, end: 55 })
         );
         assert_eq!(
-            parser::LogicLiteralParser::new().parse("False"),
-            Ok(LogicLiteral::False { value: false })
+            check(
+                "trait Area { fn area(self) -> Int; } \
+                 impl Area for C { fn area(self) -> Int { 1 } fn perimeter(self) -> Int { 4 } }"
+            ),
+            Err(InternalParserError::UnknownTraitMethod { start: 82, end: 113 })
         );
         assert_eq!(
-            parser::LogicLiteralParser::new().parse("missing"),
-            Ok(LogicLiteral::Missing)
+            check("impl C { fn f(self) -> Int { 1 } } impl C { fn f(self) -> Int { 2 } }"),
+            Err(InternalParserError::DuplicateMethod { start: 44, end: 67 })
         );
         assert_eq!(
-            parser::LogicLiteralParser::new().parse("Missing"),
-            Ok(LogicLiteral::Missing)
+            check(
+                "trait A { fn f(self) -> Int; } \
+                 impl A for C { fn f(self) -> Int { 1 } fn f(self) -> Int { 2 } }"
+            ),
+            Err(InternalParserError::DuplicateMethod { start: 70, end: 93 })
+        );
+        assert_eq!(
+            check("impl C { fn f(self) -> Int { self.g() } }"),
+            Err(InternalParserError::UnknownMethod { start: 29, end: 37 })
+        );
+        assert_eq!(
+            check(
+                "struct C { r: Int } impl C { fn area(self) { self.r } } \
+                 fn main() { c := C { r: 1 }; c.nosuch(); }"
+            ),
+            Err(InternalParserError::UnknownMethod { start: 85, end: 95 })
+        );
+        assert_eq!(
+            check(
+                "trait A { fn f(self) -> Int { 1 } } struct C; \
+                 impl A for C {} impl A for C {}"
+            ),
+            Err(InternalParserError::DuplicateImpl { start: 62, end: 77 })
+        );
+        assert_eq!(
+            check("struct C; x := C {}.f();"),
+            Err(InternalParserError::UnknownMethod { start: 15, end: 23 })
+        );
+        assert_eq!(
+            check(
+                "trait A { fn name(self) -> Int { 1 } } trait B { fn name(self) -> Int { 2 } } \
+                 struct C; impl A for C {} impl B for C {} n := C {}.name();"
+            ),
+            Err(InternalParserError::AmbiguousMethod {
+                start: 125,
+                end: 136
+            })
+        );
+        assert_eq!(
+            check(
+                "trait A { fn name(self) -> Int; } trait B { fn name(self) -> Int; } \
+                 trait C where Self: A + B { fn greet(self) -> Int { self.name() } }"
+            ),
+            Err(InternalParserError::AmbiguousMethod {
+                start: 120,
+                end: 131
+            })
         );
     }
-
-    // fn test_lex_paths() {
-    // Simple path segments
-    // assert_eq!(
-    //     parser::SimplePathSegmentParser::new().parse("a"),
-    //     Ok(PathSegment::Simple(SimplePathSegment {
-    //         name: String::from("a"),
-    //     }))
-    // );
-    // assert_eq!(
-    //     parser::SimplePathSegmentParser::new().parse("super"),
-    //     Ok(SimplePathSegment::SuperSegment {
-    //         segment: String::from("super"),
-    //     })
-    // );
-    // assert_eq!(
-    //     parser::SimplePathSegmentParser::new().parse("self"),
-    //     Ok(SimplePathSegment::SelfValueSegment {
-    //         segment: String::from("self"),
-    //     })
-    // );
-    // assert_eq!(
-    //     parser::SimplePathSegmentParser::new().parse("crate"),
-    //     Ok(SimplePathSegment::CrateSegment {
-    //         segment: String::from("crate"),
-    //     })
-    // );
-    // }
 }
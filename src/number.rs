@@ -0,0 +1,521 @@
+//! Numbers, with exact arithmetic on integers and rationals.
+//!
+//! Integers and rationals are arbitrary precision, so adding, subtracting,
+//! multiplying or dividing them never loses precision (e.g. `1/3r + 2/3r` is
+//! exactly `1`, and `1 / 3` is exactly `1/3`). A number only becomes a float
+//! when combined with one, or when explicitly converted (see
+//! [`Number::to_f64`]). Likewise, a number only becomes complex when combined
+//! with an imaginary or complex number (e.g. `3 + 4i`).
+//!
+//! Exact results are bounded by [`MAX_EXACT_BITS`], beyond which they're
+//! computed as floats instead, so that e.g. `∏(k in 1..=10^6) k` doesn't
+//! exhaust memory.
+
+use std::{
+    cell::RefCell,
+    cmp::Ordering,
+    collections::HashMap,
+    fmt,
+    mem::{self, Discriminant},
+    ops::{Add, Mul, Neg, Sub},
+};
+
+use rug::ops::Pow;
+
+use crate::ast::{Float, Integer, MathematicalConstant, NumericLiteral};
+
+/// The minimum precision (in bits) of the real and imaginary parts of a complex
+/// number, matching that of an f64. Complex numbers converted from exact ones
+/// are more precise if needed to represent them (see
+/// [`Number::complex_precision`]).
+const COMPLEX_PRECISION: u32 = f64::MANTISSA_DIGITS;
+
+/// The largest number of bits an exact result may need (about 315,000 decimal
+/// digits), beyond which it's computed as a float instead (e.g. `10^2000000000`
+/// is `inf`).
+pub(crate) const MAX_EXACT_BITS: u64 = 1 << 20;
+
+/// The exact values of constants with a precision suffix, by constant and
+/// number of digits.
+type PreciseConstants =
+    HashMap<(Discriminant<MathematicalConstant>, u32), rug::Rational>;
+
+thread_local! {
+    /// The precise constants computed so far, so that a constant evaluated
+    /// repeatedly (e.g. the `π_1000` of `∑(k in 1..=100) k·π_1000`, or of an
+    /// integrand) is only computed once.
+    static PRECISE_CONSTANTS: RefCell<PreciseConstants> =
+        RefCell::new(HashMap::new());
+}
+
+/// The exact value of a constant's digits (e.g. of `π_100`), computed on first
+/// use.
+fn precise_constant(constant: &MathematicalConstant, digits: u32) -> rug::Rational {
+    PRECISE_CONSTANTS.with(|constants| {
+        constants
+            .borrow_mut()
+            .entry((mem::discriminant(constant), digits))
+            .or_insert_with(|| {
+                constant
+                    .to_digits(digits)
+                    .to_rational()
+                    .expect("precise constants are finite")
+            })
+            .clone()
+    })
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Number {
+    Integer(rug::Integer),
+    /// A rational which isn't an integer (i.e. whose denominator isn't 1)
+    Rational(rug::Rational),
+    Float(f64),
+    Complex(rug::Complex),
+}
+
+impl Number {
+    /// The rational with the given value, or an integer if its denominator is
+    /// 1, so that exact results have a single representation.
+    pub fn rational(value: rug::Rational) -> Number {
+        if *value.denom() == 1 {
+            Number::Integer(value.into_numer_denom().0)
+        } else {
+            Number::Rational(value)
+        }
+    }
+
+    /// Converts the number to its nearest f64, where complex numbers with a
+    /// nonzero imaginary part are NaN.
+    pub fn to_f64(&self) -> f64 {
+        match self {
+            Number::Integer(n) => n.to_f64(),
+            Number::Rational(q) => q.to_f64(),
+            Number::Float(f) => *f,
+            Number::Complex(z) if z.imag().is_zero() => z.real().to_f64(),
+            Number::Complex(_) => f64::NAN,
+        }
+    }
+
+    pub fn is_exact(&self) -> bool {
+        matches!(self, Number::Integer(_) | Number::Rational(_))
+    }
+
+    /// The number of bits of an exact number's numerator and denominator (or 0
+    /// for an inexact number).
+    fn exact_bits(&self) -> u64 {
+        match self {
+            Number::Integer(n) => u64::from(n.significant_bits()),
+            Number::Rational(q) => {
+                u64::from(q.numer().significant_bits())
+                    + u64::from(q.denom().significant_bits())
+            }
+            Number::Float(_) | Number::Complex(_) => 0,
+        }
+    }
+
+    pub fn is_complex(&self) -> bool {
+        matches!(self, Number::Complex(_))
+    }
+
+    pub fn is_zero(&self) -> bool {
+        match self {
+            Number::Integer(n) => *n == 0,
+            Number::Rational(_) => false,
+            Number::Float(f) => *f == 0.0,
+            Number::Complex(z) => z.real().is_zero() && z.imag().is_zero(),
+        }
+    }
+
+    /// Divides by `rhs`, exactly unless either operand is a float or complex
+    /// (e.g. `1 / 3` is `1/3`) or the quotient may exceed [`MAX_EXACT_BITS`],
+    /// or `None` when dividing an exact number by exact zero.
+    pub fn checked_div(self, rhs: Number) -> Option<Number> {
+        match (self, rhs) {
+            (lhs, rhs) if lhs.is_complex() || rhs.is_complex() => {
+                let (lhs, rhs) = complex_operands(lhs, rhs);
+                Some(Number::Complex(lhs / rhs))
+            }
+            (lhs, rhs) if !lhs.is_exact() || !rhs.is_exact() => {
+                Some(Number::Float(lhs.to_f64() / rhs.to_f64()))
+            }
+            (_, rhs) if rhs.is_zero() => None,
+            (lhs, rhs) if exceeds_exact(&lhs, &rhs) => {
+                Some(Number::Float(lhs.to_f64() / rhs.to_f64()))
+            }
+            (lhs, rhs) => {
+                Some(Number::rational(lhs.into_rational() / rhs.into_rational()))
+            }
+        }
+    }
+
+    /// The remainder of dividing by `rhs`, whose quotient is truncated toward
+    /// zero (e.g. `-7 % 3` is `-1`), or `None` when dividing an exact number by
+    /// exact zero. Complex numbers have no remainder (i.e. it's NaN).
+    pub fn checked_rem(self, rhs: Number) -> Option<Number> {
+        match (self, rhs) {
+            (lhs, rhs) if !lhs.is_exact() || !rhs.is_exact() => {
+                Some(Number::Float(lhs.to_f64() % rhs.to_f64()))
+            }
+            (_, rhs) if rhs.is_zero() => None,
+            (Number::Integer(lhs), Number::Integer(rhs)) => {
+                Some(Number::Integer(lhs % rhs))
+            }
+            (lhs, rhs) => {
+                let (lhs, rhs) = (lhs.into_rational(), rhs.into_rational());
+                let quotient = rug::Rational::from(&lhs / &rhs).trunc();
+                Some(Number::rational(lhs - rhs * quotient))
+            }
+        }
+    }
+
+    /// Compares the numbers' values (e.g. `1/2` equals `0.5`), exactly unless
+    /// both are floats. Complex numbers are only ordered when both are real,
+    /// and otherwise only compare equal to an equal number.
+    pub fn compare(&self, rhs: &Number) -> Option<Ordering> {
+        if self.is_complex() || rhs.is_complex() {
+            let (lhs, rhs) = complex_operands(self.clone(), rhs.clone());
+            return if lhs == rhs {
+                Some(Ordering::Equal)
+            } else if lhs.imag().is_zero() && rhs.imag().is_zero() {
+                lhs.real().partial_cmp(rhs.real())
+            } else {
+                None
+            };
+        }
+        match (self.is_exact(), rhs.is_exact()) {
+            (true, true) => Some(
+                self.clone()
+                    .into_rational()
+                    .cmp(&rhs.clone().into_rational()),
+            ),
+            (true, false) => self.clone().into_rational().partial_cmp(&rhs.to_f64()),
+            (false, true) => rhs
+                .clone()
+                .into_rational()
+                .partial_cmp(&self.to_f64())
+                .map(Ordering::reverse),
+            (false, false) => self.to_f64().partial_cmp(&rhs.to_f64()),
+        }
+    }
+
+    /// Raises the number to the power `rhs`, or `None` when raising an exact
+    /// zero to a negative power (which, like `1/0`, divides by zero).
+    pub fn checked_pow(self, rhs: Number) -> Option<Number> {
+        let zero = Number::Integer(rug::Integer::new());
+        if self.is_exact()
+            && self.is_zero()
+            && rhs.compare(&zero) == Some(Ordering::Less)
+        {
+            return None;
+        }
+        Some(self.pow(rhs))
+    }
+
+    /// Raises the number to the power `rhs`. Integer powers of exact numbers
+    /// are exact (e.g. `(2/3)^-2` is `9/4`), unless they'd need more than
+    /// [`MAX_EXACT_BITS`], while a complex base or exponent takes the
+    /// principal value (e.g. `ℯ^(𝑖π)` is `-1`, to within rounding).
+    pub fn pow(self, rhs: Number) -> Number {
+        match (self, rhs) {
+            (lhs, rhs) if lhs.is_complex() || rhs.is_complex() => {
+                let (lhs, rhs) = complex_operands(lhs, rhs);
+                Number::Complex(lhs.pow(rhs))
+            }
+            (lhs, Number::Integer(n)) if lhs.is_exact() => {
+                let base = lhs.into_rational();
+                // The numerator and denominator of `base^n` need about `n` times
+                // as many bits as those of `base` (beyond the first)
+                let bits = base
+                    .numer()
+                    .significant_bits()
+                    .max(base.denom().significant_bits());
+                let exact = |n: i32| {
+                    u64::from(bits.saturating_sub(1)) * u64::from(n.unsigned_abs())
+                        <= MAX_EXACT_BITS
+                };
+                match n.to_i32() {
+                    Some(n) if (n >= 0 || base != 0) && exact(n) => {
+                        Number::rational(base.pow(n))
+                    }
+                    _ => Number::Float(base.to_f64().powf(n.to_f64())),
+                }
+            }
+            (lhs, rhs) => Number::Float(lhs.to_f64().powf(rhs.to_f64())),
+        }
+    }
+
+    /// The `index`th root of the number (e.g. `∛x` is the 3rd root of `x`), or
+    /// `None` for an exact zeroth root.
+    ///
+    /// Roots of exact perfect powers are exact (e.g. `∛(8/27)` is `2/3`), odd
+    /// roots of negative real numbers are real (e.g. `∛(-8)` is `-2`), and even
+    /// roots of negative numbers are their principal complex root (e.g. `√(-4)`
+    /// is `2𝑖`).
+    pub fn checked_root(self, index: Number) -> Option<Number> {
+        if index.is_exact() && index.is_zero() {
+            return None;
+        }
+        let n = match &index {
+            Number::Integer(n) if !self.is_complex() => n.to_u32(),
+            _ => None,
+        };
+        let n = match n {
+            Some(n) => n,
+            None => {
+                let exponent = Number::Integer(rug::Integer::from(1))
+                    .checked_div(index)
+                    .unwrap();
+                // The principal root of a negative number is complex
+                return Some(if !self.is_complex() && self.to_f64() < 0.0 {
+                    let (radicand, exponent) = complex_operands(self, exponent);
+                    Number::Complex(radicand.pow(exponent))
+                } else {
+                    self.pow(exponent)
+                });
+            }
+        };
+
+        let negative = self.to_f64() < 0.0;
+        let magnitude = if negative { -self } else { self };
+        let root = match magnitude {
+            Number::Integer(_) | Number::Rational(_) => {
+                let magnitude = magnitude.into_rational();
+                let (numerator, denominator) = magnitude.clone().into_numer_denom();
+                match (exact_root(numerator, n), exact_root(denominator, n)) {
+                    (Some(numerator), Some(denominator)) => Number::rational(
+                        rug::Rational::from((numerator, denominator)),
+                    ),
+                    _ => Number::Float(magnitude.to_f64().powf(1.0 / f64::from(n))),
+                }
+            }
+            magnitude => Number::Float(magnitude.to_f64().powf(1.0 / f64::from(n))),
+        };
+        Some(if !negative {
+            root
+        } else if n % 2 == 1 {
+            -root
+        } else if n == 2 {
+            root * Number::Complex(rug::Complex::with_val(COMPLEX_PRECISION, (0, 1)))
+        } else {
+            // e^(iπ/n) is the principal nth root of -1
+            let angle = std::f64::consts::PI / f64::from(n);
+            root * Number::Complex(rug::Complex::with_val(
+                COMPLEX_PRECISION,
+                (angle.cos(), angle.sin()),
+            ))
+        })
+    }
+
+    /// Raises `ℯ` to the power of the number.
+    pub fn exp(self) -> Number {
+        match self {
+            Number::Complex(z) => Number::Complex(z.exp()),
+            x => Number::Float(x.to_f64().exp()),
+        }
+    }
+
+    /// Applies a function given by its real and complex definitions (e.g.
+    /// `f64::sin` and `rug::Complex::sin`), where a real number is first
+    /// converted to a float.
+    pub fn apply(
+        self,
+        real: fn(f64) -> f64,
+        complex: fn(rug::Complex) -> rug::Complex,
+    ) -> Number {
+        match self {
+            Number::Complex(z) => Number::Complex(complex(z)),
+            x => Number::Float(real(x.to_f64())),
+        }
+    }
+
+    /// The absolute value (or modulus, if complex) of the number, which is
+    /// exact for integers and rationals.
+    pub fn abs(self) -> Number {
+        match self {
+            Number::Integer(n) => Number::Integer(n.abs()),
+            Number::Rational(q) => Number::Rational(q.abs()),
+            Number::Float(f) => Number::Float(f.abs()),
+            Number::Complex(z) => Number::Float(z.abs().real().to_f64()),
+        }
+    }
+
+    /// The exact value of an integer or rational.
+    fn into_rational(self) -> rug::Rational {
+        match self {
+            Number::Integer(n) => rug::Rational::from(n),
+            Number::Rational(q) => q,
+            Number::Float(_) | Number::Complex(_) => {
+                unreachable!("floats and complex numbers aren't exact")
+            }
+        }
+    }
+
+    /// The number as a complex number of (at least) the given precision.
+    fn into_complex(self, precision: u32) -> rug::Complex {
+        match self {
+            Number::Integer(n) => rug::Complex::with_val(precision, n),
+            Number::Rational(q) => rug::Complex::with_val(precision, q),
+            Number::Float(f) => rug::Complex::with_val(precision, f),
+            Number::Complex(mut z) => {
+                let (real, imag) = z.prec();
+                z.set_prec((real.max(precision), imag.max(precision)));
+                z
+            }
+        }
+    }
+
+    /// The precision needed by the parts of the number as a complex number:
+    /// that of its parts if it's already complex, or otherwise enough to
+    /// represent an integer or the numerator and denominator of a rational
+    /// exactly, and never less than [`COMPLEX_PRECISION`].
+    fn complex_precision(&self) -> u32 {
+        let precision = match self {
+            Number::Integer(n) => n.significant_bits(),
+            Number::Rational(q) => q
+                .numer()
+                .significant_bits()
+                .max(q.denom().significant_bits()),
+            Number::Float(_) => f64::MANTISSA_DIGITS,
+            Number::Complex(z) => z.prec().0.max(z.prec().1),
+        };
+        precision.max(COMPLEX_PRECISION)
+    }
+}
+
+/// The exact `index`th root of a non-negative integer, if it's a perfect power.
+fn exact_root(n: rug::Integer, index: u32) -> Option<rug::Integer> {
+    let (root, remainder) = n.root_rem(rug::Integer::new(), index);
+    if remainder == 0 {
+        Some(root)
+    } else {
+        None
+    }
+}
+
+/// Converts the operands of an operation on a complex number to complex
+/// numbers of the greater of their precisions.
+fn complex_operands(lhs: Number, rhs: Number) -> (rug::Complex, rug::Complex) {
+    let precision = lhs.complex_precision().max(rhs.complex_precision());
+    (lhs.into_complex(precision), rhs.into_complex(precision))
+}
+
+impl From<&NumericLiteral> for Number {
+    fn from(literal: &NumericLiteral) -> Self {
+        match literal {
+            NumericLiteral::Integer(integer) => Number::Integer(match integer {
+                Integer::U8 { n } => rug::Integer::from(*n),
+                Integer::U16 { n } => rug::Integer::from(*n),
+                Integer::U32 { n } => rug::Integer::from(*n),
+                Integer::U64 { n } => rug::Integer::from(*n),
+                Integer::U128 { n } => rug::Integer::from(*n),
+                Integer::USize { n } => rug::Integer::from(*n),
+                Integer::I8 { n } => rug::Integer::from(*n),
+                Integer::I16 { n } => rug::Integer::from(*n),
+                Integer::I32 { n } => rug::Integer::from(*n),
+                Integer::I64 { n } => rug::Integer::from(*n),
+                Integer::I128 { n } => rug::Integer::from(*n),
+                Integer::ISize { n } => rug::Integer::from(*n),
+                Integer::Big { n } => n.clone(),
+            }),
+            NumericLiteral::Float(Float::F32 { f }) => Number::Float(f64::from(*f)),
+            NumericLiteral::Float(Float::F64 { f }) => Number::Float(*f),
+            NumericLiteral::Float(Float::Exact { value }) => {
+                Number::rational(value.clone())
+            }
+            NumericLiteral::Rational(value) => Number::rational(value.clone()),
+            NumericLiteral::Imaginary(value) => {
+                let precision = Number::rational(value.clone()).complex_precision();
+                Number::Complex(rug::Complex::with_val(precision, (0, value)))
+            }
+            // A constant with a precision suffix is the exact value of its
+            // digits (e.g. `π_100`), like an exact float literal
+            NumericLiteral::MathematicalConstant(constant) => {
+                match constant.digits() {
+                    Some(digits) => {
+                        Number::rational(precise_constant(constant, digits))
+                    }
+                    None => Number::Float(constant.to_f64()),
+                }
+            }
+        }
+    }
+}
+
+/// Whether the operands are exact, and the result of an exact operation on them
+/// (i.e. a sum, difference, product or quotient) may need more than
+/// [`MAX_EXACT_BITS`], which is bounded by the sum of the operands' bits.
+pub(crate) fn exceeds_exact(lhs: &Number, rhs: &Number) -> bool {
+    lhs.is_exact()
+        && rhs.is_exact()
+        && lhs.exact_bits() + rhs.exact_bits() > MAX_EXACT_BITS
+}
+
+/// Applies an operator exactly to integers and rationals (unless the result may
+/// exceed [`MAX_EXACT_BITS`]), to complex numbers if either operand is complex,
+/// or otherwise to the operands' nearest f64s.
+macro_rules! arithmetic {
+    ($trait:ident, $method:ident, $op:tt) => {
+        impl $trait for Number {
+            type Output = Number;
+
+            fn $method(self, rhs: Number) -> Number {
+                match (self, rhs) {
+                    (lhs, rhs) if exceeds_exact(&lhs, &rhs) => {
+                        Number::Float(lhs.to_f64() $op rhs.to_f64())
+                    }
+                    (Number::Integer(lhs), Number::Integer(rhs)) => {
+                        Number::Integer(lhs $op rhs)
+                    }
+                    (lhs, rhs) if lhs.is_complex() || rhs.is_complex() => {
+                        let (lhs, rhs) = complex_operands(lhs, rhs);
+                        Number::Complex(lhs $op rhs)
+                    }
+                    (lhs, rhs) if !lhs.is_exact() || !rhs.is_exact() => {
+                        Number::Float(lhs.to_f64() $op rhs.to_f64())
+                    }
+                    (lhs, rhs) => {
+                        Number::rational(lhs.into_rational() $op rhs.into_rational())
+                    }
+                }
+            }
+        }
+    };
+}
+
+arithmetic!(Add, add, +);
+arithmetic!(Sub, sub, -);
+arithmetic!(Mul, mul, *);
+
+impl Neg for Number {
+    type Output = Number;
+
+    fn neg(self) -> Number {
+        match self {
+            Number::Integer(n) => Number::Integer(-n),
+            Number::Rational(q) => Number::Rational(-q),
+            Number::Float(f) => Number::Float(-f),
+            Number::Complex(z) => Number::Complex(-z),
+        }
+    }
+}
+
+/// Integers and floats are written as usual, rationals as a quotient (e.g.
+/// `3/4`), and complex numbers as the sum of their parts (e.g. `3+4i`).
+impl fmt::Display for Number {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Number::Integer(n) => write!(f, "{}", n),
+            Number::Rational(q) => write!(f, "{}", q),
+            Number::Float(x) => write!(f, "{}", x),
+            Number::Complex(z) => {
+                let (real, imag) = (z.real().to_f64(), z.imag().to_f64());
+                if imag.is_sign_negative() {
+                    write!(f, "{}-{}i", real, -imag)
+                } else {
+                    write!(f, "{}+{}i", real, imag)
+                }
+            }
+        }
+    }
+}
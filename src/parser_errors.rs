@@ -1,11 +1,194 @@
-use miette::Diagnostic;
+use miette::{Diagnostic, SourceSpan};
 use thiserror::Error;
 
+use crate::{set::MismatchedTypes, units::IncompatibleDimensions};
+
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub enum InternalParserError {
-    /// e.g. (e+_, E-_, etc.)
-    /// Float exponents must contain at least one digit
+    /// e.g. (e+_, E-_, p+_, 0x1.8, etc.)
+    /// Float exponents must contain at least one digit, and hexadecimal floats
+    /// must have one
     InvalidFloatExponent,
+    /// e.g. (1._5, 1.e5, 1.foo, etc.)
+    /// The byte range of a decimal point followed by an underscore or an
+    /// identifier, rather than a digit
+    InvalidFloatLiteral { start: usize, end: usize },
+    /// e.g. (`12u8.`, `1i32.`, etc.)
+    /// The byte range of a float literal ending in a decimal point whose
+    /// digits have an integer suffix
+    SuffixedFloatLiteral { start: usize, end: usize },
+    /// e.g. (`@`, `~`, `¬`, etc.)
+    /// The byte range of a sequence of characters which doesn't begin any token
+    UnrecognizedToken { start: usize, end: usize },
+    /// e.g. (`"\q"`, `"\u{D800}"`, `b"é"`, etc.)
+    /// The byte range of a malformed escape sequence (or disallowed
+    /// character) within a string literal
+    InvalidEscape {
+        error: EscapeError,
+        start: usize,
+        end: usize,
+    },
+    /// e.g. (`300u8`, `0x1_0000_0000u32`, etc.)
+    /// The byte range of an integer literal whose value can't be represented
+    /// by the type `ty` named by its suffix
+    IntegerLiteralOutOfRange {
+        ty: &'static str,
+        start: usize,
+        end: usize,
+    },
+    /// e.g. (`''`, `b''`)
+    /// The byte range of a character or byte literal without a character
+    EmptyCharLiteral { start: usize, end: usize },
+    /// e.g. (`'ab'`, `'e\u{301}'`, `b'ab'`, etc.)
+    /// The byte range of a character or byte literal with more than one
+    /// character (i.e. Unicode codepoint)
+    OverlongCharLiteral { start: usize, end: usize },
+    /// e.g. (`1/0r`, `0/0_r`)
+    /// The byte range of a rational literal whose denominator is zero
+    ZeroDenominator { start: usize, end: usize },
+    /// e.g. (`1e999999999i`, `0x1p-9999999`, etc.)
+    /// The byte range of a float (or imaginary) literal whose exact value needs
+    /// too many bits to compute
+    FloatLiteralOutOfRange { start: usize, end: usize },
+    /// e.g. (`π_0`, `pi_4000000000`)
+    /// The byte range of a mathematical constant whose precision suffix
+    /// requests no digits, or too many
+    InvalidConstantPrecision { start: usize, end: usize },
+    /// e.g. (`1 m + 1 s`, `3 kg - 2 N`, etc.)
+    /// The byte range of an addition or subtraction whose operands are
+    /// quantities of differing dimensions
+    IncompatibleUnits {
+        error: IncompatibleDimensions,
+        start: usize,
+        end: usize,
+    },
+    /// e.g. (`1 m¹²⁷·m`, `(1 m¹⁰⁰)^2`, etc.)
+    /// The byte range of a unit (or of a product, quotient or power of
+    /// quantities) one of whose exponents doesn't fit in a byte
+    UnitExponentOutOfRange { start: usize, end: usize },
+    /// e.g. (`1 / 0`, `1 % (2 - 2)`, etc.)
+    /// The byte range of an exact division (or remainder) by exact zero
+    DivisionByZero { start: usize, end: usize },
+    /// e.g. (`-true`, `!1`, etc.)
+    /// The byte range of a unary operation whose operand is of the type named
+    /// `operand`, to which the operator doesn't apply
+    InvalidOperand {
+        operator: &'static str,
+        operand: &'static str,
+        start: usize,
+        end: usize,
+    },
+    /// e.g. (`1 + true`, `2i < 3`, `"a" * 2`, etc.)
+    /// The byte range of a binary operation whose operands are of the types
+    /// named `left` and `right`, to which the operator doesn't apply
+    InvalidOperands {
+        operator: &'static str,
+        left: &'static str,
+        right: &'static str,
+        start: usize,
+        end: usize,
+    },
+    /// e.g. (`x + 1`, `x = 1`, `1..2`, etc.)
+    /// The byte range of an expression which can't be evaluated to a constant,
+    /// and so is left to run time (see [`crate::eval::check`])
+    NotConstant { start: usize, end: usize },
+    /// e.g. (`∑(i in 1.5..3) i`, `∏(k in 5) k`, etc.)
+    /// The byte range of the domain (or one of its bounds) of a big operator
+    /// which isn't the kind of range named by `expected`
+    InvalidDomain {
+        operator: &'static str,
+        expected: &'static str,
+        start: usize,
+        end: usize,
+    },
+    /// e.g. (`{1, true}`, `1 ∈ {"a"}`, `{1} ∪ {'a'}`, etc.)
+    /// The byte range of a set (or set operation) whose elements are of
+    /// differing types
+    MismatchedSetTypes {
+        error: MismatchedTypes,
+        start: usize,
+        end: usize,
+    },
+    /// e.g. (`foo(1)`, `sine(x)`, etc.)
+    /// The byte range of a call of a function which doesn't exist
+    UnknownFunction { start: usize, end: usize },
+    /// e.g. (`sin()`, `sin(1, 2)`, etc.)
+    /// The byte range of a call with `found` arguments of a function taking
+    /// `expected` arguments
+    WrongArgumentCount {
+        expected: usize,
+        found: usize,
+        start: usize,
+        end: usize,
+    },
+    /// e.g. (`∫(x in 0..1) x < 1 dx`, `∫(x in 0..1) 𝑖·x dx`, etc.)
+    /// The byte range of an integrand whose value is of the type named
+    /// `found`, rather than a real number
+    InvalidIntegrand {
+        found: &'static str,
+        start: usize,
+        end: usize,
+    },
+    /// e.g. (`∫(x in 0..1) 1/x dx`, `∫(x in 0..Inf) sin(x) dx`, etc.)
+    /// The byte range of an integral which doesn't converge to within the
+    /// tolerance
+    NonConvergentIntegral { start: usize, end: usize },
+    /// e.g. (`{x | x ∈ S, x + 1}`, etc.)
+    /// The byte range of a condition (e.g. the filter of a comprehension)
+    /// whose value is of the type named `found`, rather than a logic value
+    InvalidCondition {
+        found: &'static str,
+        start: usize,
+        end: usize,
+    },
+    /// e.g. (`assert 1 > 2;`, `assert ∃ x ∈ {1, 2}: x > 5;`, etc.)
+    /// The byte range of the condition of an assertion which is constant, and
+    /// doesn't hold
+    FailedAssertion { start: usize, end: usize },
+    /// e.g. (`impl Area for Circle {}` without `trait Area`, etc.)
+    /// The byte range of a trait (of an impl or a bound) which isn't declared
+    UnknownTrait { start: usize, end: usize },
+    /// e.g. (`impl Area for Circle {}`, where `Area` requires `fn area(self)`)
+    /// The byte range of an impl which doesn't define a method required by
+    /// its trait
+    MissingTraitMethod { start: usize, end: usize },
+    /// e.g. (`impl Area for Circle { fn perimeter(self) {} }`, etc.)
+    /// The byte range of a method of a trait impl which isn't a method of the
+    /// trait
+    UnknownTraitMethod { start: usize, end: usize },
+    /// e.g. (`impl Circle { fn area(self) {} fn area(self) {} }`, etc.)
+    /// The byte range of a method of a type which is already defined for it
+    DuplicateMethod { start: usize, end: usize },
+    /// e.g. (`impl Area for Circle {} impl Area for Circle {}`)
+    /// The byte range of an impl of a trait which the type already implements
+    DuplicateImpl { start: usize, end: usize },
+    /// e.g. (`self.volume()`, where `Self` has no method `volume`)
+    /// The byte range of a call of a method which the receiver's type doesn't
+    /// have
+    UnknownMethod { start: usize, end: usize },
+    /// e.g. (`self.area()`, where two traits of `Self` define `area`)
+    /// The byte range of a call of a method which more than one of the traits
+    /// of the receiver's type define
+    AmbiguousMethod { start: usize, end: usize },
+}
+
+/// The ways in which the contents of a string literal can fail to decode.
+#[derive(Error, Debug, Copy, Clone, PartialEq, Eq)]
+pub enum EscapeError {
+    #[error("unknown character escape")]
+    UnknownCharacterEscape,
+    #[error("expected two hexadecimal digits")]
+    InvalidHexEscape,
+    #[error("hexadecimal escapes in strings must be at most `\\x7F`")]
+    OutOfRangeHexEscape,
+    #[error("malformed unicode escape")]
+    InvalidUnicodeEscape,
+    #[error("unicode escapes must be at most `\\u{{10FFFF}}` and not a surrogate")]
+    OutOfRangeUnicodeEscape,
+    #[error("unicode escape in byte literal")]
+    UnicodeEscapeInByteString,
+    #[error("non-ASCII character in byte literal")]
+    NonAsciiCharacterInByteString,
 }
 
 #[derive(Error, Debug, Diagnostic, PartialEq, Eq)]
@@ -13,6 +196,448 @@ pub enum InternalParserError {
 #[diagnostic(
     code(balsapop::parser::invalid_float_exponent),
     url(docsrs),
-    help("Float exponents must contain at least one digit (0-9).")
+    help("Float exponents must contain at least one digit (0-9), and hexadecimal floats must have a binary exponent (e.g. `0x1.8p3`).")
 )]
 pub(crate) struct InvalidFloatExponent {}
+
+#[derive(Error, Debug, Diagnostic, PartialEq, Eq)]
+#[error("Unexpected token")]
+#[diagnostic(
+    code(balsapop::parser::unexpected_token),
+    url(docsrs),
+    help("Statements end with a `;` (e.g. `a := 3;`), except for an expression which ends its block.")
+)]
+pub(crate) struct UnexpectedToken {
+    #[source_code]
+    pub(crate) src: String,
+    #[label("expected one of {expected}")]
+    pub(crate) span: SourceSpan,
+    pub(crate) expected: String,
+}
+
+#[derive(Error, Debug, Diagnostic, PartialEq, Eq)]
+#[error("Invalid float literal")]
+#[diagnostic(
+    code(balsapop::lexer::invalid_float_literal),
+    url(docsrs),
+    help("The decimal point of a float literal must be followed by a digit (e.g. `1.5`, `1.0e5`), not an underscore or an identifier.")
+)]
+pub(crate) struct InvalidFloatLiteral {
+    #[source_code]
+    pub(crate) src: String,
+    #[label("expected a digit after the decimal point")]
+    pub(crate) span: SourceSpan,
+}
+
+#[derive(Error, Debug, Diagnostic, PartialEq, Eq)]
+#[error("Integer suffix on a float literal")]
+#[diagnostic(
+    code(balsapop::parser::suffixed_float_literal),
+    url(docsrs),
+    help("Float literals can't have an integer suffix (e.g. `12u8.`). Remove the suffix (e.g. `12.`), or use a float suffix (e.g. `12f32`).")
+)]
+pub(crate) struct SuffixedFloatLiteral {
+    #[source_code]
+    pub(crate) src: String,
+    #[label("this literal has an integer suffix")]
+    pub(crate) span: SourceSpan,
+}
+
+#[derive(Error, Debug, Diagnostic, PartialEq, Eq)]
+#[error("Unrecognized token")]
+#[diagnostic(
+    code(balsapop::lexer::unrecognized_token),
+    url(docsrs),
+    help("Remove or replace the character(s) which aren't part of the Balsa language.")
+)]
+pub(crate) struct UnrecognizedToken {
+    #[source_code]
+    pub(crate) src: String,
+    #[label("unrecognized token")]
+    pub(crate) span: SourceSpan,
+}
+
+#[derive(Error, Debug, Diagnostic, PartialEq, Eq)]
+#[error("Invalid escape sequence")]
+#[diagnostic(
+    code(balsapop::lexer::invalid_escape),
+    url(docsrs),
+    help("Valid escapes are `\\n`, `\\r`, `\\t`, `\\\\`, `\\0`, `\\'`, `\\\"`, `\\x7F` and `\\u{{7FFF}}`.")
+)]
+pub(crate) struct InvalidEscape {
+    #[source_code]
+    pub(crate) src: String,
+    #[label("{error}")]
+    pub(crate) span: SourceSpan,
+    pub(crate) error: EscapeError,
+}
+
+#[derive(Error, Debug, Diagnostic, PartialEq, Eq)]
+#[error("Empty character literal")]
+#[diagnostic(
+    code(balsapop::lexer::empty_char_literal),
+    url(docsrs),
+    help("Character and byte literals must contain exactly one character (e.g. `'a'`, `b'a'`). Use a string literal (e.g. `\"\"`) for empty text.")
+)]
+pub(crate) struct EmptyCharLiteral {
+    #[source_code]
+    pub(crate) src: String,
+    #[label("empty character literal")]
+    pub(crate) span: SourceSpan,
+}
+
+#[derive(Error, Debug, Diagnostic, PartialEq, Eq)]
+#[error("Character literal may only contain one codepoint")]
+#[diagnostic(
+    code(balsapop::lexer::overlong_char_literal),
+    url(docsrs),
+    help("Character and byte literals must contain exactly one character (e.g. `'a'`, `b'a'`). Use a string literal (e.g. `\"ab\"`) for multiple characters.")
+)]
+pub(crate) struct OverlongCharLiteral {
+    #[source_code]
+    pub(crate) src: String,
+    #[label("more than one codepoint")]
+    pub(crate) span: SourceSpan,
+}
+
+#[derive(Error, Debug, Diagnostic, PartialEq, Eq)]
+#[error("Integer literal is out of range for `{ty}`")]
+#[diagnostic(
+    code(balsapop::parser::integer_literal_out_of_range),
+    url(docsrs),
+    help("Integer literals must be representable by the type named by their suffix (e.g. `u8` ranges over 0..=255). Use a wider integer suffix (e.g. `u32`).")
+)]
+pub(crate) struct IntegerLiteralOutOfRange {
+    #[source_code]
+    pub(crate) src: String,
+    #[label("doesn't fit in `{ty}`")]
+    pub(crate) span: SourceSpan,
+    pub(crate) ty: &'static str,
+}
+
+#[derive(Error, Debug, Diagnostic, PartialEq, Eq)]
+#[error("Rational literal has a zero denominator")]
+#[diagnostic(
+    code(balsapop::parser::zero_denominator),
+    url(docsrs),
+    help("The denominator of a rational literal (e.g. the `4` of `3/4r`) must be nonzero.")
+)]
+pub(crate) struct ZeroDenominator {
+    #[source_code]
+    pub(crate) src: String,
+    #[label("division by zero")]
+    pub(crate) span: SourceSpan,
+}
+
+#[derive(Error, Debug, Diagnostic, PartialEq, Eq)]
+#[error("Float literal is out of range")]
+#[diagnostic(
+    code(balsapop::parser::float_literal_out_of_range),
+    url(docsrs),
+    help("The exact value of a float or imaginary literal (e.g. the `1/10` of `0.1`) may have at most about 315,000 digits, so its exponent must be smaller (e.g. `1e300000i` rather than `1e999999999i`).")
+)]
+pub(crate) struct FloatLiteralOutOfRange {
+    #[source_code]
+    pub(crate) src: String,
+    #[label("exponent too large")]
+    pub(crate) span: SourceSpan,
+}
+
+#[derive(Error, Debug, Diagnostic, PartialEq, Eq)]
+#[error("Invalid precision of a mathematical constant")]
+#[diagnostic(
+    code(balsapop::parser::invalid_constant_precision),
+    url(docsrs),
+    help("The precision suffix of a constant (e.g. the `_40` of `pi_40`) is its number of significant decimal digits, which must be between 1 and 10000.")
+)]
+pub(crate) struct InvalidConstantPrecision {
+    #[source_code]
+    pub(crate) src: String,
+    #[label("invalid precision")]
+    pub(crate) span: SourceSpan,
+}
+
+#[derive(Error, Debug, Diagnostic, PartialEq, Eq)]
+#[error("Incompatible units")]
+#[diagnostic(
+    code(balsapop::units::incompatible_units),
+    url(docsrs),
+    help("Only quantities of the same dimension may be added or subtracted (e.g. `1 km + 500 m`), whereas quantities of any dimension may be multiplied or divided (e.g. `2 m / 4 s`).")
+)]
+pub(crate) struct IncompatibleUnits {
+    #[source_code]
+    pub(crate) src: String,
+    #[label("{error}")]
+    pub(crate) span: SourceSpan,
+    pub(crate) error: IncompatibleDimensions,
+}
+
+#[derive(Error, Debug, Diagnostic, PartialEq, Eq)]
+#[error("Unit exponent out of range")]
+#[diagnostic(
+    code(balsapop::units::unit_exponent_out_of_range),
+    url(docsrs),
+    help("The exponent of each base unit (e.g. the `²` of `m²`) must be between -128 and 127.")
+)]
+pub(crate) struct UnitExponentOutOfRange {
+    #[source_code]
+    pub(crate) src: String,
+    #[label("exponent out of range")]
+    pub(crate) span: SourceSpan,
+}
+
+#[derive(Error, Debug, Diagnostic, PartialEq, Eq)]
+#[error("Division by zero")]
+#[diagnostic(
+    code(balsapop::eval::division_by_zero),
+    url(docsrs),
+    help("Integers and rationals are divided exactly, and so can't be divided by zero. Divide by a float (e.g. `1 / 0.0`) for an infinite result.")
+)]
+pub(crate) struct DivisionByZero {
+    #[source_code]
+    pub(crate) src: String,
+    #[label("division by zero")]
+    pub(crate) span: SourceSpan,
+}
+
+#[derive(Error, Debug, Diagnostic, PartialEq, Eq)]
+#[error("Cannot apply `{operator}` to `{operand}`")]
+#[diagnostic(
+    code(balsapop::eval::invalid_operand),
+    url(docsrs),
+    help("`-` negates numbers and quantities, `!` negates logic values (i.e. `true`, `false` and `missing`), and functions (e.g. `sin`) apply to numbers.")
+)]
+pub(crate) struct InvalidOperand {
+    #[source_code]
+    pub(crate) src: String,
+    #[label("`{operator}` doesn't apply to `{operand}`")]
+    pub(crate) span: SourceSpan,
+    pub(crate) operator: &'static str,
+    pub(crate) operand: &'static str,
+}
+
+#[derive(Error, Debug, Diagnostic, PartialEq, Eq)]
+#[error("Cannot apply `{operator}` to `{left}` and `{right}`")]
+#[diagnostic(
+    code(balsapop::eval::invalid_operands),
+    url(docsrs),
+    help("Arithmetic applies to numbers and quantities, logical operators (`&&`, `||`) to logic values, and comparisons to operands of the same type.")
+)]
+pub(crate) struct InvalidOperands {
+    #[source_code]
+    pub(crate) src: String,
+    #[label("`{operator}` doesn't apply to `{left}` and `{right}`")]
+    pub(crate) span: SourceSpan,
+    pub(crate) operator: &'static str,
+    pub(crate) left: &'static str,
+    pub(crate) right: &'static str,
+}
+
+#[derive(Error, Debug, Diagnostic, PartialEq, Eq)]
+#[error("Invalid domain of `{operator}`")]
+#[diagnostic(
+    code(balsapop::eval::invalid_domain),
+    url(docsrs),
+    help("The variable of `{operator}` ranges over {expected} (e.g. `i in 1..=10`), where `a..b` excludes `b` and `a..=b` includes it.")
+)]
+pub(crate) struct InvalidDomain {
+    #[source_code]
+    pub(crate) src: String,
+    #[label("expected {expected}")]
+    pub(crate) span: SourceSpan,
+    pub(crate) operator: &'static str,
+    pub(crate) expected: &'static str,
+}
+
+#[derive(Error, Debug, Diagnostic, PartialEq, Eq)]
+#[error("Unknown function")]
+#[diagnostic(
+    code(balsapop::eval::unknown_function),
+    url(docsrs),
+    help("The built-in functions are `sin`, `cos`, `tan`, `asin`, `acos`, `atan`, `sinh`, `cosh`, `tanh`, `exp`, `ln`, `sqrt` and `abs`.")
+)]
+pub(crate) struct UnknownFunction {
+    #[source_code]
+    pub(crate) src: String,
+    #[label("no such function")]
+    pub(crate) span: SourceSpan,
+}
+
+#[derive(Error, Debug, Diagnostic, PartialEq, Eq)]
+#[error("Wrong number of arguments")]
+#[diagnostic(code(balsapop::eval::wrong_argument_count), url(docsrs))]
+pub(crate) struct WrongArgumentCount {
+    #[source_code]
+    pub(crate) src: String,
+    #[label("expected {expected} argument(s), found {found}")]
+    pub(crate) span: SourceSpan,
+    pub(crate) expected: usize,
+    pub(crate) found: usize,
+}
+
+#[derive(Error, Debug, Diagnostic, PartialEq, Eq)]
+#[error("Invalid integrand")]
+#[diagnostic(
+    code(balsapop::eval::invalid_integrand),
+    url(docsrs),
+    help("Integrals are computed numerically, and so their integrand must be a real number.")
+)]
+pub(crate) struct InvalidIntegrand {
+    #[source_code]
+    pub(crate) src: String,
+    #[label("expected a real number, found `{found}`")]
+    pub(crate) span: SourceSpan,
+    pub(crate) found: &'static str,
+}
+
+#[derive(Error, Debug, Diagnostic, PartialEq, Eq)]
+#[error("Integral doesn't converge")]
+#[diagnostic(
+    code(balsapop::eval::non_convergent_integral),
+    url(docsrs),
+    help("The integral may diverge (e.g. `∫(x in 0..1) 1/x dx`), oscillate, or require a looser tolerance.")
+)]
+pub(crate) struct NonConvergentIntegral {
+    #[source_code]
+    pub(crate) src: String,
+    #[label("doesn't converge to within the tolerance")]
+    pub(crate) span: SourceSpan,
+}
+
+#[derive(Error, Debug, Diagnostic, PartialEq, Eq)]
+#[error("Invalid condition")]
+#[diagnostic(
+    code(balsapop::eval::invalid_condition),
+    url(docsrs),
+    help("A condition must be `true`, `false` or `missing` (e.g. `x % 2 == 0`), where `missing` doesn't hold.")
+)]
+pub(crate) struct InvalidCondition {
+    #[source_code]
+    pub(crate) src: String,
+    #[label("expected `logic`, found `{found}`")]
+    pub(crate) span: SourceSpan,
+    pub(crate) found: &'static str,
+}
+
+#[derive(Error, Debug, Diagnostic, PartialEq, Eq)]
+#[error("Assertion failed")]
+#[diagnostic(
+    code(balsapop::eval::failed_assertion),
+    url(docsrs),
+    help("The condition of this assertion is constant, and is `false` or `missing` (which doesn't hold).")
+)]
+pub(crate) struct FailedAssertion {
+    #[source_code]
+    pub(crate) src: String,
+    #[label("doesn't hold")]
+    pub(crate) span: SourceSpan,
+}
+
+#[derive(Error, Debug, Diagnostic, PartialEq, Eq)]
+#[error("Mismatched set types")]
+#[diagnostic(
+    code(balsapop::set::mismatched_types),
+    url(docsrs),
+    help("The elements of a set are all of the same type (e.g. `{{1, 2, 3}}` is a set of numbers), and only sets of the same type may be combined or compared.")
+)]
+pub(crate) struct MismatchedSetTypes {
+    #[source_code]
+    pub(crate) src: String,
+    #[label("{error}")]
+    pub(crate) span: SourceSpan,
+    pub(crate) error: MismatchedTypes,
+}
+
+#[derive(Error, Debug, Diagnostic, PartialEq, Eq)]
+#[error("Unknown trait")]
+#[diagnostic(
+    code(balsapop::resolve::unknown_trait),
+    url(docsrs),
+    help("A trait must be declared (e.g. `trait Area {{ fn area(self) -> Float; }}`) before it can be implemented or used as a bound.")
+)]
+pub(crate) struct UnknownTrait {
+    #[source_code]
+    pub(crate) src: String,
+    #[label("no such trait")]
+    pub(crate) span: SourceSpan,
+}
+
+#[derive(Error, Debug, Diagnostic, PartialEq, Eq)]
+#[error("Missing trait method")]
+#[diagnostic(
+    code(balsapop::resolve::missing_trait_method),
+    url(docsrs),
+    help("An impl of a trait must define each of the trait's methods which has no default body.")
+)]
+pub(crate) struct MissingTraitMethod {
+    #[source_code]
+    pub(crate) src: String,
+    #[label("doesn't define a required method of its trait")]
+    pub(crate) span: SourceSpan,
+}
+
+#[derive(Error, Debug, Diagnostic, PartialEq, Eq)]
+#[error("Method isn't a member of the trait")]
+#[diagnostic(
+    code(balsapop::resolve::unknown_trait_method),
+    url(docsrs),
+    help("An impl of a trait may only define the trait's methods; other methods belong in an inherent impl (e.g. `impl Circle {{ ... }}`).")
+)]
+pub(crate) struct UnknownTraitMethod {
+    #[source_code]
+    pub(crate) src: String,
+    #[label("not a method of the trait")]
+    pub(crate) span: SourceSpan,
+}
+
+#[derive(Error, Debug, Diagnostic, PartialEq, Eq)]
+#[error("Duplicate method")]
+#[diagnostic(code(balsapop::resolve::duplicate_method), url(docsrs))]
+pub(crate) struct DuplicateMethod {
+    #[source_code]
+    pub(crate) src: String,
+    #[label("already defined for this type")]
+    pub(crate) span: SourceSpan,
+}
+
+#[derive(Error, Debug, Diagnostic, PartialEq, Eq)]
+#[error("Duplicate impl")]
+#[diagnostic(
+    code(balsapop::resolve::duplicate_impl),
+    url(docsrs),
+    help("A type implements each trait at most once.")
+)]
+pub(crate) struct DuplicateImpl {
+    #[source_code]
+    pub(crate) src: String,
+    #[label("the trait is already implemented for this type")]
+    pub(crate) span: SourceSpan,
+}
+
+#[derive(Error, Debug, Diagnostic, PartialEq, Eq)]
+#[error("Unknown method")]
+#[diagnostic(
+    code(balsapop::resolve::unknown_method),
+    url(docsrs),
+    help("The methods of a type are those of its impls, and of the traits which it implements.")
+)]
+pub(crate) struct UnknownMethod {
+    #[source_code]
+    pub(crate) src: String,
+    #[label("no such method")]
+    pub(crate) span: SourceSpan,
+}
+
+#[derive(Error, Debug, Diagnostic, PartialEq, Eq)]
+#[error("Ambiguous method")]
+#[diagnostic(
+    code(balsapop::resolve::ambiguous_method),
+    url(docsrs),
+    help("More than one trait of the receiver's type defines this method, and the type has no inherent method of that name.")
+)]
+pub(crate) struct AmbiguousMethod {
+    #[source_code]
+    pub(crate) src: String,
+    #[label("defined by more than one trait")]
+    pub(crate) span: SourceSpan,
+}
@@ -0,0 +1,192 @@
+//! Adaptive numerical quadrature of definite integrals.
+//!
+//! An integral is approximated by 15-point Gauss–Kronrod quadrature, whose
+//! difference from the embedded 7-point Gauss quadrature estimates its error.
+//! The subinterval with the largest estimated error is bisected until the
+//! total estimate is within the tolerance. Improper integrals (i.e. those with
+//! an infinite bound) are first mapped onto a finite interval, which works
+//! since the Gauss–Kronrod nodes exclude the endpoints of their interval.
+
+/// The default error tolerance of [`integrate`].
+pub const DEFAULT_TOLERANCE: f64 = 1e-10;
+
+/// The number of subintervals after which an integral which still isn't within
+/// the tolerance is deemed not to converge.
+const MAX_SUBINTERVALS: usize = 1000;
+
+/// The positive nodes of the 15-point Kronrod rule on `[-1, 1]`, where the odd
+/// ones (i.e. `KRONROD_NODES[1]`, `[3]`, `[5]` and `[7]`) are the nodes of the
+/// 7-point Gauss rule.
+const KRONROD_NODES: [f64; 8] = [
+    0.991_455_371_120_812_6,
+    0.949_107_912_342_758_5,
+    0.864_864_423_359_769_1,
+    0.741_531_185_599_394_4,
+    0.586_087_235_467_691_1,
+    0.405_845_151_377_397_2,
+    0.207_784_955_007_898_5,
+    0.0,
+];
+
+const KRONROD_WEIGHTS: [f64; 8] = [
+    0.022_935_322_010_529_22,
+    0.063_092_092_629_978_55,
+    0.104_790_010_322_250_18,
+    0.140_653_259_715_525_92,
+    0.169_004_726_639_267_9,
+    0.190_350_578_064_785_4,
+    0.204_432_940_075_298_9,
+    0.209_482_141_084_727_83,
+];
+
+const GAUSS_WEIGHTS: [f64; 4] = [
+    0.129_484_966_168_869_7,
+    0.279_705_391_489_276_7,
+    0.381_830_050_505_118_9,
+    0.417_959_183_673_469_4,
+];
+
+/// The ways in which an integral can fail to be computed.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum QuadratureError<E> {
+    /// The integrand failed to be evaluated
+    Integrand(E),
+    /// The integral isn't within the tolerance after [`MAX_SUBINTERVALS`]
+    /// bisections (e.g. it diverges, or the tolerance is below the precision
+    /// of `f64`)
+    NotConverged,
+}
+
+impl<E> From<E> for QuadratureError<E> {
+    fn from(error: E) -> Self {
+        QuadratureError::Integrand(error)
+    }
+}
+
+/// Integrates `f` from `a` to `b`, either of which may be infinite (but not
+/// NaN), until the estimated error is within the tolerance, either absolutely
+/// or relative to the integral (whichever is looser).
+pub fn integrate<E>(
+    mut f: impl FnMut(f64) -> Result<f64, E>,
+    a: f64,
+    b: f64,
+    tolerance: f64,
+) -> Result<f64, QuadratureError<E>> {
+    if a == b {
+        return Ok(0.0);
+    }
+    if a > b {
+        return integrate(f, b, a, tolerance).map(|integral| -integral);
+    }
+    match (a.is_finite(), b.is_finite()) {
+        (true, true) => adaptive(&mut f, a, b, tolerance),
+        // x = a + t / (1 - t), for t in [0, 1)
+        (true, false) => adaptive(
+            &mut |t| {
+                let s = 1.0 - t;
+                Ok(f(a + t / s)? / (s * s))
+            },
+            0.0,
+            1.0,
+            tolerance,
+        ),
+        // x = b - t / (1 - t), for t in [0, 1)
+        (false, true) => adaptive(
+            &mut |t| {
+                let s = 1.0 - t;
+                Ok(f(b - t / s)? / (s * s))
+            },
+            0.0,
+            1.0,
+            tolerance,
+        ),
+        // x = t / (1 - t²), for t in (-1, 1)
+        (false, false) => adaptive(
+            &mut |t| {
+                let s = 1.0 - t * t;
+                Ok(f(t / s)? * (1.0 + t * t) / (s * s))
+            },
+            -1.0,
+            1.0,
+            tolerance,
+        ),
+    }
+}
+
+/// A subinterval's integral, and its estimated error.
+struct Subinterval {
+    start: f64,
+    end: f64,
+    integral: f64,
+    error: f64,
+}
+
+fn adaptive<E>(
+    f: &mut impl FnMut(f64) -> Result<f64, E>,
+    a: f64,
+    b: f64,
+    tolerance: f64,
+) -> Result<f64, QuadratureError<E>> {
+    let mut subintervals = vec![gauss_kronrod(f, a, b)?];
+    loop {
+        let (integral, error) = subintervals.iter().fold(
+            (0.0, 0.0),
+            |(integral, error), subinterval| {
+                (integral + subinterval.integral, error + subinterval.error)
+            },
+        );
+        if integral.is_finite() && error <= tolerance.max(tolerance * integral.abs())
+        {
+            return Ok(integral);
+        }
+        if subintervals.len() >= MAX_SUBINTERVALS {
+            return Err(QuadratureError::NotConverged);
+        }
+
+        // Bisect the subinterval with the largest (or an undefined) error
+        let mut worst = 0;
+        for (i, subinterval) in subintervals.iter().enumerate() {
+            if subinterval.error.is_nan()
+                || subinterval.error > subintervals[worst].error
+            {
+                worst = i;
+            }
+        }
+        let Subinterval { start, end, .. } = subintervals.swap_remove(worst);
+        let middle = 0.5 * (start + end);
+        if middle <= start || middle >= end {
+            return Err(QuadratureError::NotConverged);
+        }
+        subintervals.push(gauss_kronrod(f, start, middle)?);
+        subintervals.push(gauss_kronrod(f, middle, end)?);
+    }
+}
+
+/// The 15-point Gauss–Kronrod quadrature of `f` over `[start, end]`.
+fn gauss_kronrod<E>(
+    f: &mut impl FnMut(f64) -> Result<f64, E>,
+    start: f64,
+    end: f64,
+) -> Result<Subinterval, E> {
+    let center = 0.5 * (start + end);
+    let half_length = 0.5 * (end - start);
+
+    let f_center = f(center)?;
+    let mut kronrod = KRONROD_WEIGHTS[7] * f_center;
+    let mut gauss = GAUSS_WEIGHTS[3] * f_center;
+    for (i, node) in KRONROD_NODES[..7].iter().enumerate() {
+        let offset = half_length * node;
+        let sum = f(center - offset)? + f(center + offset)?;
+        kronrod += KRONROD_WEIGHTS[i] * sum;
+        if i % 2 == 1 {
+            gauss += GAUSS_WEIGHTS[i / 2] * sum;
+        }
+    }
+
+    Ok(Subinterval {
+        start,
+        end,
+        integral: kronrod * half_length,
+        error: ((kronrod - gauss) * half_length).abs(),
+    })
+}
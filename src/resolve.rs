@@ -0,0 +1,491 @@
+//! Resolution of method calls through the impls of a source file.
+//!
+//! The methods of a type are those of its inherent impls (e.g.
+//! `impl Circle { ... }`) and of its trait impls (e.g.
+//! `impl Area for Circle { ... }`), where a trait impl inherits the default
+//! methods of its trait which it doesn't define itself. A method call (e.g.
+//! `c.area()`) resolves to an inherent method before a trait method, and is
+//! ambiguous if more than one trait of the receiver's type defines the method.
+//!
+//! Without type inference, only the calls whose receiver's type is known from
+//! its syntax are resolved: those on `self` within a method, on the
+//! construction of a struct or variant (e.g. `Circle { r: 1 }.area()`), and on
+//! a variable whose type is known from its declaration (e.g.
+//! `c := Circle { r: 1 };`) or annotation (e.g. `c: Circle := f();`,
+//! `fn f(c: Circle)`) as a struct or enum of the source file. Within a default
+//! method of a trait, `self` has the methods of the trait and of the traits
+//! which bound `Self` in the where clauses of the trait and method.
+
+use std::collections::{HashMap, HashSet};
+
+use crate::{
+    ast::{
+        Block, Clause, Enum, Expression, Function, Identifier, Impl, Item, Span,
+        Statement, Struct, Trait, Type, WherePredicate,
+    },
+    parser_errors::InternalParserError,
+};
+
+/// Checks the impls of a source file against their traits, and resolves the
+/// method calls whose receiver's type is known to the methods which they call,
+/// by the spans of the calls.
+pub fn check(
+    program: &Block,
+) -> Result<HashMap<Span, &Function>, InternalParserError> {
+    let mut items = Vec::new();
+    collect_items(program, &mut items);
+    let mut methods = Methods::new(&items)?;
+
+    methods.check_block(program, &Receiver::Unknown, &mut HashMap::new())?;
+    for item in items {
+        match item {
+            Item::Function(function) => {
+                methods.check_function(function, &Receiver::Unknown)?
+            }
+            Item::Trait(declaration) => {
+                methods.check_bounds(&declaration.where_clause)?;
+                for method in &declaration.methods {
+                    let traits = methods.self_bounds(declaration, method)?;
+                    methods.check_function(method, &Receiver::Bounded(traits))?;
+                }
+            }
+            Item::Impl(implementation) => {
+                methods.check_bounds(&implementation.where_clause)?;
+                let self_type = Receiver::Type(type_name(&implementation.self_type));
+                for method in &implementation.methods {
+                    methods.check_function(method, &self_type)?;
+                }
+            }
+            Item::Struct(_) | Item::Enum(_) => {}
+        }
+    }
+    Ok(methods.resolved)
+}
+
+/// The items of a block, including those within the bodies of its functions.
+fn collect_items<'a>(block: &'a Block, items: &mut Vec<&'a Item>) {
+    for statement in &block.statements {
+        if let Statement::Item(item) = statement {
+            items.push(item);
+            for body in item
+                .functions()
+                .iter()
+                .filter_map(|function| function.body.as_ref())
+            {
+                collect_items(body, items);
+            }
+        }
+    }
+}
+
+/// The name of a type of an impl's header, which the grammar only allows to be
+/// a named type.
+fn type_name(ty: &Type) -> &str {
+    match ty {
+        Type::Named { name, .. } => &name.name,
+        Type::SelfType { .. } => unreachable!(),
+    }
+}
+
+/// The type of the receiver of a method call (e.g. `self` within a method, or
+/// a variable), as far as it's known.
+#[derive(Clone)]
+enum Receiver<'a> {
+    /// Outside of a method (or within a method of an unknown type), or of a
+    /// value of an unknown type
+    Unknown,
+    /// Within a method of an impl of the named type, or of a value of the type
+    Type(&'a str),
+    /// Within a method of a trait, whose implementor implements the traits
+    Bounded(Vec<&'a Trait>),
+}
+
+/// The types of the variables in scope, by name, where `self` is named `self`.
+type Scope<'a> = HashMap<&'a str, Receiver<'a>>;
+
+/// The traits and methods of the types of a source file.
+struct Methods<'a> {
+    /// The names of the structs and enums
+    types: HashSet<&'a str>,
+    traits: HashMap<&'a str, &'a Trait>,
+    /// The traits which each type implements, by the names of the type and
+    /// trait
+    implemented: HashSet<(&'a str, &'a str)>,
+    /// The inherent methods of each type, by the names of the type and method
+    inherent: HashMap<(&'a str, &'a str), &'a Function>,
+    /// The methods of each type's trait impls, including the default methods
+    /// which they inherit, by the names of the type and method
+    provided: HashMap<(&'a str, &'a str), Vec<&'a Function>>,
+    /// The method which each resolved call calls, by the span of the call
+    resolved: HashMap<Span, &'a Function>,
+}
+
+impl<'a> Methods<'a> {
+    fn new(items: &[&'a Item]) -> Result<Methods<'a>, InternalParserError> {
+        let mut methods = Methods {
+            types: HashSet::new(),
+            traits: HashMap::new(),
+            implemented: HashSet::new(),
+            inherent: HashMap::new(),
+            provided: HashMap::new(),
+            resolved: HashMap::new(),
+        };
+        for item in items.iter().copied() {
+            match item {
+                Item::Trait(declaration) => {
+                    methods
+                        .traits
+                        .insert(declaration.name.name.as_str(), declaration);
+                }
+                Item::Struct(Struct { name, .. })
+                | Item::Enum(Enum { name, .. }) => {
+                    methods.types.insert(name.name.as_str());
+                }
+                Item::Function(_) | Item::Impl(_) => {}
+            }
+        }
+        for item in items.iter().copied() {
+            if let Item::Impl(implementation) = item {
+                methods.add_impl(implementation)?;
+            }
+        }
+        Ok(methods)
+    }
+
+    fn add_impl(
+        &mut self,
+        implementation: &'a Impl,
+    ) -> Result<(), InternalParserError> {
+        let self_type = type_name(&implementation.self_type);
+        let trait_type = match &implementation.trait_type {
+            Some(trait_type) => trait_type,
+            None => {
+                for method in &implementation.methods {
+                    let key = (self_type, method.name.name.as_str());
+                    if self.inherent.insert(key, method).is_some() {
+                        return Err(duplicate_method(method.span));
+                    }
+                }
+                return Ok(());
+            }
+        };
+
+        let declaration = self.lookup_trait(trait_type)?;
+        if !self
+            .implemented
+            .insert((self_type, declaration.name.name.as_str()))
+        {
+            return Err(InternalParserError::DuplicateImpl {
+                start: implementation.span.start,
+                end: implementation.span.end,
+            });
+        }
+        for (i, method) in implementation.methods.iter().enumerate() {
+            if !declaration.methods.iter().any(|m| m.name == method.name) {
+                return Err(InternalParserError::UnknownTraitMethod {
+                    start: method.span.start,
+                    end: method.span.end,
+                });
+            }
+            if implementation.methods[..i]
+                .iter()
+                .any(|m| m.name == method.name)
+            {
+                return Err(duplicate_method(method.span));
+            }
+        }
+        for required in &declaration.methods {
+            let method = implementation
+                .methods
+                .iter()
+                .find(|method| method.name == required.name)
+                .or_else(|| required.body.as_ref().map(|_| required))
+                .ok_or(InternalParserError::MissingTraitMethod {
+                    start: implementation.span.start,
+                    end: implementation.span.end,
+                })?;
+            self.provided
+                .entry((self_type, required.name.name.as_str()))
+                .or_default()
+                .push(method);
+        }
+        Ok(())
+    }
+
+    /// The trait named by a type (e.g. the `Area` of `impl Area for Circle`).
+    fn lookup_trait(&self, ty: &Type) -> Result<&'a Trait, InternalParserError> {
+        let unknown = InternalParserError::UnknownTrait {
+            start: ty.span().start,
+            end: ty.span().end,
+        };
+        match ty {
+            Type::Named {
+                name, arguments, ..
+            } if arguments.is_empty() => {
+                self.traits.get(name.name.as_str()).copied().ok_or(unknown)
+            }
+            _ => Err(unknown),
+        }
+    }
+
+    /// Checks that the bounds of a where clause are traits.
+    fn check_bounds(
+        &self,
+        where_clause: &[WherePredicate],
+    ) -> Result<(), InternalParserError> {
+        for bound in where_clause.iter().flat_map(|predicate| &predicate.bounds) {
+            self.lookup_trait(bound)?;
+        }
+        Ok(())
+    }
+
+    /// The traits which the implementor of a trait implements within one of
+    /// its methods: the trait itself, and the bounds of `Self`.
+    fn self_bounds(
+        &self,
+        declaration: &'a Trait,
+        method: &'a Function,
+    ) -> Result<Vec<&'a Trait>, InternalParserError> {
+        let mut traits = vec![declaration];
+        let predicates = declaration.where_clause.iter().chain(&method.where_clause);
+        for predicate in predicates
+            .filter(|predicate| matches!(predicate.ty, Type::SelfType { .. }))
+        {
+            for bound in &predicate.bounds {
+                let bound = self.lookup_trait(bound)?;
+                if !traits.iter().any(|t| std::ptr::eq(*t, bound)) {
+                    traits.push(bound);
+                }
+            }
+        }
+        Ok(traits)
+    }
+
+    /// The method of a type which a call (e.g. `c.area()`) resolves to.
+    fn resolve(
+        &self,
+        ty: &str,
+        method: &Identifier,
+        span: Span,
+    ) -> Result<&'a Function, InternalParserError> {
+        if let Some(function) = self.inherent.get(&(ty, method.name.as_str())) {
+            return Ok(*function);
+        }
+        match self
+            .provided
+            .get(&(ty, method.name.as_str()))
+            .map(Vec::as_slice)
+        {
+            Some([function]) => Ok(*function),
+            Some([_, _, ..]) => Err(InternalParserError::AmbiguousMethod {
+                start: span.start,
+                end: span.end,
+            }),
+            _ => Err(InternalParserError::UnknownMethod {
+                start: span.start,
+                end: span.end,
+            }),
+        }
+    }
+
+    /// The method which a call (e.g. `self.area()`, `c.area()`) resolves to,
+    /// if the type of its receiver is known.
+    fn resolve_receiver(
+        &self,
+        receiver: &Receiver<'a>,
+        method: &Identifier,
+        span: Span,
+    ) -> Result<Option<&'a Function>, InternalParserError> {
+        match receiver {
+            Receiver::Unknown => Ok(None),
+            Receiver::Type(ty) => self.resolve(ty, method, span).map(Some),
+            Receiver::Bounded(traits) => {
+                let mut candidates = traits
+                    .iter()
+                    .copied()
+                    .flat_map(|declaration| &declaration.methods)
+                    .filter(|function| function.name == *method);
+                match (candidates.next(), candidates.next()) {
+                    (Some(function), None) => Ok(Some(function)),
+                    (Some(_), Some(_)) => {
+                        Err(InternalParserError::AmbiguousMethod {
+                            start: span.start,
+                            end: span.end,
+                        })
+                    }
+                    (None, _) => Err(InternalParserError::UnknownMethod {
+                        start: span.start,
+                        end: span.end,
+                    }),
+                }
+            }
+        }
+    }
+
+    /// Checks the body of a function, where `self_type` is the type of `Self`
+    /// (e.g. within a method of an impl).
+    fn check_function(
+        &mut self,
+        function: &'a Function,
+        self_type: &Receiver<'a>,
+    ) -> Result<(), InternalParserError> {
+        self.check_bounds(&function.where_clause)?;
+        let mut scope = Scope::new();
+        // `self` is only bound by a method with a receiver
+        if function.receiver.is_some() {
+            scope.insert("self", self_type.clone());
+        }
+        for parameter in &function.parameters {
+            let ty = self.type_of(&parameter.ty, self_type);
+            scope.insert(&parameter.name.name, ty);
+        }
+        match &function.body {
+            Some(body) => self.check_block(body, self_type, &mut scope),
+            None => Ok(()),
+        }
+    }
+
+    /// Checks the statements and value of a block, but not its items (which
+    /// are checked on their own), where each declaration binds the type of its
+    /// variable in the scope.
+    fn check_block(
+        &mut self,
+        block: &'a Block,
+        self_type: &Receiver<'a>,
+        scope: &mut Scope<'a>,
+    ) -> Result<(), InternalParserError> {
+        for statement in &block.statements {
+            match statement {
+                Statement::Declaration {
+                    name, ty, value, ..
+                } => {
+                    self.check_expression(value, scope)?;
+                    let ty = match ty {
+                        Some(ty) => self.type_of(ty, self_type),
+                        None => self.receiver_type(value, scope),
+                    };
+                    scope.insert(&name.name, ty);
+                }
+                Statement::Expression {
+                    expression: value, ..
+                }
+                | Statement::Return {
+                    value: Some(value), ..
+                }
+                | Statement::Assertion {
+                    condition: value, ..
+                } => self.check_expression(value, scope)?,
+                Statement::Return { value: None, .. } | Statement::Item(_) => {}
+            }
+        }
+        match &block.value {
+            Some(value) => self.check_expression(value, scope),
+            None => Ok(()),
+        }
+    }
+
+    fn check_expression(
+        &mut self,
+        expression: &'a Expression,
+        scope: &Scope<'a>,
+    ) -> Result<(), InternalParserError> {
+        if let Expression::MethodCall {
+            receiver: value,
+            method,
+            span,
+            ..
+        } = expression
+        {
+            let receiver = self.receiver_type(value, scope);
+            if let Some(function) =
+                self.resolve_receiver(&receiver, method, *span)?
+            {
+                self.resolved.insert(*span, function);
+            }
+        }
+
+        // The variables of big operators, quantifiers, integrals and
+        // comprehensions shadow those of the scope
+        let bound = bound_variables(expression);
+        if bound.is_empty() {
+            for operand in expression.operands() {
+                self.check_expression(operand, scope)?;
+            }
+        } else {
+            let mut scope = scope.clone();
+            for variable in bound {
+                scope.remove(variable.name.as_str());
+            }
+            for operand in expression.operands() {
+                self.check_expression(operand, &scope)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// The type of a variable or parameter of the given type, which is known if
+    /// it's `Self` or a struct or enum of the source file.
+    fn type_of(&self, ty: &'a Type, self_type: &Receiver<'a>) -> Receiver<'a> {
+        match ty {
+            Type::SelfType { .. } => self_type.clone(),
+            Type::Named {
+                name, arguments, ..
+            } if arguments.is_empty() && self.types.contains(name.name.as_str()) => {
+                Receiver::Type(&name.name)
+            }
+            Type::Named { .. } => Receiver::Unknown,
+        }
+    }
+
+    /// The type of the receiver of a method call, as far as it's known from
+    /// its syntax and the scope.
+    fn receiver_type(
+        &self,
+        value: &'a Expression,
+        scope: &Scope<'a>,
+    ) -> Receiver<'a> {
+        match value {
+            Expression::SelfValue { .. } => scope.get("self").cloned(),
+            Expression::Identifier { identifier, .. } => {
+                scope.get(identifier.name.as_str()).cloned()
+            }
+            // The type of a construction is that of its struct, or the enum of
+            // its variant (e.g. the `Shape` of `Shape::Circle { r }`), if it's
+            // declared by the source file
+            Expression::Struct { path, .. }
+                if self.types.contains(path[0].name.as_str()) =>
+            {
+                Some(Receiver::Type(&path[0].name))
+            }
+            Expression::Grouped { expression, .. } => {
+                Some(self.receiver_type(expression, scope))
+            }
+            _ => None,
+        }
+        .unwrap_or(Receiver::Unknown)
+    }
+}
+
+/// The variables which an expression binds within its operands.
+fn bound_variables(expression: &Expression) -> Vec<&Identifier> {
+    match expression {
+        Expression::BigOperator { binding, .. }
+        | Expression::Integral { binding, .. }
+        | Expression::Quantifier { binding, .. } => vec![&binding.variable],
+        Expression::Comprehension { clauses, .. } => clauses
+            .iter()
+            .filter_map(|clause| match clause {
+                Clause::Generator(binding) => Some(&binding.variable),
+                Clause::Filter(_) => None,
+            })
+            .collect(),
+        _ => vec![],
+    }
+}
+
+fn duplicate_method(span: Span) -> InternalParserError {
+    InternalParserError::DuplicateMethod {
+        start: span.start,
+        end: span.end,
+    }
+}
@@ -0,0 +1,151 @@
+//! Finite sets of values.
+//!
+//! The elements of a set are all of the same type (i.e. a set is a `Set<T>`,
+//! where `T` is the type of its elements), so that e.g. `{1, true}` is an
+//! error. The type of the empty set `{}` is unknown, and so it's compatible
+//! with sets of any type. Elements are distinct as compared by `==` (e.g.
+//! `{1, 1.0, 2/2r}` has a single element), and are kept in the order in which
+//! they were first inserted.
+
+use std::cmp::Ordering;
+
+use thiserror::Error;
+
+use crate::eval::Value;
+
+/// A finite set of values of the same type.
+#[derive(Clone, Debug, Default)]
+pub struct Set {
+    elements: Vec<Value>,
+}
+
+/// The error of combining a set with a value or set of a differing type (e.g.
+/// `1 ∈ {true}`, `{1} ∪ {"a"}`).
+#[derive(Error, Debug, Copy, Clone, PartialEq, Eq)]
+#[error("expected `{expected}`, found `{found}`")]
+pub struct MismatchedTypes {
+    pub expected: &'static str,
+    pub found: &'static str,
+}
+
+impl Set {
+    pub fn new() -> Set {
+        Set::default()
+    }
+
+    /// The name of the type of the set's elements, unless it's empty.
+    pub fn element_type(&self) -> Option<&'static str> {
+        self.elements.first().map(Value::type_name)
+    }
+
+    pub fn len(&self) -> usize {
+        self.elements.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.elements.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Value> {
+        self.elements.iter()
+    }
+
+    /// Checks that a value is of the type of the set's elements.
+    pub fn check_element_type(&self, value: &Value) -> Result<(), MismatchedTypes> {
+        self.check_type(value.type_name())
+    }
+
+    fn check_type(&self, found: &'static str) -> Result<(), MismatchedTypes> {
+        match self.element_type() {
+            Some(expected) if expected != found => {
+                Err(MismatchedTypes { expected, found })
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Whether the value is an element of the set.
+    pub fn contains(&self, value: &Value) -> bool {
+        self.elements
+            .iter()
+            .any(|element| same_element(element, value))
+    }
+
+    /// Inserts a value of the type of the set's elements, returning whether it
+    /// wasn't already an element.
+    pub fn insert(&mut self, value: Value) -> Result<bool, MismatchedTypes> {
+        self.check_element_type(&value)?;
+        if self.contains(&value) {
+            return Ok(false);
+        }
+        self.elements.push(value);
+        Ok(true)
+    }
+
+    /// The elements of either set.
+    pub fn union(&self, other: &Set) -> Result<Set, MismatchedTypes> {
+        self.check_set_type(other)?;
+        let mut union = self.clone();
+        for element in other.iter() {
+            if !union.contains(element) {
+                union.elements.push(element.clone());
+            }
+        }
+        Ok(union)
+    }
+
+    /// The elements of both sets.
+    pub fn intersection(&self, other: &Set) -> Result<Set, MismatchedTypes> {
+        self.check_set_type(other)?;
+        Ok(Set {
+            elements: self
+                .iter()
+                .filter(|element| other.contains(element))
+                .cloned()
+                .collect(),
+        })
+    }
+
+    /// How the set relates to another by inclusion, i.e. `Less` if it's a
+    /// proper subset of the other, `Equal` if they're equal, `Greater` if it's
+    /// a proper superset, or `None` if neither contains the other.
+    pub fn compare(&self, other: &Set) -> Result<Option<Ordering>, MismatchedTypes> {
+        self.check_set_type(other)?;
+        let is_subset = self.iter().all(|element| other.contains(element));
+        let is_superset = other.iter().all(|element| self.contains(element));
+        Ok(match (is_subset, is_superset) {
+            (true, true) => Some(Ordering::Equal),
+            (true, false) => Some(Ordering::Less),
+            (false, true) => Some(Ordering::Greater),
+            (false, false) => None,
+        })
+    }
+
+    fn check_set_type(&self, other: &Set) -> Result<(), MismatchedTypes> {
+        match other.element_type() {
+            Some(found) => self.check_type(found),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Sets are equal when they have the same elements, in any order.
+impl PartialEq for Set {
+    fn eq(&self, other: &Set) -> bool {
+        self.compare(other) == Ok(Some(Ordering::Equal))
+    }
+}
+
+/// Whether two values are the same element, i.e. equal by `==` (e.g. `1` and
+/// `1.0`, or `1 km` and `1000 m`).
+fn same_element(a: &Value, b: &Value) -> bool {
+    match (a, b) {
+        (Value::Number(a), Value::Number(b)) => {
+            a.compare(b) == Some(Ordering::Equal)
+        }
+        (Value::Quantity(a), Value::Quantity(b)) => {
+            a.unit.dimension == b.unit.dimension && a.si_value() == b.si_value()
+        }
+        _ => a == b,
+    }
+}
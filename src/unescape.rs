@@ -0,0 +1,267 @@
+use std::{iter::Peekable, ops::Range};
+
+use crate::parser_errors::{EscapeError, InternalParserError};
+
+// QUOTE_ESCAPE :
+//    \' | \"
+// ASCII_ESCAPE :
+//       \x OCT_DIGIT HEX_DIGIT
+//    | \n | \r | \t | \\ | \0
+// BYTE_ESCAPE :
+//       \x HEX_DIGIT HEX_DIGIT
+//    | \n | \r | \t | \\ | \0 | \' | \"
+// UNICODE_ESCAPE :
+//    \u{ ( HEX_DIGIT _* )1..6 }
+// STRING_CONTINUE :
+//    \ followed by \n
+
+/// Whether the literal being decoded is a character (string) or a byte
+/// (string), as byte literals only admit ASCII characters and byte escapes.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum Mode {
+    Str,
+    ByteStr,
+}
+
+/// Decodes the lexeme of a string literal (e.g. `"a\tb"`), where `offset` is
+/// the byte offset of the lexeme within the source text.
+pub(crate) fn unescape_string(
+    lexeme: &str,
+    offset: usize,
+) -> Result<String, InternalParserError> {
+    unescape(lexeme, 1..lexeme.len() - 1, Mode::Str)
+        .map_err(|(error, span)| invalid_escape(error, span, offset))
+}
+
+/// Decodes the lexeme of a byte string literal (e.g. `b"a\x7Fb"`), where
+/// `offset` is the byte offset of the lexeme within the source text.
+pub(crate) fn unescape_byte_string(
+    lexeme: &str,
+    offset: usize,
+) -> Result<Vec<u8>, InternalParserError> {
+    unescape(lexeme, 2..lexeme.len() - 1, Mode::ByteStr)
+        .map(|value| value.chars().map(|c| c as u8).collect())
+        .map_err(|(error, span)| invalid_escape(error, span, offset))
+}
+
+/// Decodes the lexeme of a character literal (e.g. `'\u{1F600}'`), where
+/// `offset` is the byte offset of the lexeme within the source text.
+pub(crate) fn unescape_char(
+    lexeme: &str,
+    offset: usize,
+) -> Result<char, InternalParserError> {
+    unescape(lexeme, 1..lexeme.len() - 1, Mode::Str)
+        .map_err(|(error, span)| invalid_escape(error, span, offset))
+        .and_then(|value| single_char(&value, lexeme, offset))
+}
+
+/// Decodes the lexeme of a byte literal (e.g. `b'\x7F'`), where `offset` is
+/// the byte offset of the lexeme within the source text.
+pub(crate) fn unescape_byte(
+    lexeme: &str,
+    offset: usize,
+) -> Result<u8, InternalParserError> {
+    unescape(lexeme, 2..lexeme.len() - 1, Mode::ByteStr)
+        .map_err(|(error, span)| invalid_escape(error, span, offset))
+        .and_then(|value| single_char(&value, lexeme, offset))
+        .map(|c| c as u8)
+}
+
+/// Character and byte literals must decode to exactly one character.
+fn single_char(
+    value: &str,
+    lexeme: &str,
+    offset: usize,
+) -> Result<char, InternalParserError> {
+    let mut chars = value.chars();
+    match (chars.next(), chars.next()) {
+        (Some(c), None) => Ok(c),
+        (None, _) => Err(InternalParserError::EmptyCharLiteral {
+            start: offset,
+            end: offset + lexeme.len(),
+        }),
+        (Some(_), Some(_)) => Err(InternalParserError::OverlongCharLiteral {
+            start: offset,
+            end: offset + lexeme.len(),
+        }),
+    }
+}
+
+/// Returns the contents of a raw string literal (e.g. `r#"a "b" c"#`), which
+/// are taken verbatim.
+pub(crate) fn raw_string(lexeme: &str) -> String {
+    String::from(&lexeme[raw_contents(lexeme)])
+}
+
+/// Returns the contents of a raw byte string literal (e.g. `br"a\b"`), where
+/// `offset` is the byte offset of the lexeme within the source text. Raw byte
+/// strings may only contain ASCII characters.
+pub(crate) fn raw_byte_string(
+    lexeme: &str,
+    offset: usize,
+) -> Result<Vec<u8>, InternalParserError> {
+    let contents = raw_contents(lexeme);
+    match lexeme[contents.clone()]
+        .char_indices()
+        .find(|(_, c)| !c.is_ascii())
+    {
+        Some((i, c)) => Err(invalid_escape(
+            EscapeError::NonAsciiCharacterInByteString,
+            contents.start + i..contents.start + i + c.len_utf8(),
+            offset,
+        )),
+        None => Ok(lexeme[contents].bytes().collect()),
+    }
+}
+
+/// The byte range of a raw (byte) string literal's contents, i.e. everything
+/// between the opening `r#*"` and the closing `"#*`.
+fn raw_contents(lexeme: &str) -> Range<usize> {
+    let prefix = lexeme.find('"').unwrap() + 1;
+    let hashes = prefix - lexeme.find('r').unwrap() - 2;
+    prefix..lexeme.len() - 1 - hashes
+}
+
+fn invalid_escape(
+    error: EscapeError,
+    span: Range<usize>,
+    offset: usize,
+) -> InternalParserError {
+    InternalParserError::InvalidEscape {
+        error,
+        start: offset + span.start,
+        end: offset + span.end,
+    }
+}
+
+/// Decodes the `contents` of a (byte) string or character literal's
+/// `lexeme`. Byte literals are decoded into a string of characters in the range `\0`..=`\u{FF}`,
+/// one per byte.
+///
+/// On failure, the offending escape is returned alongside its byte range
+/// within the lexeme.
+fn unescape(
+    lexeme: &str,
+    contents: Range<usize>,
+    mode: Mode,
+) -> Result<String, (EscapeError, Range<usize>)> {
+    let mut value = String::new();
+    let mut chars = lexeme[contents.clone()]
+        .char_indices()
+        .map(|(i, c)| (contents.start + i, c))
+        .peekable();
+
+    while let Some((start, c)) = chars.next() {
+        if c != '\\' {
+            if mode == Mode::ByteStr && !c.is_ascii() {
+                return Err((
+                    EscapeError::NonAsciiCharacterInByteString,
+                    start..start + c.len_utf8(),
+                ));
+            }
+            value.push(c);
+            continue;
+        }
+
+        // The lexer only produces string literals whose backslashes are
+        // followed by another character
+        let (_, escape) = chars.next().unwrap();
+        match escape {
+            'n' => value.push('\n'),
+            'r' => value.push('\r'),
+            't' => value.push('\t'),
+            '\\' => value.push('\\'),
+            '0' => value.push('\0'),
+            '\'' => value.push('\''),
+            '"' => value.push('"'),
+            // Line continuations skip the newline and any leading whitespace
+            // on the following line
+            '\n' | '\r' => {
+                while chars.next_if(|(_, c)| c.is_ascii_whitespace()).is_some() {}
+            }
+            'x' => {
+                let mut digits = String::new();
+                while digits.len() < 2 {
+                    match chars.next_if(|(_, c)| c.is_ascii_hexdigit()) {
+                        Some((_, c)) => digits.push(c),
+                        None => break,
+                    }
+                }
+                if digits.len() != 2 {
+                    return Err((
+                        EscapeError::InvalidHexEscape,
+                        start..position(&mut chars, contents.end),
+                    ));
+                }
+                let byte = u8::from_str_radix(&digits, 16).unwrap();
+                if mode == Mode::Str && !byte.is_ascii() {
+                    return Err((
+                        EscapeError::OutOfRangeHexEscape,
+                        start..position(&mut chars, contents.end),
+                    ));
+                }
+                value.push(char::from(byte));
+            }
+            'u' => {
+                if mode == Mode::ByteStr {
+                    return Err((
+                        EscapeError::UnicodeEscapeInByteString,
+                        start..position(&mut chars, contents.end),
+                    ));
+                }
+                if chars.next_if(|(_, c)| *c == '{').is_none() {
+                    return Err((
+                        EscapeError::InvalidUnicodeEscape,
+                        start..position(&mut chars, contents.end),
+                    ));
+                }
+                let mut digits = String::new();
+                loop {
+                    match chars.next() {
+                        Some((_, '}')) => break,
+                        Some((_, '_')) if !digits.is_empty() => {}
+                        Some((_, c)) if c.is_ascii_hexdigit() => digits.push(c),
+                        _ => {
+                            return Err((
+                                EscapeError::InvalidUnicodeEscape,
+                                start..position(&mut chars, contents.end),
+                            ))
+                        }
+                    }
+                }
+                if digits.is_empty() || digits.len() > 6 {
+                    return Err((
+                        EscapeError::InvalidUnicodeEscape,
+                        start..position(&mut chars, contents.end),
+                    ));
+                }
+                match char::from_u32(u32::from_str_radix(&digits, 16).unwrap()) {
+                    Some(c) => value.push(c),
+                    None => {
+                        return Err((
+                            EscapeError::OutOfRangeUnicodeEscape,
+                            start..position(&mut chars, contents.end),
+                        ))
+                    }
+                }
+            }
+            _ => {
+                return Err((
+                    EscapeError::UnknownCharacterEscape,
+                    start..position(&mut chars, contents.end),
+                ));
+            }
+        }
+    }
+
+    Ok(value)
+}
+
+/// The byte offset of the next character to be decoded, or `end` if there are
+/// none left.
+fn position(
+    chars: &mut Peekable<impl Iterator<Item = (usize, char)>>,
+    end: usize,
+) -> usize {
+    chars.peek().map_or(end, |(i, _)| *i)
+}
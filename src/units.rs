@@ -0,0 +1,350 @@
+//! Units of measure and dimensional analysis.
+//!
+//! Every unit is a scale of a coherent SI unit (e.g. `km` is `1000 m`, `min` is
+//! `60 s`), whose dimension is a product of powers of the seven SI base
+//! dimensions (e.g. `N` is `kg·m·s⁻²`). Quantities may only be added or
+//! subtracted when their dimensions agree, while multiplying or dividing them
+//! combines their dimensions. The exponents of a dimension are bytes, and so
+//! combining dimensions whose exponents overflow (e.g. `m¹²⁷·m`) is an error.
+
+use std::fmt;
+
+use thiserror::Error;
+
+/// The symbols of the SI base units, in the order of [`Dimension`]'s
+/// exponents.
+const BASE_UNITS: [&str; 7] = ["kg", "m", "s", "A", "K", "mol", "cd"];
+
+const MASS: usize = 0;
+const LENGTH: usize = 1;
+const TIME: usize = 2;
+const CURRENT: usize = 3;
+const TEMPERATURE: usize = 4;
+const AMOUNT: usize = 5;
+const LUMINOUS_INTENSITY: usize = 6;
+
+/// The exponents of the SI base dimensions (mass, length, time, electric
+/// current, temperature, amount of substance, and luminous intensity) making
+/// up a dimension (e.g. velocity is `m·s⁻¹`).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Hash)]
+pub struct Dimension {
+    exponents: [i8; 7],
+}
+
+impl Dimension {
+    pub const DIMENSIONLESS: Dimension = Dimension { exponents: [0; 7] };
+
+    /// The dimension with the given exponents of the SI base dimensions.
+    const fn new(exponents: [i8; 7]) -> Dimension {
+        Dimension { exponents }
+    }
+
+    const fn base(index: usize) -> Dimension {
+        let mut exponents = [0; 7];
+        exponents[index] = 1;
+        Dimension { exponents }
+    }
+
+    pub fn is_dimensionless(&self) -> bool {
+        *self == Dimension::DIMENSIONLESS
+    }
+
+    pub fn checked_powi(self, n: i8) -> Result<Dimension, ExponentOutOfRange> {
+        self.combine(Dimension::DIMENSIONLESS, |exponent, _| {
+            exponent.checked_mul(n)
+        })
+    }
+
+    /// Dimensions are multiplied by adding their exponents (e.g. `m·m` is
+    /// `m²`).
+    pub fn checked_mul(
+        self,
+        rhs: Dimension,
+    ) -> Result<Dimension, ExponentOutOfRange> {
+        self.combine(rhs, i8::checked_add)
+    }
+
+    pub fn checked_div(
+        self,
+        rhs: Dimension,
+    ) -> Result<Dimension, ExponentOutOfRange> {
+        self.combine(rhs, i8::checked_sub)
+    }
+
+    /// The `n`th root of the dimension, if each of its exponents is a multiple
+    /// of `n` (e.g. `√(m²)` is `m`, whereas `√m` has no dimension).
+    pub fn root(self, n: i8) -> Option<Dimension> {
+        self.combine(Dimension::DIMENSIONLESS, |exponent, _| {
+            match exponent.checked_rem(n)? {
+                0 => exponent.checked_div(n),
+                _ => None,
+            }
+        })
+        .ok()
+    }
+
+    /// Combines the exponents of two dimensions pairwise, unless any of them
+    /// can't be combined.
+    fn combine(
+        self,
+        rhs: Dimension,
+        f: impl Fn(i8, i8) -> Option<i8>,
+    ) -> Result<Dimension, ExponentOutOfRange> {
+        let mut exponents = self.exponents;
+        for (exponent, rhs) in exponents.iter_mut().zip(rhs.exponents) {
+            *exponent = f(*exponent, rhs).ok_or(ExponentOutOfRange)?;
+        }
+        Ok(Dimension { exponents })
+    }
+}
+
+/// The error of combining dimensions whose exponents don't fit in a byte (e.g.
+/// `m¹²⁷·m`, `(m¹⁰⁰)²`).
+#[derive(Error, Debug, Copy, Clone, PartialEq, Eq)]
+#[error("the exponents of a unit must be between -128 and 127")]
+pub struct ExponentOutOfRange;
+
+/// Written in terms of the SI base units (e.g. `kg·m·s⁻²`), or `1` if
+/// dimensionless.
+impl fmt::Display for Dimension {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_dimensionless() {
+            return write!(f, "1");
+        }
+        let factors = BASE_UNITS
+            .iter()
+            .zip(self.exponents)
+            .filter(|(_, exponent)| *exponent != 0);
+        for (i, (symbol, exponent)) in factors.enumerate() {
+            if i > 0 {
+                write!(f, "·")?;
+            }
+            write!(f, "{}", symbol)?;
+            if exponent != 1 {
+                write!(f, "{}", superscript(exponent))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+fn superscript(n: i8) -> String {
+    const DIGITS: [char; 10] = ['⁰', '¹', '²', '³', '⁴', '⁵', '⁶', '⁷', '⁸', '⁹'];
+    let digits = n.unsigned_abs().to_string();
+    let digits = digits
+        .bytes()
+        .map(|digit| DIGITS[usize::from(digit - b'0')]);
+    if n < 0 {
+        std::iter::once('⁻').chain(digits).collect()
+    } else {
+        digits.collect()
+    }
+}
+
+/// A unit of measure, as a scale of the coherent SI unit of its dimension
+/// (e.g. `km` has a scale of 1000 and the dimension of length).
+#[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
+pub struct Unit {
+    pub scale: f64,
+    pub dimension: Dimension,
+}
+
+/// The units which may be written without a prefix, and their definitions
+/// (including the derived SI units with special names, e.g. `N`).
+const UNITS: [(&str, f64, Dimension); 18] = [
+    ("m", 1.0, Dimension::base(LENGTH)),
+    ("g", 1e-3, Dimension::base(MASS)),
+    ("s", 1.0, Dimension::base(TIME)),
+    ("A", 1.0, Dimension::base(CURRENT)),
+    ("K", 1.0, Dimension::base(TEMPERATURE)),
+    ("mol", 1.0, Dimension::base(AMOUNT)),
+    ("cd", 1.0, Dimension::base(LUMINOUS_INTENSITY)),
+    ("min", 60.0, Dimension::base(TIME)),
+    ("h", 3600.0, Dimension::base(TIME)),
+    ("L", 1e-3, Dimension::new([0, 3, 0, 0, 0, 0, 0])),
+    ("Hz", 1.0, Dimension::new([0, 0, -1, 0, 0, 0, 0])),
+    ("N", 1.0, Dimension::new([1, 1, -2, 0, 0, 0, 0])),
+    ("J", 1.0, Dimension::new([1, 2, -2, 0, 0, 0, 0])),
+    ("W", 1.0, Dimension::new([1, 2, -3, 0, 0, 0, 0])),
+    ("Pa", 1.0, Dimension::new([1, -1, -2, 0, 0, 0, 0])),
+    ("C", 1.0, Dimension::new([0, 0, 1, 1, 0, 0, 0])),
+    ("V", 1.0, Dimension::new([1, 2, -3, -1, 0, 0, 0])),
+    ("Ω", 1.0, Dimension::new([1, 2, -3, -2, 0, 0, 0])),
+];
+
+/// The SI prefixes (e.g. the `k` of `km`).
+const PREFIXES: [(&str, f64); 20] = [
+    ("Y", 1e24),
+    ("Z", 1e21),
+    ("E", 1e18),
+    ("P", 1e15),
+    ("T", 1e12),
+    ("G", 1e9),
+    ("M", 1e6),
+    ("k", 1e3),
+    ("h", 1e2),
+    ("da", 1e1),
+    ("d", 1e-1),
+    ("c", 1e-2),
+    ("m", 1e-3),
+    ("μ", 1e-6),
+    ("µ", 1e-6),
+    ("n", 1e-9),
+    ("p", 1e-12),
+    ("f", 1e-15),
+    ("a", 1e-18),
+    ("z", 1e-21),
+];
+
+impl Unit {
+    pub const DIMENSIONLESS: Unit = Unit {
+        scale: 1.0,
+        dimension: Dimension::DIMENSIONLESS,
+    };
+
+    /// The unit named by a (possibly prefixed) unit symbol (e.g. `m`, `km`,
+    /// `kg`, `ms`, `min`), if any. Unprefixed symbols take precedence (e.g.
+    /// `min` is a minute, not a milli-inch).
+    pub fn from_symbol(symbol: &str) -> Option<Unit> {
+        let unit = |symbol: &str| {
+            UNITS.iter().find(|(name, _, _)| *name == symbol).map(
+                |(_, scale, dimension)| Unit {
+                    scale: *scale,
+                    dimension: *dimension,
+                },
+            )
+        };
+        unit(symbol).or_else(|| {
+            PREFIXES.iter().find_map(|(prefix, factor)| {
+                let unit = unit(symbol.strip_prefix(prefix)?)?;
+                Some(Unit {
+                    scale: factor * unit.scale,
+                    dimension: unit.dimension,
+                })
+            })
+        })
+    }
+
+    pub fn checked_powi(self, n: i8) -> Result<Unit, ExponentOutOfRange> {
+        Ok(Unit {
+            scale: self.scale.powi(i32::from(n)),
+            dimension: self.dimension.checked_powi(n)?,
+        })
+    }
+
+    pub fn checked_mul(self, rhs: Unit) -> Result<Unit, ExponentOutOfRange> {
+        Ok(Unit {
+            scale: self.scale * rhs.scale,
+            dimension: self.dimension.checked_mul(rhs.dimension)?,
+        })
+    }
+
+    pub fn checked_div(self, rhs: Unit) -> Result<Unit, ExponentOutOfRange> {
+        Ok(Unit {
+            scale: self.scale / rhs.scale,
+            dimension: self.dimension.checked_div(rhs.dimension)?,
+        })
+    }
+
+    /// The `n`th root of the unit, if its dimension has one (see
+    /// [`Dimension::root`]).
+    pub fn root(self, n: i8) -> Option<Unit> {
+        Some(Unit {
+            scale: self.scale.powf(1.0 / f64::from(n)),
+            dimension: self.dimension.root(n)?,
+        })
+    }
+}
+
+/// The error of adding (or subtracting, or comparing) quantities of differing
+/// dimensions (e.g. `1 m + 1 s`).
+#[derive(Error, Debug, Copy, Clone, PartialEq, Eq)]
+#[error("cannot add `{right}` to `{left}`")]
+pub struct IncompatibleDimensions {
+    pub left: Dimension,
+    pub right: Dimension,
+}
+
+/// A value measured in some unit (e.g. `9.81 m/s²`).
+#[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
+pub struct Quantity {
+    pub value: f64,
+    pub unit: Unit,
+}
+
+impl Quantity {
+    pub fn new(value: f64, unit: Unit) -> Quantity {
+        Quantity { value, unit }
+    }
+
+    /// The quantity's value in the coherent SI unit of its dimension (e.g.
+    /// `1 km` is `1000` metres).
+    pub fn si_value(&self) -> f64 {
+        self.value * self.unit.scale
+    }
+
+    /// Adds a quantity of the same dimension, converting it to this
+    /// quantity's unit (e.g. `1 km + 500 m` is `1.5 km`).
+    pub fn checked_add(
+        self,
+        rhs: Quantity,
+    ) -> Result<Quantity, IncompatibleDimensions> {
+        let rhs = self.convert(rhs)?;
+        Ok(Quantity::new(self.value + rhs, self.unit))
+    }
+
+    /// Subtracts a quantity of the same dimension, converting it to this
+    /// quantity's unit.
+    pub fn checked_sub(
+        self,
+        rhs: Quantity,
+    ) -> Result<Quantity, IncompatibleDimensions> {
+        let rhs = self.convert(rhs)?;
+        Ok(Quantity::new(self.value - rhs, self.unit))
+    }
+
+    /// The value of `rhs` in this quantity's unit.
+    fn convert(&self, rhs: Quantity) -> Result<f64, IncompatibleDimensions> {
+        if self.unit.dimension != rhs.unit.dimension {
+            return Err(IncompatibleDimensions {
+                left: self.unit.dimension,
+                right: rhs.unit.dimension,
+            });
+        }
+        Ok(rhs.si_value() / self.unit.scale)
+    }
+
+    pub fn checked_powi(self, n: i8) -> Result<Quantity, ExponentOutOfRange> {
+        Ok(Quantity::new(
+            self.value.powi(i32::from(n)),
+            self.unit.checked_powi(n)?,
+        ))
+    }
+
+    pub fn checked_mul(self, rhs: Quantity) -> Result<Quantity, ExponentOutOfRange> {
+        Ok(Quantity::new(
+            self.value * rhs.value,
+            self.unit.checked_mul(rhs.unit)?,
+        ))
+    }
+
+    pub fn checked_div(self, rhs: Quantity) -> Result<Quantity, ExponentOutOfRange> {
+        Ok(Quantity::new(
+            self.value / rhs.value,
+            self.unit.checked_div(rhs.unit)?,
+        ))
+    }
+
+    /// The `n`th root of the quantity, if its unit has one (e.g. `√(4 m²)` is
+    /// `2 m`), where odd roots of negative values are negative (e.g. `∛(-8 m³)`
+    /// is `-2 m`).
+    pub fn root(self, n: i8) -> Option<Quantity> {
+        let root = |value: f64| value.powf(1.0 / f64::from(n));
+        let value = if self.value < 0.0 && n % 2 != 0 {
+            -root(-self.value)
+        } else {
+            root(self.value)
+        };
+        Some(Quantity::new(value, self.unit.root(n)?))
+    }
+}